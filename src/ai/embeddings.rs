@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{EmbeddingConfig, EmbeddingProvider, ProxyRoute};
+use crate::error::{AppError, Result};
+
+const OPENAI_EMBEDDINGS_URL: &str = "https://api.openai.com/v1/embeddings";
+const DEFAULT_OPENAI_MODEL: &str = "text-embedding-3-small";
+const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+const DEFAULT_OLLAMA_MODEL: &str = "nomic-embed-text";
+
+// Input text beyond this many bytes is not embedded
+const MAX_EMBED_CHARS: usize = 8000;
+
+#[derive(Debug, Serialize)]
+struct OpenAiRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiResponse {
+    data: Vec<OpenAiEmbedding>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiEmbedding {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaResponse {
+    embedding: Vec<f32>,
+}
+
+/// Client for text embedding APIs (OpenAI or a local Ollama server)
+pub struct Embedder {
+    client: Client,
+    provider: EmbeddingProvider,
+    api_key: Option<String>,
+    model: String,
+    url: String,
+}
+
+impl Embedder {
+    pub fn new(config: &EmbeddingConfig, proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
+            .timeout(Duration::from_secs(60))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        let (default_model, default_url) = match config.provider {
+            EmbeddingProvider::OpenAi => (DEFAULT_OPENAI_MODEL, OPENAI_EMBEDDINGS_URL),
+            EmbeddingProvider::Ollama => (DEFAULT_OLLAMA_MODEL, DEFAULT_OLLAMA_URL),
+        };
+
+        Self {
+            client,
+            provider: config.provider,
+            api_key: config.api_key.clone(),
+            model: config
+                .model
+                .clone()
+                .unwrap_or_else(|| default_model.to_string()),
+            url: config.url.clone().unwrap_or_else(|| default_url.to_string()),
+        }
+    }
+
+    /// Model name stored alongside vectors, so switching models re-embeds
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Embed a batch of texts, returning one vector per input in order
+    pub async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let texts: Vec<String> = texts.iter().map(|t| truncate(t)).collect();
+        match self.provider {
+            EmbeddingProvider::OpenAi => self.embed_openai(&texts).await,
+            EmbeddingProvider::Ollama => {
+                let mut vectors = Vec::with_capacity(texts.len());
+                for text in &texts {
+                    vectors.push(self.embed_ollama(text).await?);
+                }
+                Ok(vectors)
+            }
+        }
+    }
+
+    async fn embed_openai(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let api_key = self
+            .api_key
+            .as_ref()
+            .ok_or_else(|| AppError::Config("embeddings.api_key is required for OpenAI".into()))?;
+
+        let response = self
+            .client
+            .post(&self.url)
+            .bearer_auth(api_key)
+            .json(&OpenAiRequest {
+                model: &self.model,
+                input: texts,
+            })
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(AppError::Embedding(format!("API error: {}", error_text)));
+        }
+
+        let mut data = response.json::<OpenAiResponse>().await?.data;
+        data.sort_by_key(|e| e.index);
+        Ok(data.into_iter().map(|e| e.embedding).collect())
+    }
+
+    async fn embed_ollama(&self, text: &str) -> Result<Vec<f32>> {
+        let response = self
+            .client
+            .post(format!("{}/api/embeddings", self.url.trim_end_matches('/')))
+            .json(&OllamaRequest {
+                model: &self.model,
+                prompt: text,
+            })
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(AppError::Embedding(format!("Ollama error: {}", error_text)));
+        }
+
+        Ok(response.json::<OllamaResponse>().await?.embedding)
+    }
+}
+
+fn truncate(text: &str) -> String {
+    if text.len() <= MAX_EMBED_CHARS {
+        return text.to_string();
+    }
+    let mut end = MAX_EMBED_CHARS;
+    while end > 0 && !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    text[..end].to_string()
+}
+
+/// Cosine similarity of two vectors (0.0 if either is empty or lengths differ)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
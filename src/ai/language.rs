@@ -0,0 +1,15 @@
+// Enough text for a confident guess; more only slows detection down
+const SAMPLE_CHARS: usize = 2000;
+
+/// English name of the language an article is written in, e.g. "German",
+/// when it can be told with confidence
+pub fn detect_language(title: &str, content: &str) -> Option<&'static str> {
+    let sample: String = title
+        .chars()
+        .chain(['\n'])
+        .chain(content.chars())
+        .take(SAMPLE_CHARS)
+        .collect();
+    let info = whatlang::detect(&sample)?;
+    info.is_reliable().then(|| info.lang().eng_name())
+}
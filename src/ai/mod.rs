@@ -1,3 +1,6 @@
+mod embeddings;
+mod language;
 mod summarizer;
 
-pub use summarizer::Summarizer;
+pub use embeddings::{cosine_similarity, Embedder};
+pub use summarizer::{Summarizer, MAX_INPUT_CHARS};
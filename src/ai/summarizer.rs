@@ -1,12 +1,125 @@
-use std::time::Duration;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 
-use reqwest::Client;
+use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
 
+use crate::config::{ProxyRoute, ReadingLevel, SummaryConfig, SummaryOptions, SummaryStyle};
 use crate::error::{AppError, Result};
+use crate::models::{ChatMessage, KeyFacts, SummaryVariant, TokenUsage};
+
+use super::language::detect_language;
 
 const CLAUDE_API_URL: &str = "https://api.anthropic.com/v1/messages";
+const CLAUDE_MODELS_URL: &str = "https://api.anthropic.com/v1/models";
 const CLAUDE_MODEL: &str = "claude-3-5-haiku-20241022";
+// CLAUDE_MODEL's list price in US dollars per million tokens
+const INPUT_PRICE_PER_MTOK: f64 = 0.80;
+const OUTPUT_PRICE_PER_MTOK: f64 = 4.00;
+
+// Content longer than this is summarized in chunks (map-reduce)
+const CHUNK_CHARS: usize = 10000;
+// Upper bound on chunk count; very long articles get larger chunks instead
+const MAX_CHUNKS: usize = 8;
+/// Content beyond this many bytes is dropped before summarizing
+pub const MAX_INPUT_CHARS: usize = 200_000;
+
+// First retry delay after a 429/529; doubles with each further attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+// Anthropic's "overloaded" status
+const STATUS_OVERLOADED: u16 = 529;
+
+const SUMMARY_PROMPT: &str = "Summarize this article.";
+
+const CHUNK_PROMPT: &str = r#"You are reading one section of a longer article.
+List the key facts and findings from this section as concise bullet points.
+Output ONLY the bullet points, each starting with "• "."#;
+
+const SYNTHESIS_PROMPT: &str = r#"You are given notes taken from consecutive sections of one long article.
+Combine them into a summary of the whole article."#;
+
+const BULLETS_INSTRUCTIONS: &str = r#"Write 3-5 bullet points.
+Output ONLY the bullet points - no introductions, conclusions, or commentary.
+Start each line with "• " and state one key fact or finding.
+Never write phrases like "Here are the key points" or "In summary" - just the bullets."#;
+
+const PARAGRAPHS_INSTRUCTIONS: &str = r#"Write 2-3 short paragraphs of plain prose.
+Output ONLY the summary - no headings, introductions, or commentary.
+Never write phrases like "This article discusses" or "In summary"."#;
+
+const TRANSLATION_PROMPT: &str = r#"Translate the text you are given into {language}.
+Keep the paragraph breaks. Output ONLY the translation - no notes, explanations or commentary.
+If part of the text is already in {language}, keep that part as it is."#;
+
+// Translations and plain-language rewrites are about as long as the original
+// (translations longer for CJK sources)
+const TRANSLATION_MAX_TOKENS: u32 = 8192;
+
+const SIMPLIFY_PROMPT: &str = r#"Rewrite the text you are given in plain language for {reader}.
+Keep every fact, name and number, and keep the paragraph breaks; leave nothing out that matters.
+Write in the same language as the text.
+Output ONLY the rewritten text - no notes, explanations or commentary."#;
+
+const KEY_FACTS_PROMPT: &str = r#"Take structured notes on the article you are given.
+Reply with ONLY a JSON object in this form - no code fences or commentary:
+{"tldr": "...", "key_facts": ["..."], "people": ["..."], "organizations": ["..."], "places": ["..."]}
+tldr is one sentence. key_facts holds 3-6 facts or findings, one short sentence each.
+List only the people, companies or organizations, and places the article is about;
+leave a list empty when there are none."#;
+
+const KEY_FACTS_MAX_TOKENS: u32 = 1024;
+
+const CHAT_PROMPT: &str = r#"You are discussing an article with its reader.
+Answer their questions from the article below, and say so when it doesn't cover something.
+Keep answers short and in plain prose unless asked for more.
+
+Title: {title}
+
+Article:
+{content}"#;
+
+const CHAT_MAX_TOKENS: u32 = 1024;
+
+const BLURB_PROMPT: &str = r#"Write the opening of a social media post sharing the article you are given.
+In one or two sentences of at most {max_chars} characters, say what it is about and why
+it is worth reading. Write in the article's language.
+Reply with the text only: no hashtags, emoji, quotation marks or links."#;
+
+const BLURB_MAX_TOKENS: u32 = 256;
+
+const TAGS_PROMPT: &str = r#"Suggest tags for bookmarking the article you are given.
+Give 3 to 5 short lowercase tags, a word or two each, naming what it is about.
+Reuse these tags, already in use, where they fit: {known}
+Reply with ONLY the tags, separated by commas."#;
+
+const TAGS_MAX_TOKENS: u32 = 64;
+const MAX_SUGGESTED_TAGS: usize = 5;
+// Tags in use that are offered to Claude, most used first
+const MAX_KNOWN_TAGS: usize = 50;
+
+const TLDR_INSTRUCTIONS: &str = r#"Write a TL;DR of one or two sentences.
+Output ONLY those sentences - no "TL;DR:" prefix and no commentary."#;
+
+const SHORTER_INSTRUCTIONS: &str = r#"Write a very short summary of at most three sentences.
+Keep only the single most important point and what follows from it.
+Output ONLY the summary - no introductions or commentary."#;
+
+const LONGER_INSTRUCTIONS: &str = r#"Write a detailed summary of 4-6 paragraphs of plain prose.
+Cover the main argument, the supporting evidence, notable numbers and quotes, and the conclusion.
+Output ONLY the summary - no headings, introductions, or commentary."#;
+
+const ELI5_INSTRUCTIONS: &str = r#"Explain the article as you would to a curious five-year-old.
+Use short sentences, everyday words and a simple comparison where it helps; no jargon.
+Output ONLY the explanation - no introductions or commentary."#;
+
+const SKEPTICAL_INSTRUCTIONS: &str = r#"Sum up the article's claims in one short paragraph, then question them.
+Under the heading "Questions:", list as "• " bullets the weak evidence, missing context,
+unstated assumptions, conflicts of interest and claims that need checking.
+Be fair: say so when a claim is well supported. Output nothing else."#;
+
+// Detailed summaries get room to run past the configured limit
+const LONGER_MAX_TOKENS: u32 = 2048;
 
 #[derive(Debug, Serialize)]
 struct MessageRequest {
@@ -25,6 +138,14 @@ struct Message {
 #[derive(Debug, Deserialize)]
 struct MessageResponse {
     content: Vec<ContentBlock>,
+    #[serde(default)]
+    usage: Usage,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Usage {
+    input_tokens: u64,
+    output_tokens: u64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -35,65 +156,349 @@ struct ContentBlock {
     text: Option<String>,
 }
 
+/// Spaces requests out to stay under the API rate limit, and holds every
+/// request back while a rate-limit backoff is in progress
+struct RateLimiter {
+    interval: Duration,
+    state: Mutex<LimiterState>,
+}
+
+struct LimiterState {
+    next_slot: Instant,
+    retry_at: Option<Instant>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_minute: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(60) / requests_per_minute.max(1),
+            state: Mutex::new(LimiterState {
+                next_slot: Instant::now(),
+                retry_at: None,
+            }),
+        }
+    }
+
+    /// Wait for the next free request slot
+    async fn acquire(&self) {
+        let wait = {
+            let mut state = self.state.lock().unwrap();
+            let now = Instant::now();
+            let slot = state.next_slot.max(now);
+            state.next_slot = slot + self.interval;
+            slot - now
+        };
+        tokio::time::sleep(wait).await;
+    }
+
+    /// Hold all requests back until `delay` has passed
+    fn back_off(&self, delay: Duration) {
+        let mut state = self.state.lock().unwrap();
+        let until = Instant::now() + delay;
+        state.next_slot = state.next_slot.max(until);
+        state.retry_at = Some(state.retry_at.map_or(until, |at| at.max(until)));
+    }
+
+    fn retry_at(&self) -> Option<Instant> {
+        let state = self.state.lock().unwrap();
+        state.retry_at.filter(|at| *at > Instant::now())
+    }
+}
+
 pub struct Summarizer {
     client: Client,
     api_key: String,
+    max_attempts: u32,
+    limiter: RateLimiter,
 }
 
 impl Summarizer {
-    pub fn new(api_key: String) -> Self {
-        let client = Client::builder()
+    pub fn new(api_key: String, config: &SummaryConfig, proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
             .timeout(Duration::from_secs(60))
             .build()
             .expect("Failed to create HTTP client");
-        Self { client, api_key }
+        Self {
+            client,
+            api_key,
+            max_attempts: config.max_attempts.max(1),
+            limiter: RateLimiter::new(config.requests_per_minute),
+        }
     }
 
+    /// When the next request will be retried, while backing off after a 429/529
+    pub fn retry_at(&self) -> Option<Instant> {
+        self.limiter.retry_at()
+    }
+
+    /// Summarize an article, returning the summary and the tokens it took.
+    /// The summary is in the configured language, or else the article's own.
     pub async fn generate_summary(
         &self,
         article_title: &str,
         article_content: &str,
+        options: &SummaryOptions,
+    ) -> Result<(String, TokenUsage)> {
+        let article_content = truncate_input(article_content);
+        let language = options
+            .language
+            .as_deref()
+            .or_else(|| detect_language(article_title, article_content));
+        tracing::debug!("Summarizing '{}' in {:?}", article_title, language);
+
+        if article_content.len() <= CHUNK_CHARS {
+            let user_message = format!(
+                "Please summarize the following article:\n\nTitle: {}\n\nContent:\n{}",
+                article_title, article_content
+            );
+            let system = system_prompt(SUMMARY_PROMPT, options, language);
+            return self
+                .send_message(&system, user_message, summary_max_tokens(options))
+                .await;
+        }
+
+        self.generate_chunked_summary(article_title, article_content, options, language)
+            .await
+    }
+
+    /// Map-reduce summary for long articles: summarize each chunk, then
+    /// synthesize the chunk notes into a single summary
+    async fn generate_chunked_summary(
+        &self,
+        article_title: &str,
+        article_content: &str,
+        options: &SummaryOptions,
+        language: Option<&str>,
+    ) -> Result<(String, TokenUsage)> {
+        let chunk_size = CHUNK_CHARS.max(article_content.len().div_ceil(MAX_CHUNKS));
+        let chunks = split_into_chunks(article_content, chunk_size);
+        let total = chunks.len();
+        tracing::debug!("Summarizing '{}' in {} chunks", article_title, total);
+
+        let notes = futures::future::try_join_all(chunks.iter().enumerate().map(|(i, chunk)| {
+            let user_message = format!(
+                "Article title: {}\n\nSection {} of {}:\n{}",
+                article_title,
+                i + 1,
+                total,
+                chunk
+            );
+            self.send_message(CHUNK_PROMPT, user_message, 512)
+        }))
+        .await?;
+
+        let mut usage = TokenUsage::default();
+        for (_, chunk_usage) in &notes {
+            usage.add(*chunk_usage);
+        }
+        let combined = notes
+            .iter()
+            .enumerate()
+            .map(|(i, (n, _))| format!("Section {}:\n{}", i + 1, n.trim()))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let user_message = format!("Title: {}\n\nSection notes:\n{}", article_title, combined);
+        let system = system_prompt(SYNTHESIS_PROMPT, options, language);
+        let (summary, synthesis_usage) = self
+            .send_message(&system, user_message, summary_max_tokens(options))
+            .await?;
+        usage.add(synthesis_usage);
+        Ok((summary, usage))
+    }
+
+    /// Translate an article's title and text into `language`. Long texts are
+    /// translated a chunk at a time; the title comes back as the first line.
+    pub async fn translate(&self, title: &str, content: &str, language: &str) -> Result<String> {
+        let content = truncate_input(content);
+        let text = format!("{}\n\n{}", title.trim(), content.trim());
+        let system = TRANSLATION_PROMPT.replace("{language}", language);
+
+        let chunks = split_into_chunks(&text, CHUNK_CHARS);
+        let translated = futures::future::try_join_all(
+            chunks
+                .iter()
+                .map(|chunk| self.send_message(&system, chunk.to_string(), TRANSLATION_MAX_TOKENS)),
+        )
+        .await?;
+
+        Ok(translated
+            .iter()
+            .map(|(part, _)| part.trim())
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
+    /// Rewrite an article's title and text in plain language for `level`,
+    /// a chunk at a time; the title comes back as the first line.
+    pub async fn simplify(
+        &self,
+        title: &str,
+        content: &str,
+        level: ReadingLevel,
     ) -> Result<String> {
-        let system_prompt = r#"Summarize this article as 3-5 bullet points.
-Output ONLY the bullet points - no introductions, conclusions, or commentary.
-Start each line with "• " and state one key fact or finding.
-Never write phrases like "Here are the key points" or "In summary" - just the bullets."#;
+        let content = truncate_input(content);
+        let text = format!("{}\n\n{}", title.trim(), content.trim());
+        let system = SIMPLIFY_PROMPT.replace("{reader}", level.reader());
 
-        // Truncate content if too long (find valid UTF-8 boundary)
-        let content = if article_content.len() > 10000 {
-            let mut end = 10000;
-            while end > 0 && !article_content.is_char_boundary(end) {
-                end -= 1;
-            }
-            &article_content[..end]
-        } else {
-            article_content
-        };
+        let chunks = split_into_chunks(&text, CHUNK_CHARS);
+        let simplified = futures::future::try_join_all(
+            chunks
+                .iter()
+                .map(|chunk| self.send_message(&system, chunk.to_string(), TRANSLATION_MAX_TOKENS)),
+        )
+        .await?;
 
+        Ok(simplified
+            .iter()
+            .map(|(part, _)| part.trim())
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
+    /// Extract a TL;DR, key facts and named entities from an article
+    pub async fn extract_key_facts(
+        &self,
+        article_title: &str,
+        article_content: &str,
+    ) -> Result<(KeyFacts, TokenUsage)> {
         let user_message = format!(
-            "Please summarize the following article:\n\nTitle: {}\n\nContent:\n{}",
-            article_title, content
+            "Title: {}\n\nContent:\n{}",
+            article_title,
+            truncate_input(article_content)
         );
+        let (reply, usage) = self
+            .send_message(KEY_FACTS_PROMPT, user_message, KEY_FACTS_MAX_TOKENS)
+            .await?;
+
+        // Tolerate a stray sentence or code fence around the object
+        let json = match (reply.find('{'), reply.rfind('}')) {
+            (Some(start), Some(end)) if start < end => &reply[start..=end],
+            _ => return Err(AppError::ClaudeApi("Key facts reply had no JSON".to_string())),
+        };
+        Ok((serde_json::from_str(json)?, usage))
+    }
+
+    /// Answer the last question in `history` (a conversation about the
+    /// article, oldest first, ending with the reader's message)
+    pub async fn chat(
+        &self,
+        title: &str,
+        content: &str,
+        history: &[ChatMessage],
+    ) -> Result<(String, TokenUsage)> {
+        let system = CHAT_PROMPT
+            .replace("{title}", title)
+            .replace("{content}", truncate_input(content));
+        let messages = history
+            .iter()
+            .map(|message| Message {
+                role: message.role.as_str().to_string(),
+                content: message.content.clone(),
+            })
+            .collect();
+        self.send_messages(&system, messages, CHAT_MAX_TOKENS).await
+    }
+
+    /// A line or two about an article to share it with, at most `max_chars` long
+    pub async fn share_blurb(
+        &self,
+        title: &str,
+        content: &str,
+        max_chars: usize,
+    ) -> Result<String> {
+        let user_message = format!("Title: {}\n\nContent:\n{}", title, truncate_input(content));
+        let system = BLURB_PROMPT.replace("{max_chars}", &max_chars.to_string());
+        let (blurb, _) = self.send_message(&system, user_message, BLURB_MAX_TOKENS).await?;
+        Ok(blurb.trim().trim_matches('"').to_string())
+    }
+
+    /// Tags for bookmarking an article, preferring `known` ones that fit
+    pub async fn suggest_tags(
+        &self,
+        title: &str,
+        content: &str,
+        known: &[String],
+    ) -> Result<Vec<String>> {
+        let user_message = format!("Title: {}\n\nContent:\n{}", title, truncate_input(content));
+        let known = match known {
+            [] => "none yet".to_string(),
+            known => known[..known.len().min(MAX_KNOWN_TAGS)].join(", "),
+        };
+        let system = TAGS_PROMPT.replace("{known}", &known);
+        let (reply, _) = self.send_message(&system, user_message, TAGS_MAX_TOKENS).await?;
+        let mut tags: Vec<String> = Vec::new();
+        for tag in reply.split([',', '\n']) {
+            let tag = tag.trim().trim_start_matches(['#', '-', '*']).trim().to_lowercase();
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags.truncate(MAX_SUGGESTED_TAGS);
+        Ok(tags)
+    }
 
+    async fn send_message(
+        &self,
+        system_prompt: &str,
+        user_message: String,
+        max_tokens: u32,
+    ) -> Result<(String, TokenUsage)> {
+        let messages = vec![Message {
+            role: "user".to_string(),
+            content: user_message,
+        }];
+        self.send_messages(system_prompt, messages, max_tokens).await
+    }
+
+    async fn send_messages(
+        &self,
+        system_prompt: &str,
+        messages: Vec<Message>,
+        max_tokens: u32,
+    ) -> Result<(String, TokenUsage)> {
         let request = MessageRequest {
             model: CLAUDE_MODEL.to_string(),
-            max_tokens: 1024,
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: user_message,
-            }],
+            max_tokens,
+            messages,
             system: Some(system_prompt.to_string()),
         };
 
-        let response = self
-            .client
-            .post(CLAUDE_API_URL)
-            .header("x-api-key", &self.api_key)
-            .header("anthropic-version", "2023-06-01")
-            .header("content-type", "application/json")
-            .json(&request)
-            .send()
-            .await?;
+        let mut attempt = 1;
+        let response = loop {
+            self.limiter.acquire().await;
+            let response = self
+                .client
+                .post(CLAUDE_API_URL)
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", "2023-06-01")
+                .header("content-type", "application/json")
+                .json(&request)
+                .send()
+                .await?;
+
+            let status = response.status();
+            let retryable =
+                status == StatusCode::TOO_MANY_REQUESTS || status.as_u16() == STATUS_OVERLOADED;
+            if !retryable || attempt >= self.max_attempts {
+                break response;
+            }
+
+            // Honour retry-after when the API sends it, otherwise back off exponentially
+            let delay = retry_after(&response).unwrap_or_else(|| {
+                (RETRY_BASE_DELAY * 2u32.saturating_pow(attempt - 1)).min(RETRY_MAX_DELAY)
+            });
+            tracing::warn!(
+                "Claude API returned {} (attempt {}/{}), retrying in {}s",
+                status,
+                attempt,
+                self.max_attempts,
+                delay.as_secs()
+            );
+            self.limiter.back_off(delay);
+            attempt += 1;
+        };
 
         if !response.status().is_success() {
             let error_text = response.text().await?;
@@ -102,17 +507,133 @@ Never write phrases like "Here are the key points" or "In summary" - just the bu
 
         let message_response: MessageResponse = response.json().await?;
 
-        let summary = message_response
+        let text = message_response
             .content
             .into_iter()
             .filter_map(|block| block.text)
             .collect::<Vec<_>>()
             .join("\n");
+        let usage = TokenUsage {
+            input_tokens: message_response.usage.input_tokens,
+            output_tokens: message_response.usage.output_tokens,
+        };
 
-        Ok(summary)
+        Ok((text, usage))
+    }
+
+    /// Check that the API key is accepted, without spending tokens
+    pub async fn verify_credentials(&self) -> Result<()> {
+        let response = self
+            .client
+            .get(CLAUDE_MODELS_URL)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", "2023-06-01")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::ClaudeApi(format!("HTTP {}", response.status())));
+        }
+
+        Ok(())
     }
 
     pub fn model_version(&self) -> &'static str {
         CLAUDE_MODEL
     }
+
+    /// Estimated cost in US dollars of `usage`, at the model's list price
+    pub fn cost_usd(&self, usage: TokenUsage) -> f64 {
+        (usage.input_tokens as f64 * INPUT_PRICE_PER_MTOK
+            + usage.output_tokens as f64 * OUTPUT_PRICE_PER_MTOK)
+            / 1_000_000.0
+    }
+}
+
+/// Delay requested by the `retry-after` header (whole seconds)
+fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    let seconds: u64 = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds).min(RETRY_MAX_DELAY))
+}
+
+/// Truncate pathological inputs to MAX_INPUT_CHARS (on a valid UTF-8 boundary)
+fn truncate_input(content: &str) -> &str {
+    if content.len() <= MAX_INPUT_CHARS {
+        return content;
+    }
+    let mut end = MAX_INPUT_CHARS;
+    while end > 0 && !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    &content[..end]
+}
+
+/// System prompt for the final summary: the task, then the picked variant's,
+/// the custom prompt's or the style's instructions, then the output language
+fn system_prompt(task: &str, options: &SummaryOptions, language: Option<&str>) -> String {
+    let instructions = match (options.variant, &options.prompt) {
+        (Some(SummaryVariant::Shorter), _) => SHORTER_INSTRUCTIONS,
+        (Some(SummaryVariant::Longer), _) => LONGER_INSTRUCTIONS,
+        (Some(SummaryVariant::Bullets), _) => BULLETS_INSTRUCTIONS,
+        (Some(SummaryVariant::Eli5), _) => ELI5_INSTRUCTIONS,
+        (Some(SummaryVariant::Skeptical), _) => SKEPTICAL_INSTRUCTIONS,
+        (None, Some(prompt)) => prompt.as_str(),
+        (None, None) => match options.style {
+            SummaryStyle::Bullets => BULLETS_INSTRUCTIONS,
+            SummaryStyle::Paragraphs => PARAGRAPHS_INSTRUCTIONS,
+            SummaryStyle::TlDr => TLDR_INSTRUCTIONS,
+        },
+    };
+
+    let mut prompt = format!("{}\n{}", task, instructions);
+    if let Some(language) = language {
+        prompt.push_str(&format!("\nWrite the summary in {}.", language));
+    }
+    prompt
+}
+
+fn summary_max_tokens(options: &SummaryOptions) -> u32 {
+    match options.variant {
+        Some(SummaryVariant::Longer) => options.max_tokens.max(LONGER_MAX_TOKENS),
+        _ => options.max_tokens,
+    }
+}
+
+/// Split text into chunks of at most `max_chars` bytes, preferring paragraph,
+/// then line, then sentence boundaries (always on valid UTF-8 boundaries)
+fn split_into_chunks(text: &str, max_chars: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text.trim();
+
+    while rest.len() > max_chars {
+        let mut end = max_chars;
+        while end > 0 && !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        let window = &rest[..end];
+
+        // Don't accept a break in the first half, or chunks get tiny
+        let min_split = end / 2;
+        let split = ["\n\n", "\n", ". "]
+            .iter()
+            .filter_map(|sep| window.rfind(sep).map(|i| i + sep.len()))
+            .find(|&i| i > min_split)
+            .unwrap_or(end);
+
+        chunks.push(rest[..split].trim());
+        rest = rest[split..].trim_start();
+    }
+
+    if !rest.is_empty() {
+        chunks.push(rest);
+    }
+
+    chunks
 }
@@ -1,641 +1,6457 @@
+use std::cell::{Cell, OnceCell};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use futures::stream::{self, StreamExt};
+use image::DynamicImage;
+use ratatui::layout::{Margin, Position, Rect};
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
-use crate::ai::Summarizer;
-use crate::config::Config;
+use crate::ai::{cosine_similarity, Embedder, Summarizer, MAX_INPUT_CHARS};
+use crate::config::{
+    Config, EnterAction, GraphicsProtocol, HideRead, InitialImport, ListDensity, NumberKeys,
+    ProxyService, ReadingLevel, Setting, SummaryOptions, Workspace,
+};
 use crate::db::Repository;
-use crate::error::Result;
-use crate::feed::{export_opml_file, parse_opml_file, FeedFetcher};
-use crate::models::{Article, Feed, Summary, SummaryStatus};
-use crate::services::{ContentFetcher, RaindropClient};
-use crate::tui::AppAction;
+use crate::error::{AppError, Result};
+use crate::feed::{
+    export_opml_file, parse_opml_file, ArchiveBackfill, FeedFetcher, FeedPage, FeedProgress,
+    FeedRefresh,
+};
+use crate::models::{
+    extract_anchors, extract_links, markdown_link, normalize_url, origin_discussion, parse_tags,
+    title_prefix, AgeGroup, AiSpend, Annotation, Article, ArticleFilter, CatchUpPlan, ChatMessage,
+    ChatRole, DirectoryFeed, Discussion, Feed, FeedCredentials, FeedSuggestion, FeedUsage,
+    FetchBackoff, KeyFacts, NewArticle, NewFeed, OpmlImport, RefreshReport, RefreshUsage, SortMode,
+    Summary, SummarySource, SummaryStatus, SummaryVariant, TokenUsage, UnreadCounts,
+};
+use crate::services::{
+    delete_feed_secret, export_starred_notes, icon_color, notify_desktop, print_text,
+    render_print_text, store_feed_secret, suggest_feeds, sync_greader, sync_raindrop, webhook_host,
+    Clipboard,
+    ContentFetcher, DiscussionFinder, FeedDirectory, GReaderClient, HypothesisClient, ImageFetcher,
+    RaindropClient, RaindropCollection, RaindropSyncReport, ShareTarget, SocialPoster, Speaker,
+    SyncReport, WebhookArticle, WebhookSender,
+};
+use crate::tui::{
+    detect_graphics_protocol, detect_tty_mode, find_command, fuzzy_filter, AppAction, InputMode,
+    COMMANDS, FILTERS, SORTS,
+};
+
+// Articles published longer ago than this are moved to the trash
+const ARTICLE_MAX_AGE_DAYS: i64 = 7;
+
+// How long a toast message stays in the status bar
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+// Articles embedded per API request during backfill
+const EMBEDDING_BATCH_SIZE: usize = 32;
+
+// Semantic search returns at most this many results
+const SEARCH_MAX_RESULTS: usize = 50;
+
+// Feed content shorter than this is treated as an excerpt rather than the full article
+const EXCERPT_MAX_CHARS: usize = 1000;
+
+// How often to check whether another instance changed the database
+const SYNC_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+// Autocomplete suggestions shown while editing tags
+const TAG_SUGGESTIONS: usize = 5;
+
+// An article counts as read once it has been selected this long
+const READ_DELAY: Duration = Duration::from_secs(2);
+
+// Lead images load once the selection has settled this long (not while scrolling)
+const IMAGE_DELAY: Duration = Duration::from_millis(300);
+
+// Related articles are looked up once the selection has settled this long
+const RELATED_DELAY: Duration = Duration::from_millis(500);
+
+// Related articles shown for the selected article
+const RELATED_MAX_RESULTS: usize = 5;
+
+// Log lines loaded into the logs screen
+const LOG_VIEW_LINES: usize = 500;
+
+// Posts with less room than this after the title and link get no opening line
+const MIN_BLURB_CHARS: usize = 60;
+
+// Sites asked for their favicon at once
+const ICON_FETCH_CONCURRENCY: usize = 5;
+
+// Discussions asked for their comment count at once, and at most per refresh
+const COMMENT_COUNT_CONCURRENCY: usize = 4;
+const COMMENT_COUNT_BATCH: usize = 50;
+// Comment counts are kept up to date for articles this recent, rechecked
+// at most this often
+const COMMENT_COUNT_MAX_AGE_DAYS: i64 = 2;
+const COMMENT_COUNT_RECHECK_MINUTES: i64 = 60;
+
+// Lines the feed content and summary panes move per mouse wheel step
+const WHEEL_SCROLL_LINES: u16 = 3;
+
+// How long a count or `g` waits for the key completing it; a lone digit then
+// lists a single feed or switches workspace and a lone `g` opens the summary
+// styles
+const KEY_PREFIX_TIMEOUT: Duration = Duration::from_millis(800);
+const MAX_COUNT: usize = 9999;
+
+// Candidates looked up before dropping copies of the same story
+const RELATED_CANDIDATES: usize = 20;
+
+// Embedding similarity above which another article counts as related
+const RELATED_MIN_SIMILARITY: f32 = 0.5;
+
+// `ui_state` key of the view saved on quit
+const SESSION_STATE_KEY: &str = "session";
+
+// Days of refresh statistics kept for the stats view
+const REFRESH_STATS_DAYS: i64 = 30;
+
+// Decoded lead images kept in memory
+const IMAGE_CACHE_SIZE: usize = 32;
 
 // Message for completed summary
 pub struct SummaryResult {
     pub article_id: i64,
-    pub result: std::result::Result<(String, String), String>, // (content, model) or error
+    pub variant: Option<SummaryVariant>,
+    pub source: SummarySource,
+    pub result: std::result::Result<GeneratedSummary, String>,
+}
+
+/// A summary as it came back from Claude
+pub struct GeneratedSummary {
+    pub content: String,
+    pub model: String,
+    pub usage: TokenUsage,
+    pub cost_usd: f64,
 }
 
 // Message for completed refresh
 pub struct RefreshResult {
-    pub results: Vec<(i64, Vec<crate::models::NewArticle>)>,
+    pub results: Vec<FeedRefresh>,
+}
+
+/// How far a running refresh has got
+#[derive(Debug, Default)]
+pub struct RefreshProgress {
+    pub total: usize,
+    pub done: usize,
+    /// Feeds being fetched right now, in the order they started
+    pub fetching: Vec<i64>,
+}
+
+// Message for completed sync with the Google Reader API server
+pub struct SyncResult {
+    pub result: std::result::Result<SyncReport, String>,
+}
+
+// Message for completed reconcile with Raindrop.io bookmarks
+pub struct RaindropSyncResult {
+    pub result: std::result::Result<RaindropSyncReport, String>,
+}
+
+// A new article waiting to be posted to the webhooks it matched
+struct PendingWebhook {
+    article_id: i64,
+    article: NewArticle,
+    feed_title: String,
+    feed_url: String,
+    hooks: Vec<usize>,
+}
+
+// A summary asked for and waiting for a free slot, with its input gathered
+struct SummaryJob {
+    article_id: i64,
+    variant: Option<SummaryVariant>,
+    title: String,
+    content: String,
+    source: SummarySource,
+    options: SummaryOptions,
+}
+
+// A new article to summarize in the background (feeds with auto-summarize on)
+struct AutoSummaryJob {
+    article_id: i64,
+    title: String,
+    url: String,
+    content: String,
+    options: SummaryOptions,
+}
+
+// How one background summary went
+enum AutoSummaryOutcome {
+    Summarized(i64),
+    OverBudget,
+    OverMonthlyBudget,
+    Skipped,
+}
+
+// Message for a finished batch of background summaries
+pub struct AutoSummaryResult {
+    pub article_ids: Vec<i64>, // summarized
+    pub over_budget: bool,
+    pub over_monthly_budget: bool,
+}
+
+// Message for finished webhook deliveries after a refresh
+pub struct WebhookResult {
+    pub sent: usize,
+    pub failed: Vec<String>,
+}
+
+// Message for a completed article translation
+pub struct TranslationResult {
+    pub article_id: i64,
+    pub language: String,
+    pub result: std::result::Result<(String, String), String>, // (translation, model) or error
+}
+
+// Message for a completed plain-language rewrite
+pub struct SimplifyResult {
+    pub article_id: i64,
+    pub level: ReadingLevel,
+    pub result: std::result::Result<(String, String), String>, // (rewrite, model) or error
+}
+
+// Message for extracted key facts
+pub struct KeyFactsResult {
+    pub article_id: i64,
+    // (facts, model, usage, cost) or error
+    pub result: std::result::Result<(KeyFacts, String, TokenUsage, f64), String>,
+}
+
+// Message for discussions found for an article
+pub struct DiscussionResult {
+    pub article_id: i64,
+    pub result: std::result::Result<Vec<Discussion>, String>,
+}
+
+// Message for comment counts looked up in the background
+pub struct CommentCountResult {
+    pub counts: Vec<(i64, u32)>, // (article_id, comments)
+}
+
+// Message for Claude's answer in an article chat
+pub struct ChatResult {
+    pub article_id: i64,
+    pub question: String,
+    pub context: String, // article text the answer was based on
+    pub result: std::result::Result<(String, TokenUsage, f64), String>, // (answer, usage, cost)
+}
+
+// Message for a line about an article drafted to share it with
+pub struct ShareBlurbResult {
+    pub article_id: i64,
+    pub result: std::result::Result<String, String>,
+}
+
+// Message for tags Claude picked for an article being saved to Raindrop
+pub struct RaindropTagsResult {
+    pub article_id: i64,
+    pub result: std::result::Result<Vec<String>, String>,
+}
+
+// Message for a published post sharing an article
+pub struct ShareResult {
+    pub target: ShareTarget,
+    pub result: std::result::Result<String, String>, // the post's address or error
+}
+
+// Message for a downloaded OPML file
+pub struct OpmlDownloadResult {
+    pub url: String,
+    pub result: std::result::Result<Vec<NewFeed>, String>,
 }
 
 // Message for completed feed discovery
 pub struct FeedDiscoveryResult {
-    pub result: std::result::Result<crate::models::NewFeed, String>,
+    pub url: String,
+    pub credentials: Option<FeedCredentials>,
+    pub result: std::result::Result<Vec<NewFeed>, String>, // likely main feed first
+    pub unauthorized: bool,
+    pub confirm: bool, // offer the feed instead of subscribing right away
+}
+
+// Message for fetched (or newly created) Hypothes.is annotations
+pub struct AnnotationResult {
+    pub article_id: i64,
+    pub highlighted: bool,
+    pub result: std::result::Result<Vec<Annotation>, String>,
+}
+
+// Message for a loaded lead image
+pub struct ImageResult {
+    pub article_id: i64,
+    pub found_url: Option<String>, // looked up on the article page, to remember
+    pub result: std::result::Result<Option<DynamicImage>, String>,
+}
+
+// Message for completed feed directory search
+pub struct DirectoryResult {
+    pub query: String,
+    pub result: std::result::Result<Vec<DirectoryFeed>, String>,
+}
+
+// Message for feeds suggested from reading history
+pub struct SuggestionResult {
+    pub result: std::result::Result<Vec<FeedSuggestion>, String>,
+}
+
+// Message for completed semantic search
+pub struct SearchResult {
+    pub query: String,
+    pub result: std::result::Result<Vec<i64>, String>, // article ids, best match first
+}
+
+// Message for related articles found for an article
+pub struct RelatedResult {
+    pub article_id: i64,
+    pub result: std::result::Result<Vec<i64>, String>, // article ids, most related first
+}
+
+// Message for completed embedding backfill
+pub struct EmbeddingResult {
+    pub embedded: usize,
+}
+
+// Message for favicons found for feeds that had none stored (PNG)
+pub struct FeedIconResult {
+    pub icons: Vec<(i64, Vec<u8>)>,
+}
+
+/// A feed's favicon and the color its badge is drawn in
+pub struct FeedIcon {
+    pub image: DynamicImage,
+    pub color: Option<(u8, u8, u8)>, // None for monochrome icons
+}
+
+/// Other stored articles about the same topic as an article
+pub struct RelatedArticles {
+    pub article_id: i64,
+    pub article_ids: Vec<i64>,
+}
+
+/// Active semantic search results shown in place of the article list
+pub struct SearchResults {
+    pub query: String,
+    pub article_ids: Vec<i64>,
+}
+
+/// Keyword search of the public feed directory, with its results
+#[derive(Default)]
+pub struct DirectorySearch {
+    pub query: String,
+    pub status: Option<String>,
+    pub results: Vec<DirectoryFeed>,
+    pub cursor: Option<usize>, // highlighted result; None while editing the query
+    pub is_searching: bool,
+}
+
+/// Feeds suggested from reading history, on the Discover screen
+#[derive(Default)]
+pub struct FeedSuggestions {
+    pub suggestions: Vec<FeedSuggestion>,
+    pub cursor: usize,
+    pub status: Option<String>,
+    pub is_loading: bool,
+}
+
+/// Where the user left off, saved on quit and restored on the next start
+#[derive(Serialize, Deserialize)]
+struct SessionState {
+    /// Filter, sort, feed or folder, tag and list width; named after the
+    /// workspace in use, if any
+    view: Workspace,
+    article_id: Option<i64>,
+    /// List position, for when the article is no longer listed
+    index: usize,
+    reader: bool,
+}
+
+/// Network use of recent refreshes, for the stats view
+pub struct RefreshStats {
+    pub history: Vec<RefreshUsage>, // newest first
+    pub feeds: Vec<FeedUsage>,      // heaviest per refresh first
+    pub month_spend: AiSpend,       // summaries this month
+    pub total_spend: AiSpend,       // summaries over all time
+    pub monthly_budget: Option<f64>,
+    pub scroll: usize,
+}
+
+/// Tail of the log file, for seeing why a feed failed
+pub struct LogViewer {
+    pub lines: Vec<String>,
+    pub back: usize, // lines scrolled back from the end
+}
+
+/// Details of an article and the stored articles it links with
+pub struct ArticleInfo {
+    pub article: Article,
+    pub references: Vec<Article>,    // articles it links to
+    pub referenced_by: Vec<Article>, // articles linking to it
+    pub cursor: usize,               // into references, then referenced_by
+}
+
+impl ArticleInfo {
+    /// References followed by referencing articles, in cursor order
+    pub fn linked(&self) -> impl Iterator<Item = &Article> {
+        self.references.iter().chain(&self.referenced_by)
+    }
+}
+
+/// Pane that mouse wheel scrolling applies to, chosen by clicking
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Pane {
+    #[default]
+    List,
+    Content,
+    Summary,
+}
+
+/// Where the main screen was last drawn, to hit-test mouse clicks
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PaneAreas {
+    pub filter_label: Rect,
+    pub list: Rect,
+    pub content: Rect,
+    pub summary: Rect,
+}
+
+/// A count and/or `g` typed ahead of a motion key, as in Vim
+#[derive(Debug, Clone, Copy)]
+struct KeyPrefix {
+    count: Option<usize>,
+    g: bool,
+    typed_at: Instant,
+}
+
+/// A row of the article list: a date header, or the article at a list index
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListRow {
+    Header(AgeGroup),
+    Article(usize),
+}
+
+/// Tags offered while typing the tags of a Raindrop bookmark: Claude's picks
+/// for the article, then `default_tags`
+pub struct RaindropTagSuggestions {
+    pub article_id: i64,
+    pub tags: Vec<String>,
+    pub cursor: usize, // into the tags not entered yet
+    pub loading: bool,
+}
+
+/// Raindrop collections to pick from when saving the selected article
+pub struct RaindropPicker {
+    pub collections: Vec<RaindropCollection>,
+    pub cursor: usize,
+    pub tags: Vec<String>,
+}
+
+/// Runs of similarly titled entries in the list, from feeds that collapse them
+#[derive(Default)]
+struct SimilarGroups {
+    sizes: HashMap<i64, usize>, // first article of a run -> articles after it
+    leaders: HashMap<i64, i64>, // article in a run -> first article of the run
+}
+
+/// Links found in the selected article, to pick one to open
+pub struct LinkMenu {
+    pub links: Vec<(String, String)>, // (text, URL) in order of appearance
+    pub cursor: usize,
+}
+
+/// Hacker News and Reddit threads about the selected article, to pick one to open
+pub struct DiscussionMenu {
+    pub article_id: i64,
+    pub discussions: Vec<Discussion>, // most comments first
+    pub cursor: usize,
+    pub loading: bool,
+}
+
+/// Styles to (re)generate the selected article's summary in
+pub struct SummaryStylePicker {
+    pub article_id: i64,
+    pub cursor: usize,               // 0 is the regular summary, then SummaryVariant::ALL
+    pub saved: Vec<SummaryVariant>, // styles already generated for the article
+}
+
+impl SummaryStylePicker {
+    pub fn selected(&self) -> Option<SummaryVariant> {
+        self.cursor.checked_sub(1).map(|i| SummaryVariant::ALL[i])
+    }
+}
+
+/// Config values shown for viewing and editing
+pub struct SettingsScreen {
+    pub cursor: usize, // index into Setting::ALL
+    pub editing: Option<String>, // new value being typed
+    pub error: Option<String>, // why the last value was refused
+}
+
+/// A post sharing an article, edited before it goes to Mastodon or Bluesky
+pub struct ShareCompose {
+    pub article_id: i64,
+    pub targets: Vec<ShareTarget>, // accounts set up, never empty
+    pub target: usize,
+    pub text: String,
+    pub drafting: bool, // Claude is writing the opening line
+    pub posting: bool,
+    pub status: Option<String>,
+}
+
+impl ShareCompose {
+    pub fn target(&self) -> ShareTarget {
+        self.targets[self.target]
+    }
+}
+
+/// Conversation with Claude about one article
+pub struct ArticleChat {
+    pub article_id: i64,
+    pub title: String,
+    pub messages: Vec<ChatMessage>, // oldest first
+    pub input: String,
+    pub waiting: bool,
+    pub scroll: u16, // lines scrolled up from the latest message
+    // Article text sent with each question, once fetched
+    context: Option<String>,
+}
+
+/// Feeds found on a website, to pick the one to subscribe to
+pub struct FeedPicker {
+    pub feeds: Vec<NewFeed>, // likely main feed first
+    pub cursor: usize,
+    pub credentials: Option<FeedCredentials>,
+}
+
+/// The `:` command line, with the highlighted suggestion
+#[derive(Default)]
+pub struct CommandPalette {
+    pub input: String,
+    pub cursor: usize,
+}
+
+/// Checklist of feeds to include in an OPML export, grouped by category
+pub struct ExportPicker {
+    pub entries: Vec<(i64, bool)>, // (feed id, included)
+    pub cursor: usize,
 }
 
 pub struct App {
     // Data
     pub feeds: Vec<Feed>,
     pub articles: Vec<Article>,
+    pub unread_counts: UnreadCounts,
     pub current_summary: Option<Summary>,
+    pub full_text: Option<String>, // page text fetched earlier for the selected article
+    pub translation: Option<String>, // selected article in `translate_to`, once loaded
+    pub simplification: Option<(ReadingLevel, String)>, // selected article in plain language
+    pub reading_level: ReadingLevel,
+    pub annotations: Vec<Annotation>,
+
+    // Cached `filtered_articles` order, see `invalidate_filter`
+    filtered: OnceCell<Vec<usize>>,
+    list_rows: OnceCell<Vec<ListRow>>,
+    similar_groups: OnceCell<SimilarGroups>,
+    // Runs of similar titles listed in full, by their first article
+    expanded_groups: HashSet<i64>,
 
     // UI State
     pub selected_index: usize,
+    /// First list row on screen (date headers included), updated by the renderer
+    pub list_offset: Cell<usize>,
     pub show_help: bool,
     pub tag_input_active: bool,
     pub tag_input: String,
+    pub raindrop_tags: Option<RaindropTagSuggestions>,
+    pub raindrop_picker: Option<RaindropPicker>,
+    raindrop_collections: Option<Vec<RaindropCollection>>, // fetched on first save
+    pub article_tags_active: bool,
+    pub article_tags_input: String,
+    pub known_tags: Vec<String>, // every tag in use, most used first
+    pub tag_filter: Option<String>,
+    pub feed_filter: Option<i64>, // list only this feed's articles
+    pub category_filter: Option<String>, // list only feeds in this OPML folder
+    pub list_width: u16,               // percent of the window
+    pub workspace: Option<String>,     // name of the workspace last switched to
+    pub command_palette: Option<CommandPalette>,
     pub feed_input_active: bool,
     pub feed_input: String,
     pub feed_input_status: Option<String>,
+    // Set while the add-feed popup is asking for credentials for this URL
+    pub feed_auth_url: Option<String>,
+    // Feed found for the selected article's site, waiting for Enter to subscribe
+    pub feed_offer: Option<NewFeed>,
+    pub feed_picker: Option<FeedPicker>, // several feeds found, pick one
+    pub feed_import: InitialImport, // how much history the feed being added brings in
+    // Items to keep from the first fetch of newly added feeds
+    pending_imports: HashMap<i64, usize>,
+    pub directory_search: Option<DirectorySearch>,
+    pub feed_suggestions: Option<FeedSuggestions>,
+    pub refresh_stats: Option<RefreshStats>,
+    pub log_viewer: Option<LogViewer>,
+    pub article_info: Option<ArticleInfo>,
+    pub link_menu: Option<LinkMenu>,
+    pub discussion_menu: Option<DiscussionMenu>,
+    pub summary_styles: Option<SummaryStylePicker>,
+    /// Comments on articles with a known HN or Reddit discussion
+    pub comment_counts: HashMap<i64, u32>,
+    is_counting_comments: bool,
+    pub chat: Option<ArticleChat>,
+    pub chat_max_scroll: Cell<u16>,
+    pub share: Option<ShareCompose>,
+    pub settings: Option<SettingsScreen>,
+    // New auto-refresh interval for the event loop's scheduler
+    refresh_interval_change: Option<u32>,
     pub opml_input_active: bool,
     pub opml_input: String,
     pub opml_input_status: Option<String>,
     pub opml_export_active: bool,
     pub opml_export_input: String,
     pub opml_export_status: Option<String>,
+    pub opml_export_picker: Option<ExportPicker>,
+    pub catchup_input_active: bool,
+    pub catchup_input: String,
+    pub catchup_input_status: Option<String>,
+    pub catchup: Option<CatchUpPlan>,
+    pub search_input_active: bool,
+    pub search_input: String,
+    pub search_input_status: Option<String>,
+    pub search_results: Option<SearchResults>,
+    pub feed_settings: Option<i64>, // feed whose settings popup is open
+    pub reader_active: bool,
+    pub reader_scroll: u16,
+    /// Largest useful scroll offset, updated by the renderer
+    pub reader_max_scroll: Cell<u16>,
+    /// Visible height of the reader, updated by the renderer (for paging)
+    pub reader_page_height: Cell<u16>,
+    /// Where the lead image goes this frame, updated by the renderer
+    pub image_area: Cell<Option<Rect>>,
+    pub focused_pane: Pane,
+    /// Updated by the renderer
+    pub pane_areas: Cell<PaneAreas>,
+    pub content_scroll: u16,
+    pub summary_scroll: u16,
+    /// Largest useful scroll offsets of the right-hand panes, updated by the renderer
+    pub content_max_scroll: Cell<u16>,
+    pub summary_max_scroll: Cell<u16>,
+    /// None when images are off or the terminal can't show them
+    pub image_protocol: Option<GraphicsProtocol>,
+    /// ASCII borders, plain spinner and 8 colors for limited terminals
+    pub tty_mode: bool,
     pub is_saved_to_raindrop: bool,
-    pub last_deleted: Option<(i64, String)>, // (feed_id, guid) for undo
+    pub highlight_picker: Option<usize>, // cursor into highlight_candidates()
+    pub related: Option<RelatedArticles>,
+    pub related_picker: Option<usize>, // cursor into related_articles()
+    pub last_deleted: Option<i64>, // trashed article id for undo
+    pub revisit_id: Option<i64>,   // starred article resurfaced today, listed first
+    revisit_day: Option<chrono::NaiveDate>,
     pub spinner_frame: usize,
     pub saved_count: usize,
+    pub sort_mode: SortMode,
+    pub filter: ArticleFilter,
+    pub toast: Option<(String, Instant)>,
+    data_version: i64,
+    last_sync_check: Instant,
+    selected_at: Instant,
+    // Marked read in the database but not in `articles`, so views don't shift
+    read_in_db: HashSet<i64>,
+    key_prefix: Option<KeyPrefix>,
+    // Set when hiding a read article moved the cursor, so the article that
+    // took its place isn't marked read until it is chosen
+    read_timer_paused: bool,
 
     // Async state
     pub is_refreshing: bool,
+    pub refresh_progress: RefreshProgress,
+    pub is_syncing: bool,
+    is_syncing_raindrop: bool,
+    pub is_searching: bool,
+    pub is_speaking: bool,
+    pub speech_paused: bool,
+    pub show_translation: bool,
+    pub translating: Option<i64>, // article being translated
+    pub show_simplified: bool,
+    pub simplifying: Option<i64>, // article being rewritten in plain language
+    pub is_loading_annotations: bool,
+    annotations_article_id: Option<i64>,
+    loading_image: Option<i64>,
+    loading_related: Option<i64>,
+    images: HashMap<i64, Option<Arc<DynamicImage>>>, // None: article has no usable image
+    image_order: VecDeque<i64>,                      // cache eviction order
+    is_embedding: bool,
+    /// Feed badges in the article list (`feed_icons` in `[images]`)
+    pub feed_badges: bool,
+    pub feed_icons: HashMap<i64, FeedIcon>,
+    is_fetching_icons: bool,
+    // Batches of new articles still being posted to webhooks
+    sending_webhooks: usize,
+    // Batches of new articles still being summarized in the background
+    auto_summarizing: usize,
     pub summary_status: SummaryStatus,
     pub pending_summary_article_id: Option<i64>,
+    pub key_facts: Option<KeyFacts>,
+    pub extracting_facts: Option<i64>,
+    // Summaries waiting for one of the `summary.concurrency` slots, oldest first
+    summary_queue: VecDeque<SummaryJob>,
+    // Summaries being made right now
+    summarizing: Vec<(i64, Option<SummaryVariant>)>,
     summary_rx: mpsc::Receiver<SummaryResult>,
     summary_tx: mpsc::Sender<SummaryResult>,
+    translation_rx: mpsc::Receiver<TranslationResult>,
+    translation_tx: mpsc::Sender<TranslationResult>,
+    simplify_rx: mpsc::Receiver<SimplifyResult>,
+    simplify_tx: mpsc::Sender<SimplifyResult>,
+    key_facts_rx: mpsc::Receiver<KeyFactsResult>,
+    key_facts_tx: mpsc::Sender<KeyFactsResult>,
+    discussion_rx: mpsc::Receiver<DiscussionResult>,
+    discussion_tx: mpsc::Sender<DiscussionResult>,
+    comment_count_rx: mpsc::Receiver<CommentCountResult>,
+    comment_count_tx: mpsc::Sender<CommentCountResult>,
+    chat_rx: mpsc::Receiver<ChatResult>,
+    chat_tx: mpsc::Sender<ChatResult>,
+    share_blurb_rx: mpsc::Receiver<ShareBlurbResult>,
+    share_blurb_tx: mpsc::Sender<ShareBlurbResult>,
+    raindrop_tags_rx: mpsc::Receiver<RaindropTagsResult>,
+    raindrop_tags_tx: mpsc::Sender<RaindropTagsResult>,
+    share_rx: mpsc::Receiver<ShareResult>,
+    share_tx: mpsc::Sender<ShareResult>,
     refresh_rx: mpsc::Receiver<RefreshResult>,
     refresh_tx: mpsc::Sender<RefreshResult>,
+    refresh_progress_rx: mpsc::UnboundedReceiver<FeedProgress>,
+    refresh_progress_tx: mpsc::UnboundedSender<FeedProgress>,
+    sync_rx: mpsc::Receiver<SyncResult>,
+    sync_tx: mpsc::Sender<SyncResult>,
+    raindrop_sync_rx: mpsc::Receiver<RaindropSyncResult>,
+    raindrop_sync_tx: mpsc::Sender<RaindropSyncResult>,
     discovery_rx: mpsc::Receiver<FeedDiscoveryResult>,
     discovery_tx: mpsc::Sender<FeedDiscoveryResult>,
+    opml_download_rx: mpsc::Receiver<OpmlDownloadResult>,
+    opml_download_tx: mpsc::Sender<OpmlDownloadResult>,
+    directory_rx: mpsc::Receiver<DirectoryResult>,
+    directory_tx: mpsc::Sender<DirectoryResult>,
+    suggestion_rx: mpsc::Receiver<SuggestionResult>,
+    suggestion_tx: mpsc::Sender<SuggestionResult>,
+    annotation_rx: mpsc::Receiver<AnnotationResult>,
+    annotation_tx: mpsc::Sender<AnnotationResult>,
+    image_rx: mpsc::Receiver<ImageResult>,
+    image_tx: mpsc::Sender<ImageResult>,
+    related_rx: mpsc::Receiver<RelatedResult>,
+    related_tx: mpsc::Sender<RelatedResult>,
+    search_rx: mpsc::Receiver<SearchResult>,
+    search_tx: mpsc::Sender<SearchResult>,
+    embedding_rx: mpsc::Receiver<EmbeddingResult>,
+    embedding_tx: mpsc::Sender<EmbeddingResult>,
+    icon_rx: mpsc::Receiver<FeedIconResult>,
+    icon_tx: mpsc::Sender<FeedIconResult>,
+    webhook_rx: mpsc::Receiver<WebhookResult>,
+    webhook_tx: mpsc::Sender<WebhookResult>,
+    auto_summary_rx: mpsc::Receiver<AutoSummaryResult>,
+    auto_summary_tx: mpsc::Sender<AutoSummaryResult>,
 
     // Services
+    config: Config,
     pub repository: Repository,
     fetcher: FeedFetcher,
     summarizer: Option<Arc<Summarizer>>,
+    embedder: Option<Arc<Embedder>>,
     raindrop: Option<RaindropClient>,
+    hypothesis: Option<Arc<HypothesisClient>>,
+    social: Option<Arc<SocialPoster>>,
+    greader: Option<Arc<GReaderClient>>,
+    speaker: Option<Speaker>,
+    clipboard: Clipboard,
     content_fetcher: ContentFetcher,
+    image_fetcher: ImageFetcher,
+    discussion_finder: DiscussionFinder,
+    feed_directory: FeedDirectory,
+    webhook_sender: WebhookSender,
 }
 
 impl App {
-    pub async fn new(config: &Config) -> Result<Self> {
+    /// `cleanup` trashes old articles on the way, as every start does except
+    /// a dry run
+    pub async fn new(config: &Config, cleanup: bool) -> Result<Self> {
         let repository = Repository::new(&config.db_path).await?;
-        let fetcher = FeedFetcher::new();
+        let fetcher = FeedFetcher::new(config.proxy_for(ProxyService::Feeds));
 
-        let summarizer = config
-            .claude_api_key
-            .as_ref()
-            .map(|key| Arc::new(Summarizer::new(key.clone())));
+        let summarizer = config.claude_api_key.as_ref().map(|key| {
+            let proxy = config.proxy_for(ProxyService::Claude);
+            Arc::new(Summarizer::new(key.clone(), &config.summary, proxy))
+        });
+
+        let raindrop = config.raindrop_token.as_ref().map(|token| {
+            RaindropClient::new(token.clone(), config.proxy_for(ProxyService::Raindrop))
+        });
+
+        let hypothesis = config.hypothesis_token.as_ref().map(|token| {
+            let proxy = config.proxy_for(ProxyService::Hypothesis);
+            Arc::new(HypothesisClient::new(token.clone(), proxy))
+        });
+
+        let social = SocialPoster::new(
+            config.social.mastodon_instance.as_deref().zip(config.mastodon_token.as_deref()),
+            config.social.bluesky_handle.as_deref().zip(config.bluesky_app_password.as_deref()),
+            config.proxy_for(ProxyService::Social),
+        )
+        .map(Arc::new);
 
-        let raindrop = config
-            .raindrop_token
+        let greader = config
+            .sync
             .as_ref()
-            .map(|token| RaindropClient::new(token.clone()));
+            .map(|sync| Arc::new(GReaderClient::new(sync, config.proxy_for(ProxyService::Sync))));
+
+        let embedder = config.embeddings.as_ref().map(|embeddings| {
+            Arc::new(Embedder::new(embeddings, config.proxy_for(ProxyService::Embeddings)))
+        });
+
+        let content_fetcher =
+            ContentFetcher::new(&config.cookie_domains, config.proxy_for(ProxyService::Content));
+
+        let mut feed_icons = HashMap::new();
+        if config.images.feed_icons {
+            for (feed_id, png) in repository.get_feed_icons().await? {
+                if let Some(icon) = Self::decode_feed_icon(&png) {
+                    feed_icons.insert(feed_id, icon);
+                }
+            }
+        }
 
-        let content_fetcher = ContentFetcher::new();
+        let image_protocol = if config.images.enabled {
+            config.images.protocol.or_else(detect_graphics_protocol)
+        } else {
+            None
+        };
 
-        // Clean up articles older than 7 days
-        let deleted = repository.delete_old_articles(7).await?;
-        if deleted > 0 {
-            tracing::info!("Deleted {} articles older than 7 days", deleted);
+        // Move articles older than 7 days to the trash
+        if cleanup {
+            let trashed = repository
+                .delete_old_articles(
+                    ARTICLE_MAX_AGE_DAYS,
+                    config.trash_retention_days as i64,
+                    config.cleanup,
+                )
+                .await?;
+            if trashed > 0 {
+                tracing::info!("Moved {} articles older than 7 days to trash", trashed);
+            }
         }
 
         let feeds = repository.get_all_feeds().await?;
         let articles = repository.get_all_articles_sorted().await?;
+        let data_version = repository.data_version().await?;
+        let unread_counts = UnreadCounts::new(repository.get_unread_counts().await?);
+        let known_tags = repository.get_all_tags().await?;
+        let comment_counts = repository.get_comment_counts().await?;
 
         let (summary_tx, summary_rx) = mpsc::channel(1);
+        let (translation_tx, translation_rx) = mpsc::channel(1);
+        let (simplify_tx, simplify_rx) = mpsc::channel(1);
+        let (key_facts_tx, key_facts_rx) = mpsc::channel(1);
+        let (discussion_tx, discussion_rx) = mpsc::channel(1);
+        let (comment_count_tx, comment_count_rx) = mpsc::channel(1);
+        let (chat_tx, chat_rx) = mpsc::channel(1);
+        let (share_blurb_tx, share_blurb_rx) = mpsc::channel(1);
+        let (raindrop_tags_tx, raindrop_tags_rx) = mpsc::channel(1);
+        let (share_tx, share_rx) = mpsc::channel(1);
         let (refresh_tx, refresh_rx) = mpsc::channel(1);
+        let (refresh_progress_tx, refresh_progress_rx) = mpsc::unbounded_channel();
+        let (sync_tx, sync_rx) = mpsc::channel(1);
+        let (raindrop_sync_tx, raindrop_sync_rx) = mpsc::channel(1);
+        let (webhook_tx, webhook_rx) = mpsc::channel(4);
+        let (auto_summary_tx, auto_summary_rx) = mpsc::channel(4);
         let (discovery_tx, discovery_rx) = mpsc::channel(1);
+        let (opml_download_tx, opml_download_rx) = mpsc::channel(1);
+        let (directory_tx, directory_rx) = mpsc::channel(1);
+        let (suggestion_tx, suggestion_rx) = mpsc::channel(1);
+        let (annotation_tx, annotation_rx) = mpsc::channel(1);
+        let (image_tx, image_rx) = mpsc::channel(1);
+        let (related_tx, related_rx) = mpsc::channel(1);
+        let (search_tx, search_rx) = mpsc::channel(1);
+        let (embedding_tx, embedding_rx) = mpsc::channel(1);
+        let (icon_tx, icon_rx) = mpsc::channel(1);
 
         Ok(Self {
             feeds,
             articles,
+            unread_counts,
             current_summary: None,
+            full_text: None,
+            translation: None,
+            simplification: None,
+            reading_level: config.reading_level,
+            annotations: Vec::new(),
+            filtered: OnceCell::new(),
+            list_rows: OnceCell::new(),
+            similar_groups: OnceCell::new(),
+            expanded_groups: HashSet::new(),
             selected_index: 0,
+            list_offset: Cell::new(0),
             show_help: false,
             tag_input_active: false,
             tag_input: String::new(),
+            raindrop_tags: None,
+            raindrop_picker: None,
+            raindrop_collections: None,
+            article_tags_active: false,
+            article_tags_input: String::new(),
+            known_tags,
+            tag_filter: None,
+            feed_filter: None,
+            category_filter: None,
+            list_width: config.list_width.clamp(10, 90),
+            workspace: None,
+            command_palette: None,
             feed_input_active: false,
             feed_input: String::new(),
             feed_input_status: None,
+            feed_auth_url: None,
+            feed_offer: None,
+            feed_picker: None,
+            feed_import: config.initial_import,
+            pending_imports: HashMap::new(),
+            directory_search: None,
+            feed_suggestions: None,
             opml_input_active: false,
             opml_input: String::new(),
             opml_input_status: None,
             opml_export_active: false,
             opml_export_input: String::new(),
             opml_export_status: None,
+            opml_export_picker: None,
+            catchup_input_active: false,
+            catchup_input: String::new(),
+            catchup_input_status: None,
+            catchup: None,
+            search_input_active: false,
+            search_input: String::new(),
+            search_input_status: None,
+            search_results: None,
+            feed_settings: None,
+            reader_active: false,
+            reader_scroll: 0,
+            reader_max_scroll: Cell::new(0),
+            reader_page_height: Cell::new(0),
+            image_area: Cell::new(None),
+            focused_pane: Pane::List,
+            pane_areas: Cell::new(PaneAreas::default()),
+            content_scroll: 0,
+            summary_scroll: 0,
+            content_max_scroll: Cell::new(0),
+            summary_max_scroll: Cell::new(0),
+            image_protocol,
+            tty_mode: config.tty_mode.unwrap_or_else(detect_tty_mode),
             is_saved_to_raindrop: false,
+            highlight_picker: None,
+            related: None,
+            related_picker: None,
+            refresh_stats: None,
+            log_viewer: None,
+            article_info: None,
+            link_menu: None,
+            discussion_menu: None,
+            summary_styles: None,
+            comment_counts,
+            is_counting_comments: false,
+            chat: None,
+            share: None,
+            chat_max_scroll: Cell::new(0),
+            settings: None,
+            refresh_interval_change: None,
             last_deleted: None,
             spinner_frame: 0,
             saved_count: 0,
+            revisit_id: None,
+            revisit_day: None,
+            sort_mode: config.sort_mode,
+            filter: ArticleFilter::default(),
+            toast: None,
             is_refreshing: false,
+            refresh_progress: RefreshProgress::default(),
+            is_syncing: false,
+            is_syncing_raindrop: false,
+            is_searching: false,
+            is_speaking: false,
+            speech_paused: false,
+            show_translation: false,
+            translating: None,
+            show_simplified: false,
+            simplifying: None,
+            is_loading_annotations: false,
+            annotations_article_id: None,
+            loading_image: None,
+            loading_related: None,
+            images: HashMap::new(),
+            image_order: VecDeque::new(),
+            is_embedding: false,
+            feed_badges: config.images.feed_icons,
+            feed_icons,
+            is_fetching_icons: false,
+            sending_webhooks: 0,
+            auto_summarizing: 0,
             summary_status: SummaryStatus::NotGenerated,
             pending_summary_article_id: None,
+            key_facts: None,
+            extracting_facts: None,
+            summary_queue: VecDeque::new(),
+            summarizing: Vec::new(),
             summary_rx,
             summary_tx,
+            translation_rx,
+            translation_tx,
+            simplify_rx,
+            simplify_tx,
+            key_facts_rx,
+            key_facts_tx,
+            discussion_rx,
+            discussion_tx,
+            comment_count_rx,
+            comment_count_tx,
+            chat_rx,
+            chat_tx,
+            share_blurb_rx,
+            share_blurb_tx,
+            raindrop_tags_rx,
+            raindrop_tags_tx,
+            share_rx,
+            share_tx,
             refresh_rx,
             refresh_tx,
+            refresh_progress_rx,
+            refresh_progress_tx,
+            sync_rx,
+            sync_tx,
+            raindrop_sync_rx,
+            raindrop_sync_tx,
             discovery_rx,
             discovery_tx,
+            opml_download_rx,
+            opml_download_tx,
+            directory_rx,
+            directory_tx,
+            suggestion_rx,
+            suggestion_tx,
+            annotation_rx,
+            annotation_tx,
+            image_rx,
+            image_tx,
+            related_rx,
+            related_tx,
+            search_rx,
+            search_tx,
+            embedding_rx,
+            embedding_tx,
+            icon_rx,
+            icon_tx,
+            webhook_rx,
+            webhook_tx,
+            auto_summary_rx,
+            auto_summary_tx,
+            data_version,
+            last_sync_check: Instant::now(),
+            selected_at: Instant::now(),
+            read_in_db: HashSet::new(),
+            key_prefix: None,
+            read_timer_paused: false,
+            config: config.clone(),
             repository,
             fetcher,
             summarizer,
+            embedder,
             raindrop,
+            hypothesis,
+            social,
+            greader,
+            speaker: Speaker::new(config.speech.command.as_deref()),
+            clipboard: Clipboard::new(),
             content_fetcher,
+            image_fetcher: ImageFetcher::new(config.proxy_for(ProxyService::Images)),
+            discussion_finder: DiscussionFinder::new(config.proxy_for(ProxyService::Discussions)),
+            feed_directory: FeedDirectory::new(config.proxy_for(ProxyService::Directory)),
+            webhook_sender: WebhookSender::new(config.proxy_for(ProxyService::Webhooks)),
+        })
+    }
+
+    /// Popup that currently receives key input (help takes precedence)
+    pub fn input_mode(&self) -> InputMode {
+        if self.show_help {
+            InputMode::Help
+        } else if self.command_palette.is_some() {
+            InputMode::CommandPalette
+        } else if let Some(settings) = &self.settings {
+            if settings.editing.is_some() {
+                InputMode::SettingsEdit
+            } else {
+                InputMode::Settings
+            }
+        } else if self.tag_input_active {
+            InputMode::TagInput
+        } else if self.raindrop_picker.is_some() {
+            InputMode::RaindropPicker
+        } else if self.article_tags_active {
+            InputMode::ArticleTags
+        } else if self.feed_picker.is_some() {
+            InputMode::FeedPicker
+        } else if self.feed_input_active {
+            InputMode::FeedInput
+        } else if self.directory_search.is_some() {
+            InputMode::FeedDirectory
+        } else if self.feed_suggestions.is_some() {
+            InputMode::Discover
+        } else if self.opml_input_active {
+            InputMode::OpmlInput
+        } else if self.opml_export_active {
+            InputMode::OpmlExport
+        } else if self.opml_export_picker.is_some() {
+            InputMode::OpmlExportPicker
+        } else if self.catchup_input_active {
+            InputMode::CatchUpInput
+        } else if self.search_input_active {
+            InputMode::SearchInput
+        } else if self.feed_settings.is_some() {
+            InputMode::FeedSettings
+        } else if self.highlight_picker.is_some() {
+            InputMode::HighlightPicker
+        } else if self.related_picker.is_some() {
+            InputMode::RelatedPicker
+        } else if self.refresh_stats.is_some() {
+            InputMode::RefreshStats
+        } else if self.log_viewer.is_some() {
+            InputMode::Logs
+        } else if self.article_info.is_some() {
+            InputMode::ArticleInfo
+        } else if self.link_menu.is_some() {
+            InputMode::LinkMenu
+        } else if self.discussion_menu.is_some() {
+            InputMode::DiscussionMenu
+        } else if self.summary_styles.is_some() {
+            InputMode::SummaryStyles
+        } else if self.chat.is_some() {
+            InputMode::Chat
+        } else if self.share.is_some() {
+            InputMode::Share
+        } else if self.reader_active {
+            InputMode::Reader
+        } else {
+            InputMode::Normal
+        }
+    }
+
+    /// Articles in the list, in list order
+    pub fn filtered_articles(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = &Article> + ExactSizeIterator {
+        self.filtered_indices().iter().map(|&i| &self.articles[i])
+    }
+
+    pub fn filtered_len(&self) -> usize {
+        self.filtered_indices().len()
+    }
+
+    pub fn filtered_article(&self, index: usize) -> Option<&Article> {
+        self.filtered_indices().get(index).map(|&i| &self.articles[i])
+    }
+
+    /// Indices into `articles` of the listed articles, computed once and kept
+    /// until `invalidate_filter`
+    fn filtered_indices(&self) -> &[usize] {
+        self.filtered.get_or_init(|| {
+            let (indices, groups) = self.collapse_similar(self.filter_articles());
+            let _ = self.similar_groups.set(groups);
+            indices
+        })
+    }
+
+    /// Forget the cached article list; call after changing articles, feeds or
+    /// any of the filters
+    fn invalidate_filter(&mut self) {
+        self.filtered.take();
+        self.similar_groups.take();
+        self.list_rows.take();
+    }
+
+    /// Rows of the article list: the articles, under date headers when the
+    /// list is sorted by date
+    pub fn list_rows(&self) -> &[ListRow] {
+        self.list_rows.get_or_init(|| {
+            if !self.groups_by_date() {
+                return (0..self.filtered_len()).map(ListRow::Article).collect();
+            }
+            let today = chrono::Local::now().date_naive();
+            let mut rows = Vec::with_capacity(self.filtered_len() + 4);
+            let mut group = None;
+            for (index, article) in self.filtered_articles().enumerate() {
+                let age = article.age_group(today);
+                if group != Some(age) {
+                    rows.push(ListRow::Header(age));
+                    group = Some(age);
+                }
+                rows.push(ListRow::Article(index));
+            }
+            rows
         })
     }
 
-    pub fn filtered_articles(&self) -> Vec<&Article> {
-        self.articles.iter().collect()
+    /// Row of the list the selected article is on
+    pub fn selected_row(&self) -> usize {
+        let rows = self.list_rows();
+        rows.iter()
+            .position(|row| *row == ListRow::Article(self.selected_index))
+            .unwrap_or(0)
+    }
+
+    // Catch-up plans, search results and the queue have their own order
+    fn groups_by_date(&self) -> bool {
+        self.config.group_by_date
+            && matches!(self.sort_mode, SortMode::NewestFirst | SortMode::OldestFirst)
+            && self.filter != ArticleFilter::Queue
+            && self.catchup.is_none()
+            && self.search_results.is_none()
+    }
+
+    /// For the first article of a run of similar titles: how many follow it,
+    /// and whether they are listed
+    pub fn similar_titles(&self, article_id: i64) -> Option<(usize, bool)> {
+        self.filtered_indices();
+        let more = *self.similar_groups.get()?.sizes.get(&article_id)?;
+        Some((more, self.expanded_groups.contains(&article_id)))
+    }
+
+    /// First article of the run of similar titles an article belongs to
+    fn similar_group_of(&self, article_id: i64) -> Option<i64> {
+        self.filtered_indices();
+        self.similar_groups.get()?.leaders.get(&article_id).copied()
+    }
+
+    /// Keep only the first of each run of consecutive entries that share a
+    /// title prefix, in feeds with `collapse_similar`, unless the run is
+    /// expanded. Catch-up, search results and the queue list everything.
+    fn collapse_similar(&self, indices: Vec<usize>) -> (Vec<usize>, SimilarGroups) {
+        let mut groups = SimilarGroups::default();
+        let collapsing: HashSet<i64> = self
+            .feeds
+            .iter()
+            .filter(|f| f.rules.collapse_similar)
+            .map(|f| f.id)
+            .collect();
+        if collapsing.is_empty()
+            || self.catchup.is_some()
+            || self.search_results.is_some()
+            || self.filter == ArticleFilter::Queue
+        {
+            return (indices, groups);
+        }
+
+        let mut kept = Vec::with_capacity(indices.len());
+        // First article, feed and title prefix of the current run
+        let mut run: Option<(i64, i64, String)> = None;
+        for i in indices {
+            let article = &self.articles[i];
+            let prefix = collapsing
+                .contains(&article.feed_id)
+                .then(|| title_prefix(&article.title))
+                .flatten();
+            if let (Some((leader, feed_id, run_prefix)), Some(prefix)) = (&run, &prefix) {
+                if *feed_id == article.feed_id && run_prefix == prefix {
+                    *groups.sizes.entry(*leader).or_default() += 1;
+                    groups.leaders.insert(*leader, *leader);
+                    groups.leaders.insert(article.id, *leader);
+                    if self.expanded_groups.contains(leader) {
+                        kept.push(i);
+                    }
+                    continue;
+                }
+            }
+            run = prefix.map(|prefix| (article.id, article.feed_id, prefix));
+            kept.push(i);
+        }
+        (kept, groups)
+    }
+
+    /// Expand or collapse the run of similar titles the selected article is
+    /// in, keeping the run's first article selected
+    async fn toggle_similar_group(&mut self) -> Result<()> {
+        let Some(id) = self.selected_article().map(|a| a.id) else {
+            return Ok(());
+        };
+        let Some(leader) = self.similar_group_of(id) else {
+            self.show_toast("No similar titles to expand".to_string());
+            return Ok(());
+        };
+        if !self.expanded_groups.remove(&leader) {
+            self.expanded_groups.insert(leader);
+        }
+        self.invalidate_filter();
+        self.select_article_id(leader).await
+    }
+
+    fn filter_articles(&self) -> Vec<usize> {
+        // Catch-up mode shows only the plan, in plan order; search mode
+        // shows matches, best first
+        let listed = self
+            .catchup
+            .as_ref()
+            .map(|plan| &plan.article_ids)
+            .or(self.search_results.as_ref().map(|search| &search.article_ids));
+        if let Some(ids) = listed {
+            let positions: HashMap<i64, usize> =
+                self.articles.iter().enumerate().map(|(i, a)| (a.id, i)).collect();
+            return ids
+                .iter()
+                .filter_map(|id| positions.get(id).copied())
+                .filter(|&i| !self.articles[i].is_trashed())
+                .collect();
+        }
+
+        // Copies of a story from other feeds only show through their canonical article
+        let loaded: HashSet<i64> = self.articles.iter().map(|a| a.id).collect();
+
+        let category_feeds: Option<HashSet<i64>> = self.category_filter.as_ref().map(|category| {
+            self.feeds
+                .iter()
+                .filter(|f| f.category.as_ref() == Some(category))
+                .map(|f| f.id)
+                .collect()
+        });
+
+        // Feeds that opted out of the unread filter list read articles too
+        let skip_unread: Vec<i64> = self
+            .feeds
+            .iter()
+            .filter(|f| f.rules.skip_unread_filter)
+            .map(|f| f.id)
+            .collect();
+        let mut indices: Vec<usize> = self
+            .articles
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| !a.canonical_id.is_some_and(|id| loaded.contains(&id)))
+            .filter(|(_, a)| self.tag_filter.as_ref().is_none_or(|tag| a.tags.contains(tag)))
+            .filter(|(_, a)| self.feed_filter.is_none_or(|id| a.feed_id == id))
+            .filter(|(_, a)| category_feeds.as_ref().is_none_or(|ids| ids.contains(&a.feed_id)))
+            .filter(|(_, a)| {
+                self.filter.matches(a)
+                    || (self.filter == ArticleFilter::Unread
+                        && !a.is_trashed()
+                        && skip_unread.contains(&a.feed_id))
+            })
+            .map(|(i, _)| i)
+            .collect();
+        let article = |i: &usize| &self.articles[*i];
+        // The reading queue keeps the order articles were added in
+        if self.filter == ArticleFilter::Queue {
+            indices.sort_by_key(|i| article(i).queued_at);
+            return indices;
+        }
+        match self.sort_mode {
+            // Repository already returns newest first
+            SortMode::NewestFirst => {}
+            SortMode::OldestFirst => indices.reverse(),
+            SortMode::ByFeed => indices.sort_by_cached_key(|i| {
+                article(i).feed_title.as_deref().unwrap_or("").to_lowercase()
+            }),
+            SortMode::ByTitle => {
+                indices.sort_by_cached_key(|i| article(i).title.to_lowercase());
+            }
+            SortMode::UnreadFirst => indices.sort_by_key(|i| article(i).is_read),
+            SortMode::Deadline => {
+                // Upcoming soonest first, then undated, then expired (most recent first)
+                let today = chrono::Local::now().date_naive();
+                indices.sort_by_key(|i| match article(i).deadline {
+                    Some(date) if date >= today => (0, date.signed_duration_since(today).num_days()),
+                    None => (1, 0),
+                    Some(date) => (2, today.signed_duration_since(date).num_days()),
+                });
+            }
+        }
+        // The starred article resurfaced today leads the list
+        let revisit = self
+            .revisit_id
+            .and_then(|id| indices.iter().position(|&i| self.articles[i].id == id));
+        if let Some(position) = revisit {
+            let index = indices.remove(position);
+            indices.insert(0, index);
+        }
+        indices
     }
 
     pub fn selected_article(&self) -> Option<&Article> {
-        let articles = self.filtered_articles();
-        articles.get(self.selected_index).copied()
+        self.filtered_article(self.selected_index)
     }
 
     pub async fn handle_action(&mut self, action: AppAction) -> Result<bool> {
+        // A count or `g` only applies to the key right after it
+        let prefix = self.key_prefix.take();
+        let count = prefix.and_then(|p| p.count);
         match action {
             AppAction::Quit => {
-                // Compact database on exit (remove old articles and vacuum)
-                let _ = self.repository.compact_database(7).await;
+                self.stop_speaking();
+                self.save_session().await;
+                // Compact database on exit (retire old articles, purge trash, vacuum)
+                let _ = self
+                    .repository
+                    .compact_database(
+                        ARTICLE_MAX_AGE_DAYS,
+                        self.config.trash_retention_days as i64,
+                        self.config.cleanup,
+                    )
+                    .await;
                 return Ok(true);
             }
 
             AppAction::MoveUp => {
-                let len = self.filtered_articles().len();
-                if len > 0 && self.selected_index > 0 {
-                    self.selected_index -= 1;
-                    self.on_selection_changed().await?;
-                }
+                let index = self.selected_index.saturating_sub(count.unwrap_or(1));
+                self.select_index(index).await?;
             }
 
             AppAction::MoveDown => {
-                let len = self.filtered_articles().len();
-                if len > 0 && self.selected_index < len - 1 {
-                    self.selected_index += 1;
-                    self.on_selection_changed().await?;
-                }
+                self.select_index(self.selected_index + count.unwrap_or(1)).await?;
             }
 
+            // With a count, both go to that article (5G, 5gg)
             AppAction::MoveToTop => {
-                if !self.filtered_articles().is_empty() && self.selected_index != 0 {
-                    self.selected_index = 0;
-                    self.on_selection_changed().await?;
-                }
+                self.select_index(count.map_or(0, |c| c - 1)).await?;
             }
 
             AppAction::MoveToBottom => {
-                let len = self.filtered_articles().len();
-                if len > 0 && self.selected_index != len - 1 {
-                    self.selected_index = len - 1;
-                    self.on_selection_changed().await?;
-                }
+                self.select_index(count.map_or(usize::MAX, |c| c - 1)).await?;
             }
 
-            AppAction::SelectArticle => {
-                self.generate_summary().await?;
+            AppAction::HalfPageDown => {
+                self.select_index(self.selected_index + self.half_page()).await?;
             }
 
-            AppAction::RefreshFeeds => {
-                self.refresh_feeds();
+            AppAction::HalfPageUp => {
+                let index = self.selected_index.saturating_sub(self.half_page());
+                self.select_index(index).await?;
             }
 
-            AppAction::OpenInBrowser => {
-                if let Some(article) = self.selected_article() {
-                    let url = article.url.clone();
-                    std::thread::spawn(move || {
-                        let _ = open::that(&url);
+            AppAction::NextGroup | AppAction::PrevGroup => {
+                let forward = matches!(action, AppAction::NextGroup);
+                let mut index = self.selected_index;
+                for _ in 0..count.unwrap_or(1) {
+                    match self.group_start(index, forward) {
+                        Some(start) if start != index => index = start,
+                        _ => break,
+                    }
+                }
+                self.select_index(index).await?;
+            }
+
+            AppAction::CountDigit(digit) => {
+                // A leading 0 isn't a count; on its own it lists all feeds again
+                if count.is_none() && digit == 0 {
+                    if self.config.number_keys() == NumberKeys::Feeds {
+                        return Box::pin(self.handle_action(AppAction::FilterFeed(None))).await;
+                    }
+                } else {
+                    let count = (count.unwrap_or(0) * 10 + digit).min(MAX_COUNT);
+                    self.key_prefix = Some(KeyPrefix {
+                        count: Some(count),
+                        g: false,
+                        typed_at: Instant::now(),
                     });
                 }
             }
 
-            AppAction::EmailArticle => {
-                if let Some(article) = self.selected_article() {
-                    self.email_article(article);
+            AppAction::GoPrefix => {
+                if prefix.is_some_and(|p| p.g) {
+                    self.select_index(count.map_or(0, |c| c - 1)).await?;
+                    return Ok(false);
                 }
+                self.key_prefix = Some(KeyPrefix {
+                    count,
+                    g: true,
+                    typed_at: Instant::now(),
+                });
             }
 
-            AppAction::SaveToRaindrop => {
-                if self.raindrop.is_some() && self.selected_article().is_some() {
-                    self.tag_input_active = true;
-                    self.tag_input.clear();
+            AppAction::CycleSortMode => {
+                self.cycle_sort_mode();
+            }
+
+            AppAction::CycleHideRead => {
+                self.config.hide_read = self.config.hide_read.next();
+                if let Err(e) = self.config.save() {
+                    tracing::warn!("Failed to save hide_read: {}", e);
+                }
+                if self.config.hide_read != HideRead::Session {
+                    self.hide_read_articles(true);
                 }
+                self.show_toast(self.config.hide_read.description().to_string());
             }
 
-            AppAction::RegenerateSummary => {
-                self.summary_status = SummaryStatus::NotGenerated;
-                self.current_summary = None;
-                self.generate_summary().await?;
+            AppAction::CycleListDensity => {
+                self.config.list_density = self.config.list_density.next();
+                if let Err(e) = self.config.save() {
+                    tracing::warn!("Failed to save list_density: {}", e);
+                }
+                self.show_toast(self.config.list_density.description().to_string());
             }
 
-            AppAction::DeleteArticle => {
-                if let Some(article) = self.selected_article() {
-                    let id = article.id;
-                    let feed_id = article.feed_id;
-                    let guid = article.guid.clone();
-                    self.repository.delete_article(id).await?;
-                    // Store for undo
-                    self.last_deleted = Some((feed_id, guid));
-                    // Remove from local list
-                    self.articles.retain(|a| a.id != id);
-                    // Adjust selection if needed
-                    let len = self.filtered_articles().len();
-                    if len > 0 && self.selected_index >= len {
-                        self.selected_index = len - 1;
-                    }
-                    // Reset summary state
-                    self.summary_status = SummaryStatus::NotGenerated;
-                    self.current_summary = None;
+            AppAction::SelectArticle => {
+                // Inside the reader Enter always summarizes
+                let action = if self.reader_active {
+                    EnterAction::Summarize
+                } else {
+                    self.config.enter_action
+                };
+                if action.opens_browser() {
+                    self.open_in_browser();
+                }
+                if action.opens_reader() {
+                    self.open_reader().await?;
+                }
+                if action.summarizes() {
+                    self.generate_summary(None, false).await?;
                 }
             }
 
-            AppAction::DeleteFeed => {
-                if let Some(article) = self.selected_article() {
-                    let feed_id = article.feed_id;
-                    // Delete the feed (cascades to articles via foreign key)
-                    self.repository.delete_feed(feed_id).await?;
-                    // Remove all articles from this feed from local list
-                    self.articles.retain(|a| a.feed_id != feed_id);
-                    // Reload feeds list
-                    self.feeds = self.repository.get_all_feeds().await?;
-                    // Adjust selection if needed
-                    let len = self.filtered_articles().len();
-                    if len > 0 && self.selected_index >= len {
-                        self.selected_index = len - 1;
+            AppAction::RefreshFeeds => {
+                self.refresh_feeds();
+            }
+
+            AppAction::RefreshFeed => {
+                self.refresh_current_feed();
+            }
+
+            AppAction::OpenInBrowser => {
+                self.open_in_browser();
+            }
+
+            AppAction::ToggleTranslation => {
+                self.toggle_translation().await?;
+            }
+
+            AppAction::ToggleSimplified => {
+                self.toggle_simplified().await?;
+            }
+
+            AppAction::Speak => {
+                self.speak_article();
+            }
+
+            AppAction::StopSpeaking => {
+                self.stop_speaking();
+            }
+
+            AppAction::EmailArticle => {
+                if let Some(article) = self.selected_article() {
+                    self.email_article(article).await;
+                }
+            }
+
+            AppAction::PrintArticle => {
+                if let Some(article) = self.selected_article().cloned() {
+                    self.print_article(article).await;
+                }
+            }
+
+            AppAction::CopyUrl => {
+                match self.selected_article().filter(|a| !a.url.is_empty()) {
+                    Some(article) => {
+                        let url = article.url.clone();
+                        self.copy_to_clipboard(&url, "URL");
                     }
-                    // Reset summary state
-                    self.summary_status = SummaryStatus::NotGenerated;
-                    self.current_summary = None;
+                    None => self.show_toast("Article has no URL to copy".to_string()),
                 }
             }
 
-            AppAction::UndeleteArticle => {
-                if let Some((feed_id, guid)) = self.last_deleted.take() {
-                    self.repository.undelete_article(feed_id, &guid).await?;
-                    // Article will return on next refresh (press 'r')
+            AppAction::CopyMarkdownLink => {
+                match self.selected_article().filter(|a| !a.url.is_empty()) {
+                    Some(article) => {
+                        let link = markdown_link(&article.title, &article.url);
+                        self.copy_to_clipboard(&link, "Markdown link");
+                    }
+                    None => self.show_toast("Article has no URL to copy".to_string()),
                 }
             }
 
-            AppAction::ShowHelp => {
-                self.show_help = true;
+            AppAction::CopySummary => {
+                if let Some(article) = self.selected_article() {
+                    let article_id = article.id;
+                    self.copy_summary(article_id).await;
+                }
             }
 
-            AppAction::HideHelp => {
-                self.show_help = false;
+            AppAction::RaindropSync => {
+                if self.raindrop.is_none() {
+                    self.show_toast("Set raindrop_token in config.toml to sync".to_string());
+                } else {
+                    self.start_raindrop_sync();
+                }
             }
 
-            AppAction::TagInputChar(c) => {
-                self.tag_input.push(c);
+            AppAction::SaveToRaindrop => {
+                if self.raindrop.is_some() && self.selected_article().is_some() {
+                    self.tag_input_active = true;
+                    self.tag_input.clear();
+                    self.suggest_raindrop_tags();
+                }
             }
 
-            AppAction::TagInputBackspace => {
-                self.tag_input.pop();
+            AppAction::HighlightStart => {
+                if self.hypothesis.is_none() {
+                    self.show_toast("Set hypothesis_token in config.toml to highlight".to_string());
+                } else if self.highlight_candidates().is_empty() {
+                    self.show_toast("Generate a summary to highlight from".to_string());
+                } else {
+                    self.highlight_picker = Some(0);
+                }
             }
 
-            AppAction::TagInputConfirm => {
-                self.save_to_raindrop().await?;
-                self.tag_input_active = false;
-                self.tag_input.clear();
+            AppAction::HighlightUp => {
+                if let Some(cursor) = self.highlight_picker.as_mut() {
+                    *cursor = cursor.saturating_sub(1);
+                }
             }
 
-            AppAction::TagInputCancel => {
-                self.tag_input_active = false;
-                self.tag_input.clear();
+            AppAction::HighlightDown => {
+                let last = self.highlight_candidates().len().saturating_sub(1);
+                if let Some(cursor) = self.highlight_picker.as_mut() {
+                    *cursor = (*cursor + 1).min(last);
+                }
             }
 
-            AppAction::AddFeed => {
-                self.feed_input_active = true;
-                self.feed_input.clear();
-                self.feed_input_status = None;
+            AppAction::HighlightConfirm => {
+                self.create_highlight();
+                self.highlight_picker = None;
             }
 
-            AppAction::FeedInputChar(c) => {
-                self.feed_input.push(c);
+            AppAction::HighlightCancel => {
+                self.highlight_picker = None;
             }
 
-            AppAction::FeedInputBackspace => {
-                self.feed_input.pop();
+            AppAction::RelatedStart => {
+                if self.related_articles().is_empty() {
+                    self.show_toast("No related articles found".to_string());
+                } else {
+                    self.related_picker = Some(0);
+                }
             }
 
-            AppAction::FeedInputConfirm => {
-                self.start_feed_discovery();
+            AppAction::RelatedUp => {
+                if let Some(cursor) = self.related_picker.as_mut() {
+                    *cursor = cursor.saturating_sub(1);
+                }
             }
 
-            AppAction::FeedInputCancel => {
-                self.feed_input_active = false;
-                self.feed_input.clear();
-                self.feed_input_status = None;
+            AppAction::RelatedDown => {
+                let last = self.related_articles().len().saturating_sub(1);
+                if let Some(cursor) = self.related_picker.as_mut() {
+                    *cursor = (*cursor + 1).min(last);
+                }
             }
 
-            AppAction::ImportOpmlStart => {
-                self.opml_input_active = true;
-                self.opml_input.clear();
-                self.opml_input_status = None;
+            AppAction::RelatedConfirm => {
+                let id = self
+                    .related_picker
+                    .take()
+                    .and_then(|cursor| self.related_articles().get(cursor).map(|a| a.id));
+                if let Some(id) = id {
+                    self.select_article_id(id).await?;
+                }
             }
 
-            AppAction::OpmlInputChar(c) => {
-                self.opml_input.push(c);
+            AppAction::RelatedCancel => {
+                self.related_picker = None;
             }
 
-            AppAction::OpmlInputBackspace => {
-                self.opml_input.pop();
+            AppAction::StatsOpen => {
+                let (month_spend, total_spend) = self.repository.get_ai_spend().await?;
+                self.refresh_stats = Some(RefreshStats {
+                    history: self.repository.get_refresh_history().await?,
+                    feeds: self.repository.get_feed_usage().await?,
+                    month_spend,
+                    total_spend,
+                    monthly_budget: self.config.summary.monthly_budget_usd,
+                    scroll: 0,
+                });
             }
 
-            AppAction::OpmlInputConfirm => {
-                self.import_opml_from_input().await?;
+            AppAction::StatsScrollUp => {
+                if let Some(stats) = self.refresh_stats.as_mut() {
+                    stats.scroll = stats.scroll.saturating_sub(1);
+                }
             }
 
-            AppAction::OpmlInputCancel => {
-                self.opml_input_active = false;
-                self.opml_input.clear();
-                self.opml_input_status = None;
+            AppAction::StatsScrollDown => {
+                if let Some(stats) = self.refresh_stats.as_mut() {
+                    stats.scroll = (stats.scroll + 1).min(stats.feeds.len().saturating_sub(1));
+                }
             }
 
-            AppAction::ExportOpmlStart => {
-                self.opml_export_active = true;
-                self.opml_export_input = "~/feeds.opml".to_string();
-                self.opml_export_status = None;
+            AppAction::StatsClose => {
+                self.refresh_stats = None;
             }
 
-            AppAction::OpmlExportChar(c) => {
-                self.opml_export_input.push(c);
+            AppAction::LogsOpen => match crate::logging::recent_lines(LOG_VIEW_LINES) {
+                Ok(lines) => self.log_viewer = Some(LogViewer { lines, back: 0 }),
+                Err(e) => self.show_toast(format!("Cannot read the log: {}", e)),
+            },
+
+            AppAction::LogsScrollUp => {
+                if let Some(logs) = self.log_viewer.as_mut() {
+                    logs.back = (logs.back + 1).min(logs.lines.len().saturating_sub(1));
+                }
             }
 
-            AppAction::OpmlExportBackspace => {
-                self.opml_export_input.pop();
+            AppAction::LogsScrollDown => {
+                if let Some(logs) = self.log_viewer.as_mut() {
+                    logs.back = logs.back.saturating_sub(1);
+                }
             }
 
-            AppAction::OpmlExportConfirm => {
-                self.export_opml_to_file()?;
+            AppAction::LogsTop => {
+                if let Some(logs) = self.log_viewer.as_mut() {
+                    logs.back = logs.lines.len().saturating_sub(1);
+                }
             }
 
-            AppAction::OpmlExportCancel => {
-                self.opml_export_active = false;
-                self.opml_export_input.clear();
-                self.opml_export_status = None;
+            AppAction::LogsBottom => {
+                if let Some(logs) = self.log_viewer.as_mut() {
+                    logs.back = 0;
+                }
             }
-        }
 
-        Ok(false)
-    }
+            AppAction::LogsClose => {
+                self.log_viewer = None;
+            }
 
-    async fn on_selection_changed(&mut self) -> Result<()> {
-        // Reset state when selection changes
-        self.summary_status = SummaryStatus::NotGenerated;
-        self.current_summary = None;
-        self.is_saved_to_raindrop = false;
+            AppAction::InfoOpen => {
+                if let Some(id) = self.selected_article().map(|a| a.id) {
+                    self.open_article_info(id).await?;
+                }
+            }
 
-        // Check if current article is saved to raindrop
-        let article_id = self.selected_article().map(|a| a.id);
-        if let Some(id) = article_id {
-            self.is_saved_to_raindrop = self
-                .repository
-                .is_saved_to_raindrop(id)
-                .await?;
+            AppAction::InfoUp => {
+                if let Some(info) = self.article_info.as_mut() {
+                    info.cursor = info.cursor.saturating_sub(1);
+                }
+            }
 
-            // Check for cached summary
-            if let Some(summary) = self.repository.get_summary(id).await? {
-                self.current_summary = Some(summary);
-                self.summary_status = SummaryStatus::Generated;
+            AppAction::InfoDown => {
+                if let Some(info) = self.article_info.as_mut() {
+                    info.cursor = (info.cursor + 1).min(info.linked().count().saturating_sub(1));
+                }
             }
-        }
 
-        Ok(())
-    }
+            AppAction::InfoConfirm => {
+                // Follow the link and show what the other article links with
+                let id = self
+                    .article_info
+                    .as_ref()
+                    .and_then(|info| info.linked().nth(info.cursor).map(|a| a.id));
+                if let Some(id) = id {
+                    self.select_article_id(id).await?;
+                    self.open_article_info(id).await?;
+                }
+            }
 
-    async fn generate_summary(&mut self) -> Result<()> {
-        let Some(summarizer) = &self.summarizer else {
-            self.summary_status = SummaryStatus::NoApiKey;
-            return Ok(());
-        };
+            AppAction::InfoClose => {
+                self.article_info = None;
+            }
 
-        let Some(article) = self.selected_article() else {
-            return Ok(());
-        };
+            AppAction::LinksOpen => {
+                let links = self.selected_article().map(Self::article_links).unwrap_or_default();
+                if links.is_empty() {
+                    self.show_toast("No links in this article".to_string());
+                } else {
+                    self.link_menu = Some(LinkMenu { links, cursor: 0 });
+                }
+            }
 
-        // Check cache first
-        if let Some(summary) = self.repository.get_summary(article.id).await? {
-            self.current_summary = Some(summary);
-            self.summary_status = SummaryStatus::Generated;
-            return Ok(());
-        }
+            AppAction::LinksUp => {
+                if let Some(menu) = self.link_menu.as_mut() {
+                    menu.cursor = menu.cursor.saturating_sub(1);
+                }
+            }
 
-        let article_id = article.id;
-        let title = article.title.clone();
-        let article_url = article.url.clone();
+            AppAction::LinksDown => {
+                if let Some(menu) = self.link_menu.as_mut() {
+                    menu.cursor = (menu.cursor + 1).min(menu.links.len().saturating_sub(1));
+                }
+            }
 
-        // Get RSS content as fallback
-        let rss_content = article
-            .content_text
-            .clone()
-            .or_else(|| article.content.clone())
-            .unwrap_or_default();
+            AppAction::LinksConfirm => {
+                if let Some(menu) = self.link_menu.take() {
+                    if let Some((_, url)) = menu.links.get(menu.cursor) {
+                        Self::open_url(url.clone());
+                    }
+                }
+            }
 
-        self.summary_status = SummaryStatus::Generating;
-        self.pending_summary_article_id = Some(article_id);
+            AppAction::LinksNumber(number) => {
+                let url = self
+                    .link_menu
+                    .as_ref()
+                    .and_then(|menu| menu.links.get(number.wrapping_sub(1)))
+                    .map(|(_, url)| url.clone());
+                if let Some(url) = url {
+                    self.link_menu = None;
+                    Self::open_url(url);
+                }
+            }
 
-        // Try to fetch full content using browser cookies
-        let content = match self.content_fetcher.fetch_full_content(&article_url).await {
-            Ok(Some(full_content)) => {
-                tracing::info!("Fetched full content for: {}", article_url);
-                full_content
+            AppAction::LinksClose => {
+                self.link_menu = None;
             }
-            Ok(None) => {
-                tracing::debug!("No full content available, using RSS content");
-                rss_content
+
+            AppAction::DiscussionsOpen => {
+                self.open_discussions().await?;
             }
-            Err(e) => {
-                tracing::debug!("Failed to fetch full content: {}, using RSS", e);
-                rss_content
+
+            AppAction::DiscussionsUp => {
+                if let Some(menu) = self.discussion_menu.as_mut() {
+                    menu.cursor = menu.cursor.saturating_sub(1);
+                }
+            }
+
+            AppAction::DiscussionsDown => {
+                if let Some(menu) = self.discussion_menu.as_mut() {
+                    menu.cursor = (menu.cursor + 1).min(menu.discussions.len().saturating_sub(1));
+                }
+            }
+
+            AppAction::DiscussionsConfirm => {
+                if let Some(menu) = self.discussion_menu.take() {
+                    if let Some(discussion) = menu.discussions.get(menu.cursor) {
+                        Self::open_url(discussion.url.clone());
+                    }
+                }
+            }
+
+            AppAction::DiscussionsClose => {
+                self.discussion_menu = None;
+            }
+
+            AppAction::ChatOpen => {
+                self.open_chat().await?;
+            }
+            AppAction::ChatChar(c) => {
+                if let Some(chat) = self.chat.as_mut() {
+                    chat.input.push(c);
+                }
+            }
+            AppAction::ChatBackspace => {
+                if let Some(chat) = self.chat.as_mut() {
+                    chat.input.pop();
+                }
+            }
+            AppAction::ChatSend => {
+                self.send_chat_message();
+            }
+            AppAction::ChatScrollUp => {
+                if let Some(chat) = self.chat.as_mut() {
+                    let max_scroll = self.chat_max_scroll.get();
+                    chat.scroll = (chat.scroll + WHEEL_SCROLL_LINES).min(max_scroll);
+                }
+            }
+            AppAction::ChatScrollDown => {
+                if let Some(chat) = self.chat.as_mut() {
+                    chat.scroll = chat.scroll.saturating_sub(WHEEL_SCROLL_LINES);
+                }
+            }
+            AppAction::ChatClose => {
+                self.chat = None;
+            }
+
+            AppAction::ShareOpen => {
+                self.open_share();
+            }
+            AppAction::ShareChar(c) => {
+                if let Some(share) = self.share.as_mut().filter(|s| !s.drafting && !s.posting) {
+                    share.text.push(c);
+                    share.status = None;
+                }
+            }
+            AppAction::ShareBackspace => {
+                if let Some(share) = self.share.as_mut().filter(|s| !s.drafting && !s.posting) {
+                    share.text.pop();
+                    share.status = None;
+                }
+            }
+            AppAction::ShareNextTarget => {
+                if let Some(share) = self.share.as_mut() {
+                    share.target = (share.target + 1) % share.targets.len();
+                }
+            }
+            AppAction::ShareSend => {
+                self.send_share();
+            }
+            AppAction::ShareClose => {
+                self.share = None;
+            }
+
+            AppAction::SettingsOpen => {
+                self.settings = Some(SettingsScreen {
+                    cursor: 0,
+                    editing: None,
+                    error: None,
+                });
+            }
+            AppAction::SettingsUp => {
+                if let Some(settings) = self.settings.as_mut() {
+                    settings.cursor = settings.cursor.saturating_sub(1);
+                }
+            }
+            AppAction::SettingsDown => {
+                if let Some(settings) = self.settings.as_mut() {
+                    settings.cursor = (settings.cursor + 1).min(Setting::ALL.len() - 1);
+                }
+            }
+            AppAction::SettingsEdit => {
+                if let Some(cursor) = self.settings.as_ref().map(|s| s.cursor) {
+                    let setting = Setting::ALL[cursor];
+                    // Secrets are typed anew rather than shown
+                    let value = if setting.is_secret() {
+                        String::new()
+                    } else {
+                        self.config.setting_value(setting)
+                    };
+                    if let Some(settings) = self.settings.as_mut() {
+                        settings.editing = Some(value);
+                        settings.error = None;
+                    }
+                }
+            }
+            AppAction::SettingsChar(c) => {
+                if let Some(value) = self.settings.as_mut().and_then(|s| s.editing.as_mut()) {
+                    value.push(c);
+                }
+            }
+            AppAction::SettingsBackspace => {
+                if let Some(value) = self.settings.as_mut().and_then(|s| s.editing.as_mut()) {
+                    value.pop();
+                }
+            }
+            AppAction::SettingsConfirm => {
+                self.confirm_setting();
+            }
+            AppAction::SettingsCancel => {
+                if let Some(settings) = self.settings.as_mut() {
+                    settings.editing = None;
+                    settings.error = None;
+                }
+            }
+            AppAction::SettingsClose => {
+                self.settings = None;
+            }
+
+            AppAction::SummaryStylesOpen => {
+                self.open_summary_styles().await?;
+            }
+            AppAction::SummaryStylesUp => {
+                if let Some(picker) = self.summary_styles.as_mut() {
+                    picker.cursor = picker.cursor.saturating_sub(1);
+                }
+            }
+            AppAction::SummaryStylesDown => {
+                if let Some(picker) = self.summary_styles.as_mut() {
+                    picker.cursor = (picker.cursor + 1).min(SummaryVariant::ALL.len());
+                }
+            }
+            AppAction::SummaryStylesConfirm | AppAction::SummaryStylesRegenerate => {
+                if let Some(picker) = self.summary_styles.take() {
+                    let force = matches!(action, AppAction::SummaryStylesRegenerate);
+                    if self.selected_article().map(|a| a.id) == Some(picker.article_id) {
+                        self.summary_status = SummaryStatus::NotGenerated;
+                        self.current_summary = None;
+                        self.generate_summary(picker.selected(), force).await?;
+                    }
+                }
+            }
+            AppAction::SummaryStylesClose => {
+                self.summary_styles = None;
+            }
+
+            AppAction::DeleteArticle => {
+                if let Some(article) = self.selected_article() {
+                    let id = article.id;
+                    let article_feed_id = article.feed_id;
+                    if self.filter == ArticleFilter::Trash {
+                        // Deleting from the trash is permanent
+                        self.repository.delete_article(id).await?;
+                        self.articles.retain(|a| a.id != id);
+                    } else {
+                        self.repository.trash_article(id).await?;
+                        if self.is_unread(id) {
+                            self.unread_counts.decrement(article_feed_id);
+                        }
+                        // Store for undo
+                        self.last_deleted = Some(id);
+                        let now = chrono::Utc::now();
+                        for article in self
+                            .articles
+                            .iter_mut()
+                            .filter(|a| a.id == id || a.canonical_id == Some(id))
+                        {
+                            article.deleted_at = Some(now);
+                        }
+                    }
+                    if let Some(plan) = self.catchup.as_mut() {
+                        plan.article_ids.retain(|a| *a != id);
+                    }
+                    self.invalidate_filter();
+                    // Adjust selection if needed
+                    let len = self.filtered_len();
+                    if len > 0 && self.selected_index >= len {
+                        self.selected_index = len - 1;
+                    }
+                    // Reset summary state
+                    self.summary_status = SummaryStatus::NotGenerated;
+                    self.current_summary = None;
+                }
+            }
+
+            AppAction::ToggleStar => {
+                if let Some(article) = self.selected_article() {
+                    let id = article.id;
+                    let is_starred = !article.is_starred;
+                    self.repository.set_article_starred(id, is_starred).await?;
+                    if let Some(article) = self.articles.iter_mut().find(|a| a.id == id) {
+                        article.is_starred = is_starred;
+                    }
+                    self.invalidate_filter();
+                }
+            }
+
+            AppAction::QueueToggle => {
+                if let Some(article) = self.selected_article() {
+                    let id = article.id;
+                    let queued_at = match article.queued_at {
+                        Some(_) => None,
+                        None => Some(chrono::Utc::now()),
+                    };
+                    self.repository.set_article_queued(id, queued_at).await?;
+                    if let Some(article) = self.articles.iter_mut().find(|a| a.id == id) {
+                        article.queued_at = queued_at;
+                    }
+                    self.invalidate_filter();
+                    let message = match queued_at {
+                        Some(_) => format!("Added to the reading queue ({} queued)", self.queued()),
+                        None => "Taken out of the reading queue".to_string(),
+                    };
+                    self.show_toast(message);
+                }
+            }
+
+            AppAction::QueueFinish => {
+                let id = self.selected_article().filter(|a| a.queued_at.is_some()).map(|a| a.id);
+                if let Some(id) = id {
+                    self.repository.set_article_queued(id, None).await?;
+                    self.mark_read(id).await?;
+                    if let Some(article) = self.articles.iter_mut().find(|a| a.id == id) {
+                        article.queued_at = None;
+                    }
+                    self.invalidate_filter();
+                    let left = self.queued();
+                    self.show_toast(format!("Finished, {} left in the reading queue", left));
+                } else if self.selected_article().is_some() {
+                    self.show_toast("Not in the reading queue (+ adds it)".to_string());
+                }
+            }
+
+            AppAction::ToggleSimilarGroup => {
+                self.toggle_similar_group().await?;
+            }
+
+            AppAction::FeedSettingsOpen => {
+                if let Some(article) = self.selected_article() {
+                    self.feed_settings = Some(article.feed_id);
+                }
+            }
+
+            AppAction::FeedSettingsToggle(rule) => {
+                if let Some(feed) = self
+                    .feed_settings
+                    .and_then(|id| self.feeds.iter_mut().find(|f| f.id == id))
+                {
+                    feed.rules.toggle(rule);
+                    let (feed_id, rules) = (feed.id, feed.rules);
+                    self.repository.set_feed_rules(feed_id, rules).await?;
+                    self.invalidate_filter();
+                }
+            }
+
+            AppAction::FeedSettingsClose => {
+                self.feed_settings = None;
+            }
+
+            AppAction::ExportNotes => {
+                let message = match self.config.notes_path() {
+                    Some(dir) => match export_starred_notes(&self.repository, &dir).await {
+                        Ok(export) => format!(
+                            "Exported {} notes to {} ({} already there)",
+                            export.written,
+                            dir.display(),
+                            export.skipped
+                        ),
+                        Err(e) => format!("Note export failed: {}", e),
+                    },
+                    None => "Set notes_dir in config.toml to export notes".to_string(),
+                };
+                self.show_toast(message);
+            }
+
+            AppAction::DeleteFeed => {
+                if let Some(article) = self.selected_article() {
+                    let feed_id = article.feed_id;
+                    if let Some(feed) = self.feeds.iter().find(|f| f.id == feed_id && f.auth.is_some()) {
+                        if let Err(e) = delete_feed_secret(&feed.url).await {
+                            tracing::warn!("Failed to remove feed credentials: {}", e);
+                        }
+                    }
+                    // Delete the feed (cascades to articles via foreign key)
+                    self.repository.delete_feed(feed_id).await?;
+                    // Remove all articles from this feed from local list
+                    self.articles.retain(|a| a.feed_id != feed_id);
+                    self.unread_counts.remove_feed(feed_id);
+                    // Reload feeds list
+                    self.feeds = self.repository.get_all_feeds().await?;
+                    self.invalidate_filter();
+                    // Adjust selection if needed
+                    let len = self.filtered_len();
+                    if len > 0 && self.selected_index >= len {
+                        self.selected_index = len - 1;
+                    }
+                    // Reset summary state
+                    self.summary_status = SummaryStatus::NotGenerated;
+                    self.current_summary = None;
+                }
+            }
+
+            AppAction::UndeleteArticle => {
+                // In the trash view restore the selection, otherwise undo the last delete
+                let id = if self.filter == ArticleFilter::Trash {
+                    self.selected_article().map(|a| a.id)
+                } else {
+                    self.last_deleted.take()
+                };
+                if let Some(id) = id {
+                    self.repository.restore_article(id).await?;
+                    for article in self
+                        .articles
+                        .iter_mut()
+                        .filter(|a| a.id == id || a.canonical_id == Some(id))
+                    {
+                        article.deleted_at = None;
+                    }
+                    self.invalidate_filter();
+                    let feed_id = self.articles.iter().find(|a| a.id == id).map(|a| a.feed_id);
+                    if let Some(feed_id) = feed_id.filter(|_| self.is_unread(id)) {
+                        self.unread_counts.increment(feed_id);
+                    }
+                    let len = self.filtered_len();
+                    if len > 0 && self.selected_index >= len {
+                        self.selected_index = len - 1;
+                    }
+                    self.on_selection_changed().await?;
+                }
+            }
+
+            AppAction::OpenReader => {
+                self.open_reader().await?;
+            }
+
+            AppAction::CloseReader => {
+                self.reader_active = false;
+                self.hide_read_immediately().await?;
+            }
+
+            AppAction::ReaderScrollDown => {
+                self.reader_scroll = (self.reader_scroll + 1).min(self.reader_max_scroll.get());
+            }
+
+            AppAction::ReaderScrollUp => {
+                self.reader_scroll = self.reader_scroll.saturating_sub(1);
+            }
+
+            AppAction::ReaderPageDown => {
+                let page = self.reader_page_height.get().saturating_sub(2).max(1);
+                self.reader_scroll = (self.reader_scroll + page).min(self.reader_max_scroll.get());
+            }
+
+            AppAction::ReaderPageUp => {
+                let page = self.reader_page_height.get().saturating_sub(2).max(1);
+                self.reader_scroll = self.reader_scroll.saturating_sub(page);
+            }
+
+            AppAction::ReaderNextUnread => {
+                let next = self
+                    .filtered_articles()
+                    .enumerate()
+                    .skip(self.selected_index + 1)
+                    .find(|(_, a)| !a.is_read && !self.read_in_db.contains(&a.id))
+                    .map(|(i, _)| i);
+                self.jump_in_reader(next).await?;
+            }
+
+            AppAction::ReaderPrevUnread => {
+                let prev = self
+                    .filtered_articles()
+                    .enumerate()
+                    .take(self.selected_index)
+                    .rev()
+                    .find(|(_, a)| !a.is_read && !self.read_in_db.contains(&a.id))
+                    .map(|(i, _)| i);
+                self.jump_in_reader(prev).await?;
+            }
+
+            AppAction::Click { column, row } => {
+                let areas = self.pane_areas.get();
+                let position = Position::new(column, row);
+                if areas.filter_label.contains(position) {
+                    return Box::pin(self.handle_action(AppAction::CycleFilter)).await;
+                }
+                if areas.list.contains(position) {
+                    self.focused_pane = Pane::List;
+                    let rows = areas.list.inner(Margin::new(1, 1));
+                    if rows.contains(position) {
+                        // Date headers aren't selectable
+                        let clicked = self.list_row_at((row - rows.y) as usize);
+                        if let Some(ListRow::Article(index)) = clicked {
+                            if index != self.selected_index {
+                                self.selected_index = index;
+                                self.on_selection_changed().await?;
+                            }
+                        }
+                    }
+                } else if areas.content.contains(position) {
+                    self.focused_pane = Pane::Content;
+                } else if areas.summary.contains(position) {
+                    self.focused_pane = Pane::Summary;
+                }
+            }
+
+            AppAction::WheelDown => match self.focused_pane {
+                Pane::List => return Box::pin(self.handle_action(AppAction::MoveDown)).await,
+                Pane::Content => {
+                    self.content_scroll = (self.content_scroll + WHEEL_SCROLL_LINES)
+                        .min(self.content_max_scroll.get());
+                }
+                Pane::Summary => {
+                    self.summary_scroll = (self.summary_scroll + WHEEL_SCROLL_LINES)
+                        .min(self.summary_max_scroll.get());
+                }
+            },
+
+            AppAction::WheelUp => match self.focused_pane {
+                Pane::List => return Box::pin(self.handle_action(AppAction::MoveUp)).await,
+                Pane::Content => {
+                    self.content_scroll = self.content_scroll.saturating_sub(WHEEL_SCROLL_LINES);
+                }
+                Pane::Summary => {
+                    self.summary_scroll = self.summary_scroll.saturating_sub(WHEEL_SCROLL_LINES);
+                }
+            },
+
+            AppAction::CycleFilter => {
+                self.filter = self.filter.next();
+                self.invalidate_filter();
+                self.selected_index = 0;
+                self.on_selection_changed().await?;
+            }
+
+            AppAction::ShowHelp => {
+                self.show_help = true;
+            }
+
+            AppAction::HideHelp => {
+                self.show_help = false;
+            }
+
+            AppAction::EditTags => {
+                if let Some(article) = self.selected_article() {
+                    self.article_tags_input = article.tags.join(", ");
+                    if !self.article_tags_input.is_empty() {
+                        self.article_tags_input.push_str(", ");
+                    }
+                    self.article_tags_active = true;
+                }
+            }
+
+            AppAction::ArticleTagsChar(c) => {
+                self.article_tags_input.push(c);
+            }
+
+            AppAction::ArticleTagsBackspace => {
+                self.article_tags_input.pop();
+            }
+
+            AppAction::ArticleTagsComplete => {
+                if let Some(tag) = self.tag_suggestions().first().map(|t| t.to_string()) {
+                    let keep = self.article_tags_input.rfind(',').map_or(0, |i| i + 1);
+                    self.article_tags_input.truncate(keep);
+                    if keep > 0 {
+                        self.article_tags_input.push(' ');
+                    }
+                    self.article_tags_input.push_str(&tag);
+                    self.article_tags_input.push_str(", ");
+                }
+            }
+
+            AppAction::ArticleTagsConfirm => {
+                self.save_article_tags().await?;
+                self.article_tags_active = false;
+                self.article_tags_input.clear();
+            }
+
+            AppAction::ArticleTagsCancel => {
+                self.article_tags_active = false;
+                self.article_tags_input.clear();
+            }
+
+            AppAction::CycleTagFilter => {
+                // No tag filter, then each tag in use, then back
+                self.tag_filter = match &self.tag_filter {
+                    None => self.known_tags.first().cloned(),
+                    Some(current) => self
+                        .known_tags
+                        .iter()
+                        .skip_while(|tag| *tag != current)
+                        .nth(1)
+                        .cloned(),
+                };
+                self.invalidate_filter();
+                if self.known_tags.is_empty() {
+                    self.show_toast("No tagged articles yet (t to tag)".to_string());
+                }
+                self.selected_index = 0;
+                self.on_selection_changed().await?;
+            }
+
+            AppAction::TagInputChar(c) => {
+                self.tag_input.push(c);
+            }
+
+            AppAction::TagInputBackspace => {
+                self.tag_input.pop();
+            }
+
+            AppAction::TagInputNext => {
+                let choices = self.raindrop_tag_choices().len();
+                if let Some(suggestions) = self.raindrop_tags.as_mut() {
+                    if suggestions.cursor + 1 < choices {
+                        suggestions.cursor += 1;
+                    }
+                }
+            }
+
+            AppAction::TagInputPrev => {
+                if let Some(suggestions) = self.raindrop_tags.as_mut() {
+                    suggestions.cursor = suggestions.cursor.saturating_sub(1);
+                }
+            }
+
+            AppAction::TagInputAccept => {
+                let choices = self.raindrop_tag_choices();
+                let cursor = self.raindrop_tags.as_ref().map_or(0, |s| s.cursor);
+                let Some(tag) = choices.get(cursor.min(choices.len().saturating_sub(1))) else {
+                    return Ok(false);
+                };
+                let tag = tag.to_string();
+                // A tag being typed is replaced by the suggestion it starts
+                let (entered, fragment) =
+                    self.tag_input.rsplit_once(',').unwrap_or(("", &self.tag_input));
+                let fragment = fragment.trim();
+                let mut tags: Vec<&str> =
+                    entered.split(',').map(str::trim).filter(|t| !t.is_empty()).collect();
+                if !fragment.is_empty() && !tag.starts_with(&fragment.to_lowercase()) {
+                    tags.push(fragment);
+                }
+                tags.push(&tag);
+                self.tag_input = format!("{}, ", tags.join(", "));
+                let choices = self.raindrop_tag_choices().len();
+                if let Some(suggestions) = self.raindrop_tags.as_mut() {
+                    suggestions.cursor = suggestions.cursor.min(choices.saturating_sub(1));
+                }
+            }
+
+            AppAction::TagInputConfirm => {
+                self.tag_input_active = false;
+                self.raindrop_tags = None;
+                self.open_raindrop_picker().await?;
+                self.tag_input.clear();
+            }
+
+            AppAction::TagInputCancel => {
+                self.tag_input_active = false;
+                self.raindrop_tags = None;
+                self.tag_input.clear();
+            }
+
+            AppAction::RaindropUp => {
+                if let Some(picker) = self.raindrop_picker.as_mut() {
+                    picker.cursor = picker.cursor.saturating_sub(1);
+                }
+            }
+
+            AppAction::RaindropDown => {
+                if let Some(picker) = self.raindrop_picker.as_mut() {
+                    picker.cursor =
+                        (picker.cursor + 1).min(picker.collections.len().saturating_sub(1));
+                }
+            }
+
+            AppAction::RaindropConfirm => {
+                if let Some(picker) = self.raindrop_picker.take() {
+                    if let Some(collection) = picker.collections.get(picker.cursor) {
+                        if let Some(feed_id) = self.selected_article().map(|a| a.feed_id) {
+                            self.repository
+                                .set_feed_raindrop_collection(feed_id, collection.id)
+                                .await?;
+                        }
+                        self.save_to_raindrop(picker.tags, Some(collection.id)).await?;
+                    }
+                }
+            }
+
+            AppAction::RaindropCancel => {
+                self.raindrop_picker = None;
+            }
+
+            AppAction::AddFeed => {
+                self.feed_input_active = true;
+                self.feed_input.clear();
+                self.feed_input_status = None;
+                self.feed_auth_url = None;
+                self.feed_offer = None;
+                self.feed_picker = None;
+            }
+
+            AppAction::AddFeedFromArticle => {
+                // Look for a feed on the site the article was published on
+                let origin = self
+                    .selected_article()
+                    .and_then(|a| url::Url::parse(&a.url).ok())
+                    .filter(|url| url.has_host())
+                    .map(|url| url.origin().ascii_serialization());
+                match origin {
+                    Some(origin) => {
+                        self.feed_input_active = true;
+                        self.feed_input = origin.clone();
+                        self.feed_auth_url = None;
+                        self.feed_offer = None;
+                        self.spawn_feed_discovery(origin, None, true);
+                    }
+                    None => self.show_toast("Article has no website address".to_string()),
+                }
+            }
+
+            AppAction::DirectoryStart => {
+                self.directory_search = Some(DirectorySearch::default());
+            }
+
+            AppAction::DirectoryChar(c) => {
+                if let Some(search) = self.directory_search.as_mut() {
+                    search.query.push(c);
+                    search.cursor = None;
+                }
+            }
+
+            AppAction::DirectoryBackspace => {
+                if let Some(search) = self.directory_search.as_mut() {
+                    search.query.pop();
+                    search.cursor = None;
+                }
+            }
+
+            AppAction::DirectoryUp => {
+                if let Some(search) = self.directory_search.as_mut() {
+                    // Moving up from the first result goes back to the query
+                    search.cursor = search.cursor.and_then(|c| c.checked_sub(1));
+                }
+            }
+
+            AppAction::DirectoryDown => {
+                if let Some(search) = self.directory_search.as_mut() {
+                    if !search.results.is_empty() {
+                        search.cursor = Some(match search.cursor {
+                            Some(c) => (c + 1).min(search.results.len() - 1),
+                            None => 0,
+                        });
+                    }
+                }
+            }
+
+            AppAction::DirectoryConfirm => {
+                let picked = self
+                    .directory_search
+                    .as_ref()
+                    .and_then(|s| s.results.get(s.cursor?))
+                    .map(|feed| feed.url.clone());
+                match picked {
+                    // Subscribe through the add-feed popup, which validates the feed
+                    Some(url) => {
+                        self.directory_search = None;
+                        self.feed_input_active = true;
+                        self.feed_input = url.clone();
+                        self.feed_auth_url = None;
+                        self.feed_offer = None;
+                        self.spawn_feed_discovery(url, None, false);
+                    }
+                    None => self.start_directory_search(),
+                }
+            }
+
+            AppAction::DirectoryCancel => {
+                self.directory_search = None;
+            }
+
+            AppAction::DiscoverOpen => self.start_feed_suggestions(),
+
+            AppAction::DiscoverUp => {
+                if let Some(discover) = self.feed_suggestions.as_mut() {
+                    discover.cursor = discover.cursor.saturating_sub(1);
+                }
+            }
+
+            AppAction::DiscoverDown => {
+                if let Some(discover) = self.feed_suggestions.as_mut() {
+                    discover.cursor =
+                        (discover.cursor + 1).min(discover.suggestions.len().saturating_sub(1));
+                }
+            }
+
+            AppAction::DiscoverSubscribe => {
+                let picked = self
+                    .feed_suggestions
+                    .as_ref()
+                    .and_then(|d| d.suggestions.get(d.cursor))
+                    .map(|feed| feed.url.clone());
+                // Subscribe through the add-feed popup, which validates the
+                // feed; the suggestions stay underneath for picking more
+                if let Some(url) = picked {
+                    self.feed_input_active = true;
+                    self.feed_input = url.clone();
+                    self.feed_auth_url = None;
+                    self.feed_offer = None;
+                    self.spawn_feed_discovery(url, None, false);
+                }
+            }
+
+            AppAction::DiscoverOpenSite => {
+                let site = self
+                    .feed_suggestions
+                    .as_ref()
+                    .and_then(|d| d.suggestions.get(d.cursor))
+                    .map(|feed| feed.site_url.clone().unwrap_or_else(|| feed.url.clone()));
+                if let Some(url) = site {
+                    Self::open_url(url);
+                }
+            }
+
+            AppAction::DiscoverClose => {
+                self.feed_suggestions = None;
+            }
+
+            AppAction::FeedInputChar(c) => {
+                self.feed_input.push(c);
+                self.feed_offer = None;
+            }
+
+            AppAction::FeedInputBackspace => {
+                self.feed_input.pop();
+                self.feed_offer = None;
+            }
+
+            AppAction::FeedInputCycleImport => {
+                self.feed_import = self.feed_import.next();
+            }
+
+            AppAction::FeedInputConfirm => match self.feed_offer.take() {
+                Some(new_feed) => self.subscribe(new_feed, None).await?,
+                None => self.start_feed_discovery(),
+            },
+
+            AppAction::FeedPickerUp => {
+                if let Some(picker) = self.feed_picker.as_mut() {
+                    picker.cursor = picker.cursor.saturating_sub(1);
+                }
+            }
+
+            AppAction::FeedPickerDown => {
+                if let Some(picker) = self.feed_picker.as_mut() {
+                    picker.cursor = (picker.cursor + 1).min(picker.feeds.len().saturating_sub(1));
+                }
+            }
+
+            AppAction::FeedPickerConfirm => {
+                let existing = self
+                    .feed_picker
+                    .as_ref()
+                    .and_then(|picker| picker.feeds.get(picker.cursor))
+                    .filter(|feed| self.feeds.iter().any(|f| f.url == feed.url))
+                    .map(|feed| feed.title.clone());
+                if let Some(title) = existing {
+                    self.feed_input_status = Some(format!("Feed already exists: {}", title));
+                } else if let Some(mut picker) = self.feed_picker.take() {
+                    let new_feed = picker.feeds.remove(picker.cursor);
+                    self.subscribe(new_feed, picker.credentials).await?;
+                }
+            }
+
+            AppAction::FeedPickerCancel => {
+                self.feed_picker = None;
+                self.feed_input_status = None;
+            }
+
+            AppAction::FeedInputCancel => {
+                self.feed_input_active = false;
+                self.feed_input.clear();
+                self.feed_input_status = None;
+                self.feed_auth_url = None;
+                self.feed_offer = None;
+                self.feed_import = self.config.initial_import;
+            }
+
+            AppAction::ImportOpmlStart => {
+                self.opml_input_active = true;
+                self.opml_input.clear();
+                self.opml_input_status = None;
+            }
+
+            AppAction::OpmlInputChar(c) => {
+                self.opml_input.push(c);
+            }
+
+            AppAction::OpmlInputBackspace => {
+                self.opml_input.pop();
+            }
+
+            AppAction::OpmlInputConfirm => {
+                self.import_opml_from_input().await?;
+            }
+
+            AppAction::OpmlInputCancel => {
+                self.opml_input_active = false;
+                self.opml_input.clear();
+                self.opml_input_status = None;
+            }
+
+            AppAction::ExportOpmlStart => {
+                if self.feeds.is_empty() {
+                    self.show_toast("No feeds to export".to_string());
+                } else {
+                    let mut feeds: Vec<&Feed> = self.feeds.iter().collect();
+                    feeds.sort_by_key(|f| (f.category.clone(), f.title.to_lowercase()));
+                    self.opml_export_picker = Some(ExportPicker {
+                        entries: feeds.iter().map(|f| (f.id, true)).collect(),
+                        cursor: 0,
+                    });
+                }
+            }
+
+            AppAction::OpmlPickerUp => {
+                if let Some(picker) = self.opml_export_picker.as_mut() {
+                    picker.cursor = picker.cursor.saturating_sub(1);
+                }
+            }
+
+            AppAction::OpmlPickerDown => {
+                if let Some(picker) = self.opml_export_picker.as_mut() {
+                    picker.cursor = (picker.cursor + 1).min(picker.entries.len().saturating_sub(1));
+                }
+            }
+
+            AppAction::OpmlPickerToggle => {
+                if let Some(picker) = self.opml_export_picker.as_mut() {
+                    if let Some(entry) = picker.entries.get_mut(picker.cursor) {
+                        entry.1 = !entry.1;
+                    }
+                }
+            }
+
+            AppAction::OpmlPickerToggleAll => {
+                if let Some(picker) = self.opml_export_picker.as_mut() {
+                    let include = !picker.entries.iter().all(|(_, included)| *included);
+                    for entry in picker.entries.iter_mut() {
+                        entry.1 = include;
+                    }
+                }
+            }
+
+            AppAction::OpmlPickerToggleCategory => {
+                if let Some(picker) = self.opml_export_picker.as_mut() {
+                    let category_of = |id: i64| {
+                        self.feeds
+                            .iter()
+                            .find(|f| f.id == id)
+                            .and_then(|f| f.category.clone())
+                    };
+                    if let Some(&(id, included)) = picker.entries.get(picker.cursor) {
+                        let category = category_of(id);
+                        for entry in picker.entries.iter_mut() {
+                            if category_of(entry.0) == category {
+                                entry.1 = !included;
+                            }
+                        }
+                    }
+                }
+            }
+
+            AppAction::OpmlPickerConfirm => {
+                let any_selected = self
+                    .opml_export_picker
+                    .as_ref()
+                    .is_some_and(|p| p.entries.iter().any(|(_, included)| *included));
+                if any_selected {
+                    self.opml_export_active = true;
+                    self.opml_export_input = "~/feeds.opml".to_string();
+                    self.opml_export_status = None;
+                } else {
+                    self.show_toast("Select at least one feed to export".to_string());
+                }
+            }
+
+            AppAction::OpmlPickerCancel => {
+                self.opml_export_picker = None;
+            }
+
+            AppAction::OpmlExportChar(c) => {
+                self.opml_export_input.push(c);
+            }
+
+            AppAction::OpmlExportBackspace => {
+                self.opml_export_input.pop();
+            }
+
+            AppAction::OpmlExportConfirm => {
+                self.export_opml_to_file()?;
+            }
+
+            AppAction::OpmlExportCancel => {
+                self.opml_export_active = false;
+                self.opml_export_input.clear();
+                self.opml_export_status = None;
+                self.opml_export_picker = None;
+            }
+
+            AppAction::CatchUpStart => {
+                self.catchup_input_active = true;
+                self.catchup_input = "20".to_string();
+                self.catchup_input_status = None;
+            }
+
+            AppAction::CatchUpInputChar(c) => {
+                if c.is_ascii_digit() {
+                    self.catchup_input.push(c);
+                }
+            }
+
+            AppAction::CatchUpInputBackspace => {
+                self.catchup_input.pop();
+            }
+
+            AppAction::CatchUpInputConfirm => {
+                self.start_catchup().await?;
+            }
+
+            AppAction::CatchUpInputCancel => {
+                self.catchup_input_active = false;
+                self.catchup_input.clear();
+                self.catchup_input_status = None;
+            }
+
+            AppAction::CatchUpNext => {
+                self.advance_catchup().await?;
+            }
+
+            AppAction::SearchStart => {
+                self.search_input_active = true;
+                self.search_input.clear();
+                self.search_input_status = None;
+            }
+
+            AppAction::SearchInputChar(c) => {
+                self.search_input.push(c);
+            }
+
+            AppAction::SearchInputBackspace => {
+                self.search_input.pop();
+            }
+
+            AppAction::SearchInputConfirm => {
+                self.start_search();
+            }
+
+            AppAction::SearchInputCancel => {
+                self.search_input_active = false;
+                self.search_input.clear();
+                self.search_input_status = None;
+            }
+
+            AppAction::SwitchWorkspace(index) => {
+                match self.config.workspaces.get(index).cloned() {
+                    Some(workspace) => self.apply_workspace(&workspace).await?,
+                    None => self.show_toast(format!(
+                        "No workspace {} (save one with :save-workspace <name>)",
+                        index + 1
+                    )),
+                }
+            }
+
+            AppAction::ResizeList(step) => {
+                let width = (self.list_width as i16 + step).clamp(10, 90) as u16;
+                self.list_width = width;
+                self.config.list_width = width;
+                if let Err(e) = self.config.save() {
+                    tracing::warn!("Failed to save list_width: {}", e);
+                }
+            }
+
+            AppAction::ResizeContent(step) => {
+                let height = (self.content_height() as i16 + step).clamp(10, 90) as u16;
+                self.config.content_height = height;
+                if let Err(e) = self.config.save() {
+                    tracing::warn!("Failed to save content_height: {}", e);
+                }
+            }
+
+            AppAction::FilterFeed(index) => {
+                let feed_id = match index {
+                    Some(index) => match self.feeds.get(index) {
+                        Some(feed) => Some(feed.id),
+                        None => {
+                            self.show_toast(format!("No feed {}", index + 1));
+                            return Ok(false);
+                        }
+                    },
+                    None => None,
+                };
+                if feed_id != self.feed_filter {
+                    self.feed_filter = feed_id;
+                    self.invalidate_filter();
+                    self.selected_index = 0;
+                    self.on_selection_changed().await?;
+                }
+            }
+
+            AppAction::CommandStart => {
+                self.command_palette = Some(CommandPalette::default());
+            }
+
+            AppAction::CommandChar(c) => {
+                if let Some(palette) = self.command_palette.as_mut() {
+                    palette.input.push(c);
+                    palette.cursor = 0;
+                }
+            }
+
+            AppAction::CommandBackspace => {
+                if let Some(palette) = self.command_palette.as_mut() {
+                    palette.input.pop();
+                    palette.cursor = 0;
+                }
+            }
+
+            AppAction::CommandUp => {
+                if let Some(palette) = self.command_palette.as_mut() {
+                    palette.cursor = palette.cursor.saturating_sub(1);
+                }
+            }
+
+            AppAction::CommandDown => {
+                let count = self.command_suggestions().len();
+                if let Some(palette) = self.command_palette.as_mut() {
+                    palette.cursor = (palette.cursor + 1).min(count.saturating_sub(1));
+                }
+            }
+
+            AppAction::CommandComplete => {
+                if let Some((line, _)) = self.highlighted_suggestion() {
+                    // Leave room for the argument of commands that take one
+                    let takes_args = find_command(&line).is_some_and(|c| !c.args.is_empty());
+                    if let Some(palette) = self.command_palette.as_mut() {
+                        palette.input = if takes_args { format!("{} ", line) } else { line };
+                        palette.cursor = 0;
+                    }
+                }
+            }
+
+            AppAction::CommandConfirm => {
+                // Run the highlighted suggestion; free-text arguments have none
+                let line = match self.highlighted_suggestion() {
+                    Some((line, _)) => line,
+                    None => self
+                        .command_palette
+                        .as_ref()
+                        .map(|p| p.input.trim().to_string())
+                        .unwrap_or_default(),
+                };
+                self.command_palette = None;
+                if !line.is_empty() {
+                    return Box::pin(self.run_command(&line)).await;
+                }
+            }
+
+            AppAction::CommandCancel => {
+                self.command_palette = None;
+            }
+
+            AppAction::ExitMode => {
+                let had_catchup = self.catchup.take().is_some();
+                let had_search = self.search_results.take().is_some();
+                let had_feed = self.feed_filter.take().is_some();
+                let had_category = self.category_filter.take().is_some();
+                if had_catchup || had_search || had_feed || had_category {
+                    self.invalidate_filter();
+                    self.selected_index = 0;
+                    self.on_selection_changed().await?;
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Switch to a saved view setup
+    async fn apply_workspace(&mut self, workspace: &Workspace) -> Result<()> {
+        self.filter = workspace.filter;
+        self.sort_mode = workspace.sort;
+        self.category_filter = workspace.category.clone();
+        self.tag_filter = workspace.tag.clone();
+        self.list_width = workspace.list_width.clamp(10, 90);
+        self.feed_filter = match &workspace.feed {
+            Some(url) => match self.feeds.iter().find(|f| &f.url == url) {
+                Some(feed) => Some(feed.id),
+                None => {
+                    self.show_toast(format!("Workspace feed no longer subscribed: {}", url));
+                    None
+                }
+            },
+            None => None,
+        };
+        self.workspace = Some(workspace.name.clone());
+        self.invalidate_filter();
+        self.selected_index = 0;
+        self.on_selection_changed().await
+    }
+
+    /// The current view setup, as a workspace called `name`
+    fn current_view(&self, name: &str) -> Workspace {
+        Workspace {
+            name: name.to_string(),
+            filter: self.filter,
+            sort: self.sort_mode,
+            category: self.category_filter.clone(),
+            feed: self
+                .feed_filter
+                .and_then(|id| self.feeds.iter().find(|f| f.id == id))
+                .map(|f| f.url.clone()),
+            tag: self.tag_filter.clone(),
+            list_width: self.list_width,
+        }
+    }
+
+    /// Remember the view and selected article for the next start
+    async fn save_session(&self) {
+        if !self.config.restore_session {
+            return;
+        }
+        let session = SessionState {
+            view: self.current_view(self.workspace.as_deref().unwrap_or("")),
+            article_id: self.selected_article().map(|a| a.id),
+            index: self.selected_index,
+            reader: self.reader_active,
+        };
+        let result = match serde_json::to_string(&session) {
+            Ok(json) => self.repository.set_ui_state(SESSION_STATE_KEY, json).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = result {
+            tracing::warn!("Failed to save session: {}", e);
+        }
+    }
+
+    /// Reopen the view and article the last session was on
+    pub async fn restore_session(&mut self) -> Result<()> {
+        if !self.config.restore_session {
+            return Ok(());
+        }
+        let Some(json) = self.repository.get_ui_state(SESSION_STATE_KEY).await? else {
+            return Ok(());
+        };
+        let session: SessionState = match serde_json::from_str(&json) {
+            Ok(session) => session,
+            Err(e) => {
+                tracing::warn!("Ignoring unreadable saved session: {}", e);
+                return Ok(());
+            }
+        };
+
+        self.apply_workspace(&session.view).await?;
+        self.workspace = Some(session.view.name).filter(|name| !name.is_empty());
+        let position = session
+            .article_id
+            .and_then(|id| self.filtered_articles().position(|a| a.id == id));
+        self.selected_index = position
+            .unwrap_or(session.index)
+            .min(self.filtered_len().saturating_sub(1));
+        self.on_selection_changed().await?;
+        if session.reader {
+            self.open_reader().await?;
+        }
+        Ok(())
+    }
+
+    /// Save the current view as a workspace, replacing one of the same name
+    fn save_workspace(&mut self, name: &str) {
+        let workspace = self.current_view(name);
+        let workspaces = &mut self.config.workspaces;
+        let index = match workspaces.iter().position(|w| w.name == name) {
+            Some(index) => {
+                workspaces[index] = workspace;
+                index
+            }
+            None => {
+                workspaces.push(workspace);
+                workspaces.len() - 1
+            }
+        };
+        self.workspace = Some(name.to_string());
+        match self.config.save() {
+            Ok(()) if index < 9 => {
+                self.show_toast(format!("Saved workspace {} ({})", name, index + 1))
+            }
+            Ok(()) => self.show_toast(format!("Saved workspace {} (:workspace to open)", name)),
+            Err(e) => self.show_toast(format!("Failed to save workspace: {}", e)),
+        }
+    }
+
+    /// Completions for the command line as (command line, hint), best first:
+    /// command names, then the values a command's argument can take
+    pub fn command_suggestions(&self) -> Vec<(String, String)> {
+        let Some(palette) = &self.command_palette else {
+            return Vec::new();
+        };
+        let input = palette.input.trim_start();
+        let Some((name, arg)) = input.split_once(' ') else {
+            return fuzzy_filter(input, COMMANDS.iter().map(|c| (c.name, c)))
+                .into_iter()
+                .map(|(name, c)| {
+                    let hint = format!("{} {}", c.args, c.description);
+                    (name.to_string(), hint.trim().to_string())
+                })
+                .collect();
+        };
+
+        let arg = arg.trim();
+        let values: Vec<(&str, String)> = match name {
+            "goto" => fuzzy_filter(arg, self.feeds.iter().map(|f| (f.title.as_str(), f.id)))
+                .into_iter()
+                .map(|(title, id)| (title, format!("{} unread", self.unread_counts.for_feed(id))))
+                .collect(),
+            "filter" => fuzzy_filter(arg, FILTERS.iter().copied())
+                .into_iter()
+                .map(|(value, _)| (value, String::new()))
+                .collect(),
+            "sort" => fuzzy_filter(arg, SORTS.iter().copied())
+                .into_iter()
+                .map(|(value, _)| (value, String::new()))
+                .collect(),
+            "simplify" => fuzzy_filter(arg, ReadingLevel::ALL.map(|level| (level.key(), level)))
+                .into_iter()
+                .map(|(value, level)| {
+                    let hint = if level == self.reading_level { "current" } else { "" };
+                    (value, hint.to_string())
+                })
+                .collect(),
+            "tag" => fuzzy_filter(arg, self.known_tags.iter().map(|t| (t.as_str(), ())))
+                .into_iter()
+                .map(|(value, _)| (value, String::new()))
+                .collect(),
+            "workspace" | "delete-workspace" => fuzzy_filter(
+                arg,
+                self.config.workspaces.iter().enumerate().map(|(i, w)| (w.name.as_str(), i)),
+            )
+            .into_iter()
+            .map(|(name, i)| (name, if i < 9 { format!("key {}", i + 1) } else { String::new() }))
+            .collect(),
+            "category" => {
+                let mut categories: Vec<&str> =
+                    self.feeds.iter().filter_map(|f| f.category.as_deref()).collect();
+                categories.sort_unstable();
+                categories.dedup();
+                fuzzy_filter(arg, categories.into_iter().map(|c| (c, c)))
+                    .into_iter()
+                    .map(|(category, _)| {
+                        let unread = self.unread_counts.for_category(&self.feeds, Some(category));
+                        (category, format!("{} unread", unread))
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        values
+            .into_iter()
+            .map(|(value, hint)| (format!("{} {}", name, value), hint))
+            .collect()
+    }
+
+    fn highlighted_suggestion(&self) -> Option<(String, String)> {
+        let cursor = self.command_palette.as_ref()?.cursor;
+        self.command_suggestions().into_iter().nth(cursor)
+    }
+
+    /// Run a command line from the palette. Returns true to quit.
+    async fn run_command(&mut self, line: &str) -> Result<bool> {
+        let (name, arg) = match line.split_once(' ') {
+            Some((name, arg)) => (name, arg.trim()),
+            None => (line, ""),
+        };
+        let Some(command) = find_command(name) else {
+            self.show_toast(format!("Unknown command: {}", name));
+            return Ok(false);
+        };
+
+        let action = match command.name {
+            "addfeed" if !arg.is_empty() => {
+                self.feed_input_active = true;
+                self.feed_input = arg.to_string();
+                self.feed_auth_url = None;
+                self.feed_offer = None;
+                self.start_feed_discovery();
+                return Ok(false);
+            }
+            "addfeed" => AppAction::AddFeed,
+            "refresh" => AppAction::RefreshFeeds,
+            "refresh-feed" => AppAction::RefreshFeed,
+            "filter" => {
+                match FILTERS.iter().find(|(value, _)| *value == arg) {
+                    Some(&(_, filter)) => {
+                        self.filter = filter;
+                        self.invalidate_filter();
+                        self.selected_index = 0;
+                        self.on_selection_changed().await?;
+                    }
+                    None => self.show_toast(format!("Usage: filter {}", command.args)),
+                }
+                return Ok(false);
+            }
+            "goto" => {
+                self.feed_filter = if arg.is_empty() {
+                    None
+                } else {
+                    let feeds = self.feeds.iter().map(|f| (f.title.as_str(), f.id));
+                    match fuzzy_filter(arg, feeds).first() {
+                        Some(&(_, id)) => Some(id),
+                        None => {
+                            self.show_toast(format!("No feed matches \"{}\"", arg));
+                            return Ok(false);
+                        }
+                    }
+                };
+                self.invalidate_filter();
+                self.selected_index = 0;
+                self.on_selection_changed().await?;
+                return Ok(false);
+            }
+            "category" => {
+                self.category_filter = (!arg.is_empty()).then(|| arg.to_string());
+                self.invalidate_filter();
+                self.selected_index = 0;
+                self.on_selection_changed().await?;
+                return Ok(false);
+            }
+            "list-width" => {
+                match arg.trim_end_matches('%').parse::<u16>() {
+                    Ok(width) if (10..=90).contains(&width) => self.list_width = width,
+                    _ => self.show_toast("Usage: list-width <10-90>".to_string()),
+                }
+                return Ok(false);
+            }
+            "workspace" => {
+                let workspace = self.config.workspaces.iter().find(|w| w.name == arg).cloned();
+                match workspace {
+                    Some(workspace) => self.apply_workspace(&workspace).await?,
+                    None => self.show_toast(format!("No workspace named \"{}\"", arg)),
+                }
+                return Ok(false);
+            }
+            "save-workspace" if arg.is_empty() => {
+                self.show_toast("Usage: save-workspace <name>".to_string());
+                return Ok(false);
+            }
+            "save-workspace" => {
+                self.save_workspace(arg);
+                return Ok(false);
+            }
+            "delete-workspace" => {
+                let before = self.config.workspaces.len();
+                self.config.workspaces.retain(|w| w.name != arg);
+                if self.config.workspaces.len() == before {
+                    self.show_toast(format!("No workspace named \"{}\"", arg));
+                } else if let Err(e) = self.config.save() {
+                    self.show_toast(format!("Failed to save config: {}", e));
+                } else {
+                    self.show_toast(format!("Deleted workspace {}", arg));
+                }
+                return Ok(false);
+            }
+            "tag" => {
+                self.tag_filter = (!arg.is_empty()).then(|| arg.to_string());
+                self.invalidate_filter();
+                self.selected_index = 0;
+                self.on_selection_changed().await?;
+                return Ok(false);
+            }
+            "sort" => {
+                match SORTS.iter().find(|(value, _)| *value == arg) {
+                    Some(&(_, mode)) => self.set_sort_mode(mode),
+                    None => self.show_toast(format!("Usage: sort {}", command.args)),
+                }
+                return Ok(false);
+            }
+            "search" => {
+                self.search_input_active = true;
+                self.search_input = arg.to_string();
+                self.search_input_status = None;
+                if !arg.is_empty() {
+                    self.start_search();
+                }
+                return Ok(false);
+            }
+            "mark-all-read" => {
+                let ids: Vec<i64> = self.filtered_articles().map(|a| a.id).collect();
+                for id in &ids {
+                    self.mark_read(*id).await?;
+                }
+                self.show_toast(format!("Marked {} articles read", ids.len()));
+                return Ok(false);
+            }
+            "delete-feed" => AppAction::DeleteFeed,
+            "feed-settings" => AppAction::FeedSettingsOpen,
+            "hide-read" => AppAction::CycleHideRead,
+            "find-feeds" => AppAction::DirectoryStart,
+            "discover" => AppAction::DiscoverOpen,
+            "subscribe-site" => AppAction::AddFeedFromArticle,
+            "import-opml" => AppAction::ImportOpmlStart,
+            "export-opml" => AppAction::ExportOpmlStart,
+            "export-notes" => AppAction::ExportNotes,
+            "catchup" => AppAction::CatchUpStart,
+            "summarize" => AppAction::SummaryStylesOpen,
+            "reader" => AppAction::OpenReader,
+            "open" => AppAction::OpenInBrowser,
+            "email" => AppAction::EmailArticle,
+            "related" => AppAction::RelatedStart,
+            "stats" => AppAction::StatsOpen,
+            "logs" => AppAction::LogsOpen,
+            "info" => AppAction::InfoOpen,
+            "links" => AppAction::LinksOpen,
+            "print" => AppAction::PrintArticle,
+            "copy-url" => AppAction::CopyUrl,
+            "copy-link" => AppAction::CopyMarkdownLink,
+            "copy-summary" => AppAction::CopySummary,
+            "bookmark" => AppAction::SaveToRaindrop,
+            "raindrop-sync" => AppAction::RaindropSync,
+            "star" => AppAction::ToggleStar,
+            "queue" => AppAction::QueueToggle,
+            "finish" => AppAction::QueueFinish,
+            "tags" => AppAction::EditTags,
+            "highlight" => AppAction::HighlightStart,
+            "translate" => AppAction::ToggleTranslation,
+            "chat" => AppAction::ChatOpen,
+            "share" => AppAction::ShareOpen,
+            "settings" => AppAction::SettingsOpen,
+            "simplify" if !arg.is_empty() => {
+                match ReadingLevel::ALL.into_iter().find(|level| level.key() == arg) {
+                    Some(level) => {
+                        self.reading_level = level;
+                        self.show_simplified = false;
+                        self.toggle_simplified().await?;
+                    }
+                    None => self.show_toast(format!("Usage: simplify {}", command.args)),
+                }
+                return Ok(false);
+            }
+            "simplify" => AppAction::ToggleSimplified,
+            "speak" => AppAction::Speak,
+            "stop" => AppAction::StopSpeaking,
+            "delete" => AppAction::DeleteArticle,
+            "undo" => AppAction::UndeleteArticle,
+            "help" => AppAction::ShowHelp,
+            "quit" => AppAction::Quit,
+            _ => return Ok(false),
+        };
+        self.handle_action(action).await
+    }
+
+    fn open_in_browser(&self) {
+        if let Some(article) = self.selected_article() {
+            Self::open_url(article.url.clone());
+        }
+    }
+
+    /// Open a URL in the browser (from a thread, as opening can block)
+    fn open_url(url: String) {
+        std::thread::spawn(move || {
+            let _ = open::that(&url);
+        });
+    }
+
+    /// Links in an article as (text, URL): from its HTML, or the URLs
+    /// written out in its text when there is no HTML
+    fn article_links(article: &Article) -> Vec<(String, String)> {
+        match (&article.content, &article.content_text) {
+            (Some(html), _) => extract_anchors(&article.url, html),
+            (None, Some(text)) => extract_links(&article.url, text)
+                .into_iter()
+                .map(|url| (url.clone(), url))
+                .collect(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    async fn open_reader(&mut self) -> Result<()> {
+        if let Some(id) = self.selected_article().map(|a| a.id) {
+            self.reader_active = true;
+            self.reader_scroll = 0;
+            self.mark_read(id).await?;
+            self.load_annotations();
+        }
+        Ok(())
+    }
+
+    /// What Enter does outside the reader
+    pub fn enter_action(&self) -> EnterAction {
+        self.config.enter_action
+    }
+
+    /// Whether list rows show the estimated reading time
+    pub fn list_reading_time(&self) -> bool {
+        self.config.list_reading_time
+    }
+
+    /// How much each row of the article list shows
+    pub fn list_density(&self) -> ListDensity {
+        self.config.list_density
+    }
+
+    /// Height of the feed content pane, in percent of the window beside the list
+    pub fn content_height(&self) -> u16 {
+        self.config.content_height.clamp(10, 90)
+    }
+
+    /// Switch to the next sort mode, keeping the selected article selected
+    fn cycle_sort_mode(&mut self) {
+        self.set_sort_mode(self.sort_mode.next());
+    }
+
+    /// Change (and remember) the sort order, keeping the selected article
+    fn set_sort_mode(&mut self, mode: SortMode) {
+        let selected_id = self.selected_article().map(|a| a.id);
+
+        self.sort_mode = mode;
+        self.invalidate_filter();
+        self.config.sort_mode = self.sort_mode;
+        if let Err(e) = self.config.save() {
+            tracing::warn!("Failed to save sort mode: {}", e);
+        }
+
+        let position = selected_id.and_then(|id| self.filtered_articles().position(|a| a.id == id));
+        if let Some(index) = position {
+            self.selected_index = index;
+        }
+    }
+
+    /// Show another article in the reader, marking it read in the database.
+    /// The local list keeps its read state so the filtered list doesn't shift.
+    async fn jump_in_reader(&mut self, index: Option<usize>) -> Result<()> {
+        let Some(index) = index else {
+            self.show_toast("No more unread articles".to_string());
+            return Ok(());
+        };
+        self.selected_index = index;
+        self.reader_scroll = 0;
+        self.on_selection_changed().await?;
+        if let Some(id) = self.selected_article().map(|a| a.id) {
+            self.mark_read(id).await?;
+        }
+        self.load_annotations();
+        Ok(())
+    }
+
+    /// Known tags that complete the fragment being typed, excluding tags already entered
+    pub fn tag_suggestions(&self) -> Vec<&str> {
+        let (entered, fragment) = match self.article_tags_input.rsplit_once(',') {
+            Some((entered, fragment)) => (parse_tags(entered), fragment),
+            None => (Vec::new(), self.article_tags_input.as_str()),
+        };
+        let fragment = fragment.trim().trim_start_matches('#').to_lowercase();
+        self.known_tags
+            .iter()
+            .filter(|tag| tag.starts_with(&fragment) && !entered.contains(tag))
+            .map(|tag| tag.as_str())
+            .take(TAG_SUGGESTIONS)
+            .collect()
+    }
+
+    /// Store the edited tags for the selected article
+    async fn save_article_tags(&mut self) -> Result<()> {
+        let Some(id) = self.selected_article().map(|a| a.id) else {
+            return Ok(());
+        };
+        let tags = parse_tags(&self.article_tags_input);
+        self.repository.set_article_tags(id, tags.clone()).await?;
+        if let Some(article) = self.articles.iter_mut().find(|a| a.id == id) {
+            article.tags = tags;
+        }
+        self.invalidate_filter();
+        self.known_tags = self.repository.get_all_tags().await?;
+        Ok(())
+    }
+
+    /// Articles in the reading queue
+    fn queued(&self) -> usize {
+        self.articles
+            .iter()
+            .filter(|a| ArticleFilter::Queue.matches(a))
+            .count()
+    }
+
+    /// Whether an article counts as unread, including reads not yet reflected locally
+    fn is_unread(&self, id: i64) -> bool {
+        self.articles
+            .iter()
+            .find(|a| a.id == id)
+            .is_some_and(|a| !a.is_read && !a.is_trashed() && !self.read_in_db.contains(&id))
+    }
+
+    /// Mark an article read in the database and update the unread counts.
+    /// Unless `hide_read = "immediately"`, the local list keeps its read state
+    /// so the filtered list doesn't shift.
+    async fn mark_read(&mut self, id: i64) -> Result<()> {
+        let Some(feed_id) = self.articles.iter().find(|a| a.id == id).map(|a| a.feed_id) else {
+            return Ok(());
+        };
+        if !self.is_unread(id) {
+            return Ok(());
+        }
+        self.repository.set_article_read(id, true).await?;
+        self.read_in_db.insert(id);
+        self.unread_counts.decrement(feed_id);
+        self.hide_read_immediately().await
+    }
+
+    /// With `hide_read = "immediately"`, take read articles out of the list
+    /// now, except the one open in the reader
+    async fn hide_read_immediately(&mut self) -> Result<()> {
+        if self.config.hide_read != HideRead::Immediately || self.reader_active {
+            return Ok(());
+        }
+        if self.hide_read_articles(false) {
+            self.on_selection_changed().await?;
+            self.read_timer_paused = true;
+        }
+        Ok(())
+    }
+
+    /// Apply the reads kept out of `articles` so they leave the Unread view,
+    /// optionally sparing the selected article. The cursor stays on the selected
+    /// article, or moves to the next one still listed; returns whether it moved.
+    fn hide_read_articles(&mut self, keep_selected: bool) -> bool {
+        let selected_id = self.selected_article().map(|a| a.id);
+        let kept = selected_id.filter(|_| keep_selected);
+        let hidden: Vec<i64> =
+            self.read_in_db.iter().copied().filter(|&id| Some(id) != kept).collect();
+        if hidden.is_empty() {
+            return false;
+        }
+        let following: Vec<i64> =
+            self.filtered_articles().skip(self.selected_index + 1).map(|a| a.id).collect();
+
+        for article in self.articles.iter_mut().filter(|a| hidden.contains(&a.id)) {
+            article.is_read = true;
+        }
+        self.read_in_db.retain(|id| !hidden.contains(id));
+        self.invalidate_filter();
+
+        let listed: HashMap<i64, usize> =
+            self.filtered_articles().enumerate().map(|(i, a)| (a.id, i)).collect();
+        let position = selected_id
+            .into_iter()
+            .chain(following)
+            .find_map(|id| listed.get(&id).copied());
+        self.selected_index = position.unwrap_or(listed.len().saturating_sub(1));
+        self.selected_article().map(|a| a.id) != selected_id
+    }
+
+    /// Build a catch-up plan from the entered time budget and enter catch-up mode
+    async fn start_catchup(&mut self) -> Result<()> {
+        let minutes: u32 = match self.catchup_input.trim().parse() {
+            Ok(m) if m > 0 => m,
+            _ => {
+                self.catchup_input_status = Some("Enter a number of minutes".to_string());
+                return Ok(());
+            }
+        };
+
+        // Cross-feed duplicates are read along with their canonical article
+        let articles: Vec<&Article> = self
+            .articles
+            .iter()
+            .filter(|a| !a.is_trashed() && a.canonical_id.is_none())
+            .collect();
+        let plan = CatchUpPlan::build(&articles, minutes);
+        if plan.article_ids.is_empty() {
+            self.catchup_input_status = Some("Nothing unread fits in that time".to_string());
+            return Ok(());
+        }
+
+        self.catchup_input_active = false;
+        self.catchup_input.clear();
+        self.catchup_input_status = None;
+        self.catchup = Some(plan);
+        self.invalidate_filter();
+        self.selected_index = 0;
+        self.on_selection_changed().await
+    }
+
+    /// Mark the current catch-up article read and move to the next one
+    async fn advance_catchup(&mut self) -> Result<()> {
+        let Some(current) = self.catchup.as_ref().and_then(|p| p.current()) else {
+            return Ok(());
+        };
+
+        self.mark_read(current).await?;
+        if let Some(article) = self.articles.iter_mut().find(|a| a.id == current) {
+            article.is_read = true;
+        }
+        self.invalidate_filter();
+
+        let Some(plan) = self.catchup.as_mut() else {
+            return Ok(());
+        };
+        plan.position += 1;
+        if plan.is_finished() {
+            let count = plan.article_ids.len();
+            self.catchup = None;
+            self.selected_index = 0;
+            self.show_toast(format!("Catch-up complete: {} articles read", count));
+        } else {
+            self.selected_index = plan.position;
+        }
+        self.on_selection_changed().await
+    }
+
+    /// Select an article, showing all articles first if it isn't listed
+    async fn select_article_id(&mut self, id: i64) -> Result<()> {
+        // Open a collapsed run of similar titles to reach an article in it
+        if let Some(leader) = self.similar_group_of(id).filter(|&leader| leader != id) {
+            if self.expanded_groups.insert(leader) {
+                self.invalidate_filter();
+            }
+        }
+        if !self.filtered_articles().any(|a| a.id == id) {
+            self.catchup = None;
+            self.search_results = None;
+            self.feed_filter = None;
+            self.category_filter = None;
+            self.tag_filter = None;
+            self.filter = ArticleFilter::All;
+            self.invalidate_filter();
+        }
+        let position = self.filtered_articles().position(|a| a.id == id);
+        if let Some(index) = position {
+            self.selected_index = index;
+        }
+        self.on_selection_changed().await
+    }
+
+    /// Select the listed article at `index`, or the last one past the end
+    async fn select_index(&mut self, index: usize) -> Result<()> {
+        let len = self.filtered_len();
+        if len == 0 {
+            return Ok(());
+        }
+        let index = index.min(len - 1);
+        if index != self.selected_index {
+            self.selected_index = index;
+            self.on_selection_changed().await?;
+        }
+        Ok(())
+    }
+
+    /// Half the rows of the article list, for Ctrl-d and Ctrl-u
+    fn half_page(&self) -> usize {
+        let lines = self.pane_areas.get().list.height.saturating_sub(2) as usize;
+        (lines / self.list_density().lines() / 2).max(1)
+    }
+
+    /// Lines a row of the article list takes
+    pub fn row_height(&self, row: ListRow) -> usize {
+        match row {
+            ListRow::Header(_) => 1,
+            ListRow::Article(_) => self.list_density().lines(),
+        }
+    }
+
+    /// The list row shown `line` lines below the top of the list
+    fn list_row_at(&self, line: usize) -> Option<ListRow> {
+        let mut top = 0;
+        for &row in self.list_rows().iter().skip(self.list_offset.get()) {
+            top += self.row_height(row);
+            if line < top {
+                return Some(row);
+            }
+        }
+        None
+    }
+
+    /// Where the group after the article at `index` starts or, going back,
+    /// where its own group starts (the previous one's when already there).
+    /// Groups are the date groups when the list has date headers, otherwise
+    /// runs of articles from the same feed.
+    fn group_start(&self, index: usize, forward: bool) -> Option<usize> {
+        let today = chrono::Local::now().date_naive();
+        let by_date = self.groups_by_date();
+        let keys: Vec<i64> = self
+            .filtered_articles()
+            .map(|a| if by_date { a.age_group(today) as i64 } else { a.feed_id })
+            .collect();
+        let current = *keys.get(index)?;
+        if forward {
+            return (index..keys.len()).find(|&i| keys[i] != current);
+        }
+        let mut start = index;
+        if start > 0 && keys[start - 1] != current {
+            start -= 1;
+        }
+        let key = keys[start];
+        while start > 0 && keys[start - 1] == key {
+            start -= 1;
+        }
+        Some(start)
+    }
+
+    /// Let a count or `g` typed on its own take effect once nothing follows
+    /// it: a single digit lists only the nth feed (or switches workspace with
+    /// `number_keys = "workspaces"`), `g` opens the summary styles
+    pub async fn expire_key_prefix(&mut self) -> Result<()> {
+        let Some(prefix) = self.key_prefix else {
+            return Ok(());
+        };
+        if prefix.typed_at.elapsed() < KEY_PREFIX_TIMEOUT {
+            return Ok(());
+        }
+        self.key_prefix = None;
+        match prefix {
+            KeyPrefix { g: true, count: None, .. } => {
+                self.handle_action(AppAction::SummaryStylesOpen).await?;
+            }
+            KeyPrefix { g: false, count: Some(digit @ 1..=9), .. } => {
+                let action = match self.config.number_keys() {
+                    NumberKeys::Feeds => AppAction::FilterFeed(Some(digit - 1)),
+                    NumberKeys::Workspaces => AppAction::SwitchWorkspace(digit - 1),
+                };
+                self.handle_action(action).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// A count or `g` waiting for its motion, for the status bar
+    pub fn key_prefix_label(&self) -> Option<String> {
+        let prefix = self.key_prefix?;
+        let count = prefix.count.map(|c| c.to_string()).unwrap_or_default();
+        Some(format!("{}{}", count, if prefix.g { "g" } else { "" }))
+    }
+
+    async fn on_selection_changed(&mut self) -> Result<()> {
+        // Read articles the cursor moved off leave the Unread view
+        if self.config.hide_read != HideRead::Session {
+            self.hide_read_articles(true);
+        }
+        self.read_timer_paused = false;
+
+        // Manual navigation in catch-up mode moves the plan cursor too
+        if let Some(plan) = self.catchup.as_mut() {
+            plan.position = self.selected_index;
+        }
+
+        // Reset state when selection changes
+        self.selected_at = Instant::now();
+        self.summary_status = SummaryStatus::NotGenerated;
+        self.pending_summary_article_id = None;
+        self.current_summary = None;
+        self.key_facts = None;
+        self.full_text = None;
+        self.translation = None;
+        self.show_translation = false;
+        self.simplification = None;
+        self.show_simplified = false;
+        self.is_saved_to_raindrop = false;
+        self.annotations.clear();
+        self.annotations_article_id = None;
+        self.related_picker = None;
+        self.raindrop_picker = None;
+        self.content_scroll = 0;
+        self.summary_scroll = 0;
+
+        // Check if current article is saved to raindrop
+        let article_id = self.selected_article().map(|a| a.id);
+        if let Some(id) = article_id {
+            self.is_saved_to_raindrop = self
+                .repository
+                .is_saved_to_raindrop(id)
+                .await?;
+
+            // Check for cached summary
+            if let Some(summary) = self.repository.get_summary(id).await? {
+                self.current_summary = Some(summary);
+                self.summary_status = SummaryStatus::Generated;
+            } else if self.is_summary_pending(id) {
+                self.summary_status = SummaryStatus::Generating;
+                self.pending_summary_article_id = Some(id);
+            }
+            self.key_facts = self.repository.get_key_facts(id).await?;
+            self.full_text = self.repository.get_full_text(id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Show the selected article's summary in `variant` (the regular one for
+    /// `None`), generating it unless it is stored or when `force` is set
+    async fn generate_summary(
+        &mut self,
+        variant: Option<SummaryVariant>,
+        force: bool,
+    ) -> Result<()> {
+        self.load_annotations();
+
+        if self.summarizer.is_none() {
+            self.summary_status = SummaryStatus::NoApiKey;
+            return Ok(());
+        }
+
+        let Some(article) = self.selected_article() else {
+            return Ok(());
+        };
+
+        let article_id = article.id;
+        let title = article.title.clone();
+        let article_url = article.url.clone();
+        let mut options = match self.feeds.iter().find(|f| f.id == article.feed_id) {
+            Some(feed) => self.config.summary.options_for(&feed.url, &feed.title),
+            None => self.config.summary.options_for("", ""),
+        };
+        options.variant = variant;
+
+        // Get RSS content as fallback
+        let rss_content = article
+            .content_text
+            .clone()
+            .or_else(|| article.content.clone())
+            .unwrap_or_default();
+
+        // Check cache first
+        let cached = match variant {
+            _ if force => None,
+            None => self.repository.get_summary(article_id).await?,
+            Some(variant) => self
+                .repository
+                .get_summary_variants(article_id)
+                .await?
+                .into_iter()
+                .find(|s| s.variant == Some(variant)),
+        };
+        if let Some(summary) = cached {
+            self.current_summary = Some(summary);
+            self.summary_status = SummaryStatus::Generated;
+            if self.config.summary.key_facts && self.key_facts.is_none() {
+                let content = self.repository.get_full_text(article_id).await?;
+                self.start_key_facts(article_id, title, content.unwrap_or(rss_content));
+            }
+            return Ok(());
+        }
+
+        self.summary_status = SummaryStatus::Generating;
+        self.pending_summary_article_id = Some(article_id);
+        let job = (article_id, variant);
+        if self.summarizing.contains(&job)
+            || self.summary_queue.iter().any(|j| (j.article_id, j.variant) == job)
+        {
+            return Ok(());
+        }
+
+        let (content, source) = Self::summary_input(
+            &self.content_fetcher,
+            &self.repository,
+            article_id,
+            &article_url,
+            rss_content,
+        )
+        .await;
+        if self.full_text.is_none() {
+            self.full_text = self.repository.get_full_text(article_id).await?;
+        }
+        if self.config.summary.key_facts && self.key_facts.is_none() {
+            self.start_key_facts(article_id, title.clone(), content.clone());
+        }
+
+        self.summary_queue.push_back(SummaryJob {
+            article_id,
+            variant,
+            title,
+            content,
+            source,
+            options,
+        });
+        self.start_queued_summaries();
+
+        // Don't update local is_read state - keep article visible in filtered list
+        // Database is already updated, so it will show as read next session
+
+        Ok(())
+    }
+
+    /// Whether a summary of the article is queued or being made
+    pub fn is_summary_pending(&self, article_id: i64) -> bool {
+        self.summarizing.iter().any(|(id, _)| *id == article_id)
+            || self.summary_queue.iter().any(|job| job.article_id == article_id)
+    }
+
+    /// Start queued summaries in the background while there are free slots
+    fn start_queued_summaries(&mut self) {
+        let Some(summarizer) = &self.summarizer else {
+            return;
+        };
+        let concurrency = self.config.summary.concurrency.max(1);
+        while self.summarizing.len() < concurrency {
+            let Some(job) = self.summary_queue.pop_front() else {
+                break;
+            };
+            self.summarizing.push((job.article_id, job.variant));
+            let summarizer = Arc::clone(summarizer);
+            let tx = self.summary_tx.clone();
+
+            tokio::spawn(async move {
+                let result = match summarizer
+                    .generate_summary(&job.title, &job.content, &job.options)
+                    .await
+                {
+                    Ok((summary_text, usage)) => Ok(GeneratedSummary {
+                        content: summary_text,
+                        model: summarizer.model_version().to_string(),
+                        usage,
+                        cost_usd: summarizer.cost_usd(usage),
+                    }),
+                    Err(e) => Err(e.to_string()),
+                };
+
+                let _ = tx
+                    .send(SummaryResult {
+                        article_id: job.article_id,
+                        variant: job.variant,
+                        source: job.source,
+                        result,
+                    })
+                    .await;
+            });
+        }
+    }
+
+    /// Extract an article's TL;DR, key facts and named entities in the background
+    fn start_key_facts(&mut self, article_id: i64, title: String, content: String) {
+        let Some(summarizer) = &self.summarizer else {
+            return;
+        };
+        if self.extracting_facts.is_some() {
+            return;
+        }
+        self.extracting_facts = Some(article_id);
+        let summarizer = Arc::clone(summarizer);
+        let tx = self.key_facts_tx.clone();
+
+        tokio::spawn(async move {
+            let result = summarizer
+                .extract_key_facts(&title, &content)
+                .await
+                .map(|(facts, usage)| {
+                    let model = summarizer.model_version().to_string();
+                    (facts, model, usage, summarizer.cost_usd(usage))
+                })
+                .map_err(|e| e.to_string());
+            let _ = tx.send(KeyFactsResult { article_id, result }).await;
+        });
+    }
+
+    /// Poll for extracted key facts (non-blocking)
+    pub async fn poll_key_facts_result(&mut self) -> Result<()> {
+        let Ok(result) = self.key_facts_rx.try_recv() else {
+            return Ok(());
+        };
+        self.extracting_facts = None;
+        match result.result {
+            Ok((facts, model, usage, cost_usd)) => {
+                self.repository
+                    .save_key_facts(result.article_id, &facts, model, usage, cost_usd)
+                    .await?;
+                // Only show them if the article is still selected
+                if self.selected_article().map(|a| a.id) == Some(result.article_id) {
+                    self.key_facts = Some(facts);
+                }
+            }
+            Err(e) => tracing::warn!("Key facts extraction failed: {}", e),
+        }
+        Ok(())
+    }
+
+    /// Switch the article text between the original and its translation,
+    /// translating it first if that hasn't been done yet
+    async fn toggle_translation(&mut self) -> Result<()> {
+        if self.show_translation {
+            self.show_translation = false;
+            return Ok(());
+        }
+        let Some(article) = self.selected_article() else {
+            return Ok(());
+        };
+        let article_id = article.id;
+        let language = self.config.translate_to.clone();
+
+        if self.translation.is_none() {
+            self.translation = self.repository.get_translation(article_id, &language).await?;
+        }
+        if self.translation.is_some() {
+            self.show_translation = true;
+            self.show_simplified = false;
+            return Ok(());
+        }
+
+        let Some(summarizer) = &self.summarizer else {
+            self.show_toast("Set claude_api_key in config.toml to translate".to_string());
+            return Ok(());
+        };
+        if self.translating.is_some() {
+            self.show_toast("Already translating an article".to_string());
+            return Ok(());
+        }
+        let Some(article) = self.selected_article() else {
+            return Ok(());
+        };
+        let Some(content) = article.content_text.clone().or_else(|| article.content.clone()) else {
+            self.show_toast("Nothing to translate".to_string());
+            return Ok(());
+        };
+        let title = article.title.clone();
+
+        self.translating = Some(article_id);
+        let summarizer = Arc::clone(summarizer);
+        let tx = self.translation_tx.clone();
+
+        tokio::spawn(async move {
+            let result = summarizer
+                .translate(&title, &content, &language)
+                .await
+                .map(|text| (text, summarizer.model_version().to_string()))
+                .map_err(|e| e.to_string());
+            let _ = tx
+                .send(TranslationResult {
+                    article_id,
+                    language,
+                    result,
+                })
+                .await;
+        });
+        Ok(())
+    }
+
+    /// Poll for a completed translation (non-blocking)
+    pub async fn poll_translation_result(&mut self) -> Result<()> {
+        let Ok(result) = self.translation_rx.try_recv() else {
+            return Ok(());
+        };
+        self.translating = None;
+        match result.result {
+            Ok((text, model)) => {
+                self.repository
+                    .save_translation(result.article_id, result.language, text.clone(), model)
+                    .await?;
+                // Only show it if the article is still selected
+                if self.selected_article().map(|a| a.id) == Some(result.article_id) {
+                    self.translation = Some(text);
+                    self.show_translation = true;
+                    self.show_simplified = false;
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Translation failed: {}", e);
+                self.show_toast(format!("Translation failed: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Switch the article text between the original and a plain-language
+    /// rewrite at `reading_level`, rewriting it first if that hasn't been done yet
+    async fn toggle_simplified(&mut self) -> Result<()> {
+        if self.show_simplified {
+            self.show_simplified = false;
+            return Ok(());
+        }
+        let Some(article) = self.selected_article() else {
+            return Ok(());
+        };
+        let article_id = article.id;
+        let level = self.reading_level;
+
+        if self.simplification.as_ref().is_none_or(|(cached, _)| *cached != level) {
+            self.simplification = self
+                .repository
+                .get_simplification(article_id, level.key())
+                .await?
+                .map(|text| (level, text));
+        }
+        if self.simplification.is_some() {
+            self.show_simplified = true;
+            self.show_translation = false;
+            return Ok(());
+        }
+
+        let Some(summarizer) = &self.summarizer else {
+            self.show_toast("Set claude_api_key in config.toml to simplify".to_string());
+            return Ok(());
+        };
+        if self.simplifying.is_some() {
+            self.show_toast("Already simplifying an article".to_string());
+            return Ok(());
+        }
+        let Some(article) = self.selected_article() else {
+            return Ok(());
+        };
+        let Some(content) = article.content_text.clone().or_else(|| article.content.clone()) else {
+            self.show_toast("Nothing to simplify".to_string());
+            return Ok(());
+        };
+        let title = article.title.clone();
+
+        self.simplifying = Some(article_id);
+        let summarizer = Arc::clone(summarizer);
+        let tx = self.simplify_tx.clone();
+
+        tokio::spawn(async move {
+            let result = summarizer
+                .simplify(&title, &content, level)
+                .await
+                .map(|text| (text, summarizer.model_version().to_string()))
+                .map_err(|e| e.to_string());
+            let _ = tx
+                .send(SimplifyResult {
+                    article_id,
+                    level,
+                    result,
+                })
+                .await;
+        });
+        Ok(())
+    }
+
+    /// Poll for a completed plain-language rewrite (non-blocking)
+    pub async fn poll_simplify_result(&mut self) -> Result<()> {
+        let Ok(result) = self.simplify_rx.try_recv() else {
+            return Ok(());
+        };
+        self.simplifying = None;
+        match result.result {
+            Ok((text, model)) => {
+                self.repository
+                    .save_simplification(result.article_id, result.level.key(), text.clone(), model)
+                    .await?;
+                // Only show it if the article is still selected
+                if self.selected_article().map(|a| a.id) == Some(result.article_id) {
+                    self.simplification = Some((result.level, text));
+                    self.show_simplified = true;
+                    self.show_translation = false;
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Simplification failed: {}", e);
+                self.show_toast(format!("Simplification failed: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Open the chat about the selected article, with its earlier messages
+    async fn open_chat(&mut self) -> Result<()> {
+        if self.summarizer.is_none() {
+            self.show_toast("Set claude_api_key in config.toml to chat".to_string());
+            return Ok(());
+        }
+        let Some(article) = self.selected_article() else {
+            return Ok(());
+        };
+        let (article_id, title) = (article.id, article.title.clone());
+        let messages = self.repository.get_chat_messages(article_id).await?;
+        self.chat = Some(ArticleChat {
+            article_id,
+            title,
+            messages,
+            input: String::new(),
+            waiting: false,
+            scroll: 0,
+            context: None,
+        });
+        Ok(())
+    }
+
+    /// A setting's current value for the settings screen
+    pub fn setting_value(&self, setting: Setting) -> String {
+        self.config.setting_value(setting)
+    }
+
+    /// Auto-refresh interval changed on the settings screen since last asked
+    pub fn take_refresh_interval_change(&mut self) -> Option<u32> {
+        self.refresh_interval_change.take()
+    }
+
+    /// Save the value typed on the settings screen and apply it, or keep the
+    /// input open with the reason it was refused
+    fn confirm_setting(&mut self) {
+        let Some(settings) = self.settings.as_mut() else {
+            return;
+        };
+        let Some(value) = settings.editing.clone() else {
+            return;
+        };
+        let setting = Setting::ALL[settings.cursor];
+        match self.config.set_setting(setting, &value) {
+            Ok(()) => {
+                settings.editing = None;
+                settings.error = None;
+                self.apply_setting(setting);
+                self.show_toast(format!("Saved {}", setting.key()));
+            }
+            Err(e) => settings.error = Some(e.to_string()),
+        }
+    }
+
+    /// Bring state built from the config up to date with a changed setting;
+    /// the rest is read from the config where it's used
+    fn apply_setting(&mut self, setting: Setting) {
+        match setting {
+            Setting::ClaudeApiKey
+            | Setting::SummaryStyle
+            | Setting::SummaryLanguage
+            | Setting::SummaryMonthlyBudget
+            | Setting::SummaryKeyFacts => {
+                let config = &self.config;
+                self.summarizer = config.claude_api_key.as_ref().map(|key| {
+                    let proxy = config.proxy_for(ProxyService::Claude);
+                    Arc::new(Summarizer::new(key.clone(), &config.summary, proxy))
+                });
+            }
+            Setting::RaindropToken => {
+                let proxy = self.config.proxy_for(ProxyService::Raindrop);
+                self.raindrop = self
+                    .config
+                    .raindrop_token
+                    .as_ref()
+                    .map(|token| RaindropClient::new(token.clone(), proxy));
+                self.raindrop_collections = None;
+            }
+            Setting::HypothesisToken => {
+                let proxy = self.config.proxy_for(ProxyService::Hypothesis);
+                self.hypothesis = self
+                    .config
+                    .hypothesis_token
+                    .as_ref()
+                    .map(|token| Arc::new(HypothesisClient::new(token.clone(), proxy)));
+            }
+            Setting::RefreshInterval => {
+                self.refresh_interval_change = Some(self.config.refresh_interval_minutes);
+            }
+            Setting::ListWidth => self.list_width = self.config.list_width,
+            Setting::GroupByDate => self.invalidate_filter(),
+            Setting::ReadingLevel => self.reading_level = self.config.reading_level,
+            _ => {}
+        }
+    }
+
+    /// Ask Claude the question typed in the chat. The article's full text
+    /// goes with it: the snapshot stored for its summary when there is one,
+    /// else fetched now the same way.
+    fn send_chat_message(&mut self) {
+        let Some(summarizer) = &self.summarizer else {
+            return;
+        };
+        let Some(chat) = self.chat.as_mut() else {
+            return;
+        };
+        let question = chat.input.trim().to_string();
+        if question.is_empty() || chat.waiting {
+            return;
+        }
+        let Some(article) = self.articles.iter().find(|a| a.id == chat.article_id) else {
+            return;
+        };
+        chat.input.clear();
+        chat.messages.push(ChatMessage {
+            role: ChatRole::User,
+            content: question.clone(),
+        });
+        chat.waiting = true;
+        chat.scroll = 0;
+
+        let article_id = chat.article_id;
+        let title = chat.title.clone();
+        let history = chat.messages.clone();
+        let context = chat.context.clone();
+        let article_url = article.url.clone();
+        let rss_content = article
+            .content_text
+            .clone()
+            .or_else(|| article.content.clone())
+            .unwrap_or_default();
+        let summarizer = Arc::clone(summarizer);
+        let content_fetcher = self.content_fetcher.clone();
+        let repository = self.repository.clone();
+        let tx = self.chat_tx.clone();
+
+        tokio::spawn(async move {
+            let context = match context {
+                Some(context) => context,
+                None => {
+                    Self::summary_input(
+                        &content_fetcher,
+                        &repository,
+                        article_id,
+                        &article_url,
+                        rss_content,
+                    )
+                    .await
+                    .0
+                }
+            };
+            let result = summarizer
+                .chat(&title, &context, &history)
+                .await
+                .map(|(answer, usage)| (answer, usage, summarizer.cost_usd(usage)))
+                .map_err(|e| e.to_string());
+            let _ = tx
+                .send(ChatResult {
+                    article_id,
+                    question,
+                    context,
+                    result,
+                })
+                .await;
+        });
+    }
+
+    /// Poll for Claude's answer in an article chat (non-blocking)
+    pub async fn poll_chat_result(&mut self) -> Result<()> {
+        let Ok(result) = self.chat_rx.try_recv() else {
+            return Ok(());
+        };
+        let chat = self.chat.as_mut().filter(|chat| chat.article_id == result.article_id);
+        match result.result {
+            Ok((answer, usage, cost_usd)) => {
+                if let Some(chat) = chat {
+                    chat.messages.push(ChatMessage {
+                        role: ChatRole::Assistant,
+                        content: answer.clone(),
+                    });
+                    chat.waiting = false;
+                    chat.context = Some(result.context);
+                }
+                self.repository
+                    .save_chat_exchange(result.article_id, result.question, answer, usage, cost_usd)
+                    .await?;
+            }
+            Err(e) => {
+                tracing::warn!("Chat failed: {}", e);
+                // Put the question back to retry
+                if let Some(chat) = chat {
+                    chat.messages.pop();
+                    chat.input = result.question;
+                    chat.waiting = false;
+                    chat.context = Some(result.context);
+                }
+                self.show_toast(format!("Chat failed: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Write a post sharing the selected article, opening with a line from
+    /// Claude when `social.blurb` is on
+    fn open_share(&mut self) {
+        let Some(social) = &self.social else {
+            self.show_toast("Set up [social] in config.toml to share articles".to_string());
+            return;
+        };
+        let Some(article) = self.selected_article() else {
+            return;
+        };
+        if article.url.is_empty() {
+            self.show_toast("Article has no URL to share".to_string());
+            return;
+        }
+        let targets = social.targets();
+        let text = format!("{}\n{}", article.title, article.url);
+
+        // What is left for the opening line on the strictest service
+        let room = targets
+            .iter()
+            .map(|t| t.max_chars().saturating_sub(t.post_length(&text) + 2))
+            .min()
+            .unwrap_or_default();
+        let drafting = match &self.summarizer {
+            Some(summarizer) if self.config.social.blurb && room >= MIN_BLURB_CHARS => {
+                let summarizer = Arc::clone(summarizer);
+                let tx = self.share_blurb_tx.clone();
+                let article_id = article.id;
+                let title = article.title.clone();
+                let content = article
+                    .content_text
+                    .clone()
+                    .or_else(|| article.content.clone())
+                    .unwrap_or_default();
+                tokio::spawn(async move {
+                    let result = summarizer
+                        .share_blurb(&title, &content, room)
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(ShareBlurbResult { article_id, result }).await;
+                });
+                true
+            }
+            _ => false,
+        };
+
+        self.share = Some(ShareCompose {
+            article_id: article.id,
+            targets,
+            target: 0,
+            text,
+            drafting,
+            posting: false,
+            status: None,
+        });
+    }
+
+    /// Publish the post being written (non-blocking)
+    fn send_share(&mut self) {
+        let Some(social) = &self.social else {
+            return;
+        };
+        let Some(share) = self.share.as_mut() else {
+            return;
+        };
+        let text = share.text.trim().to_string();
+        if text.is_empty() || share.drafting || share.posting {
+            return;
+        }
+        let target = share.target();
+        let length = target.post_length(&text);
+        if length > target.max_chars() {
+            share.status = Some(format!(
+                "Error: {} characters, {} takes {} at most",
+                length,
+                target.label(),
+                target.max_chars()
+            ));
+            return;
+        }
+        share.posting = true;
+        share.status = None;
+
+        let social = Arc::clone(social);
+        let tx = self.share_tx.clone();
+        tokio::spawn(async move {
+            let result = social.post(target, &text).await.map_err(|e| e.to_string());
+            let _ = tx.send(ShareResult { target, result }).await;
+        });
+    }
+
+    /// Poll for a drafted opening line and for published posts (non-blocking)
+    pub fn poll_share_result(&mut self) {
+        if let Ok(blurb) = self.share_blurb_rx.try_recv() {
+            let share = self
+                .share
+                .as_mut()
+                .filter(|s| s.article_id == blurb.article_id && s.drafting);
+            if let Some(share) = share {
+                share.drafting = false;
+                match blurb.result {
+                    Ok(line) if !line.is_empty() => {
+                        share.text = format!("{}\n\n{}", line, share.text);
+                    }
+                    Ok(_) => {}
+                    Err(e) => share.status = Some(format!("No opening line: {}", e)),
+                }
+            }
+        }
+
+        let Ok(posted) = self.share_rx.try_recv() else {
+            return;
+        };
+        match posted.result {
+            Ok(url) => {
+                self.share = None;
+                self.show_toast(format!("Shared to {}: {}", posted.target.label(), url));
+            }
+            Err(e) => {
+                tracing::warn!("Sharing to {} failed: {}", posted.target.label(), e);
+                match self.share.as_mut() {
+                    Some(share) => {
+                        share.posting = false;
+                        share.status = Some(format!("Error: {}", e));
+                    }
+                    None => self.show_toast(format!("Sharing failed: {}", e)),
+                }
+            }
+        }
+    }
+
+    /// Text shown for an article: its translation or plain-language rewrite
+    /// while toggled on, else the feed content
+    pub fn article_text<'a>(&'a self, article: &'a Article) -> Option<&'a str> {
+        let selected = self.selected_article().map(|a| a.id) == Some(article.id);
+        match (&self.translation, &self.simplification) {
+            (_, Some((_, simplified))) if self.show_simplified && selected => Some(simplified),
+            (Some(translation), _) if self.show_translation && selected => Some(translation),
+            _ if selected && self.shows_full_text(article) => self.full_text.as_deref(),
+            _ => article.content_text.as_deref().or(article.content.as_deref()),
+        }
+    }
+
+    /// Whether the page text stored for the selected article stands in for
+    /// the feed's content, which is only an excerpt
+    fn shows_full_text(&self, article: &Article) -> bool {
+        let feed_content = article.content_text.as_deref().or(article.content.as_deref());
+        self.full_text.is_some()
+            && Self::feed_content_source(feed_content.unwrap_or_default())
+                == SummarySource::FeedExcerpt
+    }
+
+    /// What the article text is showing instead of the original, if anything
+    pub fn article_text_label(&self) -> Option<String> {
+        if self.show_simplified {
+            Some(format!("simplified for {} readers", self.reading_level.key()))
+        } else if self.show_translation {
+            Some(format!("translated to {}", self.config.translate_to))
+        } else if self.selected_article().is_some_and(|a| self.shows_full_text(a)) {
+            Some("full article".to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Text to summarize an article from: the page text stored by an earlier
+    /// fetch, else the full page when it can be fetched (with browser cookies
+    /// for allowlisted domains), else the feed's content
+    async fn summary_input(
+        content_fetcher: &ContentFetcher,
+        repository: &Repository,
+        article_id: i64,
+        article_url: &str,
+        rss_content: String,
+    ) -> (String, SummarySource) {
+        let stored = repository.get_full_text(article_id).await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to read stored full text: {}", e);
+            None
+        });
+        let (content, mut source) = match stored {
+            Some(stored) => (stored, SummarySource::FullArticle),
+            None => match content_fetcher.fetch_full_content(article_url).await {
+                Ok(Some(full_content)) => {
+                    tracing::info!("Fetched full content for: {}", article_url);
+                    // Keep a snapshot so summaries, chat, search and the reader
+                    // reuse it instead of fetching the page again
+                    let text = full_content.clone();
+                    if let Err(e) = repository.save_full_text(article_id, text).await {
+                        tracing::warn!("Failed to store full text: {}", e);
+                    }
+                    (full_content, SummarySource::FullArticle)
+                }
+                Ok(None) => {
+                    tracing::debug!("No full content available, using RSS content");
+                    let source = Self::feed_content_source(&rss_content);
+                    (rss_content, source)
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to fetch full content: {}, using RSS", e);
+                    let source = Self::feed_content_source(&rss_content);
+                    (rss_content, source)
+                }
+            }
+        };
+        if content.len() > MAX_INPUT_CHARS {
+            source = SummarySource::Truncated;
+        }
+        (content, source)
+    }
+
+    /// Classify feed-provided content as full content or a short excerpt
+    fn feed_content_source(content: &str) -> SummarySource {
+        if content.trim().len() < EXCERPT_MAX_CHARS {
+            SummarySource::FeedExcerpt
+        } else {
+            SummarySource::FeedContent
+        }
+    }
+
+    /// Advance the spinner animation frame
+    pub fn tick_spinner(&mut self) {
+        self.spinner_frame = (self.spinner_frame + 1) % 10;
+    }
+
+    /// Mark the selected article read once it has been on screen for READ_DELAY.
+    /// Only the database is updated so the current list doesn't shift.
+    pub async fn check_read_timer(&mut self) -> Result<()> {
+        if self.read_timer_paused || self.selected_at.elapsed() < READ_DELAY {
+            return Ok(());
+        }
+        match self.selected_article().map(|a| a.id) {
+            Some(id) => self.mark_read(id).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Read the selected article aloud: its summary if there is one, else
+    /// the full text (always the full text in the reader). While reading,
+    /// pause or resume instead.
+    fn speak_article(&mut self) {
+        let Some(speaker) = self.speaker.as_mut() else {
+            self.show_toast("No text-to-speech command found; set command in [speech]".to_string());
+            return;
+        };
+        if speaker.is_speaking() {
+            match speaker.toggle_pause() {
+                Ok(paused) => self.speech_paused = paused,
+                Err(e) => self.show_toast(format!("Error: {}", e)),
+            }
+            return;
+        }
+
+        let Some(article) = self.selected_article() else {
+            return;
+        };
+        let summary = self
+            .current_summary
+            .as_ref()
+            .filter(|s| s.article_id == article.id && !self.reader_active)
+            .map(|s| s.content.clone());
+        let body = summary.or_else(|| self.article_text(article).map(str::to_string));
+        let Some(body) = body else {
+            self.show_toast("Nothing to read aloud".to_string());
+            return;
+        };
+        let text = format!("{}.\n\n{}", article.title, body);
+
+        let Some(speaker) = self.speaker.as_mut() else {
+            return;
+        };
+        match speaker.speak(text) {
+            Ok(()) => {
+                self.is_speaking = true;
+                self.speech_paused = false;
+            }
+            Err(e) => self.show_toast(format!("Error: {}", e)),
+        }
+    }
+
+    fn stop_speaking(&mut self) {
+        if let Some(speaker) = self.speaker.as_mut() {
+            speaker.stop();
+        }
+        self.is_speaking = false;
+        self.speech_paused = false;
+    }
+
+    /// Notice when the article has been read to the end
+    pub fn poll_speech(&mut self) {
+        if self.is_speaking {
+            self.is_speaking = self.speaker.as_mut().is_some_and(|s| s.is_speaking());
+            if !self.is_speaking {
+                self.speech_paused = false;
+            }
+        }
+    }
+
+    /// Get the current spinner character
+    pub fn spinner_char(&self) -> char {
+        const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+        const TTY_SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+        if self.tty_mode {
+            TTY_SPINNER[self.spinner_frame % TTY_SPINNER.len()]
+        } else {
+            SPINNER[self.spinner_frame]
+        }
+    }
+
+    /// Poll for completed summary results (non-blocking)
+    pub async fn poll_summary_result(&mut self) -> Result<()> {
+        // Show backoff progress while the request waits to be retried
+        if let (Some(summarizer), Some(_)) = (&self.summarizer, self.pending_summary_article_id) {
+            if matches!(
+                self.summary_status,
+                SummaryStatus::Generating | SummaryStatus::Retrying(_)
+            ) {
+                self.summary_status = match summarizer.retry_at() {
+                    Some(at) => SummaryStatus::Retrying(at),
+                    None => SummaryStatus::Generating,
+                };
+            }
+        }
+
+        while let Ok(result) = self.summary_rx.try_recv() {
+            self.summarizing.retain(|job| *job != (result.article_id, result.variant));
+            self.apply_summary_result(result).await;
+        }
+        self.start_queued_summaries();
+        Ok(())
+    }
+
+    /// Store a finished summary, and show it if its article is still selected
+    async fn apply_summary_result(&mut self, result: SummaryResult) {
+        // Check if the article still exists (might have been deleted)
+        let article_exists = self.articles.iter().any(|a| a.id == result.article_id);
+
+        // A summary for an article no longer selected is still kept for
+        // later (the reader, emails), just not shown
+        if self.pending_summary_article_id != Some(result.article_id) {
+            if let (true, Ok(summary)) = (article_exists, result.result) {
+                if let Err(e) = self
+                    .repository
+                    .save_summary(
+                        result.article_id,
+                        result.variant,
+                        summary.content,
+                        summary.model,
+                        result.source,
+                        summary.usage,
+                        summary.cost_usd,
+                    )
+                    .await
+                {
+                    tracing::warn!("Failed to save summary: {}", e);
+                }
+            }
+            return;
+        }
+
+        match result.result {
+            Ok(summary) => {
+                if article_exists {
+                    // Save to database only if article still exists
+                    if let Err(e) = self
+                        .repository
+                        .save_summary(
+                            result.article_id,
+                            result.variant,
+                            summary.content.clone(),
+                            summary.model.clone(),
+                            result.source,
+                            summary.usage,
+                            summary.cost_usd,
+                        )
+                        .await
+                    {
+                        tracing::warn!("Failed to save summary (article may have been deleted): {}", e);
+                    }
+
+                    self.current_summary = Some(Summary {
+                        id: 0,
+                        article_id: result.article_id,
+                        content: summary.content,
+                        model_version: summary.model,
+                        generated_at: chrono::Utc::now(),
+                        source: Some(result.source),
+                        variant: result.variant,
+                    });
+                    self.summary_status = SummaryStatus::Generated;
+                } else {
+                    tracing::debug!("Discarding summary for deleted article {}", result.article_id);
+                    self.summary_status = SummaryStatus::NotGenerated;
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to generate summary: {}", e);
+                self.summary_status = SummaryStatus::Failed;
+            }
+        }
+        self.pending_summary_article_id = None;
+    }
+
+    /// Fetch my Hypothes.is annotations for the selected article (non-blocking)
+    fn load_annotations(&mut self) {
+        let Some(hypothesis) = &self.hypothesis else {
+            return;
+        };
+        let Some(article) = self.selected_article() else {
+            return;
+        };
+        if self.annotations_article_id == Some(article.id) {
+            return;
+        }
+
+        let article_id = article.id;
+        let url = article.url.clone();
+        let hypothesis = Arc::clone(hypothesis);
+        let tx = self.annotation_tx.clone();
+        self.annotations_article_id = Some(article_id);
+        self.is_loading_annotations = true;
+
+        tokio::spawn(async move {
+            let result = hypothesis
+                .annotations_for(&url)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx
+                .send(AnnotationResult {
+                    article_id,
+                    highlighted: false,
+                    result,
+                })
+                .await;
+        });
+    }
+
+    /// Summary lines that can be sent to Hypothes.is as highlights
+    pub fn highlight_candidates(&self) -> Vec<String> {
+        if self.summary_status != SummaryStatus::Generated {
+            return Vec::new();
+        }
+        let Some(summary) = &self.current_summary else {
+            return Vec::new();
+        };
+        summary
+            .content
+            .lines()
+            .map(|line| {
+                line.trim()
+                    .trim_start_matches(['-', '*', '•'])
+                    .trim()
+                    .to_string()
+            })
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+
+    /// Highlight the picked summary line on the article's page (non-blocking)
+    fn create_highlight(&mut self) {
+        let Some(hypothesis) = &self.hypothesis else {
+            return;
+        };
+        let Some(quote) = self
+            .highlight_picker
+            .and_then(|cursor| self.highlight_candidates().get(cursor).cloned())
+        else {
+            return;
+        };
+        let Some(article) = self.selected_article() else {
+            return;
+        };
+
+        let article_id = article.id;
+        let url = article.url.clone();
+        let title = article.title.clone();
+        let tags = self.config.default_tags.clone();
+        let hypothesis = Arc::clone(hypothesis);
+        let tx = self.annotation_tx.clone();
+        self.is_loading_annotations = true;
+
+        tokio::spawn(async move {
+            // Re-fetch so the pane shows the new highlight alongside the rest
+            let result = async {
+                hypothesis.create_highlight(&url, &title, &quote, tags).await?;
+                hypothesis.annotations_for(&url).await
+            }
+            .await
+            .map_err(|e| e.to_string());
+            let _ = tx
+                .send(AnnotationResult {
+                    article_id,
+                    highlighted: true,
+                    result,
+                })
+                .await;
+        });
+    }
+
+    /// Poll for fetched or created annotations (non-blocking)
+    pub fn poll_annotation_result(&mut self) {
+        if let Ok(result) = self.annotation_rx.try_recv() {
+            self.is_loading_annotations = false;
+            match result.result {
+                Ok(annotations) => {
+                    if result.highlighted {
+                        self.show_toast("Highlight saved to Hypothes.is".to_string());
+                    }
+                    // Ignore annotations for an article that is no longer selected
+                    if self.annotations_article_id == Some(result.article_id) {
+                        self.annotations = annotations;
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Hypothes.is request failed: {}", e);
+                    self.show_toast(format!("Hypothes.is: {}", e));
+                }
+            }
+        }
+    }
+
+    /// Lead image of the selected article, once loaded
+    pub fn preview_image(&self) -> Option<&Arc<DynamicImage>> {
+        self.image_protocol?;
+        let article = self.selected_article()?;
+        self.images.get(&article.id)?.as_ref()
+    }
+
+    /// Load the selected article's lead image once the selection has
+    /// settled (non-blocking)
+    pub fn load_image_preview(&mut self) {
+        if self.image_protocol.is_none()
+            || self.loading_image.is_some()
+            || self.selected_at.elapsed() < IMAGE_DELAY
+        {
+            return;
+        }
+        let Some(article) = self.selected_article() else {
+            return;
+        };
+        if self.images.contains_key(&article.id) {
+            return;
+        }
+
+        let article_id = article.id;
+        let url = article.url.clone();
+        let image_url = article.image_url.clone();
+        let fetcher = self.image_fetcher.clone();
+        let tx = self.image_tx.clone();
+        self.loading_image = Some(article_id);
+
+        tokio::spawn(async move {
+            // Feeds without images get the page's og:image instead
+            let (image_url, found_url) = match image_url {
+                Some(image_url) => (Ok(Some(image_url)), None),
+                None => match fetcher.find_lead_image(&url).await {
+                    Ok(found) => (Ok(found.clone()), found),
+                    Err(e) => (Err(e.to_string()), None),
+                },
+            };
+            let result = match image_url {
+                Ok(Some(image_url)) => fetcher
+                    .fetch_image(&image_url)
+                    .await
+                    .map(Some)
+                    .map_err(|e| e.to_string()),
+                Ok(None) => Ok(None),
+                Err(e) => Err(e),
+            };
+            let _ = tx
+                .send(ImageResult {
+                    article_id,
+                    found_url,
+                    result,
+                })
+                .await;
+        });
+    }
+
+    /// Poll for loaded lead images (non-blocking)
+    pub async fn poll_image_result(&mut self) -> Result<()> {
+        let Ok(result) = self.image_rx.try_recv() else {
+            return Ok(());
+        };
+        self.loading_image = None;
+
+        if let Some(image_url) = result.found_url {
+            self.repository
+                .save_image_url(result.article_id, image_url.clone())
+                .await?;
+            if let Some(article) = self.articles.iter_mut().find(|a| a.id == result.article_id) {
+                article.image_url = Some(image_url);
+            }
+        }
+
+        // Failures are cached too, so a broken image isn't fetched over and over
+        let image = result.result.unwrap_or_else(|e| {
+            tracing::debug!("No preview for article {}: {}", result.article_id, e);
+            None
+        });
+        if self.image_order.len() >= IMAGE_CACHE_SIZE {
+            if let Some(oldest) = self.image_order.pop_front() {
+                self.images.remove(&oldest);
+            }
+        }
+        self.images.insert(result.article_id, image.map(Arc::new));
+        self.image_order.push_back(result.article_id);
+        Ok(())
+    }
+
+    /// Related articles found for the selected article, most related first
+    pub fn related_articles(&self) -> Vec<&Article> {
+        let Some(article) = self.selected_article() else {
+            return Vec::new();
+        };
+        let Some(related) = self.related.as_ref().filter(|r| r.article_id == article.id) else {
+            return Vec::new();
+        };
+        related
+            .article_ids
+            .iter()
+            .filter_map(|id| self.articles.iter().find(|a| a.id == *id))
+            .filter(|a| !a.is_trashed())
+            .collect()
+    }
+
+    /// Show an article's details with the stored articles it links to and
+    /// those linking to it
+    async fn open_article_info(&mut self, article_id: i64) -> Result<()> {
+        let Some(article) = self.repository.get_article(article_id).await? else {
+            return Ok(());
+        };
+        let (reference_ids, referenced_by_ids) =
+            self.repository.find_article_links(article_id).await?;
+        let mut info = ArticleInfo {
+            article,
+            references: Vec::new(),
+            referenced_by: Vec::new(),
+            cursor: 0,
+        };
+        for id in reference_ids {
+            info.references.extend(self.repository.get_article(id).await?);
+        }
+        for id in referenced_by_ids {
+            info.referenced_by.extend(self.repository.get_article(id).await?);
+        }
+        self.article_info = Some(info);
+        Ok(())
+    }
+
+    /// Look up articles about the same topic as the selected one once the
+    /// selection has settled: by embedding similarity when the article has a
+    /// vector, otherwise by shared title keywords (non-blocking)
+    pub fn load_related(&mut self) {
+        if self.loading_related.is_some() || self.selected_at.elapsed() < RELATED_DELAY {
+            return;
+        }
+        let Some(article) = self.selected_article() else {
+            return;
+        };
+        if self.related.as_ref().is_some_and(|r| r.article_id == article.id) {
+            return;
+        }
+
+        let article_id = article.id;
+        let title = article.title.clone();
+        let embedder = self.embedder.as_ref().map(Arc::clone);
+        let repository = self.repository.clone();
+        let tx = self.related_tx.clone();
+        self.loading_related = Some(article_id);
+
+        tokio::spawn(async move {
+            let result = async {
+                if let Some(embedder) = embedder {
+                    let embeddings = repository.get_embeddings(embedder.model()).await?;
+                    if let Some((_, vector)) = embeddings.iter().find(|(id, _)| *id == article_id) {
+                        let mut scored: Vec<(i64, f32)> = embeddings
+                            .iter()
+                            .map(|(id, other)| (*id, cosine_similarity(vector, other)))
+                            .filter(|(_, similarity)| *similarity >= RELATED_MIN_SIMILARITY)
+                            .collect();
+                        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+                        scored.truncate(RELATED_CANDIDATES);
+                        return Ok(scored.into_iter().map(|(id, _)| id).collect());
+                    }
+                }
+                repository.find_related(&title, RELATED_CANDIDATES).await
+            }
+            .await
+            .map_err(|e: crate::error::AppError| e.to_string());
+
+            let _ = tx.send(RelatedResult { article_id, result }).await;
+        });
+    }
+
+    /// Poll for found related articles (non-blocking)
+    pub fn poll_related_result(&mut self) {
+        let Ok(result) = self.related_rx.try_recv() else {
+            return;
+        };
+        self.loading_related = None;
+
+        // Failures are kept as "none found" so the lookup doesn't repeat
+        let candidates = result.result.unwrap_or_else(|e| {
+            tracing::warn!("Failed to find related articles: {}", e);
+            Vec::new()
+        });
+
+        // Copies of a story are listed through the canonical article; skip
+        // the article's own story and list each other story once
+        let positions: HashMap<i64, usize> =
+            self.articles.iter().enumerate().map(|(i, a)| (a.id, i)).collect();
+        let story = |id: i64| {
+            let article = &self.articles[*positions.get(&id)?];
+            Some(article.canonical_id.filter(|c| positions.contains_key(c)).unwrap_or(id))
+        };
+        let own_story = story(result.article_id);
+        let mut article_ids: Vec<i64> = Vec::new();
+        for id in candidates {
+            let Some(story) = story(id) else {
+                continue;
+            };
+            if Some(story) != own_story
+                && !self.articles[positions[&story]].is_trashed()
+                && !article_ids.contains(&story)
+            {
+                article_ids.push(story);
+            }
+        }
+        article_ids.truncate(RELATED_MAX_RESULTS);
+
+        self.related = Some(RelatedArticles {
+            article_id: result.article_id,
+            article_ids,
+        });
+    }
+
+    /// Start discovering a feed from a URL (non-blocking)
+    fn start_feed_discovery(&mut self) {
+        // Second step: the input holds credentials for a feed that returned 401
+        if let Some(url) = self.feed_auth_url.clone() {
+            match FeedCredentials::parse(self.feed_input.trim()) {
+                Some(credentials) => {
+                    self.feed_input.clear();
+                    self.spawn_feed_discovery(url, Some(credentials), false);
+                }
+                None => {
+                    self.feed_input_status =
+                        Some("Error: expected user:password or Header-Name: value".to_string());
+                }
+            }
+            return;
+        }
+
+        let url = self.feed_input.trim().to_string();
+        if url.is_empty() {
+            self.feed_input_active = false;
+            return;
+        }
+
+        // Normalize URL - add https:// if no protocol specified
+        let url = if !url.starts_with("http://") && !url.starts_with("https://") {
+            format!("https://{}", url)
+        } else {
+            url
+        };
+
+        self.spawn_feed_discovery(url, None, false);
+    }
+
+    fn spawn_feed_discovery(
+        &mut self,
+        url: String,
+        credentials: Option<FeedCredentials>,
+        confirm: bool,
+    ) {
+        self.feed_input_status = Some("Discovering feed...".to_string());
+
+        let fetcher = self.fetcher.clone();
+        let tx = self.discovery_tx.clone();
+
+        tokio::spawn(async move {
+            let (result, unauthorized) = match fetcher.discover_feed(&url, credentials.as_ref()).await {
+                Ok(feed) => (Ok(feed), false),
+                Err(e) => {
+                    let unauthorized = matches!(e, AppError::Unauthorized(_));
+                    (Err(e.to_string()), unauthorized)
+                }
+            };
+            let _ = tx
+                .send(FeedDiscoveryResult {
+                    url,
+                    credentials,
+                    result,
+                    unauthorized,
+                    confirm,
+                })
+                .await;
+        });
+    }
+
+    /// Add a discovered feed (storing its credentials first) and refresh it
+    async fn subscribe(
+        &mut self,
+        new_feed: NewFeed,
+        credentials: Option<FeedCredentials>,
+    ) -> Result<()> {
+        // Secrets go to the keyring before the feed exists, so a
+        // keyring failure doesn't leave a feed that can't be fetched
+        if let Some(credentials) = &credentials {
+            if let Err(e) = store_feed_secret(&new_feed.url, &credentials.secret).await {
+                self.feed_input_status = Some(format!("Error: {}", e));
+                return Ok(());
+            }
+        }
+
+        let feed_title = new_feed.title.clone();
+        match self.repository.insert_feed(new_feed).await {
+            Ok(feed_id) => {
+                if let Some(credentials) = credentials {
+                    self.repository.set_feed_auth(feed_id, credentials.auth).await?;
+                }
+                self.feed_input_status = Some(format!("Added: {}", feed_title));
+                self.feed_auth_url = None;
+                tracing::info!("Added new feed: {} (id={})", feed_title, feed_id);
+                if let Some(limit) = self.feed_import.limit() {
+                    self.pending_imports.insert(feed_id, limit);
+                }
+                self.feed_import = self.config.initial_import;
+
+                // Reload feeds list
+                self.feeds = self.repository.get_all_feeds().await?;
+                self.invalidate_filter();
+
+                // Clear input after short delay to show success message
+                self.feed_input_active = false;
+                self.feed_input.clear();
+
+                // Refresh the new feed
+                self.refresh_feeds();
+            }
+            Err(e) => {
+                self.feed_input_status = Some(format!("Error: {}", e));
+                tracing::error!("Failed to insert feed: {}", e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Poll for completed feed discovery results (non-blocking)
+    pub async fn poll_discovery_result(&mut self) -> Result<()> {
+        if let Ok(result) = self.discovery_rx.try_recv() {
+            match result.result {
+                // Let the user pick when a site has several (comments, categories...)
+                Ok(feeds) if feeds.len() > 1 => {
+                    let is_new = |feed: &NewFeed| !self.feeds.iter().any(|f| f.url == feed.url);
+                    if !feeds.iter().any(is_new) {
+                        self.feed_input_status =
+                            Some(format!("Already subscribed to all {} feeds here", feeds.len()));
+                        return Ok(());
+                    }
+                    self.feed_input_status = Some(format!(
+                        "Found {} feeds - Enter to subscribe, Esc to cancel",
+                        feeds.len()
+                    ));
+                    self.feed_picker = Some(FeedPicker {
+                        cursor: feeds.iter().position(is_new).unwrap_or(0),
+                        feeds,
+                        credentials: result.credentials,
+                    });
+                }
+                Ok(mut feeds) => {
+                    let Some(new_feed) = feeds.pop() else {
+                        return Ok(());
+                    };
+                    // Check if feed already exists
+                    if self.feeds.iter().any(|f| f.url == new_feed.url) {
+                        self.feed_input_status = Some(format!("Feed already exists: {}", new_feed.title));
+                        return Ok(());
+                    }
+
+                    if result.confirm {
+                        self.feed_input_status = Some(format!(
+                            "Found: {} - Enter to subscribe, Esc to cancel",
+                            new_feed.title
+                        ));
+                        self.feed_input = new_feed.url.clone();
+                        self.feed_offer = Some(new_feed);
+                        return Ok(());
+                    }
+
+                    self.subscribe(new_feed, result.credentials).await?;
+                }
+                Err(e) if result.unauthorized => {
+                    // Ask for credentials (again, if the ones given were rejected)
+                    self.feed_input_status = Some(if result.credentials.is_some() {
+                        "Error: credentials rejected, try again".to_string()
+                    } else {
+                        "Authentication required".to_string()
+                    });
+                    tracing::debug!("{}", e);
+                    self.feed_auth_url = Some(result.url);
+                    self.feed_input.clear();
+                }
+                Err(_) => {
+                    self.feed_input_status = Some("No feed here.".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Search the public feed directory for the typed query (non-blocking)
+    fn start_directory_search(&mut self) {
+        let Some(search) = self.directory_search.as_mut() else {
+            return;
+        };
+        let query = search.query.trim().to_string();
+        if query.is_empty() || search.is_searching {
+            return;
+        }
+
+        search.is_searching = true;
+        search.status = Some("Searching...".to_string());
+
+        let directory = self.feed_directory.clone();
+        let tx = self.directory_tx.clone();
+
+        tokio::spawn(async move {
+            let result = directory.search(&query).await.map_err(|e| e.to_string());
+            let _ = tx.send(DirectoryResult { query, result }).await;
+        });
+    }
+
+    /// Poll for completed feed directory searches (non-blocking)
+    pub fn poll_directory_result(&mut self) {
+        let Ok(result) = self.directory_rx.try_recv() else {
+            return;
+        };
+        // The popup may have been closed while the search ran
+        let Some(search) = self.directory_search.as_mut() else {
+            return;
+        };
+        search.is_searching = false;
+        match result.result {
+            Ok(feeds) if feeds.is_empty() => {
+                search.status = Some(format!("No feeds found for \"{}\"", result.query));
+                search.results.clear();
+                search.cursor = None;
+            }
+            Ok(feeds) => {
+                search.status = Some(format!(
+                    "{} feeds for \"{}\" - ↑/↓ to pick, Enter to subscribe",
+                    feeds.len(),
+                    result.query
+                ));
+                search.results = feeds;
+                search.cursor = Some(0);
+            }
+            Err(e) => {
+                search.status = Some(format!("Error: {}", e));
+            }
+        }
+    }
+
+    /// Look through reading history for feeds to suggest (non-blocking)
+    fn start_feed_suggestions(&mut self) {
+        if self.feed_suggestions.as_ref().is_some_and(|d| d.is_loading) {
+            return;
+        }
+        self.feed_suggestions = Some(FeedSuggestions {
+            status: Some("Looking through what you read...".to_string()),
+            is_loading: true,
+            ..Default::default()
+        });
+
+        let repository = self.repository.clone();
+        let fetcher = self.fetcher.clone();
+        let tx = self.suggestion_tx.clone();
+
+        tokio::spawn(async move {
+            let result = suggest_feeds(&repository, &fetcher)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(SuggestionResult { result }).await;
+        });
+    }
+
+    /// Poll for feeds suggested from reading history (non-blocking)
+    pub fn poll_suggestion_result(&mut self) {
+        let Ok(result) = self.suggestion_rx.try_recv() else {
+            return;
+        };
+        // The screen may have been closed while looking
+        let Some(discover) = self.feed_suggestions.as_mut() else {
+            return;
+        };
+        discover.is_loading = false;
+        match result.result {
+            Ok(suggestions) if suggestions.is_empty() => {
+                discover.status =
+                    Some("Nothing to suggest yet - read and star some more".to_string());
+            }
+            Ok(suggestions) => {
+                discover.status = Some(format!(
+                    "{} suggestions - ↑/↓ to pick, Enter to subscribe, o to open the site",
+                    suggestions.len()
+                ));
+                discover.suggestions = suggestions;
+                discover.cursor = 0;
+            }
+            Err(e) => {
+                discover.status = Some(format!("Error: {}", e));
+            }
+        }
+    }
+
+    /// Embed the query and rank stored articles by similarity (non-blocking)
+    fn start_search(&mut self) {
+        let query = self.search_input.trim().to_string();
+        if query.is_empty() {
+            self.search_input_active = false;
+            return;
+        }
+
+        self.is_searching = true;
+        self.search_input_status = Some("Searching...".to_string());
+
+        // Quoted phrases (or no embeddings backend) go to the full-text index
+        let phrase = query.len() > 1 && query.starts_with('"') && query.ends_with('"');
+        let embedder = self.embedder.as_ref().filter(|_| !phrase).map(Arc::clone);
+        let repository = self.repository.clone();
+        let tx = self.search_tx.clone();
+
+        tokio::spawn(async move {
+            let result = async {
+                let Some(embedder) = embedder else {
+                    return repository.search_full_text(&query, SEARCH_MAX_RESULTS).await;
+                };
+                let query_vector = embedder
+                    .embed(std::slice::from_ref(&query))
+                    .await?
+                    .pop()
+                    .unwrap_or_default();
+                let mut scored: Vec<(i64, f32)> = repository
+                    .get_embeddings(embedder.model())
+                    .await?
+                    .into_iter()
+                    .map(|(id, vector)| (id, cosine_similarity(&query_vector, &vector)))
+                    .collect();
+                scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+                scored.truncate(SEARCH_MAX_RESULTS);
+                Ok::<_, crate::error::AppError>(scored.into_iter().map(|(id, _)| id).collect())
+            }
+            .await
+            .map_err(|e| e.to_string());
+
+            let _ = tx.send(SearchResult { query, result }).await;
+        });
+    }
+
+    /// Poll for completed search results (non-blocking)
+    pub async fn poll_search_result(&mut self) -> Result<()> {
+        if let Ok(result) = self.search_rx.try_recv() {
+            self.is_searching = false;
+            match result.result {
+                Ok(article_ids) if article_ids.is_empty() => {
+                    self.search_input_status = Some("No matches".to_string());
+                }
+                Ok(article_ids) => {
+                    self.search_input_active = false;
+                    self.search_input.clear();
+                    self.search_input_status = None;
+                    self.catchup = None;
+                    self.search_results = Some(SearchResults {
+                        query: result.query,
+                        article_ids,
+                    });
+                    self.invalidate_filter();
+                    self.selected_index = 0;
+                    self.on_selection_changed().await?;
+                }
+                Err(e) => {
+                    self.search_input_status = Some(format!("Error: {}", e));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `/` searches by meaning (embeddings configured) or by keywords only
+    pub fn has_semantic_search(&self) -> bool {
+        self.embedder.is_some()
+    }
+
+    /// Embed articles that don't have a vector yet (background task)
+    fn start_embedding_backfill(&mut self) {
+        let Some(embedder) = &self.embedder else {
+            return;
+        };
+        if self.is_embedding {
+            return;
+        }
+        self.is_embedding = true;
+
+        let embedder = Arc::clone(embedder);
+        let repository = self.repository.clone();
+        let tx = self.embedding_tx.clone();
+
+        tokio::spawn(async move {
+            let mut embedded = 0;
+            loop {
+                let batch = match repository
+                    .get_articles_needing_embedding(embedder.model(), EMBEDDING_BATCH_SIZE)
+                    .await
+                {
+                    Ok(batch) if !batch.is_empty() => batch,
+                    Ok(_) => break,
+                    Err(e) => {
+                        tracing::warn!("Failed to load articles for embedding: {}", e);
+                        break;
+                    }
+                };
+
+                let texts: Vec<String> = batch.iter().map(|(_, text)| text.clone()).collect();
+                let vectors = match embedder.embed(&texts).await {
+                    Ok(vectors) => vectors,
+                    Err(e) => {
+                        tracing::warn!("Embedding request failed: {}", e);
+                        break;
+                    }
+                };
+
+                // Articles left without a vector come back in the next batch,
+                // so stop rather than ask for them over and over
+                let complete = vectors.len() == batch.len();
+                if !complete {
+                    tracing::warn!(
+                        "Embedding request returned {} vectors for {} texts",
+                        vectors.len(),
+                        batch.len()
+                    );
+                }
+                let mut saved = 0;
+                for ((article_id, _), vector) in batch.iter().zip(vectors) {
+                    if let Err(e) = repository.save_embedding(*article_id, embedder.model(), &vector).await {
+                        tracing::warn!("Failed to save embedding: {}", e);
+                    } else {
+                        saved += 1;
+                    }
+                }
+                embedded += saved;
+                if !complete || saved < batch.len() {
+                    break;
+                }
+            }
+            let _ = tx.send(EmbeddingResult { embedded }).await;
+        });
+    }
+
+    /// Look up favicons of feeds that don't have one stored yet (background task)
+    pub fn start_icon_fetch(&mut self) {
+        if !self.feed_badges || self.is_fetching_icons {
+            return;
+        }
+        self.is_fetching_icons = true;
+
+        let repository = self.repository.clone();
+        let fetcher = self.image_fetcher.clone();
+        let tx = self.icon_tx.clone();
+
+        tokio::spawn(async move {
+            let feeds = repository.feeds_without_icon().await.unwrap_or_else(|e| {
+                tracing::warn!("Failed to load feeds without icons: {}", e);
+                Vec::new()
+            });
+            let found: Vec<(i64, Option<Vec<u8>>)> = stream::iter(feeds)
+                .map(|(feed_id, site_url)| {
+                    let fetcher = fetcher.clone();
+                    async move {
+                        match fetcher.fetch_favicon(&site_url).await {
+                            Ok(icon) => Some((feed_id, icon)),
+                            // Unreachable now; try again next time
+                            Err(e) => {
+                                tracing::debug!("Favicon lookup for {} failed: {}", site_url, e);
+                                None
+                            }
+                        }
+                    }
+                })
+                .buffer_unordered(ICON_FETCH_CONCURRENCY)
+                .filter_map(|result| async move { result })
+                .collect()
+                .await;
+
+            let mut icons = Vec::new();
+            for (feed_id, icon) in found {
+                if let Err(e) = repository.save_feed_icon(feed_id, icon.clone()).await {
+                    tracing::warn!("Failed to save feed icon: {}", e);
+                }
+                icons.extend(icon.map(|png| (feed_id, png)));
+            }
+            let _ = tx.send(FeedIconResult { icons }).await;
+        });
+    }
+
+    /// Look up comment counts of recent articles from Hacker News and Reddit
+    /// feeds (background task)
+    pub fn start_comment_counts(&mut self) {
+        if self.is_counting_comments {
+            return;
+        }
+        let since = chrono::Utc::now() - chrono::Duration::days(COMMENT_COUNT_MAX_AGE_DAYS);
+        let candidates: Vec<(i64, Discussion)> = self
+            .articles
+            .iter()
+            .filter(|a| a.published_at.unwrap_or(a.fetched_at) > since)
+            .filter_map(|a| {
+                let content = a.content.as_deref().or(a.content_text.as_deref());
+                origin_discussion(&a.title, &a.url, content).map(|d| (a.id, d))
+            })
+            .collect();
+        if candidates.is_empty() {
+            return;
+        }
+        self.is_counting_comments = true;
+
+        let repository = self.repository.clone();
+        let finder = self.discussion_finder.clone();
+        let tx = self.comment_count_tx.clone();
+
+        tokio::spawn(async move {
+            let checked = repository
+                .recently_checked_discussions(COMMENT_COUNT_RECHECK_MINUTES)
+                .await
+                .unwrap_or_default();
+            let found: Vec<(i64, Discussion)> = stream::iter(
+                candidates
+                    .into_iter()
+                    .filter(|(id, _)| !checked.contains(id))
+                    .take(COMMENT_COUNT_BATCH),
+            )
+            .map(|(article_id, origin)| {
+                let finder = finder.clone();
+                async move {
+                    // hnrss.org items carry their count already
+                    if origin.comments.is_some() {
+                        return Some((article_id, origin));
+                    }
+                    match finder.refresh_counts(&origin).await {
+                        Ok(discussion) => Some((article_id, discussion)),
+                        Err(e) => {
+                            tracing::debug!("Comment count for {} failed: {}", origin.url, e);
+                            None
+                        }
+                    }
+                }
+            })
+            .buffer_unordered(COMMENT_COUNT_CONCURRENCY)
+            .filter_map(|result| async move { result })
+            .collect()
+            .await;
+
+            let mut counts = Vec::new();
+            for (article_id, discussion) in found {
+                counts.extend(discussion.comments.map(|comments| (article_id, comments)));
+                if let Err(e) = repository.save_discussions(article_id, vec![discussion]).await {
+                    tracing::warn!("Failed to save discussion: {}", e);
+                }
+            }
+            let _ = tx.send(CommentCountResult { counts }).await;
+        });
+    }
+
+    /// Poll for looked-up comment counts (non-blocking)
+    pub fn poll_comment_count_result(&mut self) {
+        if let Ok(result) = self.comment_count_rx.try_recv() {
+            self.is_counting_comments = false;
+            for (article_id, comments) in result.counts {
+                let count = self.comment_counts.entry(article_id).or_default();
+                *count = (*count).max(comments);
+            }
+        }
+    }
+
+    /// Offer the summary styles for the selected article, starting on the one shown
+    async fn open_summary_styles(&mut self) -> Result<()> {
+        let Some(article) = self.selected_article() else {
+            return Ok(());
+        };
+        let article_id = article.id;
+        let saved = self
+            .repository
+            .get_summary_variants(article_id)
+            .await?
+            .into_iter()
+            .filter_map(|s| s.variant)
+            .collect();
+        let cursor = self
+            .current_summary
+            .as_ref()
+            .and_then(|s| s.variant)
+            .and_then(|v| SummaryVariant::ALL.iter().position(|&a| a == v))
+            .map_or(0, |i| i + 1);
+        self.summary_styles = Some(SummaryStylePicker {
+            article_id,
+            cursor,
+            saved,
+        });
+        Ok(())
+    }
+
+    /// List the discussions of the selected article: those stored at once,
+    /// then everything found on Hacker News and Reddit
+    async fn open_discussions(&mut self) -> Result<()> {
+        let Some(article) = self.selected_article() else {
+            return Ok(());
+        };
+        let article_id = article.id;
+        let article_url = article.url.clone();
+        let content = article.content.as_deref().or(article.content_text.as_deref());
+        let origin = origin_discussion(&article.title, &article.url, content);
+
+        self.discussion_menu = Some(DiscussionMenu {
+            article_id,
+            discussions: self.repository.get_discussions(article_id).await?,
+            cursor: 0,
+            loading: true,
+        });
+
+        let finder = self.discussion_finder.clone();
+        let tx = self.discussion_tx.clone();
+        tokio::spawn(async move {
+            let result = finder.find(&article_url, origin).await.map_err(|e| e.to_string());
+            let _ = tx.send(DiscussionResult { article_id, result }).await;
+        });
+        Ok(())
+    }
+
+    /// Poll for discussions found for an article (non-blocking)
+    pub async fn poll_discussion_result(&mut self) -> Result<()> {
+        let Ok(result) = self.discussion_rx.try_recv() else {
+            return Ok(());
+        };
+        let menu = self
+            .discussion_menu
+            .as_mut()
+            .filter(|menu| menu.article_id == result.article_id);
+        match result.result {
+            Ok(discussions) => {
+                if let Some(comments) = discussions.iter().filter_map(|d| d.comments).max() {
+                    self.comment_counts.insert(result.article_id, comments);
+                }
+                if let Some(menu) = menu {
+                    menu.loading = false;
+                    for found in &discussions {
+                        match menu.discussions.iter_mut().find(|d| d.url == found.url) {
+                            Some(known) => *known = found.clone(),
+                            None => menu.discussions.push(found.clone()),
+                        }
+                    }
+                    menu.discussions.sort_by_key(|d| std::cmp::Reverse(d.comments));
+                    menu.cursor = 0;
+                }
+                self.repository.save_discussions(result.article_id, discussions).await?;
+            }
+            Err(e) => {
+                tracing::warn!("Discussion lookup failed: {}", e);
+                if let Some(menu) = menu {
+                    menu.loading = false;
+                }
+                self.show_toast(format!("Discussion lookup failed: {}", e));
+            }
+        }
+        Ok(())
+    }
+
+    /// Poll for looked-up favicons (non-blocking)
+    pub fn poll_icon_result(&mut self) {
+        if let Ok(result) = self.icon_rx.try_recv() {
+            self.is_fetching_icons = false;
+            for (feed_id, png) in result.icons {
+                if let Some(icon) = Self::decode_feed_icon(&png) {
+                    self.feed_icons.insert(feed_id, icon);
+                }
+            }
+        }
+    }
+
+    fn decode_feed_icon(png: &[u8]) -> Option<FeedIcon> {
+        let image = image::load_from_memory(png)
+            .map_err(|e| tracing::debug!("Unreadable feed icon: {}", e))
+            .ok()?;
+        let color = icon_color(&image);
+        Some(FeedIcon { image, color })
+    }
+
+    /// Poll for completed embedding backfill (non-blocking)
+    pub fn poll_embedding_result(&mut self) {
+        if let Ok(result) = self.embedding_rx.try_recv() {
+            self.is_embedding = false;
+            if result.embedded > 0 {
+                tracing::info!("Embedded {} articles", result.embedded);
+            }
+        }
+    }
+
+    /// Refresh on the auto-refresh timer, except during quiet hours
+    pub fn auto_refresh(&mut self) {
+        if self.config.is_quiet_now() {
+            tracing::debug!("Quiet hours, skipping the background refresh");
+            return;
+        }
+        self.refresh_feeds();
+    }
+
+    /// Refresh every feed but those waiting out a run of failed fetches
+    pub fn refresh_feeds(&mut self) {
+        let now = chrono::Utc::now();
+        let (waiting, feeds): (Vec<Feed>, Vec<Feed>) =
+            self.feeds.iter().cloned().partition(|f| f.backoff.is_waiting(now));
+        if !waiting.is_empty() {
+            tracing::info!("Skipping {} failing feeds until their backoff ends", waiting.len());
+        }
+        self.start_refresh(feeds);
+    }
+
+    /// Refresh only the feed being listed, or else the selected article's feed
+    pub fn refresh_current_feed(&mut self) {
+        if self.is_refreshing {
+            self.show_toast("A refresh is already running".to_string());
+            return;
+        }
+        let feed_id = self
+            .feed_filter
+            .or_else(|| self.selected_article().map(|a| a.feed_id));
+        match feed_id.and_then(|id| self.feeds.iter().find(|f| f.id == id)) {
+            Some(feed) => {
+                let feed = feed.clone();
+                self.start_refresh(vec![feed]);
+            }
+            None => self.show_toast("No feed to refresh".to_string()),
+        }
+    }
+
+    fn start_refresh(&mut self, feeds: Vec<Feed>) {
+        if self.is_refreshing {
+            return; // Already refreshing
+        }
+        self.is_refreshing = true;
+        self.refresh_progress = RefreshProgress {
+            total: feeds.len(),
+            ..Default::default()
+        };
+
+        let fetcher = self.fetcher.clone();
+        let tx = self.refresh_tx.clone();
+        let progress = self.refresh_progress_tx.clone();
+        let backfill = self.archive_backfill();
+
+        tokio::spawn(async move {
+            let results = fetcher.refresh_all(feeds, backfill, progress).await;
+            let _ = tx.send(RefreshResult { results }).await;
+        });
+    }
+
+    fn archive_backfill(&self) -> Option<ArchiveBackfill> {
+        (self.config.archive_backfill_pages > 0).then(|| ArchiveBackfill {
+            max_pages: self.config.archive_backfill_pages,
+            since: chrono::Utc::now() - chrono::Duration::days(ARTICLE_MAX_AGE_DAYS),
+        })
+    }
+
+    /// Fetch the feeds a refresh would and report what is new in each,
+    /// storing nothing (`--refresh --dry-run`)
+    pub async fn preview_refresh(&self) -> Result<Vec<RefreshReport>> {
+        let now = chrono::Utc::now();
+        let feeds = self.feeds.iter().filter(|f| !f.backoff.is_waiting(now)).cloned().collect();
+        let (progress, _) = mpsc::unbounded_channel();
+        let mut pages: HashMap<i64, _> = self
+            .fetcher
+            .refresh_all(feeds, self.archive_backfill(), progress)
+            .await
+            .into_iter()
+            .map(|refresh| (refresh.feed_id, refresh.page))
+            .collect();
+
+        let mut reports = Vec::new();
+        for feed in &self.feeds {
+            let (new_articles, error) = match pages.remove(&feed.id) {
+                Some(Ok(page)) => {
+                    (self.repository.preview_new_articles(page.articles).await?, None)
+                }
+                Some(Err(error)) => (Vec::new(), Some(error)),
+                None => (Vec::new(), feed.backoff.error.clone()),
+            };
+            reports.push(RefreshReport {
+                feed: feed.title.clone(),
+                url: feed.url.clone(),
+                new_articles,
+                error,
+                skipped: feed.backoff.is_waiting(now),
+            });
+        }
+        Ok(reports)
+    }
+
+    /// Highest id of the articles loaded, which articles stored later are above
+    pub fn last_article_id(&self) -> i64 {
+        self.articles.iter().map(|a| a.id).max().unwrap_or(0)
+    }
+
+    /// What a finished refresh found in each feed: the articles stored after
+    /// `last_id` and the fetches that failed. Feeds in `skipped` were left out.
+    pub fn refresh_reports(&self, last_id: i64, skipped: &HashSet<i64>) -> Vec<RefreshReport> {
+        self.feeds
+            .iter()
+            .map(|feed| RefreshReport {
+                feed: feed.title.clone(),
+                url: feed.url.clone(),
+                new_articles: self
+                    .articles
+                    .iter()
+                    .filter(|a| a.feed_id == feed.id && a.id > last_id)
+                    .map(|a| a.title.clone())
+                    .collect(),
+                error: feed.backoff.error.clone(),
+                skipped: skipped.contains(&feed.id),
+            })
+            .collect()
+    }
+
+    /// Poll for feeds starting and finishing during a refresh (non-blocking)
+    pub fn poll_refresh_progress(&mut self) {
+        while let Ok(progress) = self.refresh_progress_rx.try_recv() {
+            match progress {
+                FeedProgress::Started(feed_id) => self.refresh_progress.fetching.push(feed_id),
+                FeedProgress::Finished(feed_id) => {
+                    self.refresh_progress.fetching.retain(|id| *id != feed_id);
+                    self.refresh_progress.done += 1;
+                }
+            }
+        }
+    }
+
+    /// Poll for completed refresh results (non-blocking)
+    pub async fn poll_refresh_result(&mut self) -> Result<()> {
+        self.poll_refresh_progress();
+        if let Ok(result) = self.refresh_rx.try_recv() {
+            // Process the refresh results
+            let mut new_articles = 0;
+            let mut feeds_with_new = 0;
+            let mut webhook_articles = Vec::new();
+            let mut summary_jobs = Vec::new();
+            let usage = result.results.iter().map(|r| (r.feed_id, r.usage)).collect();
+            if let Err(e) = self
+                .repository
+                .record_refresh_usage(usage, REFRESH_STATS_DAYS)
+                .await
+            {
+                tracing::warn!("Failed to record refresh statistics: {}", e);
+            }
+            for refresh in result.results {
+                let feed_id = refresh.feed_id;
+                let mut page = match refresh.page {
+                    Ok(page) => {
+                        self.update_backoff(feed_id, None).await;
+                        page
+                    }
+                    Err(error) => {
+                        self.update_backoff(feed_id, Some(error)).await;
+                        continue;
+                    }
+                };
+                // A new feed's first items aren't news to pass on to webhooks
+                let is_import = match self.pending_imports.remove(&feed_id) {
+                    Some(limit) => {
+                        self.limit_initial_import(feed_id, &mut page, limit).await;
+                        true
+                    }
+                    None => false,
+                };
+                let feed = self.feeds.iter().find(|f| f.id == feed_id);
+                let auto_summary_feed = feed.filter(|f| {
+                    f.rules.auto_summarize
+                        && !is_import
+                        && self.summarizer.is_some()
+                        && !self.config.is_quiet_now()
+                });
+                // What to do with each article once stored, if it is new
+                let follow_ups: Vec<_> = page
+                    .articles
+                    .iter()
+                    .map(|article| {
+                        let hooks = match feed {
+                            Some(feed) if !is_import => self.matching_webhooks(feed, article),
+                            _ => Vec::new(),
+                        };
+                        let hooked = (!hooks.is_empty()).then(|| article.clone());
+                        let summary_job = auto_summary_feed.map(|feed| AutoSummaryJob {
+                            article_id: 0, // set once stored
+                            title: article.title.clone(),
+                            url: article.url.clone(),
+                            content: article
+                                .content_text
+                                .clone()
+                                .or_else(|| article.content.clone())
+                                .unwrap_or_default(),
+                            options: self.config.summary.options_for(&feed.url, &feed.title),
+                        });
+                        (hooks, hooked, summary_job)
+                    })
+                    .collect();
+
+                let ids = match self.repository.upsert_articles_batch(page.articles).await {
+                    Ok(ids) => ids,
+                    Err(e) => {
+                        tracing::warn!("Failed to store articles of feed {}: {}", feed_id, e);
+                        Vec::new()
+                    }
+                };
+                let mut new_in_feed = 0;
+                for (id, (hooks, hooked, summary_job)) in ids.into_iter().zip(follow_ups) {
+                    let Some(id) = id else {
+                        continue;
+                    };
+                    new_in_feed += 1;
+                    if let Some(job) = summary_job {
+                        summary_jobs.push(AutoSummaryJob {
+                            article_id: id,
+                            ..job
+                        });
+                    }
+                    if let (Some(article), Some(feed)) = (hooked, feed) {
+                        webhook_articles.push(PendingWebhook {
+                            article_id: id,
+                            article,
+                            feed_title: feed.title.clone(),
+                            feed_url: feed.url.clone(),
+                            hooks,
+                        });
+                    }
+                }
+                if !page.deleted_guids.is_empty() {
+                    match self
+                        .repository
+                        .delete_entries_by_guid(feed_id, page.deleted_guids)
+                        .await
+                    {
+                        Ok(0) => {}
+                        Ok(n) => tracing::info!("Feed {} deleted {} entries", feed_id, n),
+                        Err(e) => tracing::warn!("Failed to apply deleted entries: {}", e),
+                    }
+                }
+                let muted = self
+                    .feeds
+                    .iter()
+                    .find(|f| f.id == feed_id)
+                    .is_some_and(|f| self.config.notifications.is_muted(&f.url, &f.title));
+                if new_in_feed > 0 && !muted {
+                    new_articles += new_in_feed;
+                    feeds_with_new += 1;
+                }
+                if let Err(e) = self.repository.update_feed_last_fetched(feed_id).await {
+                    tracing::warn!("Failed to update feed last_fetched: {}", e);
+                }
+            }
+
+            // Move articles older than 7 days to the trash after refresh
+            let trashed = self
+                .repository
+                .delete_old_articles(
+                    ARTICLE_MAX_AGE_DAYS,
+                    self.config.trash_retention_days as i64,
+                    self.config.cleanup,
+                )
+                .await?;
+            if trashed > 0 {
+                tracing::info!("Moved {} articles older than 7 days to trash", trashed);
+            }
+
+            self.reload_articles().await?;
+            self.is_refreshing = false;
+            self.start_embedding_backfill();
+            self.start_icon_fetch();
+            self.start_comment_counts();
+            self.start_sync();
+            self.send_webhooks(webhook_articles);
+            self.start_auto_summaries(summary_jobs);
+
+            if new_articles > 0 {
+                self.notify_new_articles(new_articles, feeds_with_new).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Count a feed's failed fetch and back off from it, or clear its
+    /// failures once a fetch succeeds
+    async fn update_backoff(&mut self, feed_id: i64, error: Option<String>) {
+        let Some(feed) = self.feeds.iter_mut().find(|f| f.id == feed_id) else {
+            return;
+        };
+        let backoff = match error {
+            Some(error) => feed.backoff.after_failure(error, chrono::Utc::now()),
+            None if feed.backoff.failures == 0 => return,
+            None => FetchBackoff::default(),
+        };
+        if let Some(retry_at) = backoff.retry_at {
+            tracing::warn!(
+                "{} failed {} time{} in a row, next tried after {}",
+                feed.title,
+                backoff.failures,
+                if backoff.failures == 1 { "" } else { "s" },
+                retry_at.with_timezone(&chrono::Local).format("%H:%M")
+            );
+        }
+        feed.backoff = backoff.clone();
+        if let Err(e) = self.repository.set_feed_backoff(feed_id, backoff).await {
+            tracing::warn!("Failed to store the backoff of feed {}: {}", feed_id, e);
+        }
+    }
+
+    /// Indexes of the configured webhooks a new article should be sent to
+    fn matching_webhooks(&self, feed: &Feed, article: &NewArticle) -> Vec<usize> {
+        let text = article
+            .content_text
+            .as_deref()
+            .or(article.content.as_deref())
+            .unwrap_or_default();
+        self.config
+            .webhooks
+            .iter()
+            .enumerate()
+            .filter(|(_, hook)| hook.matches(&feed.url, &feed.title, &article.title, text))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Post new articles to their webhooks in the background, summarizing
+    /// them first (from the feed's content) for webhooks that want a summary
+    fn send_webhooks(&mut self, pending: Vec<PendingWebhook>) {
+        if pending.is_empty() {
+            return;
+        }
+        self.sending_webhooks += 1;
+
+        let webhooks = self.config.webhooks.clone();
+        let summary_config = self.config.summary.clone();
+        let summarizer = self.summarizer.clone();
+        let repository = self.repository.clone();
+        let sender = self.webhook_sender.clone();
+        let tx = self.webhook_tx.clone();
+
+        tokio::spawn(async move {
+            let mut sent = 0;
+            let mut failed = Vec::new();
+            for pending in pending {
+                let wants_summary = pending.hooks.iter().any(|&i| webhooks[i].include_summary);
+                let summary = match (&summarizer, wants_summary) {
+                    (Some(summarizer), true) => {
+                        let content = pending
+                            .article
+                            .content_text
+                            .clone()
+                            .or(pending.article.content.clone())
+                            .unwrap_or_default();
+                        let options =
+                            summary_config.options_for(&pending.feed_url, &pending.feed_title);
+                        match summarizer
+                            .generate_summary(&pending.article.title, &content, &options)
+                            .await
+                        {
+                            Ok((summary, usage)) => {
+                                let source = if content.len() > MAX_INPUT_CHARS {
+                                    SummarySource::Truncated
+                                } else {
+                                    Self::feed_content_source(&content)
+                                };
+                                let model = summarizer.model_version().to_string();
+                                let cost = summarizer.cost_usd(usage);
+                                let id = pending.article_id;
+                                let content = summary.clone();
+                                if let Err(e) = repository
+                                    .save_summary(id, None, content, model, source, usage, cost)
+                                    .await
+                                {
+                                    tracing::warn!("Failed to save summary: {}", e);
+                                }
+                                Some(summary)
+                            }
+                            Err(e) => {
+                                tracing::warn!("Summary for webhook failed: {}", e);
+                                None
+                            }
+                        }
+                    }
+                    _ => None,
+                };
+
+                let mut article = WebhookArticle {
+                    id: pending.article_id,
+                    feed_title: pending.feed_title,
+                    feed_url: pending.feed_url,
+                    title: pending.article.title,
+                    url: pending.article.url,
+                    author: pending.article.author,
+                    published_at: pending.article.published_at,
+                    summary: None,
+                };
+                for &i in &pending.hooks {
+                    let webhook = &webhooks[i];
+                    article.summary = summary.clone().filter(|_| webhook.include_summary);
+                    match sender.send(webhook, &article).await {
+                        Ok(()) => sent += 1,
+                        Err(e) => {
+                            tracing::warn!("Webhook failed: {}", e);
+                            failed.push(webhook_host(&webhook.url));
+                        }
+                    }
+                }
             }
+            let _ = tx.send(WebhookResult { sent, failed }).await;
+        });
+    }
+
+    /// Summarize new articles of feeds set to auto-summarize in the
+    /// background, a few at a time and within the daily limit
+    fn start_auto_summaries(&mut self, jobs: Vec<AutoSummaryJob>) {
+        let Some(summarizer) = &self.summarizer else {
+            return;
         };
+        if jobs.is_empty() {
+            return;
+        }
+        self.auto_summarizing += 1;
 
-        // Spawn background task for summary generation
         let summarizer = Arc::clone(summarizer);
-        let tx = self.summary_tx.clone();
+        let content_fetcher = self.content_fetcher.clone();
+        let repository = self.repository.clone();
+        let concurrency = self.config.summary.auto_concurrency.max(1);
+        let daily_limit = self.config.summary.auto_daily_limit;
+        let monthly_budget = self.config.summary.monthly_budget_usd;
+        let tx = self.auto_summary_tx.clone();
 
         tokio::spawn(async move {
-            let result = match summarizer.generate_summary(&title, &content).await {
-                Ok(summary_text) => {
-                    let model = summarizer.model_version().to_string();
-                    Ok((summary_text, model))
-                }
-                Err(e) => Err(e.to_string()),
-            };
-
-            let _ = tx.send(SummaryResult { article_id, result }).await;
+            let outcomes: Vec<AutoSummaryOutcome> = stream::iter(jobs)
+                .map(|job| {
+                    let (summarizer, content_fetcher, repository) =
+                        (&summarizer, &content_fetcher, &repository);
+                    async move {
+                        // Opened and summarized while waiting its turn
+                        if matches!(repository.get_summary(job.article_id).await, Ok(Some(_))) {
+                            return AutoSummaryOutcome::Skipped;
+                        }
+                        if let Some(budget) = monthly_budget {
+                            match repository.get_ai_spend().await {
+                                Ok((month, _)) if month.cost_usd >= budget => {
+                                    return AutoSummaryOutcome::OverMonthlyBudget;
+                                }
+                                Ok(_) => {}
+                                Err(e) => {
+                                    tracing::warn!("Failed to check the monthly budget: {}", e);
+                                    return AutoSummaryOutcome::Skipped;
+                                }
+                            }
+                        }
+                        match repository.take_auto_summary_budget(daily_limit).await {
+                            Ok(true) => {}
+                            Ok(false) => return AutoSummaryOutcome::OverBudget,
+                            Err(e) => {
+                                tracing::warn!("Failed to check the summary budget: {}", e);
+                                return AutoSummaryOutcome::Skipped;
+                            }
+                        }
+                        let (content, source) = Self::summary_input(
+                            content_fetcher,
+                            repository,
+                            job.article_id,
+                            &job.url,
+                            job.content,
+                        )
+                        .await;
+                        let summary = summarizer
+                            .generate_summary(&job.title, &content, &job.options)
+                            .await;
+                        let (summary, usage) = match summary {
+                            Ok(summary) => summary,
+                            Err(e) => {
+                                tracing::warn!("Background summary failed: {}", e);
+                                return AutoSummaryOutcome::Skipped;
+                            }
+                        };
+                        let model = summarizer.model_version().to_string();
+                        let cost = summarizer.cost_usd(usage);
+                        let id = job.article_id;
+                        let saved = repository
+                            .save_summary(id, None, summary, model, source, usage, cost)
+                            .await;
+                        match saved {
+                            Ok(()) => AutoSummaryOutcome::Summarized(id),
+                            Err(e) => {
+                                tracing::warn!("Failed to save summary: {}", e);
+                                AutoSummaryOutcome::Skipped
+                            }
+                        }
+                    }
+                })
+                .buffer_unordered(concurrency)
+                .collect()
+                .await;
+
+            let over_budget = outcomes.iter().any(|o| matches!(o, AutoSummaryOutcome::OverBudget));
+            let over_monthly_budget =
+                outcomes.iter().any(|o| matches!(o, AutoSummaryOutcome::OverMonthlyBudget));
+            let article_ids = outcomes
+                .into_iter()
+                .filter_map(|outcome| match outcome {
+                    AutoSummaryOutcome::Summarized(id) => Some(id),
+                    _ => None,
+                })
+                .collect();
+            let _ = tx
+                .send(AutoSummaryResult {
+                    article_ids,
+                    over_budget,
+                    over_monthly_budget,
+                })
+                .await;
         });
-
-        // Don't update local is_read state - keep article visible in filtered list
-        // Database is already updated, so it will show as read next session
-
-        Ok(())
-    }
-
-    /// Advance the spinner animation frame
-    pub fn tick_spinner(&mut self) {
-        self.spinner_frame = (self.spinner_frame + 1) % 10;
-    }
-
-    /// Get the current spinner character
-    pub fn spinner_char(&self) -> char {
-        const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
-        SPINNER[self.spinner_frame]
     }
 
-    /// Poll for completed summary results (non-blocking)
-    pub async fn poll_summary_result(&mut self) -> Result<()> {
-        if let Ok(result) = self.summary_rx.try_recv() {
-            // Only process if this is the summary we're waiting for
-            if self.pending_summary_article_id == Some(result.article_id) {
-                // Check if the article still exists (might have been deleted)
-                let article_exists = self.articles.iter().any(|a| a.id == result.article_id);
-
-                match result.result {
-                    Ok((summary_text, model)) => {
-                        if article_exists {
-                            // Save to database only if article still exists
-                            if let Err(e) = self
-                                .repository
-                                .save_summary(result.article_id, summary_text.clone(), model.clone())
-                                .await
-                            {
-                                tracing::warn!("Failed to save summary (article may have been deleted): {}", e);
-                            }
+    /// Poll for finished background summaries (non-blocking)
+    pub async fn poll_auto_summary_result(&mut self) -> Result<()> {
+        let Ok(result) = self.auto_summary_rx.try_recv() else {
+            return Ok(());
+        };
+        self.auto_summarizing = self.auto_summarizing.saturating_sub(1);
+        tracing::info!("Summarized {} new articles in the background", result.article_ids.len());
+        if result.over_monthly_budget {
+            self.show_toast(format!(
+                "Monthly AI budget of ${:.2} reached, background summaries paused",
+                self.config.summary.monthly_budget_usd.unwrap_or_default()
+            ));
+        } else if result.over_budget {
+            self.show_toast(format!(
+                "Daily limit of {} background summaries reached",
+                self.config.summary.auto_daily_limit
+            ));
+        }
 
-                            self.current_summary = Some(Summary {
-                                id: 0,
-                                article_id: result.article_id,
-                                content: summary_text,
-                                model_version: model,
-                                generated_at: chrono::Utc::now(),
-                            });
-                            self.summary_status = SummaryStatus::Generated;
-                        } else {
-                            tracing::debug!("Discarding summary for deleted article {}", result.article_id);
-                            self.summary_status = SummaryStatus::NotGenerated;
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to generate summary: {}", e);
-                        self.summary_status = SummaryStatus::Failed;
-                    }
+        // Show the summary if its article is on screen without one
+        let selected = self.selected_article().map(|a| a.id);
+        if let Some(id) = selected.filter(|id| result.article_ids.contains(id)) {
+            if self.summary_status == SummaryStatus::NotGenerated {
+                if let Some(summary) = self.repository.get_summary(id).await? {
+                    self.current_summary = Some(summary);
+                    self.summary_status = SummaryStatus::Generated;
                 }
-                self.pending_summary_article_id = None;
             }
         }
         Ok(())
     }
 
-    /// Start discovering a feed from a URL (non-blocking)
-    fn start_feed_discovery(&mut self) {
-        let url = self.feed_input.trim().to_string();
-        if url.is_empty() {
-            self.feed_input_active = false;
+    /// Poll for finished webhook deliveries (non-blocking)
+    pub fn poll_webhook_result(&mut self) {
+        if let Ok(result) = self.webhook_rx.try_recv() {
+            self.sending_webhooks = self.sending_webhooks.saturating_sub(1);
+            tracing::info!("Sent {} webhook messages", result.sent);
+            if !result.failed.is_empty() {
+                let count = result.failed.len();
+                let mut urls = result.failed;
+                urls.sort();
+                urls.dedup();
+                self.show_toast(format!("{} webhook messages failed ({})", count, urls.join(", ")));
+            }
+        }
+    }
+
+    /// Keep only the newest `limit` items of a new feed's first fetch. The
+    /// rest are recorded as deleted so later refreshes don't bring them in.
+    async fn limit_initial_import(&self, feed_id: i64, page: &mut FeedPage, limit: usize) {
+        if page.articles.len() <= limit {
             return;
         }
+        page.articles.sort_by_key(|a| std::cmp::Reverse(a.published_at));
+        let skipped: Vec<String> = page.articles.drain(limit..).map(|a| a.guid).collect();
+        tracing::info!("Skipping {} existing items of new feed {}", skipped.len(), feed_id);
+        if let Err(e) = self.repository.delete_entries_by_guid(feed_id, skipped).await {
+            tracing::warn!("Failed to skip existing items: {}", e);
+        }
+    }
 
-        // Normalize URL - add https:// if no protocol specified
-        let url = if !url.starts_with("http://") && !url.starts_with("https://") {
-            format!("https://{}", url)
-        } else {
-            url
+    /// Sync subscriptions, articles and read/starred state with the
+    /// configured Google Reader API server, if any
+    fn start_sync(&mut self) {
+        let Some(greader) = &self.greader else {
+            return;
         };
+        if self.is_syncing {
+            return;
+        }
+        self.is_syncing = true;
 
-        self.feed_input_status = Some("Discovering feed...".to_string());
-
-        let fetcher = self.fetcher.clone();
-        let tx = self.discovery_tx.clone();
+        let greader = Arc::clone(greader);
+        let repository = self.repository.clone();
+        let tx = self.sync_tx.clone();
+        let since = chrono::Utc::now() - chrono::Duration::days(ARTICLE_MAX_AGE_DAYS);
 
         tokio::spawn(async move {
-            let result = match fetcher.discover_feed(&url).await {
-                Ok(feed) => Ok(feed),
-                Err(e) => Err(e.to_string()),
-            };
-            let _ = tx.send(FeedDiscoveryResult { result }).await;
+            let result = sync_greader(&greader, &repository, since)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(SyncResult { result }).await;
         });
     }
 
-    /// Poll for completed feed discovery results (non-blocking)
-    pub async fn poll_discovery_result(&mut self) -> Result<()> {
-        if let Ok(result) = self.discovery_rx.try_recv() {
+    /// Poll for a completed sync (non-blocking)
+    pub async fn poll_sync_result(&mut self) -> Result<()> {
+        if let Ok(result) = self.sync_rx.try_recv() {
+            self.is_syncing = false;
             match result.result {
-                Ok(new_feed) => {
-                    // Check if feed already exists
-                    if self.feeds.iter().any(|f| f.url == new_feed.url) {
-                        self.feed_input_status = Some(format!("Feed already exists: {}", new_feed.title));
-                        return Ok(());
-                    }
-
-                    let feed_title = new_feed.title.clone();
-                    match self.repository.insert_feed(new_feed).await {
-                        Ok(feed_id) => {
-                            self.feed_input_status = Some(format!("Added: {}", feed_title));
-                            tracing::info!("Added new feed: {} (id={})", feed_title, feed_id);
-
-                            // Reload feeds list
-                            self.feeds = self.repository.get_all_feeds().await?;
-
-                            // Clear input after short delay to show success message
-                            self.feed_input_active = false;
-                            self.feed_input.clear();
-
-                            // Refresh the new feed
-                            self.refresh_feeds();
-                        }
-                        Err(e) => {
-                            self.feed_input_status = Some(format!("Error: {}", e));
-                            tracing::error!("Failed to insert feed: {}", e);
-                        }
+                Ok(report) => {
+                    tracing::info!("Sync finished: {:?}", report);
+                    if report.changed_locally() {
+                        self.reload_keeping_selection().await?;
                     }
                 }
-                Err(_) => {
-                    self.feed_input_status = Some("No feed here.".to_string());
+                Err(e) => {
+                    tracing::warn!("Sync failed: {}", e);
+                    self.show_toast(format!("Sync failed: {}", e));
                 }
             }
         }
         Ok(())
     }
 
-    pub fn refresh_feeds(&mut self) {
-        if self.is_refreshing {
-            return; // Already refreshing
+    /// Reconcile saved flags with the bookmarks on Raindrop.io, picking up
+    /// saves and deletions made on other devices
+    pub fn start_raindrop_sync(&mut self) {
+        let Some(raindrop) = &self.raindrop else {
+            return;
+        };
+        if self.is_syncing_raindrop {
+            return;
         }
-        self.is_refreshing = true;
-
-        let feeds = self.feeds.clone();
-        let fetcher = self.fetcher.clone();
-        let tx = self.refresh_tx.clone();
+        self.is_syncing_raindrop = true;
 
+        let raindrop = raindrop.clone();
+        let repository = self.repository.clone();
+        let tx = self.raindrop_sync_tx.clone();
         tokio::spawn(async move {
-            let results = fetcher.refresh_all(feeds).await;
-            let _ = tx.send(RefreshResult { results }).await;
+            let result = sync_raindrop(&raindrop, &repository)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(RaindropSyncResult { result }).await;
         });
     }
 
-    /// Poll for completed refresh results (non-blocking)
-    pub async fn poll_refresh_result(&mut self) -> Result<()> {
-        if let Ok(result) = self.refresh_rx.try_recv() {
-            // Process the refresh results
-            for (feed_id, articles) in result.results {
-                for article in articles {
-                    if let Err(e) = self.repository.upsert_article(article).await {
-                        tracing::warn!("Failed to upsert article: {}", e);
+    /// Poll for a completed Raindrop reconcile (non-blocking)
+    pub async fn poll_raindrop_sync_result(&mut self) -> Result<()> {
+        let Ok(result) = self.raindrop_sync_rx.try_recv() else {
+            return Ok(());
+        };
+        self.is_syncing_raindrop = false;
+        match result.result {
+            Ok(report) => {
+                tracing::info!("Raindrop sync finished: {:?}", report);
+                if report.changed() {
+                    if let Some(id) = self.selected_article().map(|a| a.id) {
+                        self.is_saved_to_raindrop =
+                            self.repository.is_saved_to_raindrop(id).await?;
                     }
-                }
-                if let Err(e) = self.repository.update_feed_last_fetched(feed_id).await {
-                    tracing::warn!("Failed to update feed last_fetched: {}", e);
+                    self.show_toast(format!(
+                        "Raindrop: {} saved elsewhere, {} no longer bookmarked",
+                        report.marked, report.unmarked
+                    ));
                 }
             }
-
-            // Clean up articles older than 7 days after refresh
-            let deleted = self.repository.delete_old_articles(7).await?;
-            if deleted > 0 {
-                tracing::info!("Deleted {} articles older than 7 days", deleted);
+            Err(e) => {
+                tracing::warn!("Raindrop sync failed: {}", e);
+                self.show_toast(format!("Raindrop sync failed: {}", e));
             }
+        }
+        Ok(())
+    }
 
-            self.reload_articles().await?;
-            self.is_refreshing = false;
+    /// Announce new articles via toast and/or desktop notification
+    async fn notify_new_articles(&mut self, articles: usize, feeds: usize) {
+        let message = format!(
+            "{} new article{} in {} feed{}",
+            articles,
+            if articles == 1 { "" } else { "s" },
+            feeds,
+            if feeds == 1 { "" } else { "s" },
+        );
+
+        if self.config.notifications.toast {
+            self.show_toast(message.clone());
+        }
+
+        if self.config.notifications.desktop && !self.config.is_quiet_now() {
+            // Await so headless refresh doesn't exit before the notification is sent
+            let _ = tokio::task::spawn_blocking(move || notify_desktop("SpeedyReader", &message)).await;
+        }
+    }
+
+    /// Once a day, resurface a starred article that was never opened at the
+    /// top of the list (and in a desktop notification if configured)
+    pub async fn check_revisit(&mut self) -> Result<()> {
+        let today = chrono::Local::now().date_naive();
+        if !self.config.revisit.enabled || self.revisit_day == Some(today) {
+            return Ok(());
+        }
+        self.revisit_day = Some(today);
+
+        let pick = self.repository.pick_revisit(self.config.revisit.min_age_days).await?;
+        let selected_id = self.selected_article().map(|a| a.id);
+        self.revisit_id = pick.map(|(id, _)| id);
+        self.invalidate_filter();
+        let position = selected_id.and_then(|id| self.filtered_articles().position(|a| a.id == id));
+        if let Some(index) = position {
+            self.selected_index = index;
+        }
+
+        let Some((id, is_new)) = pick else {
+            return Ok(());
+        };
+        let Some(title) = self.articles.iter().find(|a| a.id == id).map(|a| a.title.clone())
+        else {
+            return Ok(());
+        };
+        self.show_toast(format!("Revisit: {}", title));
+        if is_new && self.config.revisit.notify && !self.config.is_quiet_now() {
+            tokio::task::spawn_blocking(move || {
+                notify_desktop("SpeedyReader: starred article to revisit", &title)
+            });
         }
         Ok(())
     }
 
+    /// Terminal title showing the unread count and whether a refresh is
+    /// running, with that busy state; None when the title is left alone
+    pub fn window_title(&self) -> Option<(String, bool)> {
+        if !self.config.terminal_title {
+            return None;
+        }
+        let busy = self.is_refreshing || self.is_syncing;
+        let mut title = format!("SpeedyReader ({} unread)", self.unread_counts.total());
+        if busy {
+            title.push_str(" - refreshing");
+        }
+        Some((title, busy))
+    }
+
+    /// Show a transient message in the status bar
+    pub fn show_toast(&mut self, message: String) {
+        self.toast = Some((message, Instant::now()));
+    }
+
+    /// Current toast message, if it hasn't expired
+    pub fn active_toast(&self) -> Option<&str> {
+        self.toast
+            .as_ref()
+            .filter(|(_, shown_at)| shown_at.elapsed() < TOAST_DURATION)
+            .map(|(message, _)| message.as_str())
+    }
+
     /// Refresh feeds and wait for completion (blocking, for CLI/headless use)
     pub async fn refresh_feeds_blocking(&mut self) -> Result<()> {
         self.refresh_feeds();
@@ -643,23 +6459,156 @@ impl App {
         // Wait for the refresh to complete
         while self.is_refreshing {
             self.poll_refresh_result().await?;
-            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            tokio::time::sleep(Duration::from_millis(100)).await;
         }
 
+        while self.is_syncing {
+            self.poll_sync_result().await?;
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        while self.sending_webhooks > 0 || self.auto_summarizing > 0 {
+            self.poll_webhook_result();
+            self.poll_auto_summary_result().await?;
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        // Let the embedding backfill finish before the process exits
+        while self.is_embedding {
+            self.poll_embedding_result();
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        Ok(())
+    }
+
+    /// Pick up read/trash changes made by other instances sharing the database
+    pub async fn poll_external_changes(&mut self) -> Result<()> {
+        if self.last_sync_check.elapsed() < SYNC_CHECK_INTERVAL || self.is_refreshing {
+            return Ok(());
+        }
+        self.last_sync_check = Instant::now();
+
+        let version = self.repository.data_version().await?;
+        if version == self.data_version {
+            return Ok(());
+        }
+        self.data_version = version;
+        tracing::debug!("Database changed by another connection, reloading");
+        self.reload_keeping_selection().await
+    }
+
+    /// Reload feeds and articles changed behind the app's back
+    async fn reload_keeping_selection(&mut self) -> Result<()> {
+        // Keep the cursor on the same article if it is still visible
+        let selected_id = self.selected_article().map(|a| a.id);
+        self.feeds = self.repository.get_all_feeds().await?;
+        self.reload_articles().await?;
+
+        let len = self.filtered_len();
+        let position = selected_id.and_then(|id| self.filtered_articles().position(|a| a.id == id));
+        if let Some(index) = position {
+            self.selected_index = index;
+        } else if len > 0 && self.selected_index >= len {
+            self.selected_index = len - 1;
+        }
+        if self.selected_article().map(|a| a.id) != selected_id {
+            self.on_selection_changed().await?;
+        }
         Ok(())
     }
 
     async fn reload_articles(&mut self) -> Result<()> {
         self.articles = self.repository.get_all_articles_sorted().await?;
+        self.invalidate_filter();
+        self.unread_counts = UnreadCounts::new(self.repository.get_unread_counts().await?);
+        self.known_tags = self.repository.get_all_tags().await?;
+        self.read_in_db.clear();
         Ok(())
     }
 
-    async fn save_to_raindrop(&mut self) -> Result<()> {
+    /// Offer `default_tags` for the Raindrop bookmark of the selected article
+    /// right away, and ask Claude for tags fitting it to go before them
+    fn suggest_raindrop_tags(&mut self) {
+        let Some(article) = self.selected_article() else {
+            return;
+        };
+        let article_id = article.id;
+        let loading = match &self.summarizer {
+            Some(summarizer) if self.config.raindrop_suggest_tags => {
+                let summarizer = Arc::clone(summarizer);
+                let tx = self.raindrop_tags_tx.clone();
+                let title = article.title.clone();
+                let content = self
+                    .full_text
+                    .clone()
+                    .or_else(|| article.content_text.clone())
+                    .or_else(|| article.content.clone())
+                    .unwrap_or_default();
+                let known = self.known_tags.clone();
+                tokio::spawn(async move {
+                    let result = summarizer
+                        .suggest_tags(&title, &content, &known)
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(RaindropTagsResult { article_id, result }).await;
+                });
+                true
+            }
+            _ => false,
+        };
+        self.raindrop_tags = Some(RaindropTagSuggestions {
+            article_id,
+            tags: parse_tags(&self.config.default_tags.join(",")),
+            cursor: 0,
+            loading,
+        });
+    }
+
+    /// Suggested Raindrop tags not entered yet
+    pub fn raindrop_tag_choices(&self) -> Vec<&str> {
+        let Some(suggestions) = &self.raindrop_tags else {
+            return Vec::new();
+        };
+        let entered = parse_tags(&self.tag_input);
+        suggestions
+            .tags
+            .iter()
+            .filter(|tag| !entered.contains(tag))
+            .map(|tag| tag.as_str())
+            .collect()
+    }
+
+    /// Poll for tags Claude picked for a Raindrop bookmark (non-blocking)
+    pub fn poll_raindrop_tags_result(&mut self) {
+        let Ok(picked) = self.raindrop_tags_rx.try_recv() else {
+            return;
+        };
+        let Some(suggestions) = self
+            .raindrop_tags
+            .as_mut()
+            .filter(|s| s.article_id == picked.article_id && s.loading)
+        else {
+            return;
+        };
+        suggestions.loading = false;
+        match picked.result {
+            Ok(mut tags) => {
+                tags.retain(|tag| !suggestions.tags.contains(tag));
+                suggestions.tags.splice(0..0, tags);
+                suggestions.cursor = 0;
+            }
+            Err(e) => tracing::warn!("Tag suggestions failed: {}", e),
+        }
+    }
+
+    /// Ask which collection to save the selected article to, preselecting the
+    /// one last used for its feed, else the configured default
+    async fn open_raindrop_picker(&mut self) -> Result<()> {
         let Some(raindrop) = &self.raindrop else {
             return Ok(());
         };
-
-        let Some(article) = self.selected_article() else {
+        let Some(feed_id) = self.selected_article().map(|a| a.feed_id) else {
             return Ok(());
         };
 
@@ -670,6 +6619,44 @@ impl App {
             .filter(|s| !s.is_empty())
             .collect();
 
+        if self.raindrop_collections.is_none() {
+            match raindrop.list_collections().await {
+                Ok(collections) => self.raindrop_collections = Some(collections),
+                Err(e) => {
+                    tracing::warn!("Failed to load Raindrop collections: {}", e);
+                    self.show_toast(
+                        "Couldn't load Raindrop collections, saving to Unsorted".to_string(),
+                    );
+                    return self.save_to_raindrop(tags, None).await;
+                }
+            }
+        }
+        let collections = self.raindrop_collections.clone().unwrap_or_default();
+
+        let remembered = self.repository.get_feed_raindrop_collection(feed_id).await?;
+        let default = &self.config.raindrop_collection;
+        let cursor = remembered
+            .and_then(|id| collections.iter().position(|c| c.id == id))
+            .or_else(|| collections.iter().position(|c| c.title.eq_ignore_ascii_case(default)))
+            .unwrap_or(0);
+
+        self.raindrop_picker = Some(RaindropPicker {
+            collections,
+            cursor,
+            tags,
+        });
+        Ok(())
+    }
+
+    async fn save_to_raindrop(&mut self, tags: Vec<String>, collection: Option<i64>) -> Result<()> {
+        let Some(raindrop) = &self.raindrop else {
+            return Ok(());
+        };
+
+        let Some(article) = self.selected_article() else {
+            return Ok(());
+        };
+
         let article_id = article.id;
         let url = article.url.clone();
         let title = article.title.clone();
@@ -692,7 +6679,14 @@ impl App {
         let note = self.current_summary.as_ref().map(|s| s.content.clone());
 
         match raindrop
-            .save_bookmark(&url, Some(&title), excerpt.as_deref(), note.as_deref(), tags.clone())
+            .save_bookmark(
+                &url,
+                Some(&title),
+                excerpt.as_deref(),
+                note.as_deref(),
+                tags.clone(),
+                collection,
+            )
             .await
         {
             Ok(raindrop_id) => {
@@ -776,30 +6770,71 @@ impl App {
         Self::get_first_sentence(&text)
     }
 
-    pub async fn import_opml(&mut self, path: &Path) -> Result<()> {
+    pub async fn import_opml(&mut self, path: &Path) -> Result<OpmlImport> {
         let feeds = parse_opml_file(path)?;
+        self.import_feeds(feeds).await
+    }
+
+    /// Import the feeds of an OPML file on the web
+    pub async fn import_opml_url(&mut self, url: &str) -> Result<OpmlImport> {
+        let feeds = self.fetcher.fetch_opml(url).await?;
+        self.import_feeds(feeds).await
+    }
 
+    /// Subscribe to imported feeds, skipping ones already subscribed to (by
+    /// normalized URL) and ones listed twice, as Feedly does for feeds in
+    /// several categories
+    async fn import_feeds(&mut self, feeds: Vec<NewFeed>) -> Result<OpmlImport> {
+        let mut import = OpmlImport::default();
+        let mut known: HashSet<String> =
+            self.feeds.iter().map(|f| normalize_url(&f.url)).collect();
+        let mut folders = HashSet::new();
         for feed in feeds {
+            if !known.insert(normalize_url(&feed.url)) {
+                import.skipped += 1;
+                continue;
+            }
+            let category = feed.category.clone();
             match self.repository.insert_feed(feed).await {
-                Ok(_) => {}
+                Ok(_) => {
+                    import.added += 1;
+                    folders.extend(category);
+                }
                 Err(e) => {
                     tracing::warn!("Failed to insert feed: {}", e);
+                    import.failed += 1;
                 }
             }
         }
+        import.folders = folders.len();
 
         self.feeds = self.repository.get_all_feeds().await?;
+        self.invalidate_filter();
 
         // Refresh the newly imported feeds
-        self.refresh_feeds();
+        if import.added > 0 {
+            self.refresh_feeds();
+        }
 
-        Ok(())
+        Ok(import)
     }
 
     async fn import_opml_from_input(&mut self) -> Result<()> {
         let input = self.opml_input.trim().to_string();
         if input.is_empty() {
-            self.opml_input_status = Some("Enter a file path".to_string());
+            self.opml_input_status = Some("Enter a file path or URL".to_string());
+            return Ok(());
+        }
+
+        // Shared OPML files are downloaded in the background
+        if input.starts_with("http://") || input.starts_with("https://") {
+            self.opml_input_status = Some("Downloading...".to_string());
+            let fetcher = self.fetcher.clone();
+            let tx = self.opml_download_tx.clone();
+            tokio::spawn(async move {
+                let result = fetcher.fetch_opml(&input).await.map_err(|e| e.to_string());
+                let _ = tx.send(OpmlDownloadResult { url: input, result }).await;
+            });
             return Ok(());
         }
 
@@ -822,9 +6857,8 @@ impl App {
         }
 
         match self.import_opml(&expanded).await {
-            Ok(()) => {
-                let count = self.feeds.len();
-                self.opml_input_status = Some(format!("Imported! {} feeds total", count));
+            Ok(import) => {
+                self.show_toast(import.to_string());
                 self.opml_input_active = false;
                 self.opml_input.clear();
             }
@@ -836,6 +6870,39 @@ impl App {
         Ok(())
     }
 
+    /// Poll for a downloaded OPML file and import its feeds (non-blocking)
+    pub async fn poll_opml_download(&mut self) -> Result<()> {
+        let Ok(download) = self.opml_download_rx.try_recv() else {
+            return Ok(());
+        };
+        // The popup may have been closed while downloading
+        if !self.opml_input_active {
+            return Ok(());
+        }
+        let feeds = match download.result {
+            Ok(feeds) if !feeds.is_empty() => feeds,
+            Ok(_) => {
+                self.opml_input_status = Some(format!("Error: no feeds in {}", download.url));
+                return Ok(());
+            }
+            Err(e) => {
+                self.opml_input_status = Some(format!("Error: {}", e));
+                return Ok(());
+            }
+        };
+        match self.import_feeds(feeds).await {
+            Ok(import) => {
+                self.show_toast(import.to_string());
+                self.opml_input_active = false;
+                self.opml_input.clear();
+            }
+            Err(e) => {
+                self.opml_input_status = Some(format!("Error: {}", e));
+            }
+        }
+        Ok(())
+    }
+
     fn export_opml_to_file(&mut self) -> Result<()> {
         let input = self.opml_export_input.trim().to_string();
         if input.is_empty() {
@@ -856,12 +6923,24 @@ impl App {
 
         self.opml_export_status = Some("Exporting...".to_string());
 
-        match export_opml_file(&expanded, &self.feeds) {
+        // Export only the feeds ticked in the picker
+        let selected: Vec<Feed> = match &self.opml_export_picker {
+            Some(picker) => picker
+                .entries
+                .iter()
+                .filter(|(_, included)| *included)
+                .filter_map(|(id, _)| self.feeds.iter().find(|f| f.id == *id).cloned())
+                .collect(),
+            None => self.feeds.clone(),
+        };
+
+        match export_opml_file(&expanded, &selected) {
             Ok(()) => {
-                let count = self.feeds.len();
+                let count = selected.len();
                 self.opml_export_status = Some(format!("Exported {} feeds!", count));
                 self.opml_export_active = false;
                 self.opml_export_input.clear();
+                self.opml_export_picker = None;
             }
             Err(e) => {
                 self.opml_export_status = Some(format!("Error: {}", e));
@@ -871,7 +6950,53 @@ impl App {
         Ok(())
     }
 
-    fn email_article(&self, article: &Article) {
+    /// Copy the summary on screen, or else the article's stored summary
+    async fn copy_summary(&mut self, article_id: i64) {
+        let shown = self
+            .current_summary
+            .as_ref()
+            .filter(|s| s.article_id == article_id)
+            .filter(|_| self.summary_status == SummaryStatus::Generated)
+            .map(|s| s.content.clone());
+        let summary = match shown {
+            Some(summary) => Some(summary),
+            None => match self.repository.get_summary(article_id).await {
+                Ok(summary) => summary.map(|s| s.content),
+                Err(e) => {
+                    tracing::warn!("Failed to load summary for copying: {}", e);
+                    None
+                }
+            },
+        };
+        match summary {
+            Some(summary) => self.copy_to_clipboard(summary.trim(), "summary"),
+            None => self.show_toast("No summary to copy yet (Enter to generate)".to_string()),
+        }
+    }
+
+    fn copy_to_clipboard(&mut self, text: &str, what: &str) {
+        match self.clipboard.copy(text) {
+            Ok(()) => self.show_toast(format!("Copied {} to clipboard", what)),
+            Err(e) => self.show_toast(format!("Failed to copy {}: {}", what, e)),
+        }
+    }
+
+    async fn print_article(&mut self, article: Article) {
+        let summary = match self.repository.get_summary(article.id).await {
+            Ok(summary) => summary,
+            Err(e) => {
+                tracing::warn!("Failed to load summary for printing: {}", e);
+                None
+            }
+        };
+        let text = render_print_text(&article, summary.as_ref());
+        match print_text(&self.config.print, text).await {
+            Ok(()) => self.show_toast(format!("Sent \"{}\" to the printer", article.title)),
+            Err(e) => self.show_toast(format!("Failed to print: {}", e)),
+        }
+    }
+
+    async fn email_article(&self, article: &Article) {
         let subject = urlencoding::encode(&article.title);
 
         // Build email body with title, URL, summary (if available), and content
@@ -885,8 +7010,21 @@ impl App {
         body_parts.push(format!("URL: {}", article.url));
         body_parts.push(String::new()); // blank line
 
-        // Add AI summary if available
-        if let Some(summary) = &self.current_summary {
+        // Add the AI summary if there is one, whether or not it is on screen
+        let summary = match self.repository.get_summary(article.id).await {
+            Ok(Some(summary)) => Some(summary),
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!("Failed to load summary for email: {}", e);
+                None
+            }
+        }
+        .or_else(|| {
+            self.current_summary
+                .clone()
+                .filter(|summary| summary.article_id == article.id)
+        });
+        if let Some(summary) = &summary {
             body_parts.push("AI Summary:".to_string());
             body_parts.push(summary.content.clone());
             body_parts.push(String::new()); // blank line
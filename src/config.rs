@@ -1,7 +1,40 @@
+use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::error::{AppError, Result};
+use crate::models::{ArticleFilter, SortMode, SummaryVariant};
+use crate::services::{load_config_secret, store_config_secret};
+
+/// Secrets that can also come from the environment or the OS keyring:
+/// config key and environment variable
+pub const SECRETS: [(&str, &str); 6] = [
+    ("claude_api_key", "SPEEDY_CLAUDE_API_KEY"),
+    ("raindrop_token", "SPEEDY_RAINDROP_TOKEN"),
+    ("hypothesis_token", "SPEEDY_HYPOTHESIS_TOKEN"),
+    ("mastodon_token", "SPEEDY_MASTODON_TOKEN"),
+    ("bluesky_app_password", "SPEEDY_BLUESKY_APP_PASSWORD"),
+    ("smtp_password", "SPEEDY_SMTP_PASSWORD"),
+];
+
+/// Where a secret's value was found, in priority order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecretSource {
+    Env,
+    Keyring,
+    File,
+}
+
+impl SecretSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SecretSource::Env => "environment",
+            SecretSource::Keyring => "keyring",
+            SecretSource::File => "config file",
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -10,36 +43,1229 @@ pub struct Config {
 
     pub claude_api_key: Option<String>,
     pub raindrop_token: Option<String>,
+    /// Raindrop collection preselected when saving, by title ("Parent / Child" when nested)
+    #[serde(default = "default_raindrop_collection")]
+    pub raindrop_collection: String,
+    /// Offer tags picked by Claude for the article when saving to Raindrop
+    #[serde(default = "default_true")]
+    pub raindrop_suggest_tags: bool,
+    /// Minutes between reconciling saved flags with Raindrop bookmarks (0: never)
+    #[serde(default)]
+    pub raindrop_sync_minutes: u32,
+    /// Hypothes.is developer token for syncing annotations
+    pub hypothesis_token: Option<String>,
+    /// Access token of the Mastodon account articles are shared to
+    pub mastodon_token: Option<String>,
+    /// App password of the Bluesky account articles are shared to
+    pub bluesky_app_password: Option<String>,
+    /// Password of the SMTP account digests are emailed through
+    pub smtp_password: Option<String>,
+
+    /// Look up secrets in the OS keyring (set by `speedy-reader set-secret`)
+    #[serde(default)]
+    pub use_keyring: bool,
 
+    /// Minutes between automatic refreshes while the TUI runs (0 disables)
     #[serde(default = "default_refresh_interval")]
     pub refresh_interval_minutes: u32,
 
+    /// Milliseconds between animation frames (spinners)
+    #[serde(default = "default_tick_rate")]
+    pub tick_rate_ms: u64,
+
     #[serde(default)]
     pub default_tags: Vec<String>,
+
+    /// Days an article stays in the trash before it is purged
+    #[serde(default = "default_trash_retention")]
+    pub trash_retention_days: u32,
+
+    /// Articles spared from the age-based move to the trash
+    #[serde(default)]
+    pub cleanup: CleanupConfig,
+
+    /// Pages of RFC 5005 feed archives to read when a feed is first fetched (0 disables)
+    #[serde(default)]
+    pub archive_backfill_pages: u32,
+
+    /// Preselected choice of how many existing items to import when subscribing
+    #[serde(default)]
+    pub initial_import: InitialImport,
+
+    #[serde(default)]
+    pub sort_mode: SortMode,
+
+    /// ASCII borders and 8 colors for the Linux console or serial terminals
+    /// (default: detected from TERM)
+    #[serde(default)]
+    pub tty_mode: Option<bool>,
+
+    /// Show the unread count and refresh state in the terminal's title and
+    /// tab bar
+    #[serde(default = "default_true")]
+    pub terminal_title: bool,
+
+    /// Width of the article list, in percent of the window
+    #[serde(default = "default_list_width")]
+    pub list_width: u16,
+
+    /// Height of the feed content pane, in percent of the window beside the
+    /// list; the summary gets the rest
+    #[serde(default = "default_content_height")]
+    pub content_height: u16,
+
+    /// Group the article list under Today, Yesterday, This Week and Older
+    /// when sorted by date
+    #[serde(default = "default_true")]
+    pub group_by_date: bool,
+
+    /// Estimated reading time ("~7 min") on each row of the article list
+    #[serde(default)]
+    pub list_reading_time: bool,
+
+    /// How much each row of the article list shows
+    #[serde(default)]
+    pub list_density: ListDensity,
+
+    /// Saved view setups, switched to with `:workspace` or keys 1-9
+    #[serde(default)]
+    pub workspaces: Vec<Workspace>,
+
+    /// What keys 1-9 do on their own; unset, they switch workspace once one
+    /// is saved and list a single feed until then
+    #[serde(default)]
+    pub number_keys: Option<NumberKeys>,
+
+    /// Reopen with the filter, sort, feed or folder, tag, list width and
+    /// article of the last session
+    #[serde(default = "default_true")]
+    pub restore_session: bool,
+
+    /// What Enter does with the selected article
+    #[serde(default)]
+    pub enter_action: EnterAction,
+
+    /// When articles marked read leave the Unread view
+    #[serde(default)]
+    pub hide_read: HideRead,
+
+    /// Language articles are translated into
+    #[serde(default = "default_translate_to")]
+    pub translate_to: String,
+
+    /// Who the simplify action writes for
+    #[serde(default)]
+    pub reading_level: ReadingLevel,
+
+    #[serde(default)]
+    pub notifications: NotificationConfig,
+
+    /// URLs new articles are POSTed to
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+
+    /// Mastodon and Bluesky accounts to share articles to
+    #[serde(default)]
+    pub social: SocialConfig,
+
+    /// Resurfacing of forgotten starred articles
+    #[serde(default)]
+    pub revisit: RevisitConfig,
+
+    #[serde(default)]
+    pub summary: SummaryConfig,
+
+    #[serde(default)]
+    pub images: ImageConfig,
+
+    #[serde(default)]
+    pub speech: SpeechConfig,
+
+    #[serde(default)]
+    pub print: PrintConfig,
+
+    #[serde(default)]
+    pub email: EmailConfig,
+
+    /// Directory (e.g. an Obsidian vault folder) for starred-article Markdown notes
+    pub notes_dir: Option<String>,
+
+    /// Log file, in place of `speedy-reader.log` in the data directory
+    pub log_file: Option<String>,
+
+    /// Domains (and their subdomains) whose Firefox cookies are sent when
+    /// fetching full articles; every other fetch goes without cookies
+    #[serde(default)]
+    pub cookie_domains: Vec<String>,
+
+    /// Optional embeddings backend for semantic search (`[embeddings]` table)
+    pub embeddings: Option<EmbeddingConfig>,
+
+    /// Optional Google Reader API sync server (`[sync]` table)
+    pub sync: Option<SyncConfig>,
+
+    /// Optional nightly pause for background work (`[quiet_hours]` table)
+    pub quiet_hours: Option<QuietHours>,
+
+    /// Proxy for every HTTP request, e.g. "http://proxy.lan:3128"
+    pub http_proxy: Option<String>,
+    /// SOCKS5 proxy, taking precedence over `http_proxy`; use
+    /// "socks5h://127.0.0.1:9050" for Tor so names resolve through it too
+    pub socks5_proxy: Option<String>,
+    /// Per-service proxy: "direct" or a proxy URL, by service name (feeds,
+    /// content, images, directory, discussions, claude, embeddings, raindrop,
+    /// hypothesis, sync, webhooks)
+    #[serde(default)]
+    pub proxy_overrides: HashMap<String, String>,
+
+    // Where each secret was found, and the values the config file had
+    #[serde(skip)]
+    secret_sources: HashMap<&'static str, SecretSource>,
+    #[serde(skip)]
+    file_secrets: HashMap<&'static str, String>,
 }
 
-fn default_db_path() -> String {
-    let data_dir = dirs::data_dir()
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingProvider {
+    #[serde(rename = "openai")]
+    OpenAi,
+    Ollama,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingConfig {
+    pub provider: EmbeddingProvider,
+    pub api_key: Option<String>,
+    /// Defaults to text-embedding-3-small (OpenAI) or nomic-embed-text (Ollama)
+    pub model: Option<String>,
+    /// Override the API endpoint (e.g. a remote Ollama server)
+    pub url: Option<String>,
+}
+
+/// Parts of the app that make HTTP requests, each of which can have its own proxy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProxyService {
+    Feeds,
+    Content,
+    Images,
+    Directory,
+    Discussions,
+    Claude,
+    Embeddings,
+    Raindrop,
+    Hypothesis,
+    Sync,
+    Webhooks,
+    Social,
+}
+
+impl ProxyService {
+    pub const ALL: [ProxyService; 12] = [
+        ProxyService::Feeds,
+        ProxyService::Content,
+        ProxyService::Images,
+        ProxyService::Directory,
+        ProxyService::Discussions,
+        ProxyService::Claude,
+        ProxyService::Embeddings,
+        ProxyService::Raindrop,
+        ProxyService::Hypothesis,
+        ProxyService::Sync,
+        ProxyService::Webhooks,
+        ProxyService::Social,
+    ];
+
+    /// Name in `[proxy_overrides]`
+    pub fn key(self) -> &'static str {
+        match self {
+            ProxyService::Feeds => "feeds",
+            ProxyService::Content => "content",
+            ProxyService::Images => "images",
+            ProxyService::Directory => "directory",
+            ProxyService::Discussions => "discussions",
+            ProxyService::Claude => "claude",
+            ProxyService::Embeddings => "embeddings",
+            ProxyService::Raindrop => "raindrop",
+            ProxyService::Hypothesis => "hypothesis",
+            ProxyService::Sync => "sync",
+            ProxyService::Webhooks => "webhooks",
+            ProxyService::Social => "social",
+        }
+    }
+}
+
+/// How a service's requests reach the internet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProxyRoute {
+    /// Nothing configured: reqwest's default, which honours HTTP(S)_PROXY
+    System,
+    /// Straight to the server, even with proxy environment variables set
+    Direct,
+    /// Through this proxy URL
+    Proxy(String),
+}
+
+impl ProxyRoute {
+    /// Set up an HTTP client builder to use this route
+    pub fn apply(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        match self {
+            ProxyRoute::System => builder,
+            ProxyRoute::Direct => builder.no_proxy(),
+            ProxyRoute::Proxy(url) => builder.proxy(
+                reqwest::Proxy::all(url).expect("Proxy URL checked when loading the config"),
+            ),
+        }
+    }
+}
+
+/// Google Reader compatible API (FreshRSS, The Old Reader, ...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncConfig {
+    /// API endpoint, e.g. https://rss.example.com/api/greader.php
+    pub url: String,
+    pub username: String,
+    /// Account password, or the separate API password FreshRSS asks for
+    pub password: String,
+}
+
+/// A named view setup (`[[workspaces]]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    #[serde(default)]
+    pub filter: ArticleFilter,
+    #[serde(default)]
+    pub sort: SortMode,
+    /// Only feeds in this OPML folder
+    pub category: Option<String>,
+    /// Only this feed (by URL)
+    pub feed: Option<String>,
+    pub tag: Option<String>,
+    #[serde(default = "default_list_width")]
+    pub list_width: u16,
+}
+
+/// What pressing Enter on an article does
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EnterAction {
+    /// Generate (or show) the AI summary
+    #[default]
+    Summarize,
+    /// Open the article in the web browser
+    Browser,
+    /// Open the full-screen reader
+    Reader,
+    /// Summarize, open the reader and open the browser
+    All,
+}
+
+impl EnterAction {
+    pub fn summarizes(self) -> bool {
+        matches!(self, EnterAction::Summarize | EnterAction::All)
+    }
+
+    pub fn opens_browser(self) -> bool {
+        matches!(self, EnterAction::Browser | EnterAction::All)
+    }
+
+    pub fn opens_reader(self) -> bool {
+        matches!(self, EnterAction::Reader | EnterAction::All)
+    }
+
+    /// Short hint for the status bar
+    pub fn label(self) -> &'static str {
+        match self {
+            EnterAction::Summarize => "summarize",
+            EnterAction::Browser => "browser",
+            EnterAction::Reader => "read",
+            EnterAction::All => "read+summarize",
+        }
+    }
+}
+
+/// When articles marked read leave the Unread view
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HideRead {
+    /// As soon as they are marked read, moving the cursor to the next article
+    Immediately,
+    /// Once the cursor moves off them
+    OnNavigate,
+    /// Not until the list is next reloaded, so the list never shifts
+    #[default]
+    Session,
+}
+
+impl HideRead {
+    pub fn next(self) -> Self {
+        match self {
+            HideRead::Session => HideRead::OnNavigate,
+            HideRead::OnNavigate => HideRead::Immediately,
+            HideRead::Immediately => HideRead::Session,
+        }
+    }
+
+    /// Toast shown when switching to this behavior
+    pub fn description(self) -> &'static str {
+        match self {
+            HideRead::Immediately => "Read articles are hidden immediately",
+            HideRead::OnNavigate => "Read articles are hidden when you move on",
+            HideRead::Session => "Read articles stay listed until the next refresh",
+        }
+    }
+}
+
+/// How much each row of the article list shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListDensity {
+    /// One line per article, without feed badges
+    Compact,
+    /// One line per article
+    #[default]
+    Comfortable,
+    /// A second line with the date, author, word count and the start of the
+    /// feed's summary
+    Detailed,
+}
+
+impl ListDensity {
+    pub fn next(self) -> Self {
+        match self {
+            ListDensity::Compact => ListDensity::Comfortable,
+            ListDensity::Comfortable => ListDensity::Detailed,
+            ListDensity::Detailed => ListDensity::Compact,
+        }
+    }
+
+    /// Toast shown when switching to this density
+    pub fn description(self) -> &'static str {
+        match self {
+            ListDensity::Compact => "Compact list: one line per article, no feed badges",
+            ListDensity::Comfortable => "Comfortable list: one line per article",
+            ListDensity::Detailed => "Detailed list: date, author, length and summary below",
+        }
+    }
+
+    /// Lines each article takes in the list
+    pub fn lines(self) -> usize {
+        match self {
+            ListDensity::Compact | ListDensity::Comfortable => 1,
+            ListDensity::Detailed => 2,
+        }
+    }
+}
+
+/// What keys 1-9 do when not typed as a count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberKeys {
+    /// List only the nth feed, in title order; 0 lists all feeds again
+    Feeds,
+    /// Switch to the nth saved workspace
+    Workspaces,
+}
+
+/// Who the simplify action rewrites articles for
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingLevel {
+    /// Children of about ten
+    Elementary,
+    /// Adults without background in the subject
+    #[default]
+    General,
+    /// People reading in a language they are still learning
+    Learner,
+}
+
+impl ReadingLevel {
+    pub const ALL: [ReadingLevel; 3] =
+        [ReadingLevel::Elementary, ReadingLevel::General, ReadingLevel::Learner];
+
+    /// Name in config.toml and `:simplify`
+    pub fn key(self) -> &'static str {
+        match self {
+            ReadingLevel::Elementary => "elementary",
+            ReadingLevel::General => "general",
+            ReadingLevel::Learner => "learner",
+        }
+    }
+
+    /// The intended reader, for the prompt
+    pub fn reader(self) -> &'static str {
+        match self {
+            ReadingLevel::Elementary => {
+                "a ten-year-old: short sentences, everyday words, concrete examples"
+            }
+            ReadingLevel::General => {
+                "an adult with no background in the subject: plain words, and every \
+                 technical term or acronym explained briefly where it first appears"
+            }
+            ReadingLevel::Learner => {
+                "someone still learning the language (CEFR B1): common words, short \
+                 sentences, no idioms or slang"
+            }
+        }
+    }
+}
+
+/// How many of the items already in a feed are imported when subscribing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InitialImport {
+    /// Everything the feed (and its archives) holds
+    #[default]
+    All,
+    /// The newest `LATEST_IMPORT_COUNT` items
+    Latest,
+    /// None; only items published from now on
+    NewOnly,
+}
+
+// Items imported with `InitialImport::Latest`
+const LATEST_IMPORT_COUNT: usize = 10;
+
+impl InitialImport {
+    /// Items to keep from the first fetch, None for all
+    pub fn limit(self) -> Option<usize> {
+        match self {
+            InitialImport::All => None,
+            InitialImport::Latest => Some(LATEST_IMPORT_COUNT),
+            InitialImport::NewOnly => Some(0),
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            InitialImport::All => InitialImport::Latest,
+            InitialImport::Latest => InitialImport::NewOnly,
+            InitialImport::NewOnly => InitialImport::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            InitialImport::All => "all items in the feed",
+            InitialImport::Latest => "latest 10 items",
+            InitialImport::NewOnly => "none, only new items",
+        }
+    }
+}
+
+/// Shape of generated summaries
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryStyle {
+    #[default]
+    Bullets,
+    Paragraphs,
+    #[serde(rename = "tldr")]
+    TlDr,
+}
+
+/// Summary generation settings (`[summary]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SummaryConfig {
+    /// Replaces the built-in format instructions for the chosen style
+    pub prompt: Option<String>,
+
+    #[serde(default)]
+    pub style: SummaryStyle,
+
+    /// Language to write summaries in (default: the article's language, as
+    /// detected from its text)
+    pub language: Option<String>,
+
+    #[serde(default = "default_summary_max_tokens")]
+    pub max_tokens: u32,
+
+    /// Tries per Claude request when the API is rate limited (429) or overloaded (529)
+    #[serde(default = "default_summary_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Requests sent to the Claude API per minute at most
+    #[serde(default = "default_summary_requests_per_minute")]
+    pub requests_per_minute: u32,
+
+    /// Summaries asked for with Enter or `g` made at once; the rest wait in a queue
+    #[serde(default = "default_summary_concurrency")]
+    pub concurrency: usize,
+
+    /// Background summaries (feeds set to auto-summarize) made at once
+    #[serde(default = "default_auto_summary_concurrency")]
+    pub auto_concurrency: usize,
+
+    /// Background summaries made per day at most, across all instances
+    #[serde(default = "default_auto_summary_daily_limit")]
+    pub auto_daily_limit: u32,
+
+    /// Estimated Claude spend in US dollars per calendar month after which
+    /// background summaries stop (default: no limit)
+    pub monthly_budget_usd: Option<f64>,
+
+    /// Also extract a TL;DR, key facts and named entities, shown above the summary
+    #[serde(default)]
+    pub key_facts: bool,
+
+    /// Per-feed overrides keyed by feed URL or title (`[summary.feeds."..."]`)
+    #[serde(default)]
+    pub feeds: HashMap<String, SummaryOverride>,
+}
+
+/// Per-feed summary settings; unset fields fall back to `[summary]`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SummaryOverride {
+    pub prompt: Option<String>,
+    pub style: Option<SummaryStyle>,
+    pub language: Option<String>,
+    pub max_tokens: Option<u32>,
+}
+
+/// Effective summary settings for one article
+#[derive(Debug, Clone)]
+pub struct SummaryOptions {
+    pub prompt: Option<String>,
+    pub style: SummaryStyle,
+    pub language: Option<String>,
+    pub max_tokens: u32,
+    /// Style picked when regenerating; replaces the prompt and style
+    pub variant: Option<SummaryVariant>,
+}
+
+impl Default for SummaryConfig {
+    fn default() -> Self {
+        Self {
+            prompt: None,
+            style: SummaryStyle::default(),
+            language: None,
+            max_tokens: default_summary_max_tokens(),
+            max_attempts: default_summary_max_attempts(),
+            requests_per_minute: default_summary_requests_per_minute(),
+            concurrency: default_summary_concurrency(),
+            auto_concurrency: default_auto_summary_concurrency(),
+            auto_daily_limit: default_auto_summary_daily_limit(),
+            monthly_budget_usd: None,
+            key_facts: false,
+            feeds: HashMap::new(),
+        }
+    }
+}
+
+impl SummaryConfig {
+    /// Resolve settings for a feed, applying its override if there is one
+    pub fn options_for(&self, feed_url: &str, feed_title: &str) -> SummaryOptions {
+        let feed = self
+            .feeds
+            .iter()
+            .find(|(key, _)| *key == feed_url || key.eq_ignore_ascii_case(feed_title))
+            .map(|(_, o)| o.clone())
+            .unwrap_or_default();
+
+        SummaryOptions {
+            prompt: feed.prompt.or_else(|| self.prompt.clone()),
+            style: feed.style.unwrap_or(self.style),
+            language: feed.language.or_else(|| self.language.clone()),
+            max_tokens: feed.max_tokens.unwrap_or(self.max_tokens),
+            variant: None,
+        }
+    }
+}
+
+/// New-article notification settings (`[notifications]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationConfig {
+    /// Desktop notification after a refresh finds new articles
+    #[serde(default)]
+    pub desktop: bool,
+
+    /// Transient message in the TUI status bar
+    #[serde(default = "default_true")]
+    pub toast: bool,
+
+    /// Feeds (by URL or title) that never trigger notifications
+    #[serde(default)]
+    pub muted_feeds: Vec<String>,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            desktop: false,
+            toast: true,
+            muted_feeds: Vec::new(),
+        }
+    }
+}
+
+/// Which articles outlive the 7-day cleanup (`[cleanup]` table)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CleanupConfig {
+    #[serde(default = "default_true")]
+    pub keep_starred: bool,
+
+    /// Articles with tags of your own
+    #[serde(default = "default_true")]
+    pub keep_tagged: bool,
+
+    /// Articles saved to Raindrop.io
+    #[serde(default = "default_true")]
+    pub keep_saved: bool,
+}
+
+impl Default for CleanupConfig {
+    fn default() -> Self {
+        Self {
+            keep_starred: true,
+            keep_tagged: true,
+            keep_saved: true,
+        }
+    }
+}
+
+/// Time of day when background refreshes, background summaries and desktop
+/// notifications are held off (`[quiet_hours]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuietHours {
+    /// Local time as HH:MM, e.g. "22:00"
+    pub start: String,
+
+    /// Local time as HH:MM; earlier than `start` when the quiet hours run
+    /// past midnight
+    pub end: String,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        let (Some(start), Some(end)) = (parse_clock(&self.start), parse_clock(&self.end)) else {
+            return false;
+        };
+        if start <= end {
+            start <= time && time < end
+        } else {
+            time >= start || time < end
+        }
+    }
+}
+
+fn parse_clock(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M").ok()
+}
+
+/// Daily resurfacing of starred articles never opened (`[revisit]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevisitConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Only articles published at least this many days ago
+    #[serde(default = "default_revisit_min_age_days")]
+    pub min_age_days: u32,
+
+    /// Also announce the day's article with a desktop notification
+    #[serde(default)]
+    pub notify: bool,
+}
+
+impl Default for RevisitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_age_days: default_revisit_min_age_days(),
+            notify: false,
+        }
+    }
+}
+
+impl NotificationConfig {
+    pub fn is_muted(&self, feed_url: &str, feed_title: &str) -> bool {
+        self.muted_feeds
+            .iter()
+            .any(|f| f == feed_url || f.eq_ignore_ascii_case(feed_title))
+    }
+}
+
+/// Accounts articles are shared to (`[social]` table); their secrets are
+/// `mastodon_token` and `bluesky_app_password`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SocialConfig {
+    /// Mastodon server, e.g. "mastodon.social"
+    pub mastodon_instance: Option<String>,
+
+    /// Bluesky handle, e.g. "name.bsky.social"
+    pub bluesky_handle: Option<String>,
+
+    /// Start posts with a line about the article written by Claude
+    #[serde(default)]
+    pub blurb: bool,
+}
+
+/// A URL new articles are POSTed to (`[[webhooks]]`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+
+    /// Only articles from these feeds (by URL or title); all feeds when empty
+    #[serde(default)]
+    pub feeds: Vec<String>,
+
+    /// Only articles whose title or text mentions one of these (ignoring
+    /// case); all articles when empty
+    #[serde(default)]
+    pub keywords: Vec<String>,
+
+    /// Generate the AI summary and send it along (needs claude_api_key)
+    #[serde(default)]
+    pub include_summary: bool,
+
+    #[serde(default)]
+    pub format: WebhookFormat,
+}
+
+impl WebhookConfig {
+    pub fn matches(&self, feed_url: &str, feed_title: &str, title: &str, text: &str) -> bool {
+        let feed_matches = self.feeds.is_empty()
+            || self
+                .feeds
+                .iter()
+                .any(|f| f == feed_url || f.eq_ignore_ascii_case(feed_title));
+        let title = title.to_lowercase();
+        let text = text.to_lowercase();
+        let keyword_matches = self.keywords.is_empty()
+            || self.keywords.iter().any(|keyword| {
+                let keyword = keyword.to_lowercase();
+                title.contains(&keyword) || text.contains(&keyword)
+            });
+        feed_matches && keyword_matches
+    }
+}
+
+/// Shape of a webhook's request body
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// `{"event": "new_article", "article": {...}}` with all article fields
+    #[default]
+    Json,
+    /// A Slack incoming webhook message
+    Slack,
+    /// A Discord webhook message
+    Discord,
+}
+
+/// Terminal graphics protocols for inline images
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphicsProtocol {
+    Kitty,
+    #[serde(rename = "iterm2")]
+    ITerm2,
+    Sixel,
+}
+
+/// Lead image previews in the article pane (`[images]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageConfig {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Protocol to draw with (default: detected from the terminal)
+    pub protocol: Option<GraphicsProtocol>,
+
+    /// Feed badges in the article list: the site's favicon with Kitty
+    /// graphics, colored initials elsewhere
+    #[serde(default = "default_true")]
+    pub feed_icons: bool,
+}
+
+impl Default for ImageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            protocol: None,
+            feed_icons: true,
+        }
+    }
+}
+
+/// Reading articles aloud (`[speech]` table)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SpeechConfig {
+    /// Command that reads text from stdin, e.g. "espeak-ng --stdin -s 190"
+    /// (default: say, espeak-ng or espeak, whichever is installed)
+    pub command: Option<String>,
+}
+
+/// Printing articles (`[print]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrintConfig {
+    /// Command that prints what it reads from stdin
+    #[serde(default = "default_print_command")]
+    pub command: String,
+
+    /// Optional command between the text and the printer that reads plain
+    /// text and writes PostScript or PDF, e.g. "enscript -B -p -"
+    pub formatter: Option<String>,
+}
+
+impl Default for PrintConfig {
+    fn default() -> Self {
+        Self {
+            command: default_print_command(),
+            formatter: None,
+        }
+    }
+}
+
+/// Emailing article digests (`[email]` table); the SMTP password is the
+/// `smtp_password` secret
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailConfig {
+    /// Addresses the digest goes to
+    #[serde(default)]
+    pub to: Vec<String>,
+
+    /// Sender address; the SMTP username when unset
+    pub from: Option<String>,
+
+    /// SMTP server the digest is sent through, e.g. "smtp.fastmail.com"
+    pub host: Option<String>,
+
+    /// SMTP port; 587 with STARTTLS, else 465 (TLS from the start)
+    pub port: Option<u16>,
+
+    /// SMTP account name; no login when unset
+    pub username: Option<String>,
+
+    /// Upgrade a plain connection with STARTTLS rather than connect over TLS
+    #[serde(default = "default_true")]
+    pub starttls: bool,
+
+    /// Title of the digest, at the start of its subject line
+    #[serde(default = "default_email_subject")]
+    pub subject: String,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            to: Vec::new(),
+            from: None,
+            host: None,
+            port: None,
+            username: None,
+            starttls: true,
+            subject: default_email_subject(),
+        }
+    }
+}
+
+/// A number or true/false typed on the settings screen
+fn parse_setting<T: std::str::FromStr>(value: &str) -> Result<T> {
+    value
+        .parse()
+        .map_err(|_| AppError::Config(format!("\"{}\" isn't a valid value", value)))
+}
+
+/// One of a config enum's values, by its name in config.toml
+fn parse_enum<T: serde::de::DeserializeOwned>(value: &str) -> Result<T> {
+    use serde::de::IntoDeserializer;
+    let deserializer: serde::de::value::StrDeserializer<serde::de::value::Error> =
+        value.into_deserializer();
+    T::deserialize(deserializer).map_err(|e| AppError::Config(e.to_string()))
+}
+
+/// A config enum value's name in config.toml
+fn enum_name<T: Serialize>(value: &T) -> String {
+    match toml::Value::try_from(value) {
+        Ok(toml::Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
+
+fn default_print_command() -> String {
+    "lp".to_string()
+}
+
+fn default_email_subject() -> String {
+    "Reading roundup".to_string()
+}
+
+/// Where the database, log and crash reports are kept by default
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir()
         .unwrap_or_else(|| PathBuf::from("."))
-        .join("speedy-reader");
+        .join("speedy-reader")
+}
+
+/// Where tracing output goes unless `log_file` or `--log-file` say otherwise
+pub fn default_log_path() -> PathBuf {
+    data_dir().join("speedy-reader.log")
+}
+
+fn default_db_path() -> String {
+    let data_dir = data_dir();
     std::fs::create_dir_all(&data_dir).ok();
     data_dir.join("feeds.db").to_string_lossy().to_string()
 }
 
+fn default_raindrop_collection() -> String {
+    "News Links".to_string()
+}
+
 fn default_refresh_interval() -> u32 {
     30
 }
 
+fn default_tick_rate() -> u64 {
+    100
+}
+
+fn default_trash_retention() -> u32 {
+    30
+}
+
+fn default_summary_max_tokens() -> u32 {
+    1024
+}
+
+fn default_summary_max_attempts() -> u32 {
+    4
+}
+
+fn default_summary_requests_per_minute() -> u32 {
+    50
+}
+
+fn default_summary_concurrency() -> usize {
+    3
+}
+
+fn default_auto_summary_concurrency() -> usize {
+    2
+}
+
+fn default_auto_summary_daily_limit() -> u32 {
+    50
+}
+
+fn default_list_width() -> u16 {
+    27
+}
+
+fn default_content_height() -> u16 {
+    30
+}
+
+fn default_translate_to() -> String {
+    "English".to_string()
+}
+
+fn default_revisit_min_age_days() -> u32 {
+    3
+}
+
+fn default_true() -> bool {
+    true
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
             db_path: default_db_path(),
             claude_api_key: None,
             raindrop_token: None,
+            raindrop_collection: default_raindrop_collection(),
+            raindrop_suggest_tags: true,
+            raindrop_sync_minutes: 0,
+            hypothesis_token: None,
+            mastodon_token: None,
+            bluesky_app_password: None,
+            smtp_password: None,
+            use_keyring: false,
             refresh_interval_minutes: default_refresh_interval(),
+            tick_rate_ms: default_tick_rate(),
             default_tags: vec!["rss".to_string()],
+            trash_retention_days: default_trash_retention(),
+            cleanup: CleanupConfig::default(),
+            archive_backfill_pages: 0,
+            initial_import: InitialImport::default(),
+            sort_mode: SortMode::default(),
+            tty_mode: None,
+            terminal_title: true,
+            list_width: default_list_width(),
+            content_height: default_content_height(),
+            group_by_date: true,
+            list_reading_time: false,
+            list_density: ListDensity::default(),
+            restore_session: true,
+            workspaces: Vec::new(),
+            number_keys: None,
+            enter_action: EnterAction::default(),
+            hide_read: HideRead::default(),
+            translate_to: default_translate_to(),
+            reading_level: ReadingLevel::default(),
+            notifications: NotificationConfig::default(),
+            webhooks: Vec::new(),
+            social: SocialConfig::default(),
+            revisit: RevisitConfig::default(),
+            summary: SummaryConfig::default(),
+            images: ImageConfig::default(),
+            speech: SpeechConfig::default(),
+            print: PrintConfig::default(),
+            email: EmailConfig::default(),
+            notes_dir: None,
+            log_file: None,
+            cookie_domains: Vec::new(),
+            embeddings: None,
+            sync: None,
+            quiet_hours: None,
+            http_proxy: None,
+            socks5_proxy: None,
+            proxy_overrides: HashMap::new(),
+            secret_sources: HashMap::new(),
+            file_secrets: HashMap::new(),
+        }
+    }
+}
+
+/// Config values that can be changed from the settings screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Setting {
+    ClaudeApiKey,
+    RaindropToken,
+    HypothesisToken,
+    RefreshInterval,
+    TrashRetention,
+    ArchiveBackfillPages,
+    ListWidth,
+    ContentHeight,
+    GroupByDate,
+    ListReadingTime,
+    ListDensity,
+    EnterAction,
+    HideRead,
+    NumberKeys,
+    TerminalTitle,
+    RestoreSession,
+    TranslateTo,
+    ReadingLevel,
+    RaindropCollection,
+    RaindropSuggestTags,
+    NotesDir,
+    SummaryStyle,
+    SummaryLanguage,
+    SummaryMonthlyBudget,
+    SummaryKeyFacts,
+    DesktopNotifications,
+    ToastNotifications,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 27] = [
+        Setting::ClaudeApiKey,
+        Setting::RaindropToken,
+        Setting::HypothesisToken,
+        Setting::RefreshInterval,
+        Setting::TrashRetention,
+        Setting::ArchiveBackfillPages,
+        Setting::ListWidth,
+        Setting::ContentHeight,
+        Setting::GroupByDate,
+        Setting::ListReadingTime,
+        Setting::ListDensity,
+        Setting::EnterAction,
+        Setting::HideRead,
+        Setting::NumberKeys,
+        Setting::TerminalTitle,
+        Setting::RestoreSession,
+        Setting::TranslateTo,
+        Setting::ReadingLevel,
+        Setting::RaindropCollection,
+        Setting::RaindropSuggestTags,
+        Setting::NotesDir,
+        Setting::SummaryStyle,
+        Setting::SummaryLanguage,
+        Setting::SummaryMonthlyBudget,
+        Setting::SummaryKeyFacts,
+        Setting::DesktopNotifications,
+        Setting::ToastNotifications,
+    ];
+
+    /// Name in config.toml, with its table for nested values
+    pub fn key(self) -> &'static str {
+        match self {
+            Setting::ClaudeApiKey => "claude_api_key",
+            Setting::RaindropToken => "raindrop_token",
+            Setting::HypothesisToken => "hypothesis_token",
+            Setting::RefreshInterval => "refresh_interval_minutes",
+            Setting::TrashRetention => "trash_retention_days",
+            Setting::ArchiveBackfillPages => "archive_backfill_pages",
+            Setting::ListWidth => "list_width",
+            Setting::ContentHeight => "content_height",
+            Setting::GroupByDate => "group_by_date",
+            Setting::ListReadingTime => "list_reading_time",
+            Setting::ListDensity => "list_density",
+            Setting::EnterAction => "enter_action",
+            Setting::HideRead => "hide_read",
+            Setting::NumberKeys => "number_keys",
+            Setting::TerminalTitle => "terminal_title",
+            Setting::RestoreSession => "restore_session",
+            Setting::TranslateTo => "translate_to",
+            Setting::ReadingLevel => "reading_level",
+            Setting::RaindropCollection => "raindrop_collection",
+            Setting::RaindropSuggestTags => "raindrop_suggest_tags",
+            Setting::NotesDir => "notes_dir",
+            Setting::SummaryStyle => "summary.style",
+            Setting::SummaryLanguage => "summary.language",
+            Setting::SummaryMonthlyBudget => "summary.monthly_budget_usd",
+            Setting::SummaryKeyFacts => "summary.key_facts",
+            Setting::DesktopNotifications => "notifications.desktop",
+            Setting::ToastNotifications => "notifications.toast",
+        }
+    }
+
+    /// What the setting does, with the values it takes
+    pub fn description(self) -> &'static str {
+        match self {
+            Setting::ClaudeApiKey => "Claude API key for summaries, translation and chat",
+            Setting::RaindropToken => "Raindrop.io token for bookmarks",
+            Setting::HypothesisToken => "Hypothes.is developer token for annotations",
+            Setting::RefreshInterval => "Minutes between automatic refreshes (0: off)",
+            Setting::TrashRetention => "Days deleted articles stay in the trash",
+            Setting::ArchiveBackfillPages => "Archive pages read when a feed is first fetched",
+            Setting::ListWidth => "Width of the article list in percent (10-90)",
+            Setting::ContentHeight => "Height of the feed content pane in percent (10-90)",
+            Setting::GroupByDate => "Date headers in the article list when sorted by date",
+            Setting::ListReadingTime => "Reading time on each article in the list (true/false)",
+            Setting::ListDensity => "Article list rows: compact, comfortable or detailed",
+            Setting::EnterAction => "What Enter does: summarize, browser, reader or all",
+            Setting::HideRead => "Read articles leave Unread: immediately, on_navigate or session",
+            Setting::NumberKeys => "Keys 1-9 pick feeds or workspaces (empty: workspaces if saved)",
+            Setting::TerminalTitle => "Unread count in the terminal title (true/false)",
+            Setting::RestoreSession => "Reopen where the last session left off (true/false)",
+            Setting::TranslateTo => "Language articles are translated into",
+            Setting::ReadingLevel => "Plain-language rewrites: elementary, general or learner",
+            Setting::RaindropCollection => "Raindrop collection preselected when saving",
+            Setting::RaindropSuggestTags => "Claude suggests tags for Raindrop saves (true/false)",
+            Setting::NotesDir => "Directory for starred-article Markdown notes (empty: none)",
+            Setting::SummaryStyle => "Summary style: bullets, paragraphs or tldr",
+            Setting::SummaryLanguage => "Language summaries are written in (empty: the article's)",
+            Setting::SummaryMonthlyBudget => "US dollars a month before background summaries stop",
+            Setting::SummaryKeyFacts => "Extract key facts above the summary (true/false)",
+            Setting::DesktopNotifications => "Desktop notification for new articles (true/false)",
+            Setting::ToastNotifications => "Status bar message for new articles (true/false)",
         }
     }
+
+    pub fn is_secret(self) -> bool {
+        matches!(
+            self,
+            Setting::ClaudeApiKey | Setting::RaindropToken | Setting::HypothesisToken
+        )
+    }
 }
 
 impl Config {
@@ -48,11 +1274,15 @@ impl Config {
 
         if config_path.exists() {
             let content = std::fs::read_to_string(&config_path)?;
-            let config: Config = toml::from_str(&content)?;
+            let mut config: Config = toml::from_str(&content)?;
+            config.check_proxies()?;
+            config.check_quiet_hours()?;
+            config.resolve_secrets();
             Ok(config)
         } else {
-            let config = Config::default();
+            let mut config = Config::default();
             config.save()?;
+            config.resolve_secrets();
             Ok(config)
         }
     }
@@ -62,16 +1292,320 @@ impl Config {
         if let Some(parent) = config_path.parent() {
             std::fs::create_dir_all(parent)?;
         }
-        let content = toml::to_string_pretty(self)
+        // Secrets from the environment or keyring never end up in the file
+        let mut file = self.clone();
+        for (key, _) in SECRETS {
+            *file.secret_mut(key) = self.file_secrets.get(key).cloned();
+        }
+        let content = toml::to_string_pretty(&file)
             .map_err(|e| AppError::Config(e.to_string()))?;
         std::fs::write(config_path, content)?;
         Ok(())
     }
 
+    fn secret_mut(&mut self, key: &str) -> &mut Option<String> {
+        match key {
+            "claude_api_key" => &mut self.claude_api_key,
+            "raindrop_token" => &mut self.raindrop_token,
+            "hypothesis_token" => &mut self.hypothesis_token,
+            "mastodon_token" => &mut self.mastodon_token,
+            "bluesky_app_password" => &mut self.bluesky_app_password,
+            "smtp_password" => &mut self.smtp_password,
+            _ => unreachable!("unknown secret {}", key),
+        }
+    }
+
+    /// Take each secret from its environment variable, then the keyring (when
+    /// enabled), then the config file
+    fn resolve_secrets(&mut self) {
+        for (key, var) in SECRETS {
+            let file_value = self.secret_mut(key).take().filter(|v| !v.is_empty());
+            if let Some(value) = &file_value {
+                self.file_secrets.insert(key, value.clone());
+            }
+
+            let resolved = std::env::var(var)
+                .ok()
+                .filter(|v| !v.is_empty())
+                .map(|v| (v, SecretSource::Env))
+                .or_else(|| {
+                    self.use_keyring
+                        .then(|| load_config_secret(key))
+                        .flatten()
+                        .map(|v| (v, SecretSource::Keyring))
+                })
+                .or_else(|| file_value.map(|v| (v, SecretSource::File)));
+
+            if let Some((value, source)) = resolved {
+                *self.secret_mut(key) = Some(value);
+                self.secret_sources.insert(key, source);
+            }
+        }
+    }
+
+    /// Where a secret's value came from, None when it isn't set
+    pub fn secret_source(&self, key: &str) -> Option<SecretSource> {
+        self.secret_sources.get(key).copied()
+    }
+
+    /// Move a secret into the OS keyring, dropping it from the config file.
+    /// Without a value, the one in the config file is moved.
+    pub fn store_secret_in_keyring(
+        &mut self,
+        key: &'static str,
+        value: Option<String>,
+    ) -> Result<()> {
+        let value = value
+            .or_else(|| self.file_secrets.get(key).cloned())
+            .ok_or_else(|| AppError::Config(format!("no {} in the config file to move", key)))?;
+        store_config_secret(key, &value)?;
+        self.use_keyring = true;
+        self.file_secrets.remove(key);
+        if self.secret_source(key) != Some(SecretSource::Env) {
+            *self.secret_mut(key) = Some(value);
+            self.secret_sources.insert(key, SecretSource::Keyring);
+        }
+        self.save()
+    }
+
+    /// Route for a service's requests: its entry in `proxy_overrides`, else
+    /// `socks5_proxy`, else `http_proxy`
+    pub fn proxy_for(&self, service: ProxyService) -> ProxyRoute {
+        let proxy = match self.proxy_overrides.get(service.key()) {
+            Some(value) if value.trim().eq_ignore_ascii_case("direct") => {
+                return ProxyRoute::Direct
+            }
+            Some(value) => Some(value),
+            None => self.socks5_proxy.as_ref().or(self.http_proxy.as_ref()),
+        };
+        match proxy.map(|url| url.trim()).filter(|url| !url.is_empty()) {
+            Some(url) => ProxyRoute::Proxy(url.to_string()),
+            None => ProxyRoute::System,
+        }
+    }
+
+    /// Refuse unknown services and malformed proxy URLs up front, so traffic
+    /// meant for a proxy never goes out directly
+    fn check_proxies(&self) -> Result<()> {
+        for name in self.proxy_overrides.keys() {
+            if !ProxyService::ALL.iter().any(|service| service.key() == name) {
+                let names: Vec<&str> = ProxyService::ALL.iter().map(|s| s.key()).collect();
+                return Err(AppError::Config(format!(
+                    "unknown service \"{}\" in proxy_overrides, expected one of: {}",
+                    name,
+                    names.join(", ")
+                )));
+            }
+        }
+        for service in ProxyService::ALL {
+            if let ProxyRoute::Proxy(url) = self.proxy_for(service) {
+                reqwest::Proxy::all(&url)
+                    .map_err(|e| AppError::Config(format!("invalid proxy {}: {}", url, e)))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn check_quiet_hours(&self) -> Result<()> {
+        let Some(quiet) = &self.quiet_hours else {
+            return Ok(());
+        };
+        for (key, value) in [("start", &quiet.start), ("end", &quiet.end)] {
+            if parse_clock(value).is_none() {
+                return Err(AppError::Config(format!(
+                    "quiet_hours {} \"{}\" isn't a time like 22:00",
+                    key, value
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// What keys 1-9 do, by default workspaces once any are saved
+    pub fn number_keys(&self) -> NumberKeys {
+        self.number_keys.unwrap_or(if self.workspaces.is_empty() {
+            NumberKeys::Feeds
+        } else {
+            NumberKeys::Workspaces
+        })
+    }
+
+    /// Whether it is now within the quiet hours, if any are set
+    pub fn is_quiet_now(&self) -> bool {
+        self.quiet_hours
+            .as_ref()
+            .is_some_and(|quiet| quiet.contains(chrono::Local::now().time()))
+    }
+
+    /// A setting's value as shown on the settings screen; secrets show only
+    /// their last characters and where they come from
+    pub fn setting_value(&self, setting: Setting) -> String {
+        let optional = |value: &Option<String>| value.clone().unwrap_or_default();
+        match setting {
+            Setting::ClaudeApiKey | Setting::RaindropToken | Setting::HypothesisToken => {
+                let key = setting.key();
+                let value = match setting {
+                    Setting::ClaudeApiKey => &self.claude_api_key,
+                    Setting::RaindropToken => &self.raindrop_token,
+                    _ => &self.hypothesis_token,
+                };
+                let Some(value) = value else {
+                    return "(not set)".to_string();
+                };
+                let shown = value.chars().count().saturating_sub(4);
+                let tail: String = value.chars().skip(shown).collect();
+                let source = self.secret_source(key).map(|s| s.label()).unwrap_or_default();
+                format!("••••{} ({})", tail, source)
+            }
+            Setting::RefreshInterval => self.refresh_interval_minutes.to_string(),
+            Setting::TrashRetention => self.trash_retention_days.to_string(),
+            Setting::ArchiveBackfillPages => self.archive_backfill_pages.to_string(),
+            Setting::ListWidth => self.list_width.to_string(),
+            Setting::ContentHeight => self.content_height.to_string(),
+            Setting::GroupByDate => self.group_by_date.to_string(),
+            Setting::ListReadingTime => self.list_reading_time.to_string(),
+            Setting::ListDensity => enum_name(&self.list_density),
+            Setting::EnterAction => enum_name(&self.enter_action),
+            Setting::HideRead => enum_name(&self.hide_read),
+            Setting::NumberKeys => self.number_keys.map(|k| enum_name(&k)).unwrap_or_default(),
+            Setting::TerminalTitle => self.terminal_title.to_string(),
+            Setting::RestoreSession => self.restore_session.to_string(),
+            Setting::TranslateTo => self.translate_to.clone(),
+            Setting::ReadingLevel => enum_name(&self.reading_level),
+            Setting::RaindropCollection => self.raindrop_collection.clone(),
+            Setting::RaindropSuggestTags => self.raindrop_suggest_tags.to_string(),
+            Setting::NotesDir => optional(&self.notes_dir),
+            Setting::SummaryStyle => enum_name(&self.summary.style),
+            Setting::SummaryLanguage => optional(&self.summary.language),
+            Setting::SummaryMonthlyBudget => {
+                self.summary.monthly_budget_usd.map(|b| b.to_string()).unwrap_or_default()
+            }
+            Setting::SummaryKeyFacts => self.summary.key_facts.to_string(),
+            Setting::DesktopNotifications => self.notifications.desktop.to_string(),
+            Setting::ToastNotifications => self.notifications.toast.to_string(),
+        }
+    }
+
+    /// Validate and change a setting, then write config.toml. An empty
+    /// value unsets optional settings.
+    pub fn set_setting(&mut self, setting: Setting, value: &str) -> Result<()> {
+        let value = value.trim();
+        let optional = || (!value.is_empty()).then(|| value.to_string());
+        let required = || {
+            if value.is_empty() {
+                Err(AppError::Config(format!("{} can't be empty", setting.key())))
+            } else {
+                Ok(value.to_string())
+            }
+        };
+        match setting {
+            Setting::ClaudeApiKey | Setting::RaindropToken | Setting::HypothesisToken => {
+                return self.set_secret(setting.key(), value);
+            }
+            Setting::RefreshInterval => self.refresh_interval_minutes = parse_setting(value)?,
+            Setting::TrashRetention => self.trash_retention_days = parse_setting(value)?,
+            Setting::ArchiveBackfillPages => self.archive_backfill_pages = parse_setting(value)?,
+            Setting::ListWidth => {
+                let width: u16 = parse_setting(value)?;
+                if !(10..=90).contains(&width) {
+                    return Err(AppError::Config("list_width must be 10-90".to_string()));
+                }
+                self.list_width = width;
+            }
+            Setting::ContentHeight => {
+                let height: u16 = parse_setting(value)?;
+                if !(10..=90).contains(&height) {
+                    return Err(AppError::Config("content_height must be 10-90".to_string()));
+                }
+                self.content_height = height;
+            }
+            Setting::GroupByDate => self.group_by_date = parse_setting(value)?,
+            Setting::ListReadingTime => self.list_reading_time = parse_setting(value)?,
+            Setting::ListDensity => self.list_density = parse_enum(value)?,
+            Setting::EnterAction => self.enter_action = parse_enum(value)?,
+            Setting::HideRead => self.hide_read = parse_enum(value)?,
+            Setting::NumberKeys => {
+                self.number_keys = optional().map(|v| parse_enum(&v)).transpose()?;
+            }
+            Setting::TerminalTitle => self.terminal_title = parse_setting(value)?,
+            Setting::RestoreSession => self.restore_session = parse_setting(value)?,
+            Setting::TranslateTo => self.translate_to = required()?,
+            Setting::ReadingLevel => self.reading_level = parse_enum(value)?,
+            Setting::RaindropCollection => self.raindrop_collection = required()?,
+            Setting::RaindropSuggestTags => self.raindrop_suggest_tags = parse_setting(value)?,
+            Setting::NotesDir => self.notes_dir = optional(),
+            Setting::SummaryStyle => self.summary.style = parse_enum(value)?,
+            Setting::SummaryLanguage => self.summary.language = optional(),
+            Setting::SummaryMonthlyBudget => {
+                self.summary.monthly_budget_usd = match optional() {
+                    Some(value) => match parse_setting::<f64>(&value)? {
+                        budget if budget >= 0.0 => Some(budget),
+                        _ => {
+                            return Err(AppError::Config("the budget can't be negative".to_string()))
+                        }
+                    },
+                    None => None,
+                };
+            }
+            Setting::SummaryKeyFacts => self.summary.key_facts = parse_setting(value)?,
+            Setting::DesktopNotifications => self.notifications.desktop = parse_setting(value)?,
+            Setting::ToastNotifications => self.notifications.toast = parse_setting(value)?,
+        }
+        self.save()
+    }
+
+    /// Set a secret where it is kept: the keyring when `use_keyring` is on,
+    /// else the config file. Clearing it only removes it from the file.
+    fn set_secret(&mut self, key: &'static str, value: &str) -> Result<()> {
+        if self.secret_source(key) == Some(SecretSource::Env) {
+            let var = SECRETS.iter().find(|(k, _)| *k == key).map(|(_, var)| *var);
+            return Err(AppError::Config(format!(
+                "{} comes from {}; change it there",
+                key,
+                var.unwrap_or_default()
+            )));
+        }
+        if value.is_empty() {
+            self.file_secrets.remove(key);
+            if self.secret_source(key) == Some(SecretSource::File) {
+                *self.secret_mut(key) = None;
+                self.secret_sources.remove(key);
+            }
+            return self.save();
+        }
+        if self.use_keyring {
+            return self.store_secret_in_keyring(key, Some(value.to_string()));
+        }
+        self.file_secrets.insert(key, value.to_string());
+        *self.secret_mut(key) = Some(value.to_string());
+        self.secret_sources.insert(key, SecretSource::File);
+        self.save()
+    }
+
     pub fn config_path() -> PathBuf {
         dirs::config_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("speedy-reader")
             .join("config.toml")
     }
+
+    /// Notes directory with a leading `~/` expanded
+    pub fn notes_path(&self) -> Option<PathBuf> {
+        self.notes_dir.as_deref().map(expand_home)
+    }
+
+    /// Where tracing output goes
+    pub fn log_path(&self) -> PathBuf {
+        match self.log_file.as_deref() {
+            Some(path) => expand_home(path),
+            None => default_log_path(),
+        }
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
 }
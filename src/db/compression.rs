@@ -0,0 +1,59 @@
+use std::io::Read;
+
+use flate2::read::ZlibDecoder;
+use rusqlite::types::{Value, ValueRef};
+use rusqlite::Row;
+
+// Article HTML shorter than this is stored as it is, as compressing it saves
+// next to nothing
+pub(super) const COMPRESS_MIN_BYTES: usize = 1024;
+
+/// Start of every zstd frame; blobs without it are zlib, as stored before
+pub(super) const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+// zstd's default level: close to its best ratio for HTML at a fraction of
+// the time
+const ZSTD_LEVEL: i32 = 3;
+
+/// Article HTML as stored: a zstd-compressed blob once it is long enough to
+/// be worth it, else the text itself
+pub(super) fn pack_content(content: Option<&str>) -> Value {
+    match content {
+        None => Value::Null,
+        Some(text) if text.len() < COMPRESS_MIN_BYTES => Value::Text(text.to_string()),
+        // Reading from a slice can't fail, but keep the text if it somehow does
+        Some(text) => match zstd::encode_all(text.as_bytes(), ZSTD_LEVEL) {
+            Ok(frame) => Value::Blob(frame),
+            Err(_) => Value::Text(text.to_string()),
+        },
+    }
+}
+
+/// Article HTML stored by `pack_content`: a zstd frame, a zlib blob from
+/// before zstd, or plain text
+pub(super) fn content_column(row: &Row, index: usize) -> rusqlite::Result<Option<String>> {
+    let value = row.get_ref(index)?;
+    let conversion_failed = |e: Box<dyn std::error::Error + Send + Sync>| {
+        rusqlite::Error::FromSqlConversionFailure(index, value.data_type(), e)
+    };
+    match value {
+        ValueRef::Null => Ok(None),
+        ValueRef::Blob(bytes) if bytes.starts_with(&ZSTD_MAGIC) => {
+            let decoded = zstd::decode_all(bytes).map_err(|e| conversion_failed(Box::new(e)))?;
+            String::from_utf8(decoded)
+                .map(Some)
+                .map_err(|e| conversion_failed(Box::new(e)))
+        }
+        ValueRef::Blob(bytes) => {
+            let mut text = String::new();
+            ZlibDecoder::new(bytes)
+                .read_to_string(&mut text)
+                .map_err(|e| conversion_failed(Box::new(e)))?;
+            Ok(Some(text))
+        }
+        _ => value
+            .as_str()
+            .map(|text| Some(text.to_string()))
+            .map_err(|e| conversion_failed(Box::new(e))),
+    }
+}
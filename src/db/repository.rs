@@ -1,12 +1,39 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
 use chrono::{DateTime, Utc};
-use rusqlite::{params, OptionalExtension, Row};
+use rusqlite::backup::Progress;
+use rusqlite::{params, DatabaseName, OpenFlags, OptionalExtension, Row};
 use tokio_rusqlite::Connection;
 
-use crate::error::Result;
-use crate::models::{Article, Feed, NewArticle, NewFeed, Summary};
+use crate::config::CleanupConfig;
+use crate::error::{AppError, Result};
+use crate::models::{
+    content_hash, count_words, extract_deadline, extract_links, is_similar_title, normalize_url,
+    parse_tags, AiSpend, Article, Backup, BackupImport, ChatMessage, ChatRole, Discussion,
+    DiscussionSite, Feed, FeedAuth, FeedAuthKind, FeedRules, FeedUsage, FetchBackoff, FetchUsage,
+    KeyFacts, NewArticle, NewFeed, RefreshUsage, Summary, SummarySource, SummaryVariant, SyncItem,
+    TokenUsage,
+};
+
+use super::compression::{content_column, pack_content, COMPRESS_MIN_BYTES, ZSTD_MAGIC};
+use super::schema::{MIGRATIONS, NORMALIZED_URLS_NEEDED, SCHEMA};
+
+// Title matches are only looked for among articles fetched this recently
+const DEDUP_WINDOW_DAYS: i64 = 3;
+
+// Articles read per query when loading the list; between pages the
+// connection is free for background writes (refresh, sync)
+const ARTICLE_PAGE_SIZE: usize = 1000;
 
-use super::schema::SCHEMA;
+/// Sort key of the last article of a page, where the next page starts
+struct ArticleCursor {
+    published_at: String,
+    fetched_at: String,
+    id: i64,
+}
 
+#[derive(Clone)]
 pub struct Repository {
     conn: Connection,
 }
@@ -18,7 +45,34 @@ impl Repository {
         conn.call(|conn| {
             // Set busy timeout to 5 seconds to handle concurrent access
             conn.busy_timeout(std::time::Duration::from_secs(5))?;
+            // WAL lets a headless refresh write while the TUI reads, and the
+            // other way round, instead of failing with "database is locked"
+            let mode: String =
+                conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))?;
+            if !mode.eq_ignore_ascii_case("wal") {
+                tracing::warn!("Database stays in {} journal mode, WAL is unavailable", mode);
+            }
+            conn.pragma_update(None, "synchronous", "NORMAL")?;
             conn.execute_batch(SCHEMA)?;
+
+            // Apply any migrations newer than the database's version, each
+            // with its version bump in one transaction so an interrupted
+            // migration is neither half applied nor run again
+            let version: usize =
+                conn.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))? as usize;
+            for (i, migration) in MIGRATIONS.iter().enumerate().skip(version) {
+                // Duplicates are matched on normalized links, which older
+                // databases don't have yet
+                if i + 1 == NORMALIZED_URLS_NEEDED {
+                    backfill_normalized_urls(conn)?;
+                }
+                let tx = conn.transaction()?;
+                tx.execute_batch(migration)?;
+                tx.execute_batch(&format!("PRAGMA user_version = {}", i + 1))?;
+                tx.commit()?;
+            }
+            backfill_normalized_urls(conn)?;
+            backfill_word_counts(conn)?;
             Ok(())
         })
         .await?;
@@ -26,6 +80,35 @@ impl Repository {
         Ok(Self { conn })
     }
 
+    /// Open an existing database as it is, without creating it when missing
+    /// or migrating it, for looking it over (writable only because checking
+    /// the full-text index needs that)
+    pub async fn open_existing(db_path: &str) -> Result<Self> {
+        let conn = Connection::open_with_flags(
+            db_path,
+            OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .await?;
+        conn.call(|conn| {
+            conn.busy_timeout(std::time::Duration::from_secs(5))?;
+            Ok(())
+        })
+        .await?;
+        Ok(Self { conn })
+    }
+
+    /// Migrations not applied to the database yet; they run the next time
+    /// it's opened with `new`
+    pub async fn pending_migrations(&self) -> Result<usize> {
+        let version = self
+            .conn
+            .call(|conn| {
+                Ok(conn.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))? as usize)
+            })
+            .await?;
+        Ok(MIGRATIONS.len().saturating_sub(version))
+    }
+
     // Feed operations
 
     pub async fn insert_feed(&self, feed: NewFeed) -> Result<i64> {
@@ -33,8 +116,8 @@ impl Repository {
             .conn
             .call(move |conn| {
                 conn.execute(
-                    "INSERT INTO feeds (title, url, site_url, description) VALUES (?1, ?2, ?3, ?4)",
-                    params![feed.title, feed.url, feed.site_url, feed.description],
+                    "INSERT INTO feeds (title, url, site_url, description, category) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![feed.title, feed.url, feed.site_url, feed.description, feed.category],
                 )?;
                 Ok(conn.last_insert_rowid())
             })
@@ -47,7 +130,13 @@ impl Repository {
             .conn
             .call(|conn| {
                 let mut stmt = conn.prepare(
-                    "SELECT id, title, url, site_url, description, last_fetched, created_at, updated_at FROM feeds ORDER BY title",
+                    "SELECT f.id, f.title, f.url, f.site_url, f.description, f.last_fetched, f.created_at, f.updated_at,
+                            c.kind, c.name, f.auto_star, f.auto_archive, f.skip_unread_filter, f.category,
+                            f.auto_summarize, f.collapse_similar, f.fetch_failures, f.retry_at,
+                            f.fetch_error
+                     FROM feeds f
+                     LEFT JOIN feed_credentials c ON c.feed_id = f.id
+                     ORDER BY f.title",
                 )?;
                 let feeds = stmt
                     .query_map([], |row| Ok(feed_from_row(row)))?
@@ -71,9 +160,30 @@ impl Repository {
         Ok(())
     }
 
+    /// Record a feed's failed fetches, or clear them after a success
+    pub async fn set_feed_backoff(&self, id: i64, backoff: FetchBackoff) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    "UPDATE feeds SET fetch_failures = ?2, retry_at = ?3, fetch_error = ?4 WHERE id = ?1",
+                    params![
+                        id,
+                        backoff.failures,
+                        backoff.retry_at.map(|dt| dt.to_rfc3339()),
+                        backoff.error
+                    ],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
     pub async fn delete_feed(&self, id: i64) -> Result<()> {
         self.conn
             .call(move |conn| {
+                conn.execute("DELETE FROM feed_credentials WHERE feed_id = ?1", params![id])?;
+                conn.execute("DELETE FROM feed_icons WHERE feed_id = ?1", params![id])?;
                 conn.execute("DELETE FROM feeds WHERE id = ?1", params![id])?;
                 Ok(())
             })
@@ -81,101 +191,318 @@ impl Repository {
         Ok(())
     }
 
-    // Article operations
-
-    pub async fn upsert_article(&self, article: NewArticle) -> Result<i64> {
-        let id = self
+    /// Whether the feed has articles the user kept: starred, queued or saved
+    /// to Raindrop
+    pub async fn feed_has_kept_articles(&self, feed_id: i64) -> Result<bool> {
+        let kept = self
             .conn
             .call(move |conn| {
-                // Check if this article was previously deleted
-                let was_deleted: bool = conn.query_row(
-                    "SELECT 1 FROM deleted_articles WHERE feed_id = ?1 AND guid = ?2",
-                    params![article.feed_id, article.guid],
-                    |_| Ok(true),
-                ).unwrap_or(false);
-
-                if was_deleted {
-                    return Ok(0); // Skip deleted articles
-                }
+                let count: i64 = conn.query_row(
+                    r#"SELECT COUNT(*) FROM articles
+                       WHERE feed_id = ?1
+                         AND (is_starred = 1 OR queued_at IS NOT NULL
+                              OR id IN (SELECT article_id FROM saved_to_raindrop))"#,
+                    params![feed_id],
+                    |row| row.get(0),
+                )?;
+                Ok(count > 0)
+            })
+            .await?;
+        Ok(kept)
+    }
 
+    pub async fn set_feed_rules(&self, feed_id: i64, rules: FeedRules) -> Result<()> {
+        self.conn
+            .call(move |conn| {
                 conn.execute(
-                    r#"INSERT INTO articles (feed_id, guid, title, url, author, content, content_text, published_at)
-                       VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
-                       ON CONFLICT(feed_id, guid) DO UPDATE SET
-                           title = excluded.title,
-                           url = excluded.url,
-                           author = excluded.author,
-                           content = excluded.content,
-                           content_text = excluded.content_text,
-                           published_at = excluded.published_at"#,
+                    r#"UPDATE feeds SET auto_star = ?2, auto_archive = ?3, skip_unread_filter = ?4,
+                                        auto_summarize = ?5, collapse_similar = ?6,
+                                        updated_at = datetime('now')
+                       WHERE id = ?1"#,
                     params![
-                        article.feed_id,
-                        article.guid,
-                        article.title,
-                        article.url,
-                        article.author,
-                        article.content,
-                        article.content_text,
-                        article.published_at.map(|dt| dt.to_rfc3339()),
+                        feed_id,
+                        rules.auto_star,
+                        rules.auto_archive,
+                        rules.skip_unread_filter,
+                        rules.auto_summarize,
+                        rules.collapse_similar
                     ],
                 )?;
-                Ok(conn.last_insert_rowid())
+                Ok(())
             })
             .await?;
-        Ok(id)
+        Ok(())
     }
 
-    pub async fn get_all_articles_sorted(&self) -> Result<Vec<Article>> {
-        let articles = self
+    pub async fn set_feed_auth(&self, feed_id: i64, auth: FeedAuth) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO feed_credentials (feed_id, kind, name) VALUES (?1, ?2, ?3)",
+                    params![feed_id, auth.kind.as_str(), auth.name],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Stored favicons (PNG) by feed; feeds whose site has none are left out
+    pub async fn get_feed_icons(&self) -> Result<Vec<(i64, Vec<u8>)>> {
+        let icons = self
+            .conn
+            .call(|conn| {
+                let mut stmt =
+                    conn.prepare("SELECT feed_id, icon FROM feed_icons WHERE icon IS NOT NULL")?;
+                let icons = stmt
+                    .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(icons)
+            })
+            .await?;
+        Ok(icons)
+    }
+
+    /// Feeds whose favicon hasn't been looked for, with the page to look on.
+    /// Sites that had none are tried again after a month.
+    pub async fn feeds_without_icon(&self) -> Result<Vec<(i64, String)>> {
+        let feeds = self
             .conn
             .call(|conn| {
                 let mut stmt = conn.prepare(
-                    r#"SELECT a.id, a.feed_id, a.guid, a.title, a.url, a.author, a.content,
-                              a.content_text, a.published_at, a.fetched_at,
-                              f.title as feed_title
-                       FROM articles a
-                       JOIN feeds f ON a.feed_id = f.id
-                       ORDER BY a.published_at DESC NULLS LAST, a.fetched_at DESC"#,
+                    r#"SELECT f.id, coalesce(f.site_url, f.url) FROM feeds f
+                       LEFT JOIN feed_icons i ON i.feed_id = f.id
+                       WHERE i.feed_id IS NULL
+                          OR (i.icon IS NULL AND i.fetched_at < datetime('now', '-30 days'))"#,
                 )?;
-                let articles = stmt
-                    .query_map([], |row| Ok(article_from_row(row)))?
+                let feeds = stmt
+                    .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
                     .collect::<std::result::Result<Vec<_>, _>>()?;
-                Ok(articles)
+                Ok(feeds)
             })
             .await?;
-        Ok(articles)
+        Ok(feeds)
     }
 
-    pub async fn delete_article(&self, id: i64) -> Result<()> {
+    pub async fn save_feed_icon(&self, feed_id: i64, icon: Option<Vec<u8>>) -> Result<()> {
         self.conn
             .call(move |conn| {
-                // Record the article's feed_id and guid before deleting (to prevent re-adding)
                 conn.execute(
-                    r#"INSERT OR IGNORE INTO deleted_articles (feed_id, guid)
-                       SELECT feed_id, guid FROM articles WHERE id = ?1"#,
-                    params![id],
+                    "INSERT OR REPLACE INTO feed_icons (feed_id, icon, fetched_at) VALUES (?1, ?2, datetime('now'))",
+                    params![feed_id, icon],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    // Article operations
+
+    /// Insert or update an article. Returns the new row id if the article was
+    /// newly inserted, or None if it already existed (or was deleted).
+    /// A new article that another feed already carries is linked to that copy.
+    /// Entries re-published unchanged (same title and content, possibly under
+    /// a new guid or date) are left alone so they don't turn up as new.
+    pub async fn upsert_article(&self, article: NewArticle) -> Result<Option<i64>> {
+        let id = self
+            .conn
+            .call(move |conn| Ok(upsert_article_row(conn, &article)?))
+            .await?;
+        Ok(id)
+    }
+
+    /// Store a feed's fetched articles in one transaction. Returns the ids of
+    /// the new ones, in input order (None for updated and skipped articles);
+    /// an article that fails to store is logged and left out.
+    pub async fn upsert_articles_batch(
+        &self,
+        articles: Vec<NewArticle>,
+    ) -> Result<Vec<Option<i64>>> {
+        let ids = self
+            .conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let mut ids = Vec::with_capacity(articles.len());
+                for article in &articles {
+                    ids.push(upsert_article_row(&tx, article).unwrap_or_else(|e| {
+                        tracing::warn!("Failed to upsert article {}: {}", article.guid, e);
+                        None
+                    }));
+                }
+                tx.commit()?;
+                Ok(ids)
+            })
+            .await?;
+        Ok(ids)
+    }
+
+    /// Titles of the articles a refresh would store as new, worked out by
+    /// storing them in a transaction that is rolled back
+    pub async fn preview_new_articles(&self, articles: Vec<NewArticle>) -> Result<Vec<String>> {
+        let titles = self
+            .conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let mut titles = Vec::new();
+                for article in articles {
+                    if matches!(upsert_article_row(&tx, &article), Ok(Some(_))) {
+                        titles.push(article.title);
+                    }
+                }
+                tx.rollback()?;
+                Ok(titles)
+            })
+            .await?;
+        Ok(titles)
+    }
+
+    /// Every article, newest first, read a page at a time
+    pub async fn get_all_articles_sorted(&self) -> Result<Vec<Article>> {
+        let mut articles = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (page, next) = self.get_articles_page(cursor, ARTICLE_PAGE_SIZE).await?;
+            articles.extend(page);
+            match next {
+                Some(next) => cursor = Some(next),
+                None => return Ok(articles),
+            }
+        }
+    }
+
+    pub async fn get_article(&self, id: i64) -> Result<Option<Article>> {
+        let article = self
+            .conn
+            .call(move |conn| {
+                let article = conn
+                    .query_row(
+                        &format!(
+                            r#"SELECT {} FROM articles a
+                               JOIN feeds f ON a.feed_id = f.id
+                               WHERE a.id = ?1"#,
+                            ARTICLE_COLUMNS
+                        ),
+                        params![id],
+                        article_from_row,
+                    )
+                    .optional()?;
+                Ok(article)
+            })
+            .await?;
+        Ok(article)
+    }
+
+    /// Up to `limit` articles after `after` (from the newest when None), with
+    /// the cursor for the next page when there may be one
+    async fn get_articles_page(
+        &self,
+        after: Option<ArticleCursor>,
+        limit: usize,
+    ) -> Result<(Vec<Article>, Option<ArticleCursor>)> {
+        let page = self
+            .conn
+            .call(move |conn| {
+                // Keyset pagination: continue after the last sort key seen
+                let after_clause = if after.is_some() {
+                    "WHERE (COALESCE(a.published_at, ''), a.fetched_at, a.id) < (?2, ?3, ?4)"
+                } else {
+                    ""
+                };
+                let mut stmt = conn.prepare(&format!(
+                    r#"SELECT {}, COALESCE(a.published_at, ''), a.fetched_at
+                       FROM articles a
+                       JOIN feeds f ON a.feed_id = f.id
+                       {}
+                       ORDER BY COALESCE(a.published_at, '') DESC, a.fetched_at DESC, a.id DESC
+                       LIMIT ?1"#,
+                    ARTICLE_COLUMNS, after_clause
+                ))?;
+                let map_row = |row: &Row| {
+                    let article = article_from_row(row)?;
+                    let cursor = ArticleCursor {
+                        published_at: row.get(20)?,
+                        fetched_at: row.get(21)?,
+                        id: article.id,
+                    };
+                    Ok((article, cursor))
+                };
+                let rows = match &after {
+                    Some(after) => stmt
+                        .query_map(
+                            params![limit as i64, after.published_at, after.fetched_at, after.id],
+                            map_row,
+                        )?
+                        .collect::<std::result::Result<Vec<_>, _>>()?,
+                    None => stmt
+                        .query_map(params![limit as i64], map_row)?
+                        .collect::<std::result::Result<Vec<_>, _>>()?,
+                };
+                Ok(rows)
+            })
+            .await?;
+
+        let full = page.len() == limit;
+        let mut articles = Vec::with_capacity(page.len());
+        let mut cursor = None;
+        for (article, key) in page {
+            articles.push(article);
+            cursor = Some(key);
+        }
+        Ok((articles, cursor.filter(|_| full)))
+    }
+
+    /// Unread, non-trashed article count per feed (duplicates only count
+    /// once, for the feed of the canonical copy)
+    pub async fn get_unread_counts(&self) -> Result<HashMap<i64, usize>> {
+        let counts = self
+            .conn
+            .call(|conn| {
+                let mut stmt = conn.prepare(
+                    r#"SELECT a.feed_id, COUNT(*) FROM articles a
+                       WHERE a.is_read = 0 AND a.deleted_at IS NULL
+                         AND NOT EXISTS (SELECT 1 FROM articles c WHERE c.id = a.canonical_id)
+                       GROUP BY a.feed_id"#,
                 )?;
-                // Delete related data first
-                conn.execute("DELETE FROM summaries WHERE article_id = ?1", params![id])?;
+                let counts = stmt
+                    .query_map([], |row| Ok((row.get(0)?, row.get::<_, i64>(1)? as usize)))?
+                    .collect::<std::result::Result<HashMap<_, _>, _>>()?;
+                Ok(counts)
+            })
+            .await?;
+        Ok(counts)
+    }
+
+    pub async fn set_article_read(&self, id: i64, is_read: bool) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                // Reading a story reads its copies in other feeds too
                 conn.execute(
-                    "DELETE FROM saved_to_raindrop WHERE article_id = ?1",
-                    params![id],
+                    "UPDATE articles SET is_read = ?2 WHERE id = ?1 OR canonical_id = ?1",
+                    params![id, is_read],
                 )?;
-                // Delete the article
-                conn.execute("DELETE FROM articles WHERE id = ?1", params![id])?;
+                // Feeds with auto-archive send read articles straight to the trash
+                if is_read {
+                    conn.execute(
+                        r#"UPDATE articles SET deleted_at = datetime('now')
+                           WHERE (id = ?1 OR canonical_id = ?1) AND deleted_at IS NULL
+                             AND feed_id IN (SELECT id FROM feeds WHERE auto_archive = 1)"#,
+                        params![id],
+                    )?;
+                }
                 Ok(())
             })
             .await?;
         Ok(())
     }
 
-    pub async fn undelete_article(&self, feed_id: i64, guid: &str) -> Result<()> {
-        let guid = guid.to_string();
+    /// Store the full article text fetched from its page (indexed for full-text search)
+    pub async fn save_full_text(&self, id: i64, text: String) -> Result<()> {
         self.conn
             .call(move |conn| {
                 conn.execute(
-                    "DELETE FROM deleted_articles WHERE feed_id = ?1 AND guid = ?2",
-                    params![feed_id, guid],
+                    "UPDATE articles SET full_text = ?2 WHERE id = ?1",
+                    params![id, text],
                 )?;
                 Ok(())
             })
@@ -183,112 +510,251 @@ impl Repository {
         Ok(())
     }
 
-    pub async fn delete_old_articles(&self, days: i64) -> Result<usize> {
-        let deleted = self
+    /// Full article text stored by an earlier fetch, if any
+    pub async fn get_full_text(&self, id: i64) -> Result<Option<String>> {
+        let text = self
             .conn
             .call(move |conn| {
-                // Delete summaries and raindrop entries for old articles first
-                conn.execute(
-                    r#"DELETE FROM summaries WHERE article_id IN (
-                        SELECT id FROM articles
-                        WHERE published_at < datetime('now', '-' || ?1 || ' days')
-                           OR (published_at IS NULL AND fetched_at < datetime('now', '-' || ?1 || ' days'))
-                    )"#,
-                    params![days],
-                )?;
+                let text = conn
+                    .query_row(
+                        "SELECT full_text FROM articles WHERE id = ?1",
+                        params![id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                Ok(text.flatten())
+            })
+            .await?;
+        Ok(text)
+    }
+
+    /// Remember the lead image found on an article's page
+    pub async fn save_image_url(&self, id: i64, image_url: String) -> Result<()> {
+        self.conn
+            .call(move |conn| {
                 conn.execute(
-                    r#"DELETE FROM saved_to_raindrop WHERE article_id IN (
-                        SELECT id FROM articles
-                        WHERE published_at < datetime('now', '-' || ?1 || ' days')
-                           OR (published_at IS NULL AND fetched_at < datetime('now', '-' || ?1 || ' days'))
-                    )"#,
-                    params![days],
-                )?;
-                // Delete old articles (using published_at, fallback to fetched_at if null)
-                let deleted = conn.execute(
-                    r#"DELETE FROM articles
-                       WHERE published_at < datetime('now', '-' || ?1 || ' days')
-                          OR (published_at IS NULL AND fetched_at < datetime('now', '-' || ?1 || ' days'))"#,
-                    params![days],
+                    "UPDATE articles SET image_url = ?2 WHERE id = ?1",
+                    params![id, image_url],
                 )?;
-                Ok(deleted)
+                Ok(())
             })
             .await?;
-        Ok(deleted)
+        Ok(())
     }
 
-    pub async fn compact_database(&self, days: i64) -> Result<usize> {
-        let result = self
+    /// Keyword search over titles, feed content and full-text snapshots, best match first.
+    /// A query wrapped in double quotes matches as an exact phrase.
+    pub async fn search_full_text(&self, query: &str, limit: usize) -> Result<Vec<i64>> {
+        let query = fts_query(query);
+        let ids = self
             .conn
             .call(move |conn| {
-                // Delete old articles first
-                conn.execute(
-                    r#"DELETE FROM summaries WHERE article_id IN (
-                        SELECT id FROM articles
-                        WHERE published_at < datetime('now', '-' || ?1 || ' days')
-                           OR (published_at IS NULL AND fetched_at < datetime('now', '-' || ?1 || ' days'))
-                    )"#,
-                    params![days],
+                let mut stmt = conn.prepare(
+                    r#"SELECT a.id
+                       FROM articles_fts
+                       JOIN articles a ON a.id = articles_fts.rowid
+                       WHERE articles_fts MATCH ?1 AND a.deleted_at IS NULL
+                       ORDER BY rank
+                       LIMIT ?2"#,
                 )?;
-                conn.execute(
-                    r#"DELETE FROM saved_to_raindrop WHERE article_id IN (
-                        SELECT id FROM articles
-                        WHERE published_at < datetime('now', '-' || ?1 || ' days')
-                           OR (published_at IS NULL AND fetched_at < datetime('now', '-' || ?1 || ' days'))
-                    )"#,
-                    params![days],
+                let ids = stmt
+                    .query_map(params![query, limit as i64], |row| row.get(0))?
+                    .collect::<std::result::Result<Vec<i64>, _>>()?;
+                Ok(ids)
+            })
+            .await?;
+        Ok(ids)
+    }
+
+    /// Articles whose titles share at least two keywords with `title`, best
+    /// match first (may include the article itself)
+    pub async fn find_related(&self, title: &str, limit: usize) -> Result<Vec<i64>> {
+        let Some(query) = related_query(title) else {
+            return Ok(Vec::new());
+        };
+        let ids = self
+            .conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"SELECT a.id
+                       FROM articles_fts
+                       JOIN articles a ON a.id = articles_fts.rowid
+                       WHERE articles_fts MATCH ?1 AND a.deleted_at IS NULL
+                       ORDER BY rank
+                       LIMIT ?2"#,
                 )?;
-                let old_deleted = conn.execute(
-                    r#"DELETE FROM articles
-                       WHERE published_at < datetime('now', '-' || ?1 || ' days')
-                          OR (published_at IS NULL AND fetched_at < datetime('now', '-' || ?1 || ' days'))"#,
-                    params![days],
+                let ids = stmt
+                    .query_map(params![query, limit as i64], |row| row.get(0))?
+                    .collect::<std::result::Result<Vec<i64>, _>>()?;
+                Ok(ids)
+            })
+            .await?;
+        Ok(ids)
+    }
+
+    /// Stored articles the article links to, and stored articles linking to
+    /// it, matched by normalized URL. Copies of the article itself are left out.
+    pub async fn find_article_links(&self, article_id: i64) -> Result<(Vec<i64>, Vec<i64>)> {
+        let links = self
+            .conn
+            .call(move |conn| {
+                let Some((url, content)) = conn
+                    .query_row(
+                        "SELECT url, content, content_text FROM articles WHERE id = ?1",
+                        params![article_id],
+                        |row| Ok((row.get::<_, String>(0)?, linkable_text(row, 1)?)),
+                    )
+                    .optional()?
+                else {
+                    return Ok((Vec::new(), Vec::new()));
+                };
+                let own_url = normalize_url(&url);
+
+                let mut references = Vec::new();
+                let mut targets: Vec<String> = extract_links(&url, &content)
+                    .iter()
+                    .map(|link| normalize_url(link))
+                    .filter(|link| *link != own_url)
+                    .collect();
+                targets.sort();
+                targets.dedup();
+                let mut stmt = conn.prepare(
+                    r#"SELECT id FROM articles
+                       WHERE normalized_url = ?1 AND deleted_at IS NULL
+                       ORDER BY canonical_id IS NOT NULL, id LIMIT 1"#,
                 )?;
+                for target in &targets {
+                    let id: Option<i64> =
+                        stmt.query_row(params![target], |row| row.get(0)).optional()?;
+                    references.extend(id);
+                }
 
-                // Clean up old deleted_articles tracking entries
-                conn.execute(
-                    "DELETE FROM deleted_articles WHERE deleted_at < datetime('now', '-' || ?1 || ' days')",
-                    params![days],
+                // Candidates mention the URL without its query, which HTML
+                // may have escaped; their links are then compared exactly.
+                // Compressed HTML doesn't match, but its text lists the links.
+                let pattern = own_url.split('?').next().unwrap_or_default();
+                let pattern = format!(
+                    "%{}%",
+                    pattern.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+                );
+                let mut stmt = conn.prepare(
+                    r#"SELECT id, url, content, content_text
+                       FROM articles
+                       WHERE deleted_at IS NULL AND canonical_id IS NULL AND normalized_url != ?1
+                         AND (content LIKE ?2 ESCAPE '\' OR content_text LIKE ?2 ESCAPE '\')
+                       ORDER BY published_at DESC"#,
                 )?;
+                let candidates = stmt
+                    .query_map(params![own_url, pattern], |row| {
+                        let id: i64 = row.get(0)?;
+                        Ok((id, row.get::<_, String>(1)?, linkable_text(row, 2)?))
+                    })?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                let referenced_by = candidates
+                    .into_iter()
+                    .filter(|(_, url, content)| {
+                        extract_links(url, content)
+                            .iter()
+                            .any(|link| normalize_url(link) == own_url)
+                    })
+                    .map(|(id, _, _)| id)
+                    .collect();
 
-                // Vacuum to reclaim space
-                conn.execute("VACUUM", [])?;
+                Ok((references, referenced_by))
+            })
+            .await?;
+        Ok(links)
+    }
+
+    /// Today's starred article to revisit: the one already picked today if it
+    /// is still starred and unread, else (when none was picked yet) the
+    /// unread starred article at least `min_age_days` old resurfaced least
+    /// recently, oldest first. Returns the article and whether it is a new pick.
+    pub async fn pick_revisit(&self, min_age_days: u32) -> Result<Option<(i64, bool)>> {
+        let pick = self
+            .conn
+            .call(move |conn| {
+                let today: Option<(i64, bool)> = conn
+                    .query_row(
+                        r#"SELECT id, is_starred = 1 AND is_read = 0 AND deleted_at IS NULL
+                           FROM articles
+                           WHERE date(revisited_at, 'localtime') = date('now', 'localtime')
+                           LIMIT 1"#,
+                        [],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?;
+                if let Some((id, still_due)) = today {
+                    return Ok(still_due.then_some((id, false)));
+                }
 
-                Ok(old_deleted)
+                let id: Option<i64> = conn
+                    .query_row(
+                        r#"SELECT id FROM articles
+                           WHERE is_starred = 1 AND is_read = 0 AND deleted_at IS NULL
+                             AND coalesce(published_at, fetched_at)
+                                 < datetime('now', '-' || ?1 || ' days')
+                           ORDER BY revisited_at IS NOT NULL, revisited_at,
+                                    coalesce(published_at, fetched_at)
+                           LIMIT 1"#,
+                        params![min_age_days],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                if let Some(id) = id {
+                    conn.execute(
+                        "UPDATE articles SET revisited_at = datetime('now') WHERE id = ?1",
+                        params![id],
+                    )?;
+                }
+                Ok(id.map(|id| (id, true)))
             })
             .await?;
-        Ok(result)
+        Ok(pick)
     }
 
-    // Summary operations
+    /// Replace an article's tags
+    pub async fn set_article_tags(&self, id: i64, tags: Vec<String>) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                tx.execute("DELETE FROM article_tags WHERE article_id = ?1", params![id])?;
+                for tag in &tags {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO article_tags (article_id, tag) VALUES (?1, ?2)",
+                        params![id, tag],
+                    )?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
 
-    pub async fn get_summary(&self, article_id: i64) -> Result<Option<Summary>> {
-        let summary = self
+    /// Every tag in use, most used first (for autocomplete and filtering)
+    pub async fn get_all_tags(&self) -> Result<Vec<String>> {
+        let tags = self
             .conn
-            .call(move |conn| {
+            .call(|conn| {
                 let mut stmt = conn.prepare(
-                    "SELECT id, article_id, content, model_version, generated_at FROM summaries WHERE article_id = ?1",
+                    "SELECT tag FROM article_tags GROUP BY tag ORDER BY COUNT(*) DESC, tag",
                 )?;
-                let summary = stmt
-                    .query_row(params![article_id], |row| Ok(summary_from_row(row)))
-                    .optional()?;
-                Ok(summary)
+                let tags = stmt
+                    .query_map([], |row| row.get(0))?
+                    .collect::<std::result::Result<Vec<String>, _>>()?;
+                Ok(tags)
             })
             .await?;
-        Ok(summary)
+        Ok(tags)
     }
 
-    pub async fn save_summary(&self, article_id: i64, content: String, model: String) -> Result<()> {
+    pub async fn set_article_starred(&self, id: i64, is_starred: bool) -> Result<()> {
         self.conn
             .call(move |conn| {
                 conn.execute(
-                    r#"INSERT INTO summaries (article_id, content, model_version)
-                       VALUES (?1, ?2, ?3)
-                       ON CONFLICT(article_id) DO UPDATE SET
-                           content = excluded.content,
-                           model_version = excluded.model_version,
-                           generated_at = datetime('now')"#,
-                    params![article_id, content, model],
+                    "UPDATE articles SET is_starred = ?2 WHERE id = ?1",
+                    params![id, is_starred],
                 )?;
                 Ok(())
             })
@@ -296,20 +762,17 @@ impl Repository {
         Ok(())
     }
 
-    // Raindrop tracking
-
-    pub async fn mark_saved_to_raindrop(
+    /// Add an article to the reading queue (at `queued_at`) or take it out (None)
+    pub async fn set_article_queued(
         &self,
-        article_id: i64,
-        raindrop_id: i64,
-        tags: Vec<String>,
+        id: i64,
+        queued_at: Option<DateTime<Utc>>,
     ) -> Result<()> {
-        let tags_json = serde_json::to_string(&tags)?;
         self.conn
             .call(move |conn| {
                 conn.execute(
-                    "INSERT OR REPLACE INTO saved_to_raindrop (article_id, raindrop_id, tags) VALUES (?1, ?2, ?3)",
-                    params![article_id, raindrop_id, tags_json],
+                    "UPDATE articles SET queued_at = ?2 WHERE id = ?1",
+                    params![id, queued_at.map(|dt| dt.to_rfc3339())],
                 )?;
                 Ok(())
             })
@@ -317,33 +780,1632 @@ impl Repository {
         Ok(())
     }
 
-    pub async fn is_saved_to_raindrop(&self, article_id: i64) -> Result<bool> {
-        let exists = self
-            .conn
+    pub async fn delete_article(&self, id: i64) -> Result<()> {
+        self.conn
             .call(move |conn| {
-                let count: i64 = conn.query_row(
-                    "SELECT COUNT(*) FROM saved_to_raindrop WHERE article_id = ?1",
-                    params![article_id],
-                    |row| row.get(0),
-                )?;
-                Ok(count > 0)
+                delete_article_rows(conn, id)?;
+                Ok(())
             })
             .await?;
-        Ok(exists)
+        Ok(())
     }
-}
 
-fn parse_datetime(s: &str) -> Option<DateTime<Utc>> {
-    // Try RFC3339 first (e.g., "2026-01-11T12:34:56+00:00")
-    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
-        return Some(dt.with_timezone(&Utc));
-    }
-    // Try SQLite datetime format (e.g., "2026-01-11 12:34:56")
-    if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
-        return Some(naive.and_utc());
+    /// Apply a feed's tombstones: delete the entries the publisher removed
+    /// (unless starred) and keep them from being added again. Returns the
+    /// number of articles deleted.
+    pub async fn delete_entries_by_guid(&self, feed_id: i64, guids: Vec<String>) -> Result<usize> {
+        let deleted = self
+            .conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let mut deleted = 0;
+                for guid in &guids {
+                    let article = tx
+                        .query_row(
+                            "SELECT id, is_starred FROM articles WHERE feed_id = ?1 AND guid = ?2",
+                            params![feed_id, guid],
+                            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)? != 0)),
+                        )
+                        .optional()?;
+                    match article {
+                        Some((_, true)) => {}
+                        Some((id, false)) => {
+                            delete_article_rows(&tx, id)?;
+                            deleted += 1;
+                        }
+                        // Never seen: just make sure it doesn't show up later
+                        None => {
+                            tx.execute(
+                                r#"INSERT OR IGNORE INTO deleted_articles (feed_id, guid)
+                                   VALUES (?1, ?2)"#,
+                                params![feed_id, guid],
+                            )?;
+                        }
+                    }
+                }
+                tx.commit()?;
+                Ok(deleted)
+            })
+            .await?;
+        Ok(deleted)
     }
-    None
-}
+
+    /// Move an article and its duplicates to the trash (kept until purged)
+    pub async fn trash_article(&self, id: i64) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    "UPDATE articles SET deleted_at = datetime('now') WHERE id = ?1 OR canonical_id = ?1",
+                    params![id],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    pub async fn restore_article(&self, id: i64) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    "UPDATE articles SET deleted_at = NULL WHERE id = ?1 OR canonical_id = ?1",
+                    params![id],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Move articles older than `days` to the trash and purge trash older
+    /// than `trash_days`. Returns the number of articles trashed.
+    pub async fn delete_old_articles(
+        &self,
+        days: i64,
+        trash_days: i64,
+        cleanup: CleanupConfig,
+    ) -> Result<usize> {
+        let trashed = self
+            .conn
+            .call(move |conn| {
+                let trashed = trash_old_articles(conn, days, cleanup)?;
+                purge_trash(conn, trash_days)?;
+                Ok(trashed)
+            })
+            .await?;
+        Ok(trashed)
+    }
+
+    pub async fn compact_database(
+        &self,
+        days: i64,
+        trash_days: i64,
+        cleanup: CleanupConfig,
+    ) -> Result<usize> {
+        let result = self
+            .conn
+            .call(move |conn| {
+                // Retire old articles first
+                trash_old_articles(conn, days, cleanup)?;
+                let purged = purge_trash(conn, trash_days)?;
+
+                // Clean up old deleted_articles tracking entries
+                conn.execute(
+                    "DELETE FROM deleted_articles WHERE deleted_at < datetime('now', '-' || ?1 || ' days')",
+                    params![days.max(trash_days)],
+                )?;
+
+                // Vacuum to reclaim space
+                conn.execute("VACUUM", [])?;
+
+                Ok(purged)
+            })
+            .await?;
+        Ok(result)
+    }
+
+    /// SQLite's data_version: changes whenever another connection (e.g. a
+    /// second instance or the headless refresh) commits to the database
+    pub async fn data_version(&self) -> Result<i64> {
+        let version = self
+            .conn
+            .call(|conn| Ok(conn.query_row("PRAGMA data_version", [], |row| row.get(0))?))
+            .await?;
+        Ok(version)
+    }
+
+    /// Compress the HTML of articles stored before it was kept compressed,
+    /// returning how many were; `vacuum` then gives the space back
+    pub async fn compress_content(&self) -> Result<usize> {
+        let compressed = self
+            .conn
+            .call(|conn| {
+                let mut compressed = 0;
+                // A batch at a time, as full-content feeds make for a lot of HTML
+                loop {
+                    // Text long enough to compress, and zlib blobs from
+                    // before zstd
+                    let rows = conn
+                        .prepare(
+                            r#"SELECT id, content FROM articles
+                               WHERE (typeof(content) = 'text' AND length(content) >= ?1)
+                                  OR (typeof(content) = 'blob' AND substr(content, 1, 4) != ?2)
+                               LIMIT 200"#,
+                        )?
+                        .query_map(params![COMPRESS_MIN_BYTES as i64, &ZSTD_MAGIC[..]], |row| {
+                            Ok((row.get::<_, i64>(0)?, content_column(row, 1)?))
+                        })?
+                        .collect::<rusqlite::Result<Vec<_>>>()?;
+                    if rows.is_empty() {
+                        return Ok(compressed);
+                    }
+                    let tx = conn.transaction()?;
+                    for (id, content) in &rows {
+                        tx.execute(
+                            "UPDATE articles SET content = ?2 WHERE id = ?1",
+                            params![id, pack_content(content.as_deref())],
+                        )?;
+                    }
+                    tx.commit()?;
+                    compressed += rows.len();
+                }
+            })
+            .await?;
+        Ok(compressed)
+    }
+
+    /// Rebuild the database to reclaim free space, then fold the WAL back into
+    /// the main file
+    pub async fn vacuum(&self) -> Result<()> {
+        self.conn
+            .call(|conn| {
+                conn.execute("VACUUM", [])?;
+                conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Copy the database to `path` with SQLite's online backup API, which
+    /// gives a consistent snapshot even while another process is writing
+    pub async fn backup_to(&self, path: PathBuf) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                conn.backup(DatabaseName::Main, &path, None)?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Replace the database's contents with the backup at `path`, page by page
+    /// through the online backup API. Reopen the repository afterwards so an
+    /// older backup is migrated.
+    pub async fn restore_from(&self, path: PathBuf) -> Result<()> {
+        let display = path.display().to_string();
+        let restored = self
+            .conn
+            .call(move |conn| {
+                // Only take what looks like one of our databases
+                let source = rusqlite::Connection::open_with_flags(
+                    &path,
+                    OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+                )?;
+                let tables: i64 = source.query_row(
+                    "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name IN ('feeds', 'articles')",
+                    [],
+                    |row| row.get(0),
+                )?;
+                if tables < 2 {
+                    return Ok(false);
+                }
+                drop(source);
+                conn.restore(DatabaseName::Main, &path, None::<fn(Progress)>)?;
+                Ok(true)
+            })
+            .await?;
+        if !restored {
+            return Err(AppError::Backup(format!("{} isn't a SpeedyReader database", display)));
+        }
+        Ok(())
+    }
+
+    /// Run SQLite's integrity check, returning any problems found
+    pub async fn integrity_check(&self) -> Result<Vec<String>> {
+        let problems = self
+            .conn
+            .call(|conn| {
+                let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+                let rows = stmt
+                    .query_map([], |row| row.get::<_, String>(0))?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(rows.into_iter().filter(|r| r != "ok").collect::<Vec<_>>())
+            })
+            .await?;
+        Ok(problems)
+    }
+
+    // Summary operations
+
+    pub async fn get_summary(&self, article_id: i64) -> Result<Option<Summary>> {
+        let summary = self
+            .conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, article_id, content, model_version, generated_at, source, variant FROM summaries WHERE article_id = ?1 AND variant = ''",
+                )?;
+                let summary = stmt
+                    .query_row(params![article_id], |row| Ok(summary_from_row(row)))
+                    .optional()?;
+                Ok(summary)
+            })
+            .await?;
+        Ok(summary)
+    }
+
+    /// An article's summaries regenerated in other styles
+    pub async fn get_summary_variants(&self, article_id: i64) -> Result<Vec<Summary>> {
+        let summaries = self
+            .conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, article_id, content, model_version, generated_at, source, variant FROM summaries WHERE article_id = ?1 AND variant != ''",
+                )?;
+                let summaries = stmt
+                    .query_map(params![article_id], |row| Ok(summary_from_row(row)))?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(summaries)
+            })
+            .await?;
+        Ok(summaries)
+    }
+
+    pub async fn get_all_summaries(&self) -> Result<Vec<Summary>> {
+        let summaries = self
+            .conn
+            .call(|conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT id, article_id, content, model_version, generated_at, source, variant FROM summaries WHERE variant = ''",
+                )?;
+                let summaries = stmt
+                    .query_map([], |row| Ok(summary_from_row(row)))?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(summaries)
+            })
+            .await?;
+        Ok(summaries)
+    }
+
+    /// Count one background summary against today's limit. False (and not
+    /// counted) once `limit` have been made today.
+    pub async fn take_auto_summary_budget(&self, limit: u32) -> Result<bool> {
+        let taken = self
+            .conn
+            .call(move |conn| {
+                conn.execute(
+                    "DELETE FROM auto_summary_usage WHERE day < date('now', 'localtime', '-30 days')",
+                    [],
+                )?;
+                let changed = conn.execute(
+                    r#"INSERT INTO auto_summary_usage (day, count)
+                       SELECT date('now', 'localtime'), 1 WHERE ?1 > 0
+                       ON CONFLICT(day) DO UPDATE SET count = count + 1 WHERE count < ?1"#,
+                    params![limit],
+                )?;
+                Ok(changed > 0)
+            })
+            .await?;
+        Ok(taken)
+    }
+
+    /// Store an article's summary, or one regenerated in another style,
+    /// replacing the previous one of that kind
+    #[allow(clippy::too_many_arguments)]
+    pub async fn save_summary(
+        &self,
+        article_id: i64,
+        variant: Option<SummaryVariant>,
+        content: String,
+        model: String,
+        source: SummarySource,
+        usage: TokenUsage,
+        cost_usd: f64,
+    ) -> Result<()> {
+        let variant = variant.map(SummaryVariant::as_str).unwrap_or_default();
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                tx.execute(
+                    r#"INSERT INTO summaries
+                           (article_id, content, model_version, source,
+                            input_tokens, output_tokens, cost_usd, variant)
+                       VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                       ON CONFLICT(article_id, variant) DO UPDATE SET
+                           content = excluded.content,
+                           model_version = excluded.model_version,
+                           source = excluded.source,
+                           input_tokens = excluded.input_tokens,
+                           output_tokens = excluded.output_tokens,
+                           cost_usd = excluded.cost_usd,
+                           generated_at = datetime('now')"#,
+                    params![
+                        article_id,
+                        content,
+                        model,
+                        source.as_str(),
+                        usage.input_tokens as i64,
+                        usage.output_tokens as i64,
+                        cost_usd,
+                        variant
+                    ],
+                )?;
+                add_ai_spend(&tx, 1, usage, cost_usd)?;
+                tx.commit()?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Summary spend this month and over all time
+    pub async fn get_ai_spend(&self) -> Result<(AiSpend, AiSpend)> {
+        let spend = self
+            .conn
+            .call(|conn| {
+                let mut stmt = conn.prepare(
+                    r#"SELECT COALESCE(SUM(summaries), 0), COALESCE(SUM(input_tokens), 0),
+                              COALESCE(SUM(output_tokens), 0), COALESCE(SUM(cost_usd), 0.0)
+                       FROM ai_spend WHERE ?1 OR month = strftime('%Y-%m', 'now', 'localtime')"#,
+                )?;
+                let mut total = |all: bool| {
+                    stmt.query_row([all], |row| {
+                        Ok(AiSpend {
+                            summaries: row.get::<_, i64>(0)? as u64,
+                            input_tokens: row.get::<_, i64>(1)? as u64,
+                            output_tokens: row.get::<_, i64>(2)? as u64,
+                            cost_usd: row.get(3)?,
+                        })
+                    })
+                };
+                Ok((total(false)?, total(true)?))
+            })
+            .await?;
+        Ok(spend)
+    }
+
+    // Translation operations
+
+    pub async fn get_translation(&self, article_id: i64, language: &str) -> Result<Option<String>> {
+        let language = language.to_string();
+        let translation = self
+            .conn
+            .call(move |conn| {
+                let translation = conn
+                    .query_row(
+                        "SELECT content FROM translations WHERE article_id = ?1 AND language = ?2",
+                        params![article_id, language],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                Ok(translation)
+            })
+            .await?;
+        Ok(translation)
+    }
+
+    pub async fn save_translation(
+        &self,
+        article_id: i64,
+        language: String,
+        content: String,
+        model: String,
+    ) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    r#"INSERT OR REPLACE INTO translations (article_id, language, content, model_version)
+                       VALUES (?1, ?2, ?3, ?4)"#,
+                    params![article_id, language, content, model],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    // Simplification operations
+
+    pub async fn get_simplification(&self, article_id: i64, level: &str) -> Result<Option<String>> {
+        let level = level.to_string();
+        let simplification = self
+            .conn
+            .call(move |conn| {
+                let simplification = conn
+                    .query_row(
+                        r#"SELECT content FROM simplifications
+                           WHERE article_id = ?1 AND reading_level = ?2"#,
+                        params![article_id, level],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                Ok(simplification)
+            })
+            .await?;
+        Ok(simplification)
+    }
+
+    pub async fn save_simplification(
+        &self,
+        article_id: i64,
+        level: &str,
+        content: String,
+        model: String,
+    ) -> Result<()> {
+        let level = level.to_string();
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    r#"INSERT OR REPLACE INTO simplifications
+                           (article_id, reading_level, content, model_version)
+                       VALUES (?1, ?2, ?3, ?4)"#,
+                    params![article_id, level, content, model],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    // Key facts operations
+
+    pub async fn get_key_facts(&self, article_id: i64) -> Result<Option<KeyFacts>> {
+        let json: Option<String> = self
+            .conn
+            .call(move |conn| {
+                let json = conn
+                    .query_row(
+                        "SELECT facts FROM key_facts WHERE article_id = ?1",
+                        params![article_id],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                Ok(json)
+            })
+            .await?;
+        Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
+    pub async fn save_key_facts(
+        &self,
+        article_id: i64,
+        facts: &KeyFacts,
+        model: String,
+        usage: TokenUsage,
+        cost_usd: f64,
+    ) -> Result<()> {
+        let json = serde_json::to_string(facts)?;
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                tx.execute(
+                    r#"INSERT OR REPLACE INTO key_facts (article_id, facts, model_version)
+                       VALUES (?1, ?2, ?3)"#,
+                    params![article_id, json, model],
+                )?;
+                add_ai_spend(&tx, 0, usage, cost_usd)?;
+                tx.commit()?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    // Discussion operations
+
+    pub async fn get_discussions(&self, article_id: i64) -> Result<Vec<Discussion>> {
+        let discussions = self
+            .conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"SELECT site, url, title, comments, points FROM discussions
+                       WHERE article_id = ?1 ORDER BY comments DESC"#,
+                )?;
+                let rows = stmt.query_map(params![article_id], |row| {
+                    Ok((
+                        row.get::<_, String>(0)?,
+                        Discussion {
+                            site: DiscussionSite::HackerNews,
+                            url: row.get(1)?,
+                            title: row.get(2)?,
+                            comments: row.get(3)?,
+                            points: row.get(4)?,
+                        },
+                    ))
+                })?;
+                let mut discussions = Vec::new();
+                for row in rows {
+                    let (site, mut discussion) = row?;
+                    if let Some(site) = DiscussionSite::parse(&site) {
+                        discussion.site = site;
+                        discussions.push(discussion);
+                    }
+                }
+                Ok(discussions)
+            })
+            .await?;
+        Ok(discussions)
+    }
+
+    /// Store discussions found for an article, updating the counts of known ones
+    pub async fn save_discussions(
+        &self,
+        article_id: i64,
+        discussions: Vec<Discussion>,
+    ) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                for discussion in discussions {
+                    tx.execute(
+                        r#"INSERT INTO discussions (article_id, url, site, title, comments, points)
+                           VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                           ON CONFLICT(article_id, url) DO UPDATE SET
+                               title = excluded.title,
+                               comments = excluded.comments,
+                               points = excluded.points,
+                               checked_at = datetime('now')"#,
+                        params![
+                            article_id,
+                            discussion.url,
+                            discussion.site.as_str(),
+                            discussion.title,
+                            discussion.comments,
+                            discussion.points
+                        ],
+                    )?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Comments on each article with a known count, from its busiest discussion
+    pub async fn get_comment_counts(&self) -> Result<HashMap<i64, u32>> {
+        let counts = self
+            .conn
+            .call(|conn| {
+                let mut stmt = conn.prepare(
+                    r#"SELECT article_id, MAX(comments) FROM discussions
+                       WHERE comments IS NOT NULL GROUP BY article_id"#,
+                )?;
+                let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+                Ok(rows.collect::<rusqlite::Result<HashMap<i64, u32>>>()?)
+            })
+            .await?;
+        Ok(counts)
+    }
+
+    /// Articles whose discussions were looked up in the last `minutes`
+    pub async fn recently_checked_discussions(&self, minutes: i64) -> Result<HashSet<i64>> {
+        let ids = self
+            .conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"SELECT DISTINCT article_id FROM discussions
+                       WHERE checked_at > datetime('now', '-' || ?1 || ' minutes')"#,
+                )?;
+                let rows = stmt.query_map(params![minutes], |row| row.get(0))?;
+                Ok(rows.collect::<rusqlite::Result<HashSet<i64>>>()?)
+            })
+            .await?;
+        Ok(ids)
+    }
+
+    // Chat operations
+
+    pub async fn get_chat_messages(&self, article_id: i64) -> Result<Vec<ChatMessage>> {
+        let messages = self
+            .conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT role, content FROM chat_messages WHERE article_id = ?1 ORDER BY id",
+                )?;
+                let rows = stmt.query_map(params![article_id], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })?;
+                let mut messages = Vec::new();
+                for row in rows {
+                    let (role, content) = row?;
+                    if let Some(role) = ChatRole::parse(&role) {
+                        messages.push(ChatMessage { role, content });
+                    }
+                }
+                Ok(messages)
+            })
+            .await?;
+        Ok(messages)
+    }
+
+    /// Store a question about an article with Claude's answer, and add the
+    /// tokens it took to the month's spend
+    pub async fn save_chat_exchange(
+        &self,
+        article_id: i64,
+        question: String,
+        answer: String,
+        usage: TokenUsage,
+        cost_usd: f64,
+    ) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                for (role, content) in [(ChatRole::User, question), (ChatRole::Assistant, answer)] {
+                    tx.execute(
+                        "INSERT INTO chat_messages (article_id, role, content) VALUES (?1, ?2, ?3)",
+                        params![article_id, role.as_str(), content],
+                    )?;
+                }
+                add_ai_spend(&tx, 0, usage, cost_usd)?;
+                tx.commit()?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    // Embedding operations
+
+    /// Articles with no embedding for `model`, as (id, text to embed), newest first
+    pub async fn get_articles_needing_embedding(
+        &self,
+        model: &str,
+        limit: usize,
+    ) -> Result<Vec<(i64, String)>> {
+        let model = model.to_string();
+        let rows = self
+            .conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    r#"SELECT a.id, a.title, s.content, a.content_text, a.content
+                       FROM articles a
+                       LEFT JOIN summaries s ON s.article_id = a.id AND s.variant = ''
+                       LEFT JOIN article_embeddings e ON e.article_id = a.id AND e.model = ?1
+                       WHERE e.article_id IS NULL AND a.deleted_at IS NULL
+                       ORDER BY a.published_at DESC NULLS LAST
+                       LIMIT ?2"#,
+                )?;
+                let rows = stmt
+                    .query_map(params![model, limit as i64], |row| {
+                        let id: i64 = row.get(0)?;
+                        let title: String = row.get(1)?;
+                        let summary: Option<String> = row.get(2)?;
+                        let content = match row.get::<_, Option<String>>(3)? {
+                            Some(text) => text,
+                            None => content_column(row, 4)?.unwrap_or_default(),
+                        };
+                        let text = match summary {
+                            Some(summary) => format!("{}\n\n{}\n\n{}", title, summary, content),
+                            None => format!("{}\n\n{}", title, content),
+                        };
+                        Ok((id, text))
+                    })?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await?;
+        Ok(rows)
+    }
+
+    pub async fn save_embedding(&self, article_id: i64, model: &str, vector: &[f32]) -> Result<()> {
+        let model = model.to_string();
+        let blob: Vec<u8> = vector.iter().flat_map(|v| v.to_le_bytes()).collect();
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO article_embeddings (article_id, model, vector) VALUES (?1, ?2, ?3)",
+                    params![article_id, model, blob],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// All stored embeddings for `model` as (article_id, vector)
+    pub async fn get_embeddings(&self, model: &str) -> Result<Vec<(i64, Vec<f32>)>> {
+        let model = model.to_string();
+        let rows = self
+            .conn
+            .call(move |conn| {
+                let mut stmt = conn.prepare(
+                    "SELECT article_id, vector FROM article_embeddings WHERE model = ?1",
+                )?;
+                let rows = stmt
+                    .query_map(params![model], |row| {
+                        let id: i64 = row.get(0)?;
+                        let blob: Vec<u8> = row.get(1)?;
+                        let vector = blob
+                            .chunks_exact(4)
+                            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                            .collect();
+                        Ok((id, vector))
+                    })?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(rows)
+            })
+            .await?;
+        Ok(rows)
+    }
+
+    // Refresh statistics
+
+    /// Store each feed's network use for a refresh that just finished, and
+    /// drop history older than `keep_days`
+    pub async fn record_refresh_usage(
+        &self,
+        usage: Vec<(i64, FetchUsage)>,
+        keep_days: i64,
+    ) -> Result<()> {
+        let refreshed_at = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                for (feed_id, usage) in &usage {
+                    tx.execute(
+                        r#"INSERT OR REPLACE INTO refresh_stats
+                               (refreshed_at, feed_id, requests, bytes)
+                           VALUES (?1, ?2, ?3, ?4)"#,
+                        params![refreshed_at, feed_id, usage.requests, usage.bytes as i64],
+                    )?;
+                }
+                tx.execute(
+                    r#"DELETE FROM refresh_stats
+                       WHERE refreshed_at < datetime('now', '-' || ?1 || ' days')"#,
+                    params![keep_days],
+                )?;
+                tx.commit()?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Stored refreshes with their totals, newest first
+    pub async fn get_refresh_history(&self) -> Result<Vec<RefreshUsage>> {
+        let history = self
+            .conn
+            .call(|conn| {
+                let mut stmt = conn.prepare(
+                    r#"SELECT refreshed_at, COUNT(*), SUM(requests), SUM(bytes)
+                       FROM refresh_stats
+                       GROUP BY refreshed_at
+                       ORDER BY refreshed_at DESC"#,
+                )?;
+                let history = stmt
+                    .query_map([], |row| {
+                        let refreshed_at: String = row.get(0)?;
+                        Ok(RefreshUsage {
+                            refreshed_at: parse_datetime(&refreshed_at).unwrap_or_default(),
+                            feeds: row.get(1)?,
+                            requests: row.get::<_, i64>(2)? as u64,
+                            bytes: row.get::<_, i64>(3)? as u64,
+                        })
+                    })?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(history)
+            })
+            .await?;
+        Ok(history)
+    }
+
+    /// Each feed's network use over the stored history, heaviest per refresh first
+    pub async fn get_feed_usage(&self) -> Result<Vec<FeedUsage>> {
+        let usage = self
+            .conn
+            .call(|conn| {
+                let mut stmt = conn.prepare(
+                    r#"SELECT f.title, COUNT(*), SUM(s.requests), SUM(s.bytes),
+                              (SELECT l.bytes FROM refresh_stats l WHERE l.feed_id = s.feed_id
+                               ORDER BY l.refreshed_at DESC LIMIT 1)
+                       FROM refresh_stats s
+                       JOIN feeds f ON f.id = s.feed_id
+                       GROUP BY s.feed_id
+                       ORDER BY SUM(s.bytes) * 1.0 / COUNT(*) DESC"#,
+                )?;
+                let usage = stmt
+                    .query_map([], |row| {
+                        Ok(FeedUsage {
+                            title: row.get(0)?,
+                            refreshes: row.get(1)?,
+                            requests: row.get::<_, i64>(2)? as u64,
+                            bytes: row.get::<_, i64>(3)? as u64,
+                            last_bytes: row.get::<_, i64>(4)? as u64,
+                        })
+                    })?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(usage)
+            })
+            .await?;
+        Ok(usage)
+    }
+
+    // UI state
+
+    pub async fn get_ui_state(&self, key: &str) -> Result<Option<String>> {
+        let key = key.to_string();
+        let value = self
+            .conn
+            .call(move |conn| {
+                let value = conn
+                    .query_row("SELECT value FROM ui_state WHERE key = ?1", params![key], |row| {
+                        row.get(0)
+                    })
+                    .optional()?;
+                Ok(value)
+            })
+            .await?;
+        Ok(value)
+    }
+
+    pub async fn set_ui_state(&self, key: &str, value: String) -> Result<()> {
+        let key = key.to_string();
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO ui_state (key, value) VALUES (?1, ?2)",
+                    params![key, value],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    // Raindrop tracking
+
+    pub async fn mark_saved_to_raindrop(
+        &self,
+        article_id: i64,
+        raindrop_id: i64,
+        tags: Vec<String>,
+    ) -> Result<()> {
+        let tags_json = serde_json::to_string(&tags)?;
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    "INSERT OR REPLACE INTO saved_to_raindrop (article_id, raindrop_id, tags) VALUES (?1, ?2, ?3)",
+                    params![article_id, raindrop_id, tags_json],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Every article saved to Raindrop, as (article ID, raindrop ID, saved at)
+    pub async fn get_raindrop_saves(&self) -> Result<Vec<(i64, i64, DateTime<Utc>)>> {
+        let saves = self
+            .conn
+            .call(|conn| {
+                let mut stmt =
+                    conn.prepare("SELECT article_id, raindrop_id, saved_at FROM saved_to_raindrop")?;
+                let saves = stmt
+                    .query_map([], |row| {
+                        Ok((
+                            row.get(0)?,
+                            row.get(1)?,
+                            parse_datetime(&row.get::<_, String>(2)?).unwrap_or_else(Utc::now),
+                        ))
+                    })?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(saves)
+            })
+            .await?;
+        Ok(saves)
+    }
+
+    /// Clear the saved flag of articles whose Raindrop bookmark is gone
+    pub async fn unmark_saved_to_raindrop(&self, article_ids: Vec<i64>) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                for article_id in article_ids {
+                    tx.execute(
+                        "DELETE FROM saved_to_raindrop WHERE article_id = ?1",
+                        params![article_id],
+                    )?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Articles in any feed with one of these URLs, keyed by normalized URL
+    pub async fn find_articles_by_urls(
+        &self,
+        urls: Vec<String>,
+    ) -> Result<HashMap<String, Vec<i64>>> {
+        let found = self
+            .conn
+            .call(move |conn| {
+                let mut stmt =
+                    conn.prepare("SELECT id FROM articles WHERE normalized_url = ?1 ORDER BY id")?;
+                let mut found = HashMap::new();
+                for url in urls {
+                    let url = normalize_url(&url);
+                    let ids = stmt
+                        .query_map(params![url], |row| row.get::<_, i64>(0))?
+                        .collect::<std::result::Result<Vec<_>, _>>()?;
+                    if !ids.is_empty() {
+                        found.insert(url, ids);
+                    }
+                }
+                Ok(found)
+            })
+            .await?;
+        Ok(found)
+    }
+
+    pub async fn is_saved_to_raindrop(&self, article_id: i64) -> Result<bool> {
+        let exists = self
+            .conn
+            .call(move |conn| {
+                let count: i64 = conn.query_row(
+                    "SELECT COUNT(*) FROM saved_to_raindrop WHERE article_id = ?1",
+                    params![article_id],
+                    |row| row.get(0),
+                )?;
+                Ok(count > 0)
+            })
+            .await?;
+        Ok(exists)
+    }
+
+    /// Raindrop collection this feed's articles were last saved to
+    pub async fn get_feed_raindrop_collection(&self, feed_id: i64) -> Result<Option<i64>> {
+        let collection = self
+            .conn
+            .call(move |conn| {
+                let collection = conn
+                    .query_row(
+                        "SELECT raindrop_collection FROM feeds WHERE id = ?1",
+                        params![feed_id],
+                        |row| row.get::<_, Option<i64>>(0),
+                    )
+                    .optional()?;
+                Ok(collection.flatten())
+            })
+            .await?;
+        Ok(collection)
+    }
+
+    pub async fn set_feed_raindrop_collection(&self, feed_id: i64, collection: i64) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    "UPDATE feeds SET raindrop_collection = ?1 WHERE id = ?2",
+                    params![collection, feed_id],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    // Backup operations
+
+    /// Merge a backup into the database in one transaction: missing feeds and
+    /// articles are added, and stored articles gain the backup's read and
+    /// star state, tags and summary. Articles deleted here stay deleted.
+    pub async fn import_backup(&self, backup: Backup) -> Result<BackupImport> {
+        let import = self
+            .conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                let mut import = BackupImport::default();
+                let sqlite_time = |dt: DateTime<Utc>| dt.format("%Y-%m-%d %H:%M:%S").to_string();
+
+                for feed in backup.feeds {
+                    import.feeds += tx.execute(
+                        r#"INSERT INTO feeds (title, url, site_url, description, category,
+                                              auto_star, auto_archive, skip_unread_filter, auto_summarize,
+                                              collapse_similar)
+                           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+                           ON CONFLICT(url) DO NOTHING"#,
+                        params![
+                            feed.title,
+                            feed.url,
+                            feed.site_url,
+                            feed.description,
+                            feed.category,
+                            feed.rules.auto_star,
+                            feed.rules.auto_archive,
+                            feed.rules.skip_unread_filter,
+                            feed.rules.auto_summarize,
+                            feed.rules.collapse_similar
+                        ],
+                    )?;
+                    let feed_id: i64 = tx.query_row(
+                        "SELECT id FROM feeds WHERE url = ?1",
+                        params![feed.url],
+                        |row| row.get(0),
+                    )?;
+
+                    for article in feed.articles {
+                        let was_deleted = tx
+                            .query_row(
+                                "SELECT 1 FROM deleted_articles WHERE feed_id = ?1 AND guid = ?2",
+                                params![feed_id, article.guid],
+                                |_| Ok(()),
+                            )
+                            .optional()?
+                            .is_some();
+                        if was_deleted {
+                            continue;
+                        }
+
+                        let existing: Option<i64> = tx
+                            .query_row(
+                                "SELECT id FROM articles WHERE feed_id = ?1 AND guid = ?2",
+                                params![feed_id, article.guid],
+                                |row| row.get(0),
+                            )
+                            .optional()?;
+                        let article_id = match existing {
+                            Some(id) => {
+                                tx.execute(
+                                    r#"UPDATE articles SET is_read = max(is_read, ?2),
+                                                           is_starred = max(is_starred, ?3),
+                                                           queued_at = coalesce(queued_at, ?4)
+                                       WHERE id = ?1"#,
+                                    params![
+                                        id,
+                                        article.is_read,
+                                        article.is_starred,
+                                        article.queued_at.map(|dt| dt.to_rfc3339())
+                                    ],
+                                )?;
+                                import.merged += 1;
+                                id
+                            }
+                            None => {
+                                let normalized_url = normalize_url(&article.url);
+                                let canonical_id =
+                                    find_canonical(&tx, feed_id, &normalized_url, &article.title)?;
+                                let hash = content_hash(&article.title, article.content.as_deref());
+                                tx.execute(
+                                    r#"INSERT INTO articles (feed_id, guid, title, url, author, content, content_text,
+                                                            published_at, fetched_at, is_read, is_starred, deleted_at,
+                                                            normalized_url, canonical_id, image_url, content_hash,
+                                                            queued_at, word_count)
+                                       VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                                               ?17, ?18)"#,
+                                    params![
+                                        feed_id,
+                                        article.guid,
+                                        article.title,
+                                        article.url,
+                                        article.author,
+                                        pack_content(article.content.as_deref()),
+                                        article.content_text,
+                                        article.published_at.map(|dt| dt.to_rfc3339()),
+                                        sqlite_time(article.fetched_at),
+                                        article.is_read,
+                                        article.is_starred,
+                                        article.deleted_at.map(sqlite_time),
+                                        normalized_url,
+                                        canonical_id,
+                                        article.image_url,
+                                        hash,
+                                        article.queued_at.map(|dt| dt.to_rfc3339()),
+                                        count_words(
+                                            article.content_text.as_deref(),
+                                            article.content.as_deref()
+                                        ),
+                                    ],
+                                )?;
+                                import.articles += 1;
+                                tx.last_insert_rowid()
+                            }
+                        };
+
+                        for tag in &article.tags {
+                            tx.execute(
+                                "INSERT OR IGNORE INTO article_tags (article_id, tag) VALUES (?1, ?2)",
+                                params![article_id, tag],
+                            )?;
+                        }
+                        if let Some(summary) = article.summary {
+                            tx.execute(
+                                r#"INSERT INTO summaries (article_id, content, model_version, source, generated_at)
+                                   VALUES (?1, ?2, ?3, ?4, ?5)
+                                   ON CONFLICT(article_id, variant) DO NOTHING"#,
+                                params![
+                                    article_id,
+                                    summary.content,
+                                    summary.model_version,
+                                    summary.source.map(SummarySource::as_str),
+                                    sqlite_time(summary.generated_at),
+                                ],
+                            )?;
+                        }
+                    }
+                }
+
+                tx.commit()?;
+                Ok(import)
+            })
+            .await?;
+        Ok(import)
+    }
+
+    // Sync operations
+
+    /// Feed URLs that were subscribed both locally and on the sync server
+    /// at the end of the last sync
+    pub async fn get_synced_feed_urls(&self) -> Result<HashSet<String>> {
+        let urls = self
+            .conn
+            .call(|conn| {
+                let mut stmt = conn.prepare("SELECT url FROM sync_feeds")?;
+                let urls = stmt
+                    .query_map([], |row| row.get(0))?
+                    .collect::<std::result::Result<HashSet<String>, _>>()?;
+                Ok(urls)
+            })
+            .await?;
+        Ok(urls)
+    }
+
+    pub async fn set_synced_feed_urls(&self, urls: Vec<String>) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                let tx = conn.transaction()?;
+                tx.execute("DELETE FROM sync_feeds", [])?;
+                for url in &urls {
+                    tx.execute("INSERT OR IGNORE INTO sync_feeds (url) VALUES (?1)", params![url])?;
+                }
+                tx.commit()?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+
+    /// Every article linked to a sync server item, with its current and last
+    /// synced state
+    pub async fn get_sync_items(&self) -> Result<Vec<SyncItem>> {
+        let items = self
+            .conn
+            .call(|conn| {
+                let mut stmt = conn.prepare(
+                    r#"SELECT s.article_id, s.item_id, a.is_read, a.is_starred, s.is_read, s.is_starred
+                       FROM sync_items s
+                       JOIN articles a ON a.id = s.article_id"#,
+                )?;
+                let items = stmt
+                    .query_map([], |row| {
+                        Ok(SyncItem {
+                            article_id: row.get(0)?,
+                            item_id: row.get(1)?,
+                            is_read: row.get::<_, i64>(2)? != 0,
+                            is_starred: row.get::<_, i64>(3)? != 0,
+                            synced_read: row.get::<_, i64>(4)? != 0,
+                            synced_starred: row.get::<_, i64>(5)? != 0,
+                        })
+                    })?
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                Ok(items)
+            })
+            .await?;
+        Ok(items)
+    }
+
+    /// Article in a feed with the same (normalized) URL as a sync server
+    /// item, with its read and starred state
+    pub async fn find_article_by_url(
+        &self,
+        feed_id: i64,
+        url: &str,
+    ) -> Result<Option<(i64, bool, bool)>> {
+        let normalized_url = normalize_url(url);
+        let article = self
+            .conn
+            .call(move |conn| {
+                let article = conn
+                    .query_row(
+                        r#"SELECT id, is_read, is_starred FROM articles
+                           WHERE feed_id = ?1 AND normalized_url = ?2
+                           ORDER BY id LIMIT 1"#,
+                        params![feed_id, normalized_url],
+                        |row| {
+                            Ok((
+                                row.get(0)?,
+                                row.get::<_, i64>(1)? != 0,
+                                row.get::<_, i64>(2)? != 0,
+                            ))
+                        },
+                    )
+                    .optional()?;
+                Ok(article)
+            })
+            .await?;
+        Ok(article)
+    }
+
+    /// Link an article to a sync server item, recording the agreed state
+    pub async fn link_sync_item(
+        &self,
+        article_id: i64,
+        item_id: String,
+        is_read: bool,
+        is_starred: bool,
+    ) -> Result<()> {
+        self.conn
+            .call(move |conn| {
+                conn.execute(
+                    r#"INSERT OR REPLACE INTO sync_items (article_id, item_id, is_read, is_starred)
+                       VALUES (?1, ?2, ?3, ?4)"#,
+                    params![article_id, item_id, is_read, is_starred],
+                )?;
+                Ok(())
+            })
+            .await?;
+        Ok(())
+    }
+}
+
+/// Insert or update one fetched article; returns its id when it is new.
+/// Articles deleted for good, unchanged ones and ones whose guid was
+/// regenerated (moved over to the new guid) come back as None.
+fn upsert_article_row(
+    conn: &rusqlite::Connection,
+    article: &NewArticle,
+) -> rusqlite::Result<Option<i64>> {
+    // Check if this article was previously deleted
+    let was_deleted: bool = conn.query_row(
+        "SELECT 1 FROM deleted_articles WHERE feed_id = ?1 AND guid = ?2",
+        params![article.feed_id, article.guid],
+        |_| Ok(true),
+    ).unwrap_or(false);
+
+    if was_deleted {
+        return Ok(None); // Skip deleted articles
+    }
+
+    let hash = content_hash(&article.title, article.content.as_deref());
+    let stored_hash: Option<Option<String>> = conn.query_row(
+        "SELECT content_hash FROM articles WHERE feed_id = ?1 AND guid = ?2",
+        params![article.feed_id, article.guid],
+        |row| row.get(0),
+    ).optional()?;
+    let exists = stored_hash.is_some();
+
+    if stored_hash.flatten().as_deref() == Some(hash.as_str()) {
+        return Ok(None); // Unchanged
+    }
+
+    // A regenerated guid: move the existing entry over to it. It is the same
+    // entry if its content is unchanged, or its link, title and date are (for
+    // feeds whose ids change with every fetch). Entries without content are
+    // too alike ("Open thread") to match on content alone.
+    let normalized_url = normalize_url(&article.url);
+    let published_at = article.published_at.map(|dt| dt.to_rfc3339());
+    if !exists {
+        let has_content = article.content.as_deref().is_some_and(|c| !c.trim().is_empty());
+        let same_entry: Option<i64> = conn.query_row(
+            r#"SELECT id FROM articles
+               WHERE feed_id = ?1
+                 AND ((?2 AND content_hash = ?3)
+                      OR (?4 != '' AND normalized_url = ?4 AND title = ?5 AND published_at IS ?6))
+               ORDER BY id DESC LIMIT 1"#,
+            params![
+                article.feed_id,
+                has_content,
+                hash,
+                normalized_url,
+                article.title,
+                published_at
+            ],
+            |row| row.get(0),
+        ).optional()?;
+        if let Some(id) = same_entry {
+            conn.execute(
+                "UPDATE articles SET guid = ?2 WHERE id = ?1",
+                params![id, article.guid],
+            )?;
+            return Ok(None);
+        }
+    }
+
+    let canonical_id = if exists {
+        None
+    } else {
+        find_canonical(conn, article.feed_id, &normalized_url, &article.title)?
+    };
+
+    conn.execute(
+        r#"INSERT INTO articles (feed_id, guid, title, url, author, content, content_text, published_at, is_starred,
+                                normalized_url, canonical_id, image_url, content_hash, word_count)
+           VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8,
+                   coalesce((SELECT auto_star FROM feeds WHERE id = ?1), 0), ?9, ?10, ?11,
+                   ?12, ?13)
+           ON CONFLICT(feed_id, guid) DO UPDATE SET
+               title = excluded.title,
+               url = excluded.url,
+               author = excluded.author,
+               content = excluded.content,
+               content_text = excluded.content_text,
+               published_at = excluded.published_at,
+               normalized_url = excluded.normalized_url,
+               image_url = coalesce(excluded.image_url, articles.image_url),
+               content_hash = excluded.content_hash,
+               word_count = excluded.word_count"#,
+        params![
+            article.feed_id,
+            article.guid,
+            article.title,
+            article.url,
+            article.author,
+            pack_content(article.content.as_deref()),
+            article.content_text,
+            published_at,
+            normalized_url,
+            canonical_id,
+            article.image_url,
+            hash,
+            count_words(article.content_text.as_deref(), article.content.as_deref()),
+        ],
+    )?;
+    Ok((!exists).then(|| conn.last_insert_rowid()))
+}
+
+/// Move articles older than `days` to the trash, except queued ones and those
+/// `cleanup` says to keep
+fn trash_old_articles(
+    conn: &rusqlite::Connection,
+    days: i64,
+    cleanup: CleanupConfig,
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        r#"UPDATE articles SET deleted_at = datetime('now')
+           WHERE deleted_at IS NULL AND queued_at IS NULL
+             AND (published_at < datetime('now', '-' || ?1 || ' days')
+                  OR (published_at IS NULL AND fetched_at < datetime('now', '-' || ?1 || ' days')))
+             AND NOT (?2 AND is_starred = 1)
+             AND NOT (?3 AND id IN (SELECT article_id FROM article_tags))
+             AND NOT (?4 AND id IN (SELECT article_id FROM saved_to_raindrop))"#,
+        params![days, cleanup.keep_starred, cleanup.keep_tagged, cleanup.keep_saved],
+    )
+}
+
+/// Add a Claude request's tokens and cost (and `summaries` made) to the
+/// month's spend
+fn add_ai_spend(
+    conn: &rusqlite::Connection,
+    summaries: i64,
+    usage: TokenUsage,
+    cost_usd: f64,
+) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"INSERT INTO ai_spend (month, summaries, input_tokens, output_tokens, cost_usd)
+           VALUES (strftime('%Y-%m', 'now', 'localtime'), ?1, ?2, ?3, ?4)
+           ON CONFLICT(month) DO UPDATE SET
+               summaries = summaries + excluded.summaries,
+               input_tokens = input_tokens + excluded.input_tokens,
+               output_tokens = output_tokens + excluded.output_tokens,
+               cost_usd = cost_usd + excluded.cost_usd"#,
+        params![summaries, usage.input_tokens as i64, usage.output_tokens as i64, cost_usd],
+    )?;
+    Ok(())
+}
+
+// Tables with per-article rows, cleared when an article is deleted for good
+const ARTICLE_TABLES: [&str; 10] = [
+    "summaries",
+    "key_facts",
+    "discussions",
+    "translations",
+    "simplifications",
+    "chat_messages",
+    "saved_to_raindrop",
+    "article_embeddings",
+    "article_tags",
+    "sync_items",
+];
+
+/// Permanently delete an article and its related rows, remembering its
+/// feed_id and guid so a refresh doesn't add it again
+fn delete_article_rows(conn: &rusqlite::Connection, id: i64) -> rusqlite::Result<()> {
+    conn.execute(
+        r#"INSERT OR IGNORE INTO deleted_articles (feed_id, guid)
+           SELECT feed_id, guid FROM articles WHERE id = ?1"#,
+        params![id],
+    )?;
+    for table in ARTICLE_TABLES {
+        conn.execute(&format!("DELETE FROM {} WHERE article_id = ?1", table), params![id])?;
+    }
+    conn.execute("DELETE FROM articles WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// Permanently delete articles that have been in the trash longer than `trash_days`
+fn purge_trash(conn: &rusqlite::Connection, trash_days: i64) -> rusqlite::Result<usize> {
+    const EXPIRED: &str = "SELECT id FROM articles WHERE deleted_at < datetime('now', '-' || ?1 || ' days')";
+
+    // Remember purged articles so a refresh doesn't bring them back
+    conn.execute(
+        r#"INSERT OR IGNORE INTO deleted_articles (feed_id, guid)
+           SELECT feed_id, guid FROM articles WHERE deleted_at < datetime('now', '-' || ?1 || ' days')"#,
+        params![trash_days],
+    )?;
+    for table in ARTICLE_TABLES {
+        conn.execute(
+            &format!("DELETE FROM {} WHERE article_id IN ({})", table, EXPIRED),
+            params![trash_days],
+        )?;
+    }
+    conn.execute(
+        "DELETE FROM articles WHERE deleted_at < datetime('now', '-' || ?1 || ' days')",
+        params![trash_days],
+    )
+}
+
+/// First copy of the same story in another feed: an article with the same
+/// normalized URL, or a recent one with a near-identical title
+fn find_canonical(
+    conn: &rusqlite::Connection,
+    feed_id: i64,
+    normalized_url: &str,
+    title: &str,
+) -> rusqlite::Result<Option<i64>> {
+    let by_url = conn
+        .query_row(
+            r#"SELECT coalesce(canonical_id, id) FROM articles
+               WHERE normalized_url = ?1 AND feed_id != ?2 AND deleted_at IS NULL
+               ORDER BY id LIMIT 1"#,
+            params![normalized_url, feed_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if by_url.is_some() {
+        return Ok(by_url);
+    }
+
+    let mut stmt = conn.prepare(
+        r#"SELECT id, title FROM articles
+           WHERE canonical_id IS NULL AND feed_id != ?1 AND deleted_at IS NULL
+             AND fetched_at >= datetime('now', '-' || ?2 || ' days')
+           ORDER BY id"#,
+    )?;
+    let candidates = stmt.query_map(params![feed_id, DEDUP_WINDOW_DAYS], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for candidate in candidates {
+        let (id, candidate_title) = candidate?;
+        if is_similar_title(title, &candidate_title) {
+            return Ok(Some(id));
+        }
+    }
+    Ok(None)
+}
+
+/// Fill in normalized URLs for articles stored before deduplication existed
+fn backfill_normalized_urls(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare("SELECT id, url FROM articles WHERE normalized_url IS NULL")?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    for (id, url) in rows {
+        tx.execute(
+            "UPDATE articles SET normalized_url = ?2 WHERE id = ?1",
+            params![id, normalize_url(&url)],
+        )?;
+    }
+    tx.commit()
+}
+
+/// An article's HTML and text together, to look for links in, from its
+/// `content` column at `index` and `content_text` right after
+fn linkable_text(row: &Row, index: usize) -> rusqlite::Result<String> {
+    let content = content_column(row, index)?.unwrap_or_default();
+    let text: Option<String> = row.get(index + 1)?;
+    Ok(format!("{} {}", content, text.unwrap_or_default()))
+}
+
+/// Count the words of articles stored before word counts were kept
+fn backfill_word_counts(conn: &rusqlite::Connection) -> rusqlite::Result<()> {
+    let mut stmt =
+        conn.prepare("SELECT id, content_text, content FROM articles WHERE word_count IS NULL")?;
+    let rows = stmt
+        .query_map([], |row| {
+            let text: Option<String> = row.get(1)?;
+            let content = content_column(row, 2)?;
+            Ok((row.get::<_, i64>(0)?, count_words(text.as_deref(), content.as_deref())))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    for (id, words) in rows {
+        tx.execute("UPDATE articles SET word_count = ?2 WHERE id = ?1", params![id, words])?;
+    }
+    tx.commit()
+}
+
+/// Turn user input into an FTS5 query without exposing its operator syntax:
+/// a quoted query becomes one phrase, otherwise every word must match
+fn fts_query(input: &str) -> String {
+    let input = input.trim();
+    let quote = |s: &str| format!("\"{}\"", s.replace('"', "\"\""));
+    if input.len() > 1 && input.starts_with('"') && input.ends_with('"') {
+        quote(&input[1..input.len() - 1])
+    } else {
+        input.split_whitespace().map(quote).collect::<Vec<_>>().join(" ")
+    }
+}
+
+// Common title words that say nothing about the topic
+const TITLE_STOPWORDS: &[&str] = &[
+    "about", "after", "again", "against", "before", "being", "could", "does", "from", "have",
+    "here", "into", "just", "like", "more", "most", "much", "over", "should", "some", "than",
+    "that", "their", "there", "these", "they", "this", "what", "when", "where", "which", "while",
+    "will", "with", "would", "your",
+];
+
+// Title keywords used to find related articles
+const RELATED_MAX_KEYWORDS: usize = 8;
+
+/// FTS5 query matching titles that contain any two of `title`'s keywords,
+/// None when the title has fewer than two
+fn related_query(title: &str) -> Option<String> {
+    let mut keywords: Vec<String> = Vec::new();
+    for word in title.split(|c: char| !c.is_alphanumeric()) {
+        let word = word.to_lowercase();
+        if word.chars().count() >= 4
+            && !TITLE_STOPWORDS.contains(&word.as_str())
+            && !keywords.contains(&word)
+        {
+            keywords.push(word);
+        }
+    }
+    keywords.truncate(RELATED_MAX_KEYWORDS);
+    if keywords.len() < 2 {
+        return None;
+    }
+
+    let mut pairs = Vec::new();
+    for (i, first) in keywords.iter().enumerate() {
+        for second in &keywords[i + 1..] {
+            pairs.push(format!("(\"{}\" \"{}\")", first, second));
+        }
+    }
+    Some(format!("title : ({})", pairs.join(" OR ")))
+}
+
+fn parse_datetime(s: &str) -> Option<DateTime<Utc>> {
+    // Try RFC3339 first (e.g., "2026-01-11T12:34:56+00:00")
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    // Try SQLite datetime format (e.g., "2026-01-11 12:34:56")
+    if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
+        return Some(naive.and_utc());
+    }
+    None
+}
 
 fn feed_from_row(row: &Row) -> Feed {
     Feed {
@@ -352,6 +2414,7 @@ fn feed_from_row(row: &Row) -> Feed {
         url: row.get(2).unwrap(),
         site_url: row.get(3).unwrap(),
         description: row.get(4).unwrap(),
+        category: row.get(13).unwrap(),
         last_fetched: row
             .get::<_, Option<String>>(5)
             .unwrap()
@@ -366,30 +2429,84 @@ fn feed_from_row(row: &Row) -> Feed {
             .ok()
             .and_then(|s| parse_datetime(&s))
             .unwrap_or_else(Utc::now),
+        auth: row
+            .get::<_, Option<String>>(8)
+            .unwrap()
+            .and_then(|kind| FeedAuthKind::parse(&kind))
+            .and_then(|kind| {
+                row.get::<_, Option<String>>(9)
+                    .unwrap()
+                    .map(|name| FeedAuth { kind, name })
+            }),
+        rules: FeedRules {
+            auto_star: row.get::<_, i64>(10).unwrap_or(0) != 0,
+            auto_archive: row.get::<_, i64>(11).unwrap_or(0) != 0,
+            skip_unread_filter: row.get::<_, i64>(12).unwrap_or(0) != 0,
+            auto_summarize: row.get::<_, i64>(14).unwrap_or(0) != 0,
+            collapse_similar: row.get::<_, i64>(15).unwrap_or(0) != 0,
+        },
+        backoff: FetchBackoff {
+            failures: row.get(16).unwrap_or(0),
+            retry_at: row
+                .get::<_, Option<String>>(17)
+                .unwrap_or(None)
+                .and_then(|s| parse_datetime(&s)),
+            error: row.get(18).unwrap_or(None),
+        },
     }
 }
 
-fn article_from_row(row: &Row) -> Article {
-    Article {
-        id: row.get(0).unwrap(),
-        feed_id: row.get(1).unwrap(),
-        guid: row.get(2).unwrap(),
-        title: row.get(3).unwrap(),
-        url: row.get(4).unwrap(),
-        author: row.get(5).unwrap(),
-        content: row.get(6).unwrap(),
-        content_text: row.get(7).unwrap(),
+// Columns read by `article_from_row`, from `articles a JOIN feeds f`
+const ARTICLE_COLUMNS: &str = r#"a.id, a.feed_id, a.guid, a.title, a.url, a.author, a.content,
+    a.content_text, a.published_at, a.fetched_at,
+    f.title as feed_title, a.is_read, a.deleted_at, a.is_starred,
+    a.canonical_id,
+    (SELECT COUNT(DISTINCT d.feed_id) FROM articles d
+     WHERE d.canonical_id = a.id AND d.feed_id != a.feed_id) AS also_in_feeds,
+    (SELECT group_concat(t.tag, ',') FROM article_tags t
+     WHERE t.article_id = a.id) AS tags,
+    a.image_url, a.queued_at, a.word_count"#;
+
+fn article_from_row(row: &Row) -> rusqlite::Result<Article> {
+    let mut article = Article {
+        id: row.get(0)?,
+        feed_id: row.get(1)?,
+        guid: row.get(2)?,
+        title: row.get(3)?,
+        url: row.get(4)?,
+        author: row.get(5)?,
+        content: content_column(row, 6)?,
+        content_text: row.get(7)?,
         published_at: row
-            .get::<_, Option<String>>(8)
-            .unwrap()
+            .get::<_, Option<String>>(8)?
             .and_then(|s| parse_datetime(&s)),
         fetched_at: row
             .get::<_, String>(9)
             .ok()
             .and_then(|s| parse_datetime(&s))
             .unwrap_or_else(Utc::now),
-        feed_title: row.get(10).unwrap(),
-    }
+        feed_title: row.get(10)?,
+        is_read: row.get::<_, i64>(11).unwrap_or(0) != 0,
+        deleted_at: row
+            .get::<_, Option<String>>(12)?
+            .and_then(|s| parse_datetime(&s)),
+        is_starred: row.get::<_, i64>(13).unwrap_or(0) != 0,
+        canonical_id: row.get(14)?,
+        also_in_feeds: row.get::<_, i64>(15).unwrap_or(0) as usize,
+        tags: row
+            .get::<_, Option<String>>(16)?
+            .map(|tags| parse_tags(&tags))
+            .unwrap_or_default(),
+        image_url: row.get(17)?,
+        queued_at: row
+            .get::<_, Option<String>>(18)?
+            .and_then(|s| parse_datetime(&s)),
+        word_count: row.get::<_, Option<u32>>(19)?.unwrap_or(0),
+        deadline: None,
+    };
+    let reference = article.published_at.unwrap_or(article.fetched_at).date_naive();
+    article.deadline = extract_deadline(&article.title, article.content_text.as_deref(), reference);
+    Ok(article)
 }
 
 fn summary_from_row(row: &Row) -> Summary {
@@ -403,5 +2520,13 @@ fn summary_from_row(row: &Row) -> Summary {
             .ok()
             .and_then(|s| parse_datetime(&s))
             .unwrap_or_else(Utc::now),
+        source: row
+            .get::<_, Option<String>>(5)
+            .unwrap()
+            .and_then(|s| SummarySource::parse(&s)),
+        variant: row
+            .get::<_, String>(6)
+            .ok()
+            .and_then(|s| SummaryVariant::parse(&s)),
     }
 }
@@ -65,3 +65,277 @@ CREATE TABLE IF NOT EXISTS deleted_articles (
 
 CREATE INDEX IF NOT EXISTS idx_deleted_articles_feed_guid ON deleted_articles(feed_id, guid);
 "#;
+
+/// Incremental migrations applied on top of SCHEMA, tracked via PRAGMA user_version.
+/// Append new entries only; never reorder or edit existing ones.
+pub const MIGRATIONS: &[&str] = &[
+    // 1: provenance of the text a summary was generated from
+    "ALTER TABLE summaries ADD COLUMN source TEXT;",
+    // 2: vector embeddings for semantic search (little-endian f32 blob)
+    r#"CREATE TABLE IF NOT EXISTS article_embeddings (
+        article_id INTEGER PRIMARY KEY REFERENCES articles(id) ON DELETE CASCADE,
+        model TEXT NOT NULL,
+        vector BLOB NOT NULL,
+        created_at TEXT NOT NULL DEFAULT (datetime('now'))
+    );"#,
+    // 3: soft delete - articles sit in the trash until purged
+    r#"ALTER TABLE articles ADD COLUMN deleted_at TEXT;
+    CREATE INDEX IF NOT EXISTS idx_articles_deleted_at ON articles(deleted_at);"#,
+    // 4: per-feed credentials (the secret itself lives in the OS keyring)
+    r#"CREATE TABLE IF NOT EXISTS feed_credentials (
+        feed_id INTEGER PRIMARY KEY REFERENCES feeds(id) ON DELETE CASCADE,
+        kind TEXT NOT NULL,
+        name TEXT NOT NULL
+    );"#,
+    // 5: full-text index over titles, feed content and fetched full-article snapshots
+    r#"ALTER TABLE articles ADD COLUMN full_text TEXT;
+    CREATE VIRTUAL TABLE IF NOT EXISTS articles_fts USING fts5(title, body, tokenize = 'porter unicode61');
+    CREATE TRIGGER IF NOT EXISTS articles_fts_insert AFTER INSERT ON articles BEGIN
+        INSERT INTO articles_fts (rowid, title, body)
+        VALUES (new.id, new.title, coalesce(new.content_text, '') || ' ' || coalesce(new.full_text, ''));
+    END;
+    CREATE TRIGGER IF NOT EXISTS articles_fts_update AFTER UPDATE OF title, content_text, full_text ON articles BEGIN
+        DELETE FROM articles_fts WHERE rowid = old.id;
+        INSERT INTO articles_fts (rowid, title, body)
+        VALUES (new.id, new.title, coalesce(new.content_text, '') || ' ' || coalesce(new.full_text, ''));
+    END;
+    CREATE TRIGGER IF NOT EXISTS articles_fts_delete AFTER DELETE ON articles BEGIN
+        DELETE FROM articles_fts WHERE rowid = old.id;
+    END;
+    INSERT INTO articles_fts (rowid, title, body)
+    SELECT id, title, coalesce(content_text, '') FROM articles;"#,
+    // 6: per-feed automatic rules
+    r#"ALTER TABLE feeds ADD COLUMN auto_star INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE feeds ADD COLUMN auto_archive INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE feeds ADD COLUMN skip_unread_filter INTEGER NOT NULL DEFAULT 0;"#,
+    // 7: OPML folder a feed was imported from
+    "ALTER TABLE feeds ADD COLUMN category TEXT;",
+    // 8: cross-feed duplicates point at the first copy of the story
+    r#"ALTER TABLE articles ADD COLUMN normalized_url TEXT;
+    ALTER TABLE articles ADD COLUMN canonical_id INTEGER REFERENCES articles(id);
+    CREATE INDEX IF NOT EXISTS idx_articles_normalized_url ON articles(normalized_url);
+    CREATE INDEX IF NOT EXISTS idx_articles_canonical_id ON articles(canonical_id);"#,
+    // 9: local article tags
+    r#"CREATE TABLE IF NOT EXISTS article_tags (
+        article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+        tag TEXT NOT NULL,
+        PRIMARY KEY (article_id, tag)
+    );
+    CREATE INDEX IF NOT EXISTS idx_article_tags_tag ON article_tags(tag);"#,
+    // 10: lead image for inline previews
+    "ALTER TABLE articles ADD COLUMN image_url TEXT;",
+    // 11: Google Reader API sync: feeds on both sides at the last sync, and
+    // each linked item's read/starred state as last agreed with the server
+    r#"CREATE TABLE IF NOT EXISTS sync_feeds (
+        url TEXT PRIMARY KEY
+    );
+    CREATE TABLE IF NOT EXISTS sync_items (
+        article_id INTEGER PRIMARY KEY REFERENCES articles(id) ON DELETE CASCADE,
+        item_id TEXT NOT NULL UNIQUE,
+        is_read INTEGER NOT NULL DEFAULT 0,
+        is_starred INTEGER NOT NULL DEFAULT 0
+    );"#,
+    // 12: fingerprint of title + content, to ignore unchanged re-publishes
+    r#"ALTER TABLE articles ADD COLUMN content_hash TEXT;
+    CREATE INDEX IF NOT EXISTS idx_articles_content_hash ON articles(feed_id, content_hash);"#,
+    // 13: AI translations of article text, one per target language
+    r#"CREATE TABLE IF NOT EXISTS translations (
+        article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+        language TEXT NOT NULL,
+        content TEXT NOT NULL,
+        model_version TEXT NOT NULL,
+        translated_at TEXT NOT NULL DEFAULT (datetime('now')),
+        PRIMARY KEY (article_id, language)
+    );"#,
+    // 14: list order, for reading the article list a page at a time
+    r#"CREATE INDEX IF NOT EXISTS idx_articles_list_order
+        ON articles(COALESCE(published_at, '') DESC, fetched_at DESC, id DESC);"#,
+    // 15: network use of each feed per refresh
+    r#"CREATE TABLE IF NOT EXISTS refresh_stats (
+        refreshed_at TEXT NOT NULL,
+        feed_id INTEGER NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+        requests INTEGER NOT NULL,
+        bytes INTEGER NOT NULL,
+        PRIMARY KEY (refreshed_at, feed_id)
+    );"#,
+    // 16: UI state kept between sessions, JSON by key
+    r#"CREATE TABLE IF NOT EXISTS ui_state (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );"#,
+    // 17: plain-language rewrites of article text, one per reading level
+    r#"CREATE TABLE IF NOT EXISTS simplifications (
+        article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+        reading_level TEXT NOT NULL,
+        content TEXT NOT NULL,
+        model_version TEXT NOT NULL,
+        simplified_at TEXT NOT NULL DEFAULT (datetime('now')),
+        PRIMARY KEY (article_id, reading_level)
+    );"#,
+    // 18: when a starred article was last resurfaced for revisiting
+    "ALTER TABLE articles ADD COLUMN revisited_at TEXT;",
+    // 19: per-feed background summaries, and how many were made each day
+    r#"ALTER TABLE feeds ADD COLUMN auto_summarize INTEGER NOT NULL DEFAULT 0;
+    CREATE TABLE IF NOT EXISTS auto_summary_usage (
+        day TEXT PRIMARY KEY,
+        count INTEGER NOT NULL
+    );"#,
+    // 20: tokens and estimated cost of each summary, and spend per month
+    // (kept apart so regenerated and purged summaries still count)
+    r#"ALTER TABLE summaries ADD COLUMN input_tokens INTEGER;
+    ALTER TABLE summaries ADD COLUMN output_tokens INTEGER;
+    ALTER TABLE summaries ADD COLUMN cost_usd REAL;
+    CREATE TABLE IF NOT EXISTS ai_spend (
+        month TEXT PRIMARY KEY,
+        summaries INTEGER NOT NULL,
+        input_tokens INTEGER NOT NULL,
+        output_tokens INTEGER NOT NULL,
+        cost_usd REAL NOT NULL
+    );"#,
+    // 21: local read-later queue, in the order articles were added
+    r#"ALTER TABLE articles ADD COLUMN queued_at TEXT;
+    CREATE INDEX IF NOT EXISTS idx_articles_queued_at ON articles(queued_at);"#,
+    // 22: Raindrop collection last chosen for each feed's articles
+    "ALTER TABLE feeds ADD COLUMN raindrop_collection INTEGER;",
+    // 23: feed favicons as small PNGs (NULL when the site has none)
+    r#"CREATE TABLE IF NOT EXISTS feed_icons (
+        feed_id INTEGER PRIMARY KEY REFERENCES feeds(id) ON DELETE CASCADE,
+        icon BLOB,
+        fetched_at TEXT NOT NULL DEFAULT (datetime('now'))
+    );"#,
+    // 24: chats with Claude about an article, oldest message first
+    r#"CREATE TABLE IF NOT EXISTS chat_messages (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+        role TEXT NOT NULL,
+        content TEXT NOT NULL,
+        created_at TEXT NOT NULL DEFAULT (datetime('now'))
+    );
+    CREATE INDEX IF NOT EXISTS idx_chat_messages_article ON chat_messages(article_id);"#,
+    // 25: TL;DR, key facts and named entities of an article, as JSON
+    r#"CREATE TABLE IF NOT EXISTS key_facts (
+        article_id INTEGER PRIMARY KEY REFERENCES articles(id) ON DELETE CASCADE,
+        facts TEXT NOT NULL,
+        model_version TEXT NOT NULL,
+        extracted_at TEXT NOT NULL DEFAULT (datetime('now'))
+    );"#,
+    // 26: Hacker News and Reddit threads about an article, with their counts
+    r#"CREATE TABLE IF NOT EXISTS discussions (
+        article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+        url TEXT NOT NULL,
+        site TEXT NOT NULL,
+        title TEXT NOT NULL,
+        comments INTEGER,
+        points INTEGER,
+        checked_at TEXT NOT NULL DEFAULT (datetime('now')),
+        PRIMARY KEY (article_id, url)
+    );"#,
+    // 27: per-feed grouping of entries with similar titles in the list
+    "ALTER TABLE feeds ADD COLUMN collapse_similar INTEGER NOT NULL DEFAULT 0;",
+    // 28: summaries regenerated in another style sit beside the regular one
+    // ('' variant), so the one-summary-per-article constraint becomes per variant
+    r#"CREATE TABLE summaries_new (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+        variant TEXT NOT NULL DEFAULT '',
+        content TEXT NOT NULL,
+        model_version TEXT NOT NULL,
+        generated_at TEXT NOT NULL DEFAULT (datetime('now')),
+        source TEXT,
+        input_tokens INTEGER,
+        output_tokens INTEGER,
+        cost_usd REAL,
+        UNIQUE (article_id, variant)
+    );
+    INSERT INTO summaries_new (id, article_id, content, model_version, generated_at, source,
+                               input_tokens, output_tokens, cost_usd)
+        SELECT id, article_id, content, model_version, generated_at, source,
+               input_tokens, output_tokens, cost_usd
+        FROM summaries;
+    DROP TABLE summaries;
+    ALTER TABLE summaries_new RENAME TO summaries;
+    CREATE INDEX IF NOT EXISTS idx_summaries_article_id ON summaries(article_id);"#,
+    // 29: merge copies of an entry stored under changing guids (same link,
+    // title and date in one feed) into the oldest, keeping read and star state
+    // and moving summaries, tags and the like over where it has none
+    MERGE_DUPLICATE_ARTICLES,
+    // 30: words in each article's text, for reading time estimates (counted
+    // for older articles on startup)
+    "ALTER TABLE articles ADD COLUMN word_count INTEGER;",
+    // 31: failed fetches in a row per feed, which refreshes skip until
+    // retry_at
+    r#"ALTER TABLE feeds ADD COLUMN fetch_failures INTEGER NOT NULL DEFAULT 0;
+    ALTER TABLE feeds ADD COLUMN retry_at TEXT;
+    ALTER TABLE feeds ADD COLUMN fetch_error TEXT;"#,
+    // 32: migration 29 again, for databases upgraded from before 8 whose
+    // links weren't normalized yet when it ran
+    MERGE_DUPLICATE_ARTICLES,
+];
+
+/// Migration that needs `normalized_url` filled in, which is done in Rust
+pub const NORMALIZED_URLS_NEEDED: usize = 29;
+
+/// Merge copies of an entry stored under changing guids (same link, title and
+/// date in one feed) into the oldest. Finds nothing to merge when run again.
+const MERGE_DUPLICATE_ARTICLES: &str = r#"DROP TABLE IF EXISTS temp.duplicate_articles;
+CREATE TEMP TABLE duplicate_articles AS
+    SELECT a.id AS id, min(b.id) AS keep_id
+    FROM articles a
+    JOIN articles b ON b.feed_id = a.feed_id AND b.normalized_url = a.normalized_url
+                   AND b.title = a.title AND b.published_at IS a.published_at
+                   AND b.id < a.id
+    WHERE a.normalized_url != ''
+    GROUP BY a.id;
+UPDATE articles SET
+    is_read = is_read OR EXISTS (
+        SELECT 1 FROM duplicate_articles d JOIN articles x ON x.id = d.id
+        WHERE d.keep_id = articles.id AND x.is_read),
+    is_starred = is_starred OR EXISTS (
+        SELECT 1 FROM duplicate_articles d JOIN articles x ON x.id = d.id
+        WHERE d.keep_id = articles.id AND x.is_starred)
+    WHERE id IN (SELECT keep_id FROM duplicate_articles);
+UPDATE articles
+    SET canonical_id = (SELECT keep_id FROM duplicate_articles WHERE id = canonical_id)
+    WHERE canonical_id IN (SELECT id FROM duplicate_articles);
+UPDATE OR IGNORE summaries
+    SET article_id = (SELECT keep_id FROM duplicate_articles WHERE id = article_id)
+    WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM summaries WHERE article_id IN (SELECT id FROM duplicate_articles);
+UPDATE OR IGNORE key_facts
+    SET article_id = (SELECT keep_id FROM duplicate_articles WHERE id = article_id)
+    WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM key_facts WHERE article_id IN (SELECT id FROM duplicate_articles);
+UPDATE OR IGNORE discussions
+    SET article_id = (SELECT keep_id FROM duplicate_articles WHERE id = article_id)
+    WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM discussions WHERE article_id IN (SELECT id FROM duplicate_articles);
+UPDATE OR IGNORE translations
+    SET article_id = (SELECT keep_id FROM duplicate_articles WHERE id = article_id)
+    WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM translations WHERE article_id IN (SELECT id FROM duplicate_articles);
+UPDATE OR IGNORE simplifications
+    SET article_id = (SELECT keep_id FROM duplicate_articles WHERE id = article_id)
+    WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM simplifications WHERE article_id IN (SELECT id FROM duplicate_articles);
+UPDATE OR IGNORE chat_messages
+    SET article_id = (SELECT keep_id FROM duplicate_articles WHERE id = article_id)
+    WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM chat_messages WHERE article_id IN (SELECT id FROM duplicate_articles);
+UPDATE OR IGNORE saved_to_raindrop
+    SET article_id = (SELECT keep_id FROM duplicate_articles WHERE id = article_id)
+    WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM saved_to_raindrop WHERE article_id IN (SELECT id FROM duplicate_articles);
+UPDATE OR IGNORE article_embeddings
+    SET article_id = (SELECT keep_id FROM duplicate_articles WHERE id = article_id)
+    WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM article_embeddings WHERE article_id IN (SELECT id FROM duplicate_articles);
+UPDATE OR IGNORE article_tags
+    SET article_id = (SELECT keep_id FROM duplicate_articles WHERE id = article_id)
+    WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM article_tags WHERE article_id IN (SELECT id FROM duplicate_articles);
+UPDATE OR IGNORE sync_items
+    SET article_id = (SELECT keep_id FROM duplicate_articles WHERE id = article_id)
+    WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM sync_items WHERE article_id IN (SELECT id FROM duplicate_articles);
+DELETE FROM articles WHERE id IN (SELECT id FROM duplicate_articles);
+DROP TABLE duplicate_articles;"#;
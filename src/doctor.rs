@@ -0,0 +1,300 @@
+use std::time::Duration;
+
+use reqwest::Client;
+
+use crate::ai::Summarizer;
+use crate::config::{Config, ProxyRoute, ProxyService};
+use crate::db::Repository;
+use crate::error::Result;
+use crate::feed::FeedFetcher;
+use crate::services::{
+    load_feed_credentials, ContentFetcher, GReaderClient, HypothesisClient, RaindropClient, Speaker,
+};
+use crate::tui::detect_tty_mode;
+
+const REACHABILITY_URL: &str = "https://api.anthropic.com";
+
+/// Collects check results and prints them as a report
+#[derive(Default)]
+struct Report {
+    warnings: usize,
+    failures: usize,
+}
+
+impl Report {
+    fn section(&self, title: &str) {
+        println!();
+        println!("{}", title);
+    }
+
+    fn ok(&self, message: impl AsRef<str>) {
+        println!("  [ OK ] {}", message.as_ref());
+    }
+
+    fn warn(&mut self, message: impl AsRef<str>) {
+        self.warnings += 1;
+        println!("  [WARN] {}", message.as_ref());
+    }
+
+    fn fail(&mut self, message: impl AsRef<str>) {
+        self.failures += 1;
+        println!("  [FAIL] {}", message.as_ref());
+    }
+}
+
+/// Run all self-tests and print a diagnostic report (`speedy-reader doctor`);
+/// false when any check failed
+pub async fn run() -> Result<bool> {
+    let mut report = Report::default();
+
+    println!("SpeedyReader doctor v{}", env!("CARGO_PKG_VERSION"));
+    println!("OS: {} ({})", std::env::consts::OS, std::env::consts::ARCH);
+
+    // Configuration
+    report.section("Configuration");
+    let config_path = Config::config_path();
+    let config = if config_path.exists() {
+        match Config::load() {
+            Ok(config) => {
+                report.ok(format!("Config file parsed: {}", config_path.display()));
+                config
+            }
+            Err(e) => {
+                report.fail(format!(
+                    "Config file invalid: {} ({})",
+                    config_path.display(),
+                    e
+                ));
+                Config::default()
+            }
+        }
+    } else {
+        report.warn(format!(
+            "No config file at {}, using defaults",
+            config_path.display()
+        ));
+        Config::default()
+    };
+
+    let source = |key: &str| {
+        config
+            .secret_source(key)
+            .map(|source| source.label())
+            .unwrap_or_default()
+    };
+    match &config.claude_api_key {
+        Some(key) if key.starts_with("sk-ant-") => report.ok(format!(
+            "Claude API key present (from {})",
+            source("claude_api_key")
+        )),
+        Some(_) => report.warn("Claude API key does not look like an Anthropic key (sk-ant-...)"),
+        None => report.warn("Claude API key not set - summaries disabled"),
+    }
+    match &config.raindrop_token {
+        Some(_) => report.ok(format!("Raindrop token present (from {})", source("raindrop_token"))),
+        None => report.ok("Raindrop token not set (optional)"),
+    }
+    match &config.hypothesis_token {
+        Some(_) => report.ok(format!(
+            "Hypothes.is token present (from {})",
+            source("hypothesis_token")
+        )),
+        None => report.ok("Hypothes.is token not set (optional)"),
+    }
+    match &config.sync {
+        Some(sync) => report.ok(format!("Sync server configured: {}", sync.url)),
+        None => report.ok("Sync server not set (optional)"),
+    }
+    match Speaker::new(config.speech.command.as_deref()) {
+        Some(speaker) => report.ok(format!("Text-to-speech command: {}", speaker.program())),
+        None => report.ok("No text-to-speech command found (optional)"),
+    }
+    let term = std::env::var("TERM").unwrap_or_default();
+    if config.tty_mode.unwrap_or_else(detect_tty_mode) {
+        report.ok(format!("TERM={}: tty mode (ASCII, 8 colors)", term));
+    } else {
+        report.ok(format!("TERM={}: full Unicode and colors", term));
+    }
+
+    // Database
+    report.section("Database");
+    // Looked at as it is: a missing database isn't created, nor an old one
+    // migrated
+    let repository = if !std::path::Path::new(&config.db_path).exists() {
+        report.warn(format!("No database at {} yet (created on first start)", config.db_path));
+        None
+    } else {
+        match Repository::open_existing(&config.db_path).await {
+            Ok(repository) => {
+                report.ok(format!("Opened database: {}", config.db_path));
+                Some(repository)
+            }
+            Err(e) => {
+                report.fail(format!("Cannot open database {}: {}", config.db_path, e));
+                None
+            }
+        }
+    };
+    // Schema updates the database is waiting for; its tables don't match the
+    // queries until they are applied
+    let mut pending = 0;
+    if let Some(repository) = &repository {
+        match repository.pending_migrations().await {
+            Ok(0) => {}
+            Ok(behind) => {
+                pending = behind;
+                report.warn(format!(
+                    "Database is {} schema update(s) behind; applied on next start",
+                    behind
+                ));
+            }
+            Err(e) => report.fail(format!("Cannot read the schema version: {}", e)),
+        }
+        match repository.integrity_check().await {
+            Ok(problems) if problems.is_empty() => report.ok("Integrity check passed"),
+            Ok(problems) => {
+                for problem in problems {
+                    report.fail(format!("Integrity: {}", problem));
+                }
+            }
+            Err(e) => report.fail(format!("Integrity check failed to run: {}", e)),
+        }
+    }
+
+    // Network
+    report.section("Network");
+    for service in ProxyService::ALL {
+        if let ProxyRoute::Proxy(url) = config.proxy_for(service) {
+            report.ok(format!("{} requests go through {}", service.key(), url));
+        }
+    }
+    let client = config
+        .proxy_for(ProxyService::Claude)
+        .apply(Client::builder())
+        .timeout(Duration::from_secs(10))
+        .build()
+        .expect("Failed to create HTTP client");
+    let online = match client.head(REACHABILITY_URL).send().await {
+        Ok(_) => {
+            report.ok(format!("Reached {}", REACHABILITY_URL));
+            true
+        }
+        Err(e) => {
+            report.fail(format!("Cannot reach {}: {}", REACHABILITY_URL, e));
+            false
+        }
+    };
+
+    // API credentials
+    report.section("API credentials");
+    if !online {
+        report.warn("Skipped (offline)");
+    } else {
+        if let Some(key) = &config.claude_api_key {
+            let proxy = config.proxy_for(ProxyService::Claude);
+            match Summarizer::new(key.clone(), &config.summary, proxy)
+                .verify_credentials()
+                .await
+            {
+                Ok(()) => report.ok("Claude API key accepted"),
+                Err(e) => report.fail(format!("Claude API key rejected: {}", e)),
+            }
+        }
+        if let Some(token) = &config.raindrop_token {
+            let proxy = config.proxy_for(ProxyService::Raindrop);
+            let raindrop = RaindropClient::new(token.clone(), proxy);
+            match raindrop.verify_credentials().await {
+                Ok(()) => {
+                    report.ok("Raindrop token accepted");
+                    let default = &config.raindrop_collection;
+                    match raindrop.list_collections().await {
+                        Ok(collections)
+                            if !collections.iter().any(|c| c.title.eq_ignore_ascii_case(default)) =>
+                        {
+                            report.warn(format!(
+                                "Raindrop collection \"{}\" not found; Unsorted is preselected",
+                                default
+                            ))
+                        }
+                        Ok(_) => report.ok(format!("Raindrop collection \"{}\" found", default)),
+                        Err(e) => report.warn(format!("Couldn't list Raindrop collections: {}", e)),
+                    }
+                }
+                Err(e) => report.fail(format!("Raindrop token rejected: {}", e)),
+            }
+        }
+        if let Some(token) = &config.hypothesis_token {
+            match HypothesisClient::new(token.clone(), config.proxy_for(ProxyService::Hypothesis))
+                .verify_credentials()
+                .await
+            {
+                Ok(()) => report.ok("Hypothes.is token accepted"),
+                Err(e) => report.fail(format!("Hypothes.is token rejected: {}", e)),
+            }
+        }
+        if let Some(sync) = &config.sync {
+            let greader = GReaderClient::new(sync, config.proxy_for(ProxyService::Sync));
+            match greader.verify_credentials().await {
+                Ok(()) => report.ok("Sync server login accepted"),
+                Err(e) => report.fail(format!("Sync server login failed: {}", e)),
+            }
+        }
+    }
+
+    // Browser cookies
+    report.section("Browser cookies");
+    if config.cookie_domains.is_empty() {
+        report.ok("No cookie_domains set - full articles are fetched without cookies");
+    } else {
+        report.ok(format!("Cookies used for: {}", config.cookie_domains.join(", ")));
+        let fetcher =
+            ContentFetcher::new(&config.cookie_domains, config.proxy_for(ProxyService::Content));
+        match fetcher.check_cookie_access() {
+            Ok(count) => report.ok(format!("Firefox cookie store readable ({} cookies)", count)),
+            Err(reason) => report.warn(format!("Firefox cookies unavailable: {}", reason)),
+        }
+    }
+
+    // Feeds
+    report.section("Feeds");
+    let feeds = match &repository {
+        Some(_) if pending > 0 => {
+            report.warn("Skipped feed checks until the database schema is updated");
+            None
+        }
+        Some(repository) => match repository.get_all_feeds().await {
+            Ok(feeds) => Some(feeds),
+            Err(e) => {
+                report.fail(format!("Cannot list feeds: {}", e));
+                None
+            }
+        },
+        None => None,
+    };
+    if let Some(feeds) = feeds {
+        if feeds.is_empty() {
+            report.warn("No feeds subscribed");
+        } else if !online {
+            report.warn(format!("Skipped {} feeds (offline)", feeds.len()));
+        } else {
+            let fetcher = FeedFetcher::new(config.proxy_for(ProxyService::Feeds));
+            for feed in &feeds {
+                let credentials = load_feed_credentials(feed).await;
+                match fetcher.fetch_feed(feed.id, &feed.url, credentials.as_ref()).await {
+                    Ok(page) => {
+                        report.ok(format!("{} ({} entries)", feed.title, page.articles.len()))
+                    }
+                    Err(e) => report.fail(format!("{} <{}>: {}", feed.title, feed.url, e)),
+                }
+            }
+        }
+    }
+
+    println!();
+    println!(
+        "{} failure(s), {} warning(s)",
+        report.failures, report.warnings
+    );
+
+    Ok(report.failures == 0)
+}
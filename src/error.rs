@@ -35,6 +35,51 @@ pub enum AppError {
     #[error("Raindrop API error: {0}")]
     RaindropApi(String),
 
+    #[error("Hypothes.is API error: {0}")]
+    HypothesisApi(String),
+
+    #[error("Embedding API error: {0}")]
+    Embedding(String),
+
+    #[error("Sync error: {0}")]
+    Sync(String),
+
+    #[error("Feed directory error: {0}")]
+    FeedDirectory(String),
+
+    #[error("Discussion lookup error: {0}")]
+    Discussion(String),
+
+    #[error("Text-to-speech error: {0}")]
+    Speech(String),
+
+    #[error("Print error: {0}")]
+    Print(String),
+
+    #[error("Email error: {0}")]
+    Email(String),
+
+    #[error("Clipboard error: {0}")]
+    Clipboard(String),
+
+    #[error("Webhook error: {0}")]
+    Webhook(String),
+
+    #[error("Sharing error: {0}")]
+    Social(String),
+
+    #[error("Backup error: {0}")]
+    Backup(String),
+
+    #[error("Image error: {0}")]
+    Image(String),
+
+    #[error("Credential store error: {0}")]
+    Credentials(String),
+
+    #[error("Authentication required for {0}")]
+    Unauthorized(String),
+
     #[error("{0}")]
     Other(#[from] anyhow::Error),
 }
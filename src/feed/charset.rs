@@ -0,0 +1,79 @@
+use std::sync::LazyLock;
+
+use encoding_rs::{Encoding, UTF_8, WINDOWS_1252};
+use regex::bytes::Regex;
+
+// encoding="..." in the XML declaration
+static XML_ENCODING: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^(\s*<\?xml\b[^>]*?\bencoding\s*=\s*["'])([A-Za-z0-9._:-]+)"#).unwrap()
+});
+
+/// Re-encode a fetched feed document as UTF-8, so documents in legacy
+/// encodings parse. The encoding comes from a byte order mark, else the
+/// Content-Type charset, else the XML declaration.
+pub fn to_utf8(bytes: Vec<u8>, content_type: Option<&str>) -> Vec<u8> {
+    if let Some((encoding, bom_length)) = Encoding::for_bom(&bytes) {
+        if encoding == UTF_8 {
+            return bytes;
+        }
+        return transcode(&bytes[bom_length..], encoding);
+    }
+
+    let declared = [header_charset(content_type), xml_encoding(&bytes)];
+    let encoding = if is_utf8(&bytes) {
+        // Text in other ASCII-based encodings is practically never valid
+        // UTF-8, so a charset claiming one is a misconfigured server; UTF-16
+        // without a BOM is the exception
+        declared
+            .into_iter()
+            .flatten()
+            .find(|encoding| !encoding.is_ascii_compatible())
+            .unwrap_or(UTF_8)
+    } else {
+        declared
+            .into_iter()
+            .flatten()
+            .find(|encoding| *encoding != UTF_8)
+            .unwrap_or(WINDOWS_1252)
+    };
+
+    if encoding == UTF_8 {
+        return bytes;
+    }
+    transcode(&bytes, encoding)
+}
+
+/// Valid UTF-8, allowing for a character cut off at the end of a truncated
+/// download
+fn is_utf8(bytes: &[u8]) -> bool {
+    match std::str::from_utf8(bytes) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none(),
+    }
+}
+
+fn transcode(bytes: &[u8], encoding: &'static Encoding) -> Vec<u8> {
+    tracing::debug!("Transcoding feed from {}", encoding.name());
+    let (text, _) = encoding.decode_without_bom_handling(bytes);
+    // The parser would otherwise decode the text a second time
+    XML_ENCODING
+        .replace(text.as_bytes(), &b"${1}UTF-8"[..])
+        .into_owned()
+}
+
+fn header_charset(content_type: Option<&str>) -> Option<&'static Encoding> {
+    content_type?.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches('"').as_bytes())
+    })
+}
+
+fn xml_encoding(bytes: &[u8]) -> Option<&'static Encoding> {
+    let prolog = &bytes[..bytes.len().min(512)];
+    let captures = XML_ENCODING.captures(prolog)?;
+    // A declaration readable as ASCII can't be in UTF-16
+    Encoding::for_label(&captures[2]).filter(|encoding| encoding.is_ascii_compatible())
+}
@@ -1,12 +1,88 @@
+use std::collections::HashSet;
+use std::sync::LazyLock;
 use std::time::Duration;
 
+use chrono::{DateTime, Utc};
 use feed_rs::parser;
 use futures::stream::{self, StreamExt};
 use regex::Regex;
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, StatusCode};
+use tokio::sync::mpsc;
 
-use crate::error::Result;
-use crate::models::{Feed, NewArticle, NewFeed};
+use crate::config::ProxyRoute;
+use crate::error::{AppError, Result};
+use crate::models::{
+    synthetic_guid, Feed, FeedAuthKind, FeedCredentials, FetchUsage, NewArticle, NewFeed,
+};
+use crate::services::{first_content_image, load_feed_credentials};
+
+use super::charset::to_utf8;
+use super::opml::parse_opml;
+
+// RFC 6721 tombstone: <at:deleted-entry ref="entry id" when="..."/> (any prefix)
+static DELETED_ENTRY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"<(?:[A-Za-z_][\w.-]*:)?deleted-entry\b[^>]*?\bref\s*=\s*["']([^"']+)["']"#)
+        .unwrap()
+});
+
+// <link> tags in a page, and their attributes
+static LINK_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)<link\b[^>]*>").unwrap());
+static TAG_ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"([A-Za-z-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap()
+});
+
+const FEED_LINK_TYPES: [&str; 3] = [
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/feed+json",
+];
+
+// Closing tag of an RSS item or Atom entry (any prefix)
+static ENTRY_END: LazyLock<regex::bytes::Regex> = LazyLock::new(|| {
+    regex::bytes::Regex::new(r"</(?:[A-Za-z_][\w.-]*:)?(item|entry)\s*>").unwrap()
+});
+
+// Feed documents are downloaded up to this size; entries past it are dropped
+const MAX_FEED_BYTES: usize = 4 * 1024 * 1024;
+
+// Entries read from one feed document
+const MAX_FEED_ENTRIES: usize = 500;
+
+// Feeds linked from a page that are fetched to offer as choices
+const MAX_FEED_CANDIDATES: usize = 10;
+
+/// One fetched feed document
+#[derive(Debug, Default)]
+pub struct FeedPage {
+    pub articles: Vec<NewArticle>,
+    /// Ids of entries the publisher has deleted (Atom tombstones)
+    pub deleted_guids: Vec<String>,
+    /// Older entries, for feeds that publish RFC 5005 archives
+    pub prev_archive: Option<String>,
+}
+
+/// Outcome of refreshing one feed; the page is why the fetch failed when it did
+#[derive(Debug)]
+pub struct FeedRefresh {
+    pub feed_id: i64,
+    pub page: std::result::Result<FeedPage, String>,
+    pub usage: FetchUsage,
+}
+
+/// Sent by `refresh_all` as each feed's fetch starts and finishes
+#[derive(Debug, Clone, Copy)]
+pub enum FeedProgress {
+    Started(i64),
+    Finished(i64),
+}
+
+/// How far back to follow feed archives the first time a feed is fetched
+#[derive(Debug, Clone, Copy)]
+pub struct ArchiveBackfill {
+    pub max_pages: u32,
+    /// Entries published before this would be retired right away
+    pub since: DateTime<Utc>,
+}
 
 #[derive(Clone)]
 pub struct FeedFetcher {
@@ -14,8 +90,9 @@ pub struct FeedFetcher {
 }
 
 impl FeedFetcher {
-    pub fn new() -> Self {
-        let client = Client::builder()
+    pub fn new(proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
             .timeout(Duration::from_secs(30))
             .connect_timeout(Duration::from_secs(10))
             .user_agent("speedy-reader/1.0")
@@ -25,15 +102,77 @@ impl FeedFetcher {
         Self { client }
     }
 
-    pub async fn fetch_feed(&self, feed_id: i64, url: &str) -> Result<Vec<NewArticle>> {
-        let response = self.client.get(url).send().await?;
+    pub async fn fetch_feed(
+        &self,
+        feed_id: i64,
+        url: &str,
+        credentials: Option<&FeedCredentials>,
+    ) -> Result<FeedPage> {
+        self.fetch_page(feed_id, url, credentials, &mut FetchUsage::default()).await
+    }
+
+    /// Fetch one feed document, adding the request and downloaded bytes to `usage`
+    async fn fetch_page(
+        &self,
+        feed_id: i64,
+        url: &str,
+        credentials: Option<&FeedCredentials>,
+        usage: &mut FetchUsage,
+    ) -> Result<FeedPage> {
+        usage.requests += 1;
+        let response = authorize(self.client.get(url), credentials).send().await?;
 
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(AppError::Unauthorized(url.to_string()));
+        }
         if !response.status().is_success() {
             return Err(anyhow::anyhow!("Failed to fetch feed: HTTP {}", response.status()).into());
         }
 
-        let bytes = response.bytes().await?;
-        let feed = parser::parse(&bytes[..])?;
+        let content_type = content_type(&response);
+        let (bytes, truncated) = read_capped(response, MAX_FEED_BYTES, usage).await?;
+        let mut bytes = to_utf8(bytes, Some(&content_type));
+        if truncated {
+            tracing::warn!("Feed {} is over {} bytes, reading what fits", url, MAX_FEED_BYTES);
+            if !close_after_last_entry(&mut bytes) {
+                return Err(anyhow::anyhow!(
+                    "Feed is larger than {} MB",
+                    MAX_FEED_BYTES / 1024 / 1024
+                )
+                .into());
+            }
+        }
+
+        // Parsing and converting entries is CPU-bound; keep it off the async workers
+        let fetcher = self.clone();
+        let url = url.to_string();
+        tokio::task::spawn_blocking(move || fetcher.parse_page(feed_id, &url, &bytes))
+            .await
+            .map_err(anyhow::Error::from)?
+    }
+
+    /// Turn a fetched feed document into articles
+    fn parse_page(&self, feed_id: i64, url: &str, bytes: &[u8]) -> Result<FeedPage> {
+        // Entries without an id are left without one here, and given a
+        // stable guid below: feed-rs makes up a random one when there is no link
+        let mut feed = parser::Builder::new()
+            .id_generator(|_, _, _| String::new())
+            .build()
+            .parse(bytes)?;
+        // Feeds list newest entries first; the rest would be retired anyway
+        feed.entries.truncate(MAX_FEED_ENTRIES);
+
+        // feed-rs skips tombstones, so look for them in the raw document
+        let deleted_guids = DELETED_ENTRY
+            .captures_iter(&String::from_utf8_lossy(bytes))
+            .map(|cap| cap[1].trim().replace("&amp;", "&"))
+            .collect();
+
+        let prev_archive = feed
+            .links
+            .iter()
+            .find(|l| l.rel.as_deref() == Some("prev-archive"))
+            .map(|l| self.resolve_url(&l.href, url));
 
         let articles: Vec<NewArticle> = feed
             .entries
@@ -50,140 +189,257 @@ impl FeedFetcher {
                     html2text::from_read(html.as_bytes(), 80).ok()
                 });
 
+                let url = entry
+                    .links
+                    .first()
+                    .map(|l| l.href.clone())
+                    .unwrap_or_default();
+
+                // Prefer an image the feed declares, then the first one in the content
+                let image_url = feed_image(&entry).or_else(|| {
+                    content_html.and_then(|html| first_content_image(html, &url))
+                });
+
+                let title = entry
+                    .title
+                    .map(|t| t.content)
+                    .unwrap_or_else(|| "Untitled".to_string());
+                let guid = if entry.id.trim().is_empty() {
+                    synthetic_guid(&url, &title, entry.published)
+                } else {
+                    entry.id
+                };
+
                 NewArticle {
                     feed_id,
-                    guid: entry.id,
-                    title: entry
-                        .title
-                        .map(|t| t.content)
-                        .unwrap_or_else(|| "Untitled".to_string()),
-                    url: entry
-                        .links
-                        .first()
-                        .map(|l| l.href.clone())
-                        .unwrap_or_default(),
+                    guid,
+                    title,
+                    url,
                     author: entry.authors.first().map(|a| a.name.clone()),
                     content: content_html.cloned(),
                     content_text,
                     published_at: entry.published.or(entry.updated),
+                    image_url,
                 }
             })
             .collect();
 
-        Ok(articles)
+        Ok(FeedPage {
+            articles,
+            deleted_guids,
+            prev_archive,
+        })
+    }
+
+    /// Follow RFC 5005 `prev-archive` links from `url`, collecting entries
+    /// published since `backfill.since`. Stops at the first page with
+    /// nothing that recent, after `max_pages`, or on any error.
+    async fn fetch_archives(
+        &self,
+        feed_id: i64,
+        url: String,
+        credentials: Option<&FeedCredentials>,
+        backfill: ArchiveBackfill,
+        usage: &mut FetchUsage,
+    ) -> Vec<NewArticle> {
+        let mut articles = Vec::new();
+        let mut visited = HashSet::new();
+        let mut next = Some(url);
+
+        while let Some(url) = next.take() {
+            if visited.len() >= backfill.max_pages as usize || !visited.insert(url.clone()) {
+                break;
+            }
+            let page = match self.fetch_page(feed_id, &url, credentials, usage).await {
+                Ok(page) => page,
+                Err(e) => {
+                    tracing::debug!("Failed to fetch archive {}: {}", url, e);
+                    break;
+                }
+            };
+
+            let before = articles.len();
+            articles.extend(
+                page.articles
+                    .into_iter()
+                    .filter(|a| a.published_at.is_some_and(|at| at >= backfill.since)),
+            );
+            if articles.len() > before {
+                next = page.prev_archive;
+            }
+        }
+        articles
     }
 
-    /// Refresh all feeds concurrently with rate limiting
-    pub async fn refresh_all(&self, feeds: Vec<Feed>) -> Vec<(i64, Vec<NewArticle>)> {
-        let results: Vec<_> = stream::iter(feeds)
+    /// Refresh all feeds concurrently with rate limiting. Feeds fetched for
+    /// the first time also pick up recent entries from their archives.
+    /// Progress goes to `progress` feed by feed.
+    pub async fn refresh_all(
+        &self,
+        feeds: Vec<Feed>,
+        backfill: Option<ArchiveBackfill>,
+        progress: mpsc::UnboundedSender<FeedProgress>,
+    ) -> Vec<FeedRefresh> {
+        let progress = &progress;
+        stream::iter(feeds)
             .map(|feed| async move {
-                match self.fetch_feed(feed.id, &feed.url).await {
-                    Ok(articles) => {
-                        tracing::debug!("Fetched {} articles from {}", articles.len(), feed.title);
-                        Some((feed.id, articles))
+                let _ = progress.send(FeedProgress::Started(feed.id));
+                let credentials = load_feed_credentials(&feed).await;
+                let mut usage = FetchUsage::default();
+                let page = match self
+                    .fetch_page(feed.id, &feed.url, credentials.as_ref(), &mut usage)
+                    .await
+                {
+                    Ok(mut page) => {
+                        tracing::debug!(
+                            "Fetched {} articles from {}",
+                            page.articles.len(),
+                            feed.title
+                        );
+                        // Archives are only read when the feed is new to us
+                        let archive = page
+                            .prev_archive
+                            .take()
+                            .filter(|_| feed.last_fetched.is_none());
+                        if let (Some(url), Some(backfill)) = (archive, backfill) {
+                            let archived = self
+                                .fetch_archives(
+                                    feed.id,
+                                    url,
+                                    credentials.as_ref(),
+                                    backfill,
+                                    &mut usage,
+                                )
+                                .await;
+                            tracing::debug!("Backfilled {} archived articles", archived.len());
+                            page.articles.extend(archived);
+                        }
+                        Ok(page)
                     }
                     Err(e) => {
                         tracing::debug!("Failed to fetch {}: {}", feed.url, e);
-                        None
+                        Err(e.to_string())
                     }
+                };
+                let _ = progress.send(FeedProgress::Finished(feed.id));
+                FeedRefresh {
+                    feed_id: feed.id,
+                    page,
+                    usage,
                 }
             })
             .buffer_unordered(5) // Max 5 concurrent fetches
-            .filter_map(|r| async { r })
             .collect()
-            .await;
-
-        results
+            .await
     }
 
-    /// Discover and create a feed from a URL
-    /// If the URL is a direct RSS/Atom feed, parse it directly
-    /// If it's an HTML page, look for feed links in <link> tags
-    pub async fn discover_feed(&self, url: &str) -> Result<NewFeed> {
-        let response = self.client.get(url).send().await?;
+    /// Discover feeds from a URL
+    /// If the URL is a direct RSS/Atom feed, that is the only one
+    /// If it's an HTML page, every feed in its <link> tags, best guess at the
+    /// site's main feed first
+    /// Returns `AppError::Unauthorized` when the server wants credentials
+    pub async fn discover_feed(
+        &self,
+        url: &str,
+        credentials: Option<&FeedCredentials>,
+    ) -> Result<Vec<NewFeed>> {
+        let response = authorize(self.client.get(url), credentials).send().await?;
 
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return Err(AppError::Unauthorized(url.to_string()));
+        }
         if !response.status().is_success() {
             return Err(anyhow::anyhow!("Failed to fetch URL: HTTP {}", response.status()).into());
         }
 
         let final_url = response.url().to_string();
-        let content_type = response
-            .headers()
-            .get("content-type")
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("")
-            .to_string();
-
-        let bytes = response.bytes().await?;
+        let content_type = content_type(&response);
+        let bytes = to_utf8(response.bytes().await?.to_vec(), Some(&content_type));
 
         // Try parsing as RSS/Atom feed first
         if let Ok(feed) = parser::parse(&bytes[..]) {
-            let title = feed
-                .title
-                .map(|t| t.content)
-                .unwrap_or_else(|| "Untitled Feed".to_string());
-            let description = feed.description.map(|d| d.content);
-            let site_url = feed.links.first().map(|l| l.href.clone());
-
-            return Ok(NewFeed {
-                title,
-                url: final_url,
-                site_url,
-                description,
-            });
+            return Ok(vec![new_feed(feed, final_url)]);
         }
 
         // If content looks like HTML, search for feed links
         if content_type.contains("html") || bytes.starts_with(b"<!") || bytes.starts_with(b"<html") {
             let html = String::from_utf8_lossy(&bytes);
-            if let Some(feed_url) = self.find_feed_link(&html, &final_url) {
-                // Fetch the discovered feed URL
-                let feed_response = self.client.get(&feed_url).send().await?;
-                if feed_response.status().is_success() {
-                    let feed_bytes = feed_response.bytes().await?;
-                    if let Ok(feed) = parser::parse(&feed_bytes[..]) {
-                        let title = feed
-                            .title
-                            .map(|t| t.content)
-                            .unwrap_or_else(|| "Untitled Feed".to_string());
-                        let description = feed.description.map(|d| d.content);
-                        let site_url = feed.links.first().map(|l| l.href.clone());
-
-                        return Ok(NewFeed {
-                            title,
-                            url: feed_url,
-                            site_url,
-                            description,
-                        });
-                    }
-                }
+            let links = self.find_feed_links(&html, &final_url);
+            // Fetched to check they are feeds, keeping the ranking
+            let feeds: Vec<NewFeed> = stream::iter(links.into_iter().take(MAX_FEED_CANDIDATES))
+                .map(|feed_url| self.fetch_linked_feed(feed_url, credentials))
+                .buffered(5)
+                .filter_map(|feed| async { feed })
+                .collect()
+                .await;
+            if !feeds.is_empty() {
+                return Ok(feeds);
             }
         }
 
         Err(anyhow::anyhow!("Could not find RSS/Atom feed at this URL").into())
     }
 
-    /// Search HTML for RSS/Atom feed links
-    fn find_feed_link(&self, html: &str, base_url: &str) -> Option<String> {
-        // Look for <link rel="alternate" type="application/rss+xml" href="...">
-        // or <link rel="alternate" type="application/atom+xml" href="...">
-        let link_re = Regex::new(
-            r#"<link[^>]*rel=["']alternate["'][^>]*type=["']application/(rss|atom)\+xml["'][^>]*href=["']([^"']+)["']"#
-        ).ok()?;
+    /// Fetch a feed a page links to, None if it isn't one
+    async fn fetch_linked_feed(
+        &self,
+        url: String,
+        credentials: Option<&FeedCredentials>,
+    ) -> Option<NewFeed> {
+        let response = authorize(self.client.get(&url), credentials).send().await.ok()?;
+        if !response.status().is_success() {
+            tracing::debug!("Linked feed {} answered HTTP {}", url, response.status());
+            return None;
+        }
+        let content_type = content_type(&response);
+        let bytes = to_utf8(response.bytes().await.ok()?.to_vec(), Some(&content_type));
+        let feed = parser::parse(&bytes[..]).ok()?;
+        Some(new_feed(feed, url))
+    }
+
+    /// The feeds a site lists in its blogroll: the OPML file its home page
+    /// points to with `<link rel="blogroll">`. Empty when it has none.
+    pub async fn find_blogroll(&self, site_url: &str) -> Result<Vec<NewFeed>> {
+        let response = self.client.get(site_url).send().await?.error_for_status()?;
+        let final_url = response.url().to_string();
+        let html = response.text().await?;
+        let Some(opml_url) = link_tags(&html)
+            .into_iter()
+            .find(|tag| tag.rel.split_whitespace().any(|r| r == "blogroll"))
+            .map(|tag| self.resolve_url(&tag.href, &final_url))
+        else {
+            return Ok(Vec::new());
+        };
+        self.fetch_opml(&opml_url).await
+    }
 
-        // Also try reverse order (type before rel)
-        let link_re2 = Regex::new(
-            r#"<link[^>]*type=["']application/(rss|atom)\+xml["'][^>]*href=["']([^"']+)["']"#
-        ).ok()?;
+    /// Download an OPML file and read the feeds in it
+    pub async fn fetch_opml(&self, url: &str) -> Result<Vec<NewFeed>> {
+        let response = self.client.get(url).send().await?.error_for_status()?;
+        parse_opml(&response.text().await?)
+    }
 
-        let href = link_re
-            .captures(html)
-            .or_else(|| link_re2.captures(html))
-            .and_then(|cap: regex::Captures| cap.get(2))
-            .map(|m: regex::Match| m.as_str().to_string())?;
+    /// Search HTML for RSS/Atom feed links, the likely main feed first
+    fn find_feed_links(&self, html: &str, base_url: &str) -> Vec<String> {
+        // <link rel="alternate" type="application/rss+xml" href="..." title="...">
+        let mut links: Vec<(String, usize)> = Vec::new();
+        for tag in link_tags(html) {
+            if !tag.rel.split_whitespace().any(|r| r == "alternate")
+                || !FEED_LINK_TYPES.contains(&tag.kind.as_str())
+            {
+                continue;
+            }
+            // Resolve relative URLs
+            let url = self.resolve_url(&tag.href, base_url);
+            if !links.iter().any(|(u, _)| *u == url) {
+                let rank = feed_link_rank(&url, &tag.title);
+                links.push((url, rank));
+            }
+        }
 
-        // Resolve relative URLs
-        Some(self.resolve_url(&href, base_url))
+        // Stable: among equals, the page's order
+        links.sort_by_key(|(_, rank)| *rank);
+        links.into_iter().map(|(url, _)| url).collect()
     }
 
     /// Resolve a potentially relative URL against a base URL
@@ -202,8 +458,164 @@ impl FeedFetcher {
     }
 }
 
-impl Default for FeedFetcher {
-    fn default() -> Self {
-        Self::new()
+/// A `<link>` tag's attributes; `rel` and `type` lowercased
+struct LinkTag {
+    rel: String,
+    kind: String,
+    href: String,
+    title: String,
+}
+
+/// The `<link>` tags in a page that have an `href`, attributes in any order
+fn link_tags(html: &str) -> Vec<LinkTag> {
+    let mut tags = Vec::new();
+    for tag in LINK_TAG.find_iter(html) {
+        let mut link = LinkTag {
+            rel: String::new(),
+            kind: String::new(),
+            href: String::new(),
+            title: String::new(),
+        };
+        for attribute in TAG_ATTRIBUTE.captures_iter(tag.as_str()) {
+            let value = attribute
+                .get(2)
+                .or_else(|| attribute.get(3))
+                .map_or("", |m| m.as_str());
+            match attribute[1].to_lowercase().as_str() {
+                "rel" => link.rel = value.to_lowercase(),
+                "type" => link.kind = value.to_lowercase(),
+                "href" => link.href = value.replace("&amp;", "&"),
+                "title" => link.title = value.to_string(),
+                _ => {}
+            }
+        }
+        if !link.href.is_empty() {
+            tags.push(link);
+        }
+    }
+    tags
+}
+
+/// Read a response body up to `limit` bytes, counting what was downloaded.
+/// Returns the body and whether it was cut off.
+/// A response's Content-Type header, empty if it has none
+fn content_type(response: &reqwest::Response) -> String {
+    response
+        .headers()
+        .get("content-type")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("")
+        .to_string()
+}
+
+async fn read_capped(
+    mut response: reqwest::Response,
+    limit: usize,
+    usage: &mut FetchUsage,
+) -> Result<(Vec<u8>, bool)> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        // Sizes after decompression: reqwest doesn't expose the compressed size
+        usage.bytes += chunk.len() as u64;
+        body.extend_from_slice(&chunk);
+        if body.len() > limit {
+            return Ok((body, true));
+        }
+    }
+    Ok((body, false))
+}
+
+/// Cut a truncated RSS/Atom document after its last complete entry and close
+/// the document so it still parses. False if there is no complete entry
+/// (or the feed isn't XML).
+fn close_after_last_entry(body: &mut Vec<u8>) -> bool {
+    let Some(last) = ENTRY_END.captures_iter(body).last() else {
+        return false;
+    };
+    let end = last.get(0).map_or(0, |m| m.end());
+    let closing: &[u8] = if &last[1] == b"entry" {
+        b"</feed>"
+    } else if body.windows(8).any(|w| w == b"<rdf:RDF") {
+        b"</rdf:RDF>" // RSS 1.0 items sit beside the channel
+    } else {
+        b"</channel></rss>"
+    };
+    body.truncate(end);
+    body.extend_from_slice(closing);
+    true
+}
+
+fn new_feed(feed: feed_rs::model::Feed, url: String) -> NewFeed {
+    NewFeed {
+        title: feed
+            .title
+            .map(|t| t.content)
+            .unwrap_or_else(|| "Untitled Feed".to_string()),
+        url,
+        site_url: feed.links.first().map(|l| l.href.clone()),
+        description: feed.description.map(|d| d.content),
+        category: None,
+    }
+}
+
+/// How unlikely a linked feed is to be the site's main feed: comment feeds
+/// last, category, tag and author feeds before them
+fn feed_link_rank(url: &str, title: &str) -> usize {
+    const SECTIONS: [&str; 7] = [
+        "/category/",
+        "/categories/",
+        "/tag/",
+        "/tags/",
+        "/topic/",
+        "/author/",
+        "/label/",
+    ];
+    let url = url.to_lowercase();
+    let title = title.to_lowercase();
+    if url.contains("comment") || title.contains("comment") {
+        2
+    } else if SECTIONS.iter().any(|s| url.contains(s)) {
+        1
+    } else {
+        0
+    }
+}
+
+/// Image an entry declares through Media RSS thumbnails, image media or enclosures
+fn feed_image(entry: &feed_rs::model::Entry) -> Option<String> {
+    entry
+        .media
+        .iter()
+        .flat_map(|m| &m.thumbnails)
+        .map(|t| t.image.uri.clone())
+        .next()
+        .or_else(|| {
+            entry
+                .media
+                .iter()
+                .flat_map(|m| &m.content)
+                .filter(|c| c.content_type.as_ref().is_some_and(|t| t.ty() == "image"))
+                .find_map(|c| c.url.as_ref().map(|u| u.to_string()))
+        })
+        .or_else(|| {
+            entry
+                .links
+                .iter()
+                .find(|l| {
+                    l.rel.as_deref() == Some("enclosure")
+                        && l.media_type.as_deref().is_some_and(|t| t.starts_with("image/"))
+                })
+                .map(|l| l.href.clone())
+        })
+}
+
+/// Attach feed credentials to a request, if any
+fn authorize(request: RequestBuilder, credentials: Option<&FeedCredentials>) -> RequestBuilder {
+    match credentials {
+        Some(c) => match c.auth.kind {
+            FeedAuthKind::Basic => request.basic_auth(&c.auth.name, Some(&c.secret)),
+            FeedAuthKind::Header => request.header(c.auth.name.as_str(), c.secret.as_str()),
+        },
+        None => request,
     }
 }
@@ -1,5 +1,6 @@
 mod opml;
 mod fetcher;
+mod charset;
 
 pub use opml::{export_opml_file, parse_opml_file};
-pub use fetcher::FeedFetcher;
+pub use fetcher::{ArchiveBackfill, FeedFetcher, FeedPage, FeedProgress, FeedRefresh};
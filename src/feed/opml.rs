@@ -5,34 +5,116 @@ use crate::error::{AppError, Result};
 use crate::models::{Feed, NewFeed};
 
 pub fn parse_opml_file(path: &Path) -> Result<Vec<NewFeed>> {
-    let content = std::fs::read_to_string(path)?;
-    let opml = OPML::from_str(&content).map_err(|e| AppError::OpmlParse(e.to_string()))?;
+    parse_opml(&std::fs::read_to_string(path)?)
+}
+
+/// The feeds in an OPML document, with the folders they are in. Feedly
+/// exports are recognized and read with [`FEEDLY_RULES`].
+pub fn parse_opml(content: &str) -> Result<Vec<NewFeed>> {
+    let opml = OPML::from_str(content).map_err(|e| AppError::OpmlParse(e.to_string()))?;
 
+    let rules = if is_feedly_export(&opml) {
+        FEEDLY_RULES
+    } else {
+        OPML_RULES
+    };
     let mut feeds = Vec::new();
-    collect_feeds(&opml.body.outlines, &mut feeds);
+    collect_feeds(&opml.body.outlines, None, rules, &mut feeds);
 
     Ok(feeds)
 }
 
-fn collect_feeds(outlines: &[Outline], feeds: &mut Vec<NewFeed>) {
+/// How outlines map to feeds and folders
+#[derive(Clone, Copy)]
+struct OutlineRules {
+    /// Name nested folders "Parent / Child" rather than after the innermost one
+    nested_folders: bool,
+    /// Feedly's built-in categories ("global.uncategorized", "Uncategorized")
+    /// put feeds in no folder
+    builtin_categories: bool,
+}
+
+const OPML_RULES: OutlineRules = OutlineRules {
+    nested_folders: false,
+    builtin_categories: false,
+};
+
+const FEEDLY_RULES: OutlineRules = OutlineRules {
+    nested_folders: true,
+    builtin_categories: true,
+};
+
+/// Feedly titles its exports "... subscriptions in feedly Cloud" and, in
+/// exports from its API, lists feeds by id ("feed/https://...")
+fn is_feedly_export(opml: &OPML) -> bool {
+    let titled = opml
+        .head
+        .as_ref()
+        .and_then(|head| head.title.as_deref())
+        .is_some_and(|title| title.to_lowercase().contains("feedly"));
+    titled || has_feedly_ids(&opml.body.outlines)
+}
+
+fn has_feedly_ids(outlines: &[Outline]) -> bool {
+    outlines.iter().any(|outline| {
+        outline.xml_url.as_deref().is_some_and(|url| url.starts_with("feed/"))
+            || has_feedly_ids(&outline.outlines)
+    })
+}
+
+fn collect_feeds(
+    outlines: &[Outline],
+    category: Option<&str>,
+    rules: OutlineRules,
+    feeds: &mut Vec<NewFeed>,
+) {
     for outline in outlines {
         // Check if this outline is a feed (has xmlUrl)
         if let Some(xml_url) = &outline.xml_url {
+            let url = xml_url.strip_prefix("feed/").unwrap_or(xml_url).to_string();
+            // Some exporters leave text empty and only fill in title
+            let title = Some(outline.text.trim())
+                .filter(|text| !text.is_empty())
+                .or(outline.title.as_deref().map(str::trim).filter(|t| !t.is_empty()))
+                .map(str::to_string)
+                .unwrap_or_else(|| url.clone());
             feeds.push(NewFeed {
-                title: outline.text.clone(),
-                url: xml_url.clone(),
+                title,
+                url,
                 site_url: outline.html_url.clone(),
                 description: outline.description.clone(),
+                category: category.map(str::to_string),
             });
         }
 
-        // Recursively process nested outlines (categories/folders)
+        // Recursively process nested outlines (categories/folders); feeds take
+        // the name of their innermost folder, or the whole path
         if !outline.outlines.is_empty() {
-            collect_feeds(&outline.outlines, feeds);
+            let folder = if outline.xml_url.is_none() {
+                folder_name(outline, category, rules)
+            } else {
+                category.map(str::to_string)
+            };
+            collect_feeds(&outline.outlines, folder.as_deref(), rules, feeds);
         }
     }
 }
 
+fn folder_name(outline: &Outline, parent: Option<&str>, rules: OutlineRules) -> Option<String> {
+    let name = Some(outline.text.trim())
+        .filter(|text| !text.is_empty())
+        .or(outline.title.as_deref().map(str::trim))
+        .unwrap_or_default();
+    let builtin = name.starts_with("global.") || name == "Uncategorized";
+    if name.is_empty() || (rules.builtin_categories && builtin) {
+        return parent.map(str::to_string);
+    }
+    match parent {
+        Some(parent) if rules.nested_folders => Some(format!("{} / {}", parent, name)),
+        _ => Some(name.to_string()),
+    }
+}
+
 pub fn export_opml_file(path: &Path, feeds: &[Feed]) -> Result<()> {
     let mut opml = OPML::default();
     opml.head = Some(opml::Head {
@@ -49,7 +131,28 @@ pub fn export_opml_file(path: &Path, feeds: &[Feed]) -> Result<()> {
             description: feed.description.clone(),
             ..Default::default()
         };
-        opml.body.outlines.push(outline);
+
+        // Feeds with a category go inside a folder outline of that name
+        let Some(category) = &feed.category else {
+            opml.body.outlines.push(outline);
+            continue;
+        };
+        let index = match opml
+            .body
+            .outlines
+            .iter()
+            .position(|o| o.xml_url.is_none() && o.text == *category)
+        {
+            Some(index) => index,
+            None => {
+                opml.body.outlines.push(Outline {
+                    text: category.clone(),
+                    ..Default::default()
+                });
+                opml.body.outlines.len() - 1
+            }
+        };
+        opml.body.outlines[index].outlines.push(outline);
     }
 
     let content = opml.to_string().map_err(|e| AppError::OpmlParse(e.to_string()))?;
@@ -2,8 +2,10 @@ pub mod ai;
 pub mod app;
 pub mod config;
 pub mod db;
+pub mod doctor;
 pub mod error;
 pub mod feed;
+pub mod logging;
 pub mod models;
 pub mod services;
 pub mod tui;
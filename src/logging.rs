@@ -0,0 +1,108 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+
+use tracing_subscriber::EnvFilter;
+
+// Past this size the log moves to `<name>.1`, replacing the previous one
+const MAX_LOG_BYTES: u64 = 1024 * 1024;
+
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+/// Send tracing output to `path` rather than stderr, where it would draw over
+/// the TUI. Warnings and errors are logged by default; each `-v` adds a level
+/// of detail for this app, and `RUST_LOG` overrides it all.
+pub fn init(path: PathBuf, verbosity: u8) -> io::Result<()> {
+    let level = match verbosity {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(format!("warn,speedy_reader={}", level)));
+    let file = RotatingFile::open(path.clone())?;
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .init();
+    let _ = LOG_PATH.set(path);
+    Ok(())
+}
+
+/// The log file in use, if logging started
+pub fn log_path() -> Option<&'static Path> {
+    LOG_PATH.get().map(PathBuf::as_path)
+}
+
+/// Up to `count` of the latest log lines, oldest first, reaching back into
+/// the rotated file when the current one is short
+pub fn recent_lines(count: usize) -> io::Result<Vec<String>> {
+    let Some(path) = log_path() else {
+        return Ok(Vec::new());
+    };
+    let mut lines = Vec::new();
+    for file in [path.to_path_buf(), rotated_path(path)] {
+        if lines.len() >= count {
+            break;
+        }
+        let bytes = match fs::read(&file) {
+            Ok(bytes) => bytes,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        let text = String::from_utf8_lossy(&bytes);
+        lines.extend(text.lines().rev().take(count - lines.len()).map(String::from));
+    }
+    lines.reverse();
+    Ok(lines)
+}
+
+/// Appends to the log, starting over in a fresh file once it grows too big
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFile {
+    fn open(path: PathBuf) -> io::Result<Self> {
+        if let Some(dir) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self { path, file, size })
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        fs::rename(&self.path, rotated_path(&self.path))?;
+        self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    // Events arrive one per write, so a rotation never splits a line
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size >= MAX_LOG_BYTES {
+            self.rotate()?;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn rotated_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".1");
+    PathBuf::from(name)
+}
@@ -1,11 +1,10 @@
+use std::collections::HashSet;
 use std::io;
 use std::path::PathBuf;
 use std::time::Duration;
 
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    event::{self, Event},
 };
 use crossterm::event::{KeyEventKind};
 use ratatui::prelude::*;
@@ -14,33 +13,294 @@ mod ai;
 mod app;
 mod config;
 mod db;
+mod doctor;
 mod error;
 mod feed;
+mod logging;
 mod models;
 mod services;
 mod tui;
 
 use app::App;
-use config::Config;
-use error::Result;
-use tui::{draw, handle_key_event};
+use config::{Config, GraphicsProtocol};
+use error::{AppError, Result};
+use tui::{
+    clear_images, draw, draw_image, handle_key_event, handle_mouse_event, icon_image_id,
+    install_panic_hook, set_progress, set_title, transmit_icon, Scheduler, TerminalGuard, Tick,
+};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging (only show warnings and errors by default)
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::WARN.into()),
-        )
-        .with_writer(std::io::stderr)
-        .init();
+    // Parse command line arguments; logging flags may go anywhere, so take
+    // them out before looking at the rest
+    let mut args: Vec<String> = Vec::new();
+    let mut verbosity = 0;
+    let mut log_file = None;
+    let mut rest = std::env::args();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "-v" | "-vv" | "-vvv" => verbosity += arg.len() as u8 - 1,
+            "--verbose" => verbosity += 1,
+            "--log-file" => log_file = rest.next().map(PathBuf::from),
+            _ => args.push(arg),
+        }
+    }
+
+    // Load configuration; doctor reports it if broken
+    let loaded = Config::load();
+
+    // Log to a file (warnings and errors unless -v is given), never over the TUI
+    let log_path = log_file
+        .or_else(|| loaded.as_ref().ok().map(Config::log_path))
+        .unwrap_or_else(config::default_log_path);
+    if let Err(e) = logging::init(log_path.clone(), verbosity) {
+        eprintln!("Cannot write log to {}: {}", log_path.display(), e);
+    }
+
+    // Panics are logged for reporting and never leave the terminal in raw mode
+    install_panic_hook(config::data_dir().join(CRASH_LOG));
 
-    // Parse command line arguments
-    let args: Vec<String> = std::env::args().collect();
+    // Diagnostics run before using the config so a broken one gets reported;
+    // failed checks show in the exit status, for scripts
+    if args.len() >= 2 && args[1] == "doctor" {
+        if !doctor::run().await? {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let mut config = loaded?;
+
+    // Move an API key or token into the OS keyring and exit
+    if args.len() >= 3 && args[1] == "set-secret" {
+        let Some(&(key, _)) = config::SECRETS.iter().find(|(key, _)| *key == args[2]) else {
+            let keys: Vec<&str> = config::SECRETS.iter().map(|(key, _)| *key).collect();
+            eprintln!("Unknown secret {}, expected one of: {}", args[2], keys.join(", "));
+            return Ok(());
+        };
+        println!("Value for {} (leave empty to move the one in config.toml):", key);
+        let mut value = String::new();
+        io::stdin().read_line(&mut value)?;
+        let value = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        config.store_secret_in_keyring(key, value)?;
+        println!("Stored {} in the keyring", key);
+        return Ok(());
+    }
 
-    // Load configuration
-    let config = Config::load()?;
+    // Export starred articles as Markdown notes and exit
+    if args.len() >= 2 && args[1] == "export-notes" {
+        let Some(dir) = config.notes_path() else {
+            eprintln!("Set notes_dir in {:?} first", Config::config_path());
+            return Ok(());
+        };
+        let repository = db::Repository::new(&config.db_path).await?;
+        let export = services::export_starred_notes(&repository, &dir).await?;
+        println!(
+            "Exported {} notes to {:?} ({} already there)",
+            export.written, dir, export.skipped
+        );
+        return Ok(());
+    }
+
+    // Publish starred articles with their summaries as an Atom feed, to a
+    // file or over HTTP
+    if args.len() >= 3 && args[1] == "starred-feed" {
+        let repository = db::Repository::new(&config.db_path).await?;
+        if args[2] == "--serve" {
+            let addr = args.get(3).map_or(STARRED_FEED_ADDR, String::as_str);
+            println!("Serving starred articles at http://{}/ (Ctrl-C to stop)", addr);
+            services::serve_starred_feed(repository, addr).await?;
+        } else {
+            let path = PathBuf::from(&args[2]);
+            let count = services::export_starred_feed(&repository, &path).await?;
+            println!("Wrote {} starred articles to {}", count, path.display());
+        }
+        return Ok(());
+    }
+
+    // Print an article with its summary and exit
+    if args.len() >= 3 && args[1] == "print" {
+        let Ok(id) = args[2].parse::<i64>() else {
+            eprintln!("Expected an article id, got {}", args[2]);
+            return Ok(());
+        };
+        let repository = db::Repository::new(&config.db_path).await?;
+        let Some(article) = repository.get_article(id).await? else {
+            eprintln!("No article with id {}", id);
+            return Ok(());
+        };
+        let summary = repository.get_summary(id).await?;
+        let text = services::render_print_text(&article, summary.as_ref());
+        services::print_text(&config.print, text).await?;
+        println!("Sent \"{}\" to the printer", article.title);
+        return Ok(());
+    }
+
+    // Round up starred, unread or queued articles with their summaries as an
+    // HTML digest, written out or emailed
+    if args.len() >= 2 && args[1] == "digest" {
+        let mut selection = services::DigestSelection::Starred;
+        let mut days = DIGEST_DAYS;
+        let mut email = false;
+        let mut path = None;
+        let mut rest = args[2..].iter();
+        while let Some(arg) = rest.next() {
+            if let Some(chosen) = services::DigestSelection::from_flag(arg) {
+                selection = chosen;
+            } else if arg == "--email" {
+                email = true;
+            } else if arg == "--days" {
+                match rest.next().and_then(|n| n.parse::<i64>().ok()) {
+                    Some(n) if n > 0 => days = n,
+                    _ => {
+                        eprintln!("Expected a number of days after --days");
+                        return Ok(());
+                    }
+                }
+            } else if arg.starts_with("--") {
+                eprintln!(
+                    "Usage: speedy-reader digest [--starred|--unread|--queue] [--days N] \
+                     [--email | <file>]"
+                );
+                return Ok(());
+            } else {
+                path = Some(PathBuf::from(arg));
+            }
+        }
+        if email && (config.email.to.is_empty() || config.email.host.is_none()) {
+            eprintln!("Set to and host in [email] in {:?} first", Config::config_path());
+            return Ok(());
+        }
+        let repository = db::Repository::new(&config.db_path).await?;
+        let title = &config.email.subject;
+        let Some(digest) = services::render_digest(&repository, selection, days, title).await?
+        else {
+            println!("No {} articles in the last {} days", selection.label(), days);
+            return Ok(());
+        };
+        if email {
+            services::send_digest(&config.email, config.smtp_password.as_deref(), &digest)
+                .await?;
+            println!("Emailed {} articles to {}", digest.articles, config.email.to.join(", "));
+        } else if let Some(path) = path {
+            std::fs::write(&path, &digest.html)?;
+            println!("Wrote {} articles to {}", digest.articles, path.display());
+        } else {
+            print!("{}", digest.html);
+        }
+        return Ok(());
+    }
+
+    // Database maintenance: integrity check, vacuum, backup or restore, and exit
+    if args.len() >= 2 && args[1] == "db" {
+        let repository = db::Repository::new(&config.db_path).await?;
+        match args.get(2).map(String::as_str) {
+            Some("check") => {
+                let problems = repository.integrity_check().await?;
+                if problems.is_empty() {
+                    println!("{}: ok", config.db_path);
+                }
+                for problem in problems {
+                    println!("{}: {}", config.db_path, problem);
+                }
+            }
+            Some("vacuum") => {
+                let before = database_size(&config.db_path);
+                let compressed = repository.compress_content().await?;
+                if compressed > 0 {
+                    println!("Compressed the content of {} articles", compressed);
+                }
+                repository.vacuum().await?;
+                println!(
+                    "Vacuumed {}: {} KiB, was {} KiB",
+                    config.db_path,
+                    database_size(&config.db_path) / 1024,
+                    before / 1024
+                );
+            }
+            Some("backup") if args.len() >= 4 => {
+                let path = PathBuf::from(&args[3]);
+                repository.backup_to(path.clone()).await?;
+                println!("Backed up {} to {}", config.db_path, path.display());
+            }
+            Some("restore") if args.len() >= 4 => {
+                let path = PathBuf::from(&args[3]);
+                if !path.is_file() {
+                    return Err(AppError::Backup(format!("no backup at {}", path.display())));
+                }
+                // Keep what is being replaced, in case the wrong backup was picked
+                let previous = PathBuf::from(format!("{}.before-restore", config.db_path));
+                repository.backup_to(previous.clone()).await?;
+                repository.restore_from(path.clone()).await?;
+                // Bring an older backup up to the current schema
+                db::Repository::new(&config.db_path).await?;
+                println!(
+                    "Restored {} from {} (the previous database is in {})",
+                    config.db_path,
+                    path.display(),
+                    previous.display()
+                );
+            }
+            _ => eprintln!("Usage: speedy-reader db <check|vacuum|backup <file>|restore <file>>"),
+        }
+        return Ok(());
+    }
+
+    // Back up the database to JSON, or merge such a backup in, and exit
+    if args.len() >= 2 && (args[1] == "export" || args[1] == "import") {
+        let (mut format, mut path) = (None, None);
+        let mut rest = args[2..].iter();
+        while let Some(arg) = rest.next() {
+            if arg == "--format" {
+                format = rest.next().map(String::as_str);
+            } else {
+                path = Some(PathBuf::from(arg));
+            }
+        }
+        let (Some("json"), Some(path)) = (format, path) else {
+            eprintln!("Usage: speedy-reader {} --format json <file>", args[1]);
+            return Ok(());
+        };
+        let repository = db::Repository::new(&config.db_path).await?;
+        if args[1] == "export" {
+            let (feeds, articles) = services::export_json(&repository, &path).await?;
+            println!("Exported {} feeds and {} articles to {}", feeds, articles, path.display());
+        } else {
+            let import = services::import_json(&repository, &path).await?;
+            println!(
+                "Imported {} feeds and {} articles from {} ({} already stored, merged)",
+                import.feeds,
+                import.articles,
+                path.display(),
+                import.merged
+            );
+        }
+        return Ok(());
+    }
+
+    // Export subscriptions as OPML, optionally limited to some categories, and exit
+    if args.len() >= 3 && args[1] == "--export" {
+        let categories: Vec<&String> = args[3..]
+            .windows(2)
+            .filter(|pair| pair[0] == "--category")
+            .map(|pair| &pair[1])
+            .collect();
+        let repository = db::Repository::new(&config.db_path).await?;
+        let feeds: Vec<_> = repository
+            .get_all_feeds()
+            .await?
+            .into_iter()
+            .filter(|f| {
+                categories.is_empty()
+                    || f.category
+                        .as_ref()
+                        .is_some_and(|c| categories.iter().any(|want| want.eq_ignore_ascii_case(c)))
+            })
+            .collect();
+        feed::export_opml_file(&PathBuf::from(&args[2]), &feeds)?;
+        println!("Exported {} feeds to {}", feeds.len(), args[2]);
+        return Ok(());
+    }
 
     // Check for --import flag
     let import_path = if args.len() >= 3 && args[1] == "--import" {
@@ -49,44 +309,99 @@ async fn main() -> Result<()> {
         None
     };
 
-    // Check for --refresh flag (headless refresh)
+    // Check for --refresh flag (headless refresh), which may be a dry run
+    // and report in JSON
     let headless_refresh = args.len() >= 2 && args[1] == "--refresh";
+    let dry_run = headless_refresh && args[2..].iter().any(|a| a == "--dry-run");
+    let json = headless_refresh && args[2..].iter().any(|a| a == "--json");
 
-    // Initialize app
-    let mut app = App::new(&config).await?;
+    // Initialize app; a dry run leaves the database as it is, old articles
+    // included
+    let mut app = App::new(&config, !dry_run).await?;
 
     // If import path provided, import OPML and exit
     if let Some(path) = import_path {
-        app.import_opml(&path).await?;
-        println!("Imported feeds from {:?}", path);
+        let source = path.to_string_lossy();
+        let import = if source.starts_with("http://") || source.starts_with("https://") {
+            app.import_opml_url(&source).await?
+        } else {
+            app.import_opml(&path).await?
+        };
+        println!("{} from {}", import, source);
         return Ok(());
     }
 
-    // If headless refresh, just refresh and exit
+    // If headless refresh, just refresh and exit; timers firing in the
+    // quiet hours do nothing
     if headless_refresh {
-        app.refresh_feeds_blocking().await?;
-        println!("Refreshed {} feeds", app.feeds.len());
+        if config.is_quiet_now() && !dry_run {
+            if json {
+                println!("[]");
+            } else {
+                println!("Quiet hours, not refreshing");
+            }
+            return Ok(());
+        }
+        let now = chrono::Utc::now();
+        let waiting: HashSet<i64> = app
+            .feeds
+            .iter()
+            .filter(|f| f.backoff.is_waiting(now))
+            .map(|f| f.id)
+            .collect();
+        let reports = if dry_run {
+            app.preview_refresh().await?
+        } else {
+            let last_id = app.last_article_id();
+            app.refresh_feeds_blocking().await?;
+            app.refresh_reports(last_id, &waiting)
+        };
+        if json {
+            println!("{}", serde_json::to_string_pretty(&reports)?);
+            return Ok(());
+        }
+        if dry_run {
+            for report in &reports {
+                match (&report.error, report.new_articles.len()) {
+                    (Some(error), _) if report.skipped => {
+                        println!("{}: skipped, failing ({})", report.feed, error)
+                    }
+                    (Some(error), _) => println!("{}: failed, {}", report.feed, error),
+                    (None, 0) => println!("{}: nothing new", report.feed),
+                    (None, n) => println!("{}: {} new", report.feed, n),
+                }
+            }
+        }
+        let verb = if dry_run { "Would refresh" } else { "Refreshed" };
+        match waiting.len() {
+            0 => println!("{} {} feeds", verb, app.feeds.len()),
+            skipped => println!(
+                "{} {} feeds, skipped {} failing ones",
+                verb,
+                app.feeds.len() - skipped,
+                skipped
+            ),
+        }
         return Ok(());
     }
 
-    // Setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    // List today's starred article to revisit first, then pick up where the
+    // last session left off
+    app.check_revisit().await?;
+    app.restore_session().await?;
+    app.start_icon_fetch();
+    app.start_comment_counts();
+
+    // Setup terminal; the guard restores it however the app exits
+    let guard = TerminalGuard::enter(config.terminal_title)?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
     // Run the app
-    let result = run_app(&mut terminal, &mut app).await;
+    let result = run_app(&mut terminal, &mut app, Scheduler::new(&config)).await;
 
     // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    drop(guard);
 
     if let Err(e) = result {
         eprintln!("Error: {}", e);
@@ -95,36 +410,181 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
+// Panic reports, in the data directory
+const CRASH_LOG: &str = "crash.log";
+
+// Where `starred-feed --serve` listens without an address
+const STARRED_FEED_ADDR: &str = "127.0.0.1:8787";
+
+// How far back a digest reaches unless --days says otherwise
+const DIGEST_DAYS: i64 = 7;
+
+// Upper bound on how long to wait for input, so background results show up promptly
+const MAX_POLL_TIMEOUT: Duration = Duration::from_millis(100);
+
+async fn run_app<B: Backend + io::Write>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    mut scheduler: Scheduler,
+) -> Result<()> {
+    // Article and screen area of the lead image currently on screen
+    let mut shown_image: Option<(i64, Rect)> = None;
+    // Title and busy state last sent to the terminal
+    let mut shown_title: Option<(String, bool)> = None;
+    // Feeds whose icon Kitty has been sent
+    let mut sent_icons: HashSet<i64> = HashSet::new();
+
     loop {
+        // Feed icons go to Kitty once; the list then shows them like text
+        if app.image_protocol == Some(GraphicsProtocol::Kitty) {
+            for (feed_id, icon) in &app.feed_icons {
+                if sent_icons.insert(*feed_id) {
+                    transmit_icon(terminal.backend_mut(), icon_image_id(*feed_id), &icon.image)?;
+                }
+            }
+        }
+
         terminal.draw(|frame| draw(frame, app))?;
 
-        // Advance spinner animation
-        app.tick_spinner();
+        if let Some((title, busy)) = app.window_title() {
+            let (old_title, was_busy) = shown_title.take().unwrap_or_default();
+            if title != old_title {
+                set_title(terminal.backend_mut(), &title)?;
+            }
+            if busy != was_busy {
+                set_progress(terminal.backend_mut(), busy)?;
+            }
+            shown_title = Some((title, busy));
+        }
+
+        // Lead images are written straight to the terminal, over cells the UI left alone
+        if let Some(protocol) = app.image_protocol {
+            let placement = app
+                .selected_article()
+                .map(|a| a.id)
+                .zip(app.image_area.get());
+            if placement != shown_image {
+                if shown_image.is_some() {
+                    // Repaint the text the old image covered
+                    clear_images(terminal.backend_mut(), protocol)?;
+                    terminal.clear()?;
+                    terminal.draw(|frame| draw(frame, app))?;
+                }
+                if let (Some((_, area)), Some(image)) = (placement, app.preview_image()) {
+                    draw_image(terminal.backend_mut(), protocol, image, area)?;
+                }
+                shown_image = placement;
+            }
+        }
+
+        if let Some(minutes) = app.take_refresh_interval_change() {
+            scheduler.set_refresh_interval(minutes);
+        }
+
+        // A count or `g` typed on its own acts once nothing follows it
+        app.expire_key_prefix().await?;
+
+        // Run periodic work that has come due, independent of input activity
+        for tick in scheduler.due() {
+            match tick {
+                Tick::Animation => app.tick_spinner(),
+                Tick::ReadTimer => app.check_read_timer().await?,
+                Tick::AutoRefresh => app.auto_refresh(),
+                Tick::Revisit => app.check_revisit().await?,
+                Tick::RaindropSync => app.start_raindrop_sync(),
+            }
+        }
 
         // Poll for completed summary results
         app.poll_summary_result().await?;
 
+        // Poll for completed translations and plain-language rewrites
+        app.poll_translation_result().await?;
+        app.poll_simplify_result().await?;
+
+        // Poll for extracted key facts
+        app.poll_key_facts_result().await?;
+
+        // Poll for looked-up discussions and comment counts
+        app.poll_discussion_result().await?;
+        app.poll_comment_count_result();
+
+        // Poll for answers in an article chat
+        app.poll_chat_result().await?;
+
+        // Poll for drafted and published posts sharing an article
+        app.poll_share_result();
+        app.poll_raindrop_tags_result();
+
         // Poll for completed refresh results
         app.poll_refresh_result().await?;
 
-        // Poll for completed feed discovery results
+        // Poll for a completed sync with the Google Reader API server
+        app.poll_sync_result().await?;
+
+        // Poll for a completed reconcile with Raindrop.io bookmarks
+        app.poll_raindrop_sync_result().await?;
+
+        // Poll for looked-up feed favicons
+        app.poll_icon_result();
+
+        // Poll for finished webhook deliveries and background summaries
+        app.poll_webhook_result();
+        app.poll_auto_summary_result().await?;
+
+        // Poll for completed feed discovery results and downloaded OPML files
         app.poll_discovery_result().await?;
+        app.poll_opml_download().await?;
+
+        // Poll for feed directory search results and suggested feeds
+        app.poll_directory_result();
+        app.poll_suggestion_result();
+
+        // Poll for fetched or created Hypothes.is annotations
+        app.poll_annotation_result();
+
+        // Notice when text-to-speech has finished
+        app.poll_speech();
+
+        // Load the selected article's lead image and pick up finished ones
+        app.load_image_preview();
+        app.poll_image_result().await?;
+
+        // Find articles related to the selected one
+        app.load_related();
+        app.poll_related_result();
+
+        // Poll for semantic search and embedding backfill results
+        app.poll_search_result().await?;
+        app.poll_embedding_result();
+
+        // Pick up changes made by other running instances
+        app.poll_external_changes().await?;
 
         // Poll for events with timeout to allow async operations
-        if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    if let Some(action) =
-                        handle_key_event(key, app.tag_input_active, app.feed_input_active, app.opml_input_active, app.opml_export_active, app.show_help)
-                    {
-                        let should_quit = app.handle_action(action).await?;
-                        if should_quit {
-                            return Ok(());
-                        }
-                    }
+        if event::poll(scheduler.until_next().min(MAX_POLL_TIMEOUT))? {
+            let action = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    handle_key_event(key, app.input_mode())
+                }
+                Event::Mouse(mouse) => handle_mouse_event(mouse, app.input_mode()),
+                _ => None,
+            };
+            if let Some(action) = action {
+                let should_quit = app.handle_action(action).await?;
+                if should_quit {
+                    return Ok(());
                 }
             }
         }
     }
 }
+
+/// Bytes taken by the database, counting its write-ahead log
+fn database_size(db_path: &str) -> u64 {
+    [db_path.to_string(), format!("{}-wal", db_path)]
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum()
+}
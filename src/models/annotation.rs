@@ -0,0 +1,13 @@
+use chrono::{DateTime, Utc};
+
+/// A Hypothes.is annotation (or highlight, when `text` is empty) on an article URL
+#[derive(Debug, Clone)]
+pub struct Annotation {
+    /// Highlighted passage, if the annotation is anchored to text
+    pub quote: Option<String>,
+    pub text: String,
+    pub tags: Vec<String>,
+    /// Visible only to the owner ("Only me" on Hypothes.is)
+    pub is_private: bool,
+    pub updated_at: DateTime<Utc>,
+}
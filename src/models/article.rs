@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Local, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,6 +14,67 @@ pub struct Article {
     pub published_at: Option<DateTime<Utc>>,
     pub fetched_at: DateTime<Utc>,
     pub feed_title: Option<String>,
+    pub is_read: bool,
+    pub is_starred: bool,
+    /// Lead image (from the feed, or the page's og:image once looked up)
+    pub image_url: Option<String>,
+    /// Set when this is a copy of an article first seen in another feed
+    pub canonical_id: Option<i64>,
+    /// Other feeds carrying the same story (for canonical articles)
+    pub also_in_feeds: usize,
+    /// Local tags, sorted
+    pub tags: Vec<String>,
+    /// Deadline or event date found in the title/content (for deadline sorting)
+    #[serde(skip)]
+    pub deadline: Option<NaiveDate>,
+    /// Set while the article is in the trash
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// When the article was added to the reading queue, while it is queued
+    pub queued_at: Option<DateTime<Utc>>,
+    /// Words in the article's text, counted when it was stored
+    pub word_count: u32,
+}
+
+impl Article {
+    pub fn is_trashed(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
+    /// How old the article is, by its local publication (or fetch) date
+    pub fn age_group(&self, today: NaiveDate) -> AgeGroup {
+        let posted = self.published_at.unwrap_or(self.fetched_at);
+        AgeGroup::of(posted.with_timezone(&Local).date_naive(), today)
+    }
+}
+
+/// Date header the article list is grouped under
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgeGroup {
+    Today,
+    Yesterday,
+    ThisWeek,
+    Older,
+}
+
+impl AgeGroup {
+    /// Group of a date; future dates count as today
+    pub fn of(date: NaiveDate, today: NaiveDate) -> Self {
+        match today.signed_duration_since(date).num_days() {
+            ..=0 => AgeGroup::Today,
+            1 => AgeGroup::Yesterday,
+            2..=6 => AgeGroup::ThisWeek,
+            _ => AgeGroup::Older,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AgeGroup::Today => "Today",
+            AgeGroup::Yesterday => "Yesterday",
+            AgeGroup::ThisWeek => "This Week",
+            AgeGroup::Older => "Older",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -26,4 +87,5 @@ pub struct NewArticle {
     pub content: Option<String>,
     pub content_text: Option<String>,
     pub published_at: Option<DateTime<Utc>>,
+    pub image_url: Option<String>,
 }
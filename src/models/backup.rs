@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{FeedRules, SummarySource};
+
+/// Format version written to backups; newer files are refused
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Portable copy of the database: feeds with their articles, read and star
+/// state, tags and summaries
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backup {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub feeds: Vec<BackupFeed>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupFeed {
+    pub url: String,
+    pub title: String,
+    pub site_url: Option<String>,
+    pub description: Option<String>,
+    pub category: Option<String>,
+    #[serde(default)]
+    pub rules: FeedRules,
+    #[serde(default)]
+    pub articles: Vec<BackupArticle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupArticle {
+    pub guid: String,
+    pub title: String,
+    pub url: String,
+    pub author: Option<String>,
+    pub content: Option<String>,
+    pub content_text: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub fetched_at: DateTime<Utc>,
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub is_read: bool,
+    #[serde(default)]
+    pub is_starred: bool,
+    /// Set while the article is in the trash
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Set while the article is in the reading queue
+    pub queued_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub summary: Option<BackupSummary>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupSummary {
+    pub content: String,
+    pub model_version: String,
+    pub generated_at: DateTime<Utc>,
+    pub source: Option<SummarySource>,
+}
+
+/// What importing a backup added
+#[derive(Debug, Default)]
+pub struct BackupImport {
+    pub feeds: usize,
+    pub articles: usize,
+    /// Articles already stored, whose read/star state and tags were merged
+    pub merged: usize,
+}
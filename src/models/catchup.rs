@@ -0,0 +1,87 @@
+use chrono::Utc;
+
+use super::Article;
+
+// Average adult silent reading speed
+const WORDS_PER_MINUTE: usize = 230;
+
+/// Words in an article's text, or its HTML when there is no text version
+pub fn count_words(content_text: Option<&str>, content: Option<&str>) -> u32 {
+    content_text
+        .or(content)
+        .map(|c| c.split_whitespace().count())
+        .unwrap_or(0) as u32
+}
+
+/// Estimated reading time in whole minutes (at least 1)
+pub fn estimate_reading_minutes(article: &Article) -> u32 {
+    (article.word_count as usize).div_ceil(WORDS_PER_MINUTE).max(1) as u32
+}
+
+/// Priority score for an unread article: newer is better, with a mild
+/// preference for articles that fit comfortably into a short session
+pub fn priority_score(article: &Article) -> f64 {
+    let age_days = article
+        .published_at
+        .unwrap_or(article.fetched_at)
+        .signed_duration_since(Utc::now())
+        .num_hours()
+        .unsigned_abs() as f64
+        / 24.0;
+    let recency = 1.0 / (1.0 + age_days);
+
+    let minutes = estimate_reading_minutes(article) as f64;
+    let length_penalty = 1.0 / (1.0 + (minutes / 15.0));
+
+    recency * 0.7 + length_penalty * 0.3
+}
+
+/// A prioritized, time-boxed reading list walked through in order
+#[derive(Debug, Clone)]
+pub struct CatchUpPlan {
+    pub article_ids: Vec<i64>,
+    pub position: usize,
+    pub budget_minutes: u32,
+    pub planned_minutes: u32,
+}
+
+impl CatchUpPlan {
+    /// Pick the highest-value unread articles that fit into `budget_minutes`
+    pub fn build(articles: &[&Article], budget_minutes: u32) -> Self {
+        let mut candidates: Vec<(&Article, f64, u32)> = articles
+            .iter()
+            .filter(|a| !a.is_read)
+            .map(|a| (*a, priority_score(a), estimate_reading_minutes(a)))
+            .collect();
+
+        // Greedy knapsack: best score per minute first
+        candidates.sort_by(|a, b| (b.1 / b.2 as f64).total_cmp(&(a.1 / a.2 as f64)));
+
+        let mut planned_minutes = 0;
+        let mut selected = Vec::new();
+        for (article, score, minutes) in candidates {
+            if planned_minutes + minutes <= budget_minutes {
+                planned_minutes += minutes;
+                selected.push((article.id, score));
+            }
+        }
+
+        // Read the most important ones first
+        selected.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        Self {
+            article_ids: selected.into_iter().map(|(id, _)| id).collect(),
+            position: 0,
+            budget_minutes,
+            planned_minutes,
+        }
+    }
+
+    pub fn current(&self) -> Option<i64> {
+        self.article_ids.get(self.position).copied()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position >= self.article_ids.len()
+    }
+}
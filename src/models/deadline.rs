@@ -0,0 +1,122 @@
+use std::sync::LazyLock;
+
+use chrono::{Datelike, NaiveDate};
+use regex::Regex;
+
+// Only look at the start of the content; event dates sit near the top
+const SCAN_CHARS: usize = 2000;
+
+static ISO_DATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(\d{4})-(\d{1,2})-(\d{1,2})\b").unwrap());
+
+// "March 15, 2026", "Mar 15", "March 15th"
+static MONTH_DAY: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]*\.?\s+(\d{1,2})(?:st|nd|rd|th)?\b(?:,?\s+(\d{4}))?").unwrap()
+});
+
+// "15 March 2026", "15th Mar"
+static DAY_MONTH: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(\d{1,2})(?:st|nd|rd|th)?\s+(jan|feb|mar|apr|may|jun|jul|aug|sep|oct|nov|dec)[a-z]*\.?(?:,?\s+(\d{4}))?\b").unwrap()
+});
+
+// Words that mark the date a reader needs to act by
+static DEADLINE_WORD: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(deadline|due|closes?|until|submissions?|cfp|call for|register)\b").unwrap()
+});
+
+/// Find the most likely deadline or event date mentioned in an entry.
+/// Dates without a year are placed on or after `reference` (usually the
+/// publication date). A date preceded by a deadline keyword wins; otherwise
+/// the earliest date on or after `reference` is used.
+pub fn extract_deadline(
+    title: &str,
+    content: Option<&str>,
+    reference: NaiveDate,
+) -> Option<NaiveDate> {
+    let content = content.unwrap_or("");
+    let end = content
+        .char_indices()
+        .nth(SCAN_CHARS)
+        .map(|(i, _)| i)
+        .unwrap_or(content.len());
+    let text = format!("{}\n{}", title, &content[..end]);
+
+    // (position in text, date)
+    let iso = ISO_DATE.captures_iter(&text).filter_map(|cap| {
+        let date = NaiveDate::from_ymd_opt(
+            cap[1].parse().ok()?,
+            cap[2].parse().ok()?,
+            cap[3].parse().ok()?,
+        )?;
+        Some((cap.get(0)?.start(), date))
+    });
+    let month_day = MONTH_DAY.captures_iter(&text).filter_map(|cap| {
+        let year = cap.get(3).and_then(|y| y.as_str().parse().ok());
+        let date = build_date(
+            year,
+            month_number(&cap[1])?,
+            cap[2].parse().ok()?,
+            reference,
+        )?;
+        Some((cap.get(0)?.start(), date))
+    });
+    let day_month = DAY_MONTH.captures_iter(&text).filter_map(|cap| {
+        let year = cap.get(3).and_then(|y| y.as_str().parse().ok());
+        let date = build_date(
+            year,
+            month_number(&cap[2])?,
+            cap[1].parse().ok()?,
+            reference,
+        )?;
+        Some((cap.get(0)?.start(), date))
+    });
+    let found: Vec<(usize, NaiveDate)> = iso.chain(month_day).chain(day_month).collect();
+
+    // Prefer a date shortly after a deadline keyword
+    let keyword_date = found
+        .iter()
+        .filter(|(pos, _)| {
+            let start = text[..*pos]
+                .char_indices()
+                .rev()
+                .nth(40)
+                .map(|(i, _)| i)
+                .unwrap_or(0);
+            DEADLINE_WORD.is_match(&text[start..*pos])
+        })
+        .map(|(_, date)| *date)
+        .min();
+
+    keyword_date.or_else(|| {
+        found
+            .iter()
+            .map(|(_, date)| *date)
+            .filter(|date| *date >= reference)
+            .min()
+    })
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    let months = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    let prefix = name.get(..3)?.to_lowercase();
+    months
+        .iter()
+        .position(|m| *m == prefix)
+        .map(|i| i as u32 + 1)
+}
+
+fn build_date(year: Option<i32>, month: u32, day: u32, reference: NaiveDate) -> Option<NaiveDate> {
+    match year {
+        Some(year) => NaiveDate::from_ymd_opt(year, month, day),
+        None => {
+            let date = NaiveDate::from_ymd_opt(reference.year(), month, day)?;
+            if date >= reference {
+                Some(date)
+            } else {
+                NaiveDate::from_ymd_opt(reference.year() + 1, month, day)
+            }
+        }
+    }
+}
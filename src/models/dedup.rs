@@ -0,0 +1,104 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+use url::Url;
+
+// Query parameters that only track where a click came from
+const TRACKING_PARAMS: &[&str] = &[
+    "fbclid", "gclid", "mc_cid", "mc_eid", "ref", "ref_src", "source",
+];
+
+// Titles sharing at least this fraction of their words are the same story
+const TITLE_SIMILARITY_THRESHOLD: f64 = 0.8;
+// Shorter titles ("Weekly links", "Release notes") are too generic to compare
+const MIN_TITLE_WORDS: usize = 4;
+
+/// Canonical form of an article URL for spotting the same story in several
+/// feeds: scheme, `www.`, fragment, tracking parameters and trailing slash
+/// are dropped, and the remaining query parameters are sorted
+pub fn normalize_url(url: &str) -> String {
+    let Ok(parsed) = Url::parse(url.trim()) else {
+        return url.trim().to_lowercase();
+    };
+
+    let host = parsed.host_str().unwrap_or("").to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    let path = parsed.path().trim_end_matches('/');
+
+    let mut query: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_") && !TRACKING_PARAMS.contains(&key.as_ref()))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    query.sort();
+
+    let mut normalized = format!("{}{}", host, path);
+    if !query.is_empty() {
+        let pairs: Vec<String> = query.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+        normalized.push('?');
+        normalized.push_str(&pairs.join("&"));
+    }
+    normalized
+}
+
+/// Whether two titles most likely name the same story (Jaccard similarity of
+/// their lowercased words, ignoring punctuation)
+pub fn is_similar_title(a: &str, b: &str) -> bool {
+    let words = |title: &str| -> HashSet<String> {
+        title
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(|w| w.to_lowercase())
+            .collect()
+    };
+    let (a, b) = (words(a), words(b));
+    if a.len() < MIN_TITLE_WORDS || b.len() < MIN_TITLE_WORDS {
+        return false;
+    }
+
+    let shared = a.intersection(&b).count() as f64;
+    let total = a.union(&b).count() as f64;
+    shared / total >= TITLE_SIMILARITY_THRESHOLD
+}
+
+/// Shared start of titles a release or changelog feed repeats ("tokio v1.2.0",
+/// "tokio v1.3.0"): the lowercased words before the first one with a digit.
+/// None when the title starts with a number.
+pub fn title_prefix(title: &str) -> Option<String> {
+    let words: Vec<String> = title
+        .split_whitespace()
+        .take_while(|w| !w.chars().any(|c| c.is_ascii_digit()))
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect();
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+/// Stable fingerprint of an entry's title and content (64-bit FNV-1a, hex),
+/// for noticing when a feed re-publishes an entry without changing it
+pub fn content_hash(title: &str, content: Option<&str>) -> String {
+    fnv1a(&[title.trim(), content.unwrap_or("").trim()])
+}
+
+/// Stand-in guid for a feed entry that has no id, from its link, title and
+/// publication date, so it stays the same from one fetch to the next
+pub fn synthetic_guid(url: &str, title: &str, published: Option<DateTime<Utc>>) -> String {
+    let published = published.map(|p| p.to_rfc3339()).unwrap_or_default();
+    format!("speedy-reader:{}", fnv1a(&[url.trim(), title.trim(), &published]))
+}
+
+/// 64-bit FNV-1a of the parts, NUL-separated, in hex
+fn fnv1a(parts: &[&str]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    for (i, part) in parts.iter().enumerate() {
+        let separator = if i == 0 { None } else { Some(0) };
+        for byte in separator.into_iter().chain(part.bytes()) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
@@ -0,0 +1,94 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+static HN_ITEM: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"https?://news\.ycombinator\.com/item\?id=(\d+)").unwrap()
+});
+
+static REDDIT_POST: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"https?://(?:www\.|old\.)?reddit\.com(/r/[^/\s"'<>]+/comments/[a-z0-9]+)"#).unwrap()
+});
+
+// hnrss.org puts the comment count in each item's description
+static HN_COMMENT_COUNT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"# Comments:\s*(\d+)").unwrap());
+
+/// Site hosting a discussion of an article
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscussionSite {
+    HackerNews,
+    Reddit,
+}
+
+impl DiscussionSite {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiscussionSite::HackerNews => "hackernews",
+            DiscussionSite::Reddit => "reddit",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "hackernews" => Some(DiscussionSite::HackerNews),
+            "reddit" => Some(DiscussionSite::Reddit),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DiscussionSite::HackerNews => "HN",
+            DiscussionSite::Reddit => "Reddit",
+        }
+    }
+}
+
+/// A comment thread about an article
+#[derive(Debug, Clone)]
+pub struct Discussion {
+    pub site: DiscussionSite,
+    pub url: String,
+    pub title: String,
+    /// None until looked up
+    pub comments: Option<u32>,
+    pub points: Option<i64>,
+}
+
+impl Discussion {
+    /// The Hacker News story id, for HN discussions
+    pub fn hn_id(&self) -> Option<&str> {
+        HN_ITEM.captures(&self.url).map(|c| c.get(1).unwrap().as_str())
+    }
+}
+
+/// The discussion an article comes from, for articles from Hacker News and
+/// Reddit feeds: the HN item or Reddit post in its URL or content
+pub fn origin_discussion(title: &str, url: &str, content: Option<&str>) -> Option<Discussion> {
+    let texts = [Some(url), content];
+    for text in texts.into_iter().flatten() {
+        if let Some(capture) = HN_ITEM.captures(text) {
+            let comments = content
+                .and_then(|content| HN_COMMENT_COUNT.captures(content))
+                .and_then(|c| c[1].parse().ok());
+            return Some(Discussion {
+                site: DiscussionSite::HackerNews,
+                url: format!("https://news.ycombinator.com/item?id={}", &capture[1]),
+                title: title.to_string(),
+                comments,
+                points: None,
+            });
+        }
+        if let Some(capture) = REDDIT_POST.captures(text) {
+            return Some(Discussion {
+                site: DiscussionSite::Reddit,
+                url: format!("https://www.reddit.com{}/", &capture[1]),
+                title: title.to_string(),
+                comments: None,
+                points: None,
+            });
+        }
+    }
+    None
+}
@@ -1,6 +1,12 @@
-use chrono::{DateTime, Utc};
+use std::hash::{BuildHasher, Hasher};
+
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
+// Wait after a feed's first failed fetch, doubling with each further failure
+const BACKOFF_BASE_MINUTES: i64 = 10;
+const BACKOFF_MAX_MINUTES: i64 = 24 * 60;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Feed {
     pub id: i64,
@@ -8,9 +14,135 @@ pub struct Feed {
     pub url: String,
     pub site_url: Option<String>,
     pub description: Option<String>,
+    /// Folder from OPML import, used to group and scope exports
+    pub category: Option<String>,
     pub last_fetched: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(skip)]
+    pub auth: Option<FeedAuth>,
+    #[serde(default)]
+    pub rules: FeedRules,
+    #[serde(skip)]
+    pub backoff: FetchBackoff,
+}
+
+/// A per-feed automatic behaviour, toggled in the feed settings popup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedRule {
+    /// Star new articles as they arrive
+    AutoStar,
+    /// Move articles to the trash once they are read
+    AutoArchive,
+    /// Keep read articles visible in the Unread filter
+    SkipUnreadFilter,
+    /// Summarize new articles in the background after a refresh
+    AutoSummarize,
+    /// List runs of entries with the same title prefix as one row
+    CollapseSimilar,
+}
+
+impl FeedRule {
+    pub const ALL: [FeedRule; 5] = [
+        FeedRule::AutoStar,
+        FeedRule::AutoArchive,
+        FeedRule::SkipUnreadFilter,
+        FeedRule::AutoSummarize,
+        FeedRule::CollapseSimilar,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FeedRule::AutoStar => "Always star new articles",
+            FeedRule::AutoArchive => "Auto-archive after read (move to trash)",
+            FeedRule::SkipUnreadFilter => "Skip unread filter (always list articles)",
+            FeedRule::AutoSummarize => "Summarize new articles in the background",
+            FeedRule::CollapseSimilar => "Collapse similar titles (releases, changelogs)",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedRules {
+    pub auto_star: bool,
+    pub auto_archive: bool,
+    pub skip_unread_filter: bool,
+    pub auto_summarize: bool,
+    pub collapse_similar: bool,
+}
+
+impl FeedRules {
+    pub fn get(&self, rule: FeedRule) -> bool {
+        match rule {
+            FeedRule::AutoStar => self.auto_star,
+            FeedRule::AutoArchive => self.auto_archive,
+            FeedRule::SkipUnreadFilter => self.skip_unread_filter,
+            FeedRule::AutoSummarize => self.auto_summarize,
+            FeedRule::CollapseSimilar => self.collapse_similar,
+        }
+    }
+
+    pub fn toggle(&mut self, rule: FeedRule) {
+        let flag = match rule {
+            FeedRule::AutoStar => &mut self.auto_star,
+            FeedRule::AutoArchive => &mut self.auto_archive,
+            FeedRule::SkipUnreadFilter => &mut self.skip_unread_filter,
+            FeedRule::AutoSummarize => &mut self.auto_summarize,
+            FeedRule::CollapseSimilar => &mut self.collapse_similar,
+        };
+        *flag = !*flag;
+    }
+}
+
+/// What a headless refresh found in one feed (`--refresh --json`)
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshReport {
+    pub feed: String,
+    pub url: String,
+    /// Titles of the articles new to the database
+    pub new_articles: Vec<String>,
+    /// Why the fetch failed, or last failed for a feed being backed off from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Left out as its fetches keep failing
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub skipped: bool,
+}
+
+/// A feed's run of failed fetches, which refreshes leave it alone for
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FetchBackoff {
+    /// Failed fetches in a row; 0 once a fetch succeeds
+    pub failures: u32,
+    /// Refreshes skip the feed until then
+    pub retry_at: Option<DateTime<Utc>>,
+    /// Why the last fetch failed
+    pub error: Option<String>,
+}
+
+impl FetchBackoff {
+    /// Whether refreshes skip the feed at `now`
+    pub fn is_waiting(&self, now: DateTime<Utc>) -> bool {
+        self.retry_at.is_some_and(|retry_at| retry_at > now)
+    }
+
+    /// The backoff after one more failure: the wait doubles with each failure
+    /// up to a day, give or take a fifth so feeds failing together spread out
+    pub fn after_failure(&self, error: String, now: DateTime<Utc>) -> Self {
+        let failures = self.failures + 1;
+        let minutes = BACKOFF_BASE_MINUTES
+            .saturating_mul(1 << (failures - 1).min(16))
+            .min(BACKOFF_MAX_MINUTES);
+        // -20% to +20%, from the randomly keyed std hasher
+        let random = std::collections::hash_map::RandomState::new().build_hasher().finish();
+        let jitter = (random % 401) as i64 - 200;
+        let seconds = minutes * 60 * (1000 + jitter) / 1000;
+        Self {
+            failures,
+            retry_at: Some(now + Duration::seconds(seconds)),
+            error: Some(error),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -19,4 +151,130 @@ pub struct NewFeed {
     pub url: String,
     pub site_url: Option<String>,
     pub description: Option<String>,
+    pub category: Option<String>,
+}
+
+/// What importing subscriptions from OPML did
+#[derive(Debug, Default)]
+pub struct OpmlImport {
+    pub added: usize,
+    /// Folders the added feeds went into
+    pub folders: usize,
+    /// Feeds already subscribed to, or listed twice in the file
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+impl std::fmt::Display for OpmlImport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Added {} feed{}", self.added, if self.added == 1 { "" } else { "s" })?;
+        if self.folders > 0 {
+            write!(f, " in {} folder{}", self.folders, if self.folders == 1 { "" } else { "s" })?;
+        }
+        if self.skipped > 0 {
+            write!(f, ", skipped {} already subscribed", self.skipped)?;
+        }
+        if self.failed > 0 {
+            write!(f, ", {} failed", self.failed)?;
+        }
+        Ok(())
+    }
+}
+
+/// A feed found in the public feed directory
+#[derive(Debug, Clone)]
+pub struct DirectoryFeed {
+    pub title: String,
+    pub url: String,
+    pub site_url: Option<String>,
+    pub description: Option<String>,
+    pub subscribers: u64,
+}
+
+/// A feed suggested from reading history, for the Discover screen
+#[derive(Debug, Clone)]
+pub struct FeedSuggestion {
+    pub title: String,
+    pub url: String,
+    pub site_url: Option<String>,
+    /// Why it is suggested, e.g. "Linked from 4 articles you read"
+    pub reason: String,
+    pub score: u32,
+}
+
+/// How a private feed authenticates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedAuthKind {
+    /// HTTP Basic auth (name is the username)
+    Basic,
+    /// Custom request header (name is the header name, e.g. "Authorization")
+    Header,
+}
+
+impl FeedAuthKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FeedAuthKind::Basic => "basic",
+            FeedAuthKind::Header => "header",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "basic" => Some(FeedAuthKind::Basic),
+            "header" => Some(FeedAuthKind::Header),
+            _ => None,
+        }
+    }
+}
+
+/// Non-secret part of a feed's credentials, stored in the database
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeedAuth {
+    pub kind: FeedAuthKind,
+    pub name: String,
+}
+
+/// Complete credentials for a feed; the secret is kept in the OS keyring
+#[derive(Clone)]
+pub struct FeedCredentials {
+    pub auth: FeedAuth,
+    pub secret: String,
+}
+
+impl FeedCredentials {
+    /// Parse credentials typed into the add-feed prompt:
+    /// `user:password` for Basic auth or `Header-Name: value` for a header token
+    pub fn parse(input: &str) -> Option<Self> {
+        let (name, secret) = input.split_once(':')?;
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return None;
+        }
+
+        let (kind, secret) = match secret.strip_prefix(' ') {
+            Some(value) => (FeedAuthKind::Header, value.trim()),
+            None => (FeedAuthKind::Basic, secret),
+        };
+        if secret.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            auth: FeedAuth {
+                kind,
+                name: name.to_string(),
+            },
+            secret: secret.to_string(),
+        })
+    }
+}
+
+// Keep secrets out of logs
+impl std::fmt::Debug for FeedCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FeedCredentials")
+            .field("auth", &self.auth)
+            .field("secret", &"***")
+            .finish()
+    }
 }
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+use super::Article;
+
+/// Which articles the list shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArticleFilter {
+    #[default]
+    All,
+    Unread,
+    Starred,
+    /// The local reading queue, in the order articles were added
+    Queue,
+    Trash,
+}
+
+impl ArticleFilter {
+    /// Next filter in the `f` key cycle
+    pub fn next(self) -> Self {
+        match self {
+            ArticleFilter::All => ArticleFilter::Unread,
+            ArticleFilter::Unread => ArticleFilter::Starred,
+            ArticleFilter::Starred => ArticleFilter::Queue,
+            ArticleFilter::Queue => ArticleFilter::Trash,
+            ArticleFilter::Trash => ArticleFilter::All,
+        }
+    }
+
+    /// Label shown in the header
+    pub fn label(self) -> &'static str {
+        match self {
+            ArticleFilter::All => "Articles",
+            ArticleFilter::Unread => "Unread",
+            ArticleFilter::Starred => "Starred",
+            ArticleFilter::Queue => "Queued",
+            ArticleFilter::Trash => "in Trash",
+        }
+    }
+
+    pub fn matches(self, article: &Article) -> bool {
+        match self {
+            ArticleFilter::All => !article.is_trashed(),
+            ArticleFilter::Unread => !article.is_trashed() && !article.is_read,
+            ArticleFilter::Starred => !article.is_trashed() && article.is_starred,
+            ArticleFilter::Queue => !article.is_trashed() && article.queued_at.is_some(),
+            ArticleFilter::Trash => article.is_trashed(),
+        }
+    }
+}
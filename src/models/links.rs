@@ -0,0 +1,88 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use url::Url;
+
+static HREF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bhref\s*=\s*["']([^"']+)["']"#).unwrap());
+
+static ANCHOR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)<a\s[^>]*?\bhref\s*=\s*["']([^"']+)["'][^>]*>(.*?)</a>"#).unwrap()
+});
+
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+
+// URLs written out in plain text
+static BARE_URL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"https?://[^\s<>"'()\[\]]+"#).unwrap());
+
+/// Web pages an article's content links to: `href`s in HTML (resolved
+/// against the article's own URL) and URLs written out in plain text
+pub fn extract_links(base_url: &str, content: &str) -> Vec<String> {
+    let base = Url::parse(base_url).ok();
+    let mut links = Vec::new();
+
+    for capture in HREF.captures_iter(content) {
+        if let Some(url) = resolve(base.as_ref(), &capture[1]) {
+            links.push(url.to_string());
+        }
+    }
+    for found in BARE_URL.find_iter(content) {
+        let url = found.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']);
+        links.push(url.replace("&amp;", "&"));
+    }
+
+    links.sort();
+    links.dedup();
+    links
+}
+
+/// The links in an article's HTML as (text, URL), in order of appearance
+/// and each URL once; links within the page and to non-web URLs are left out
+pub fn extract_anchors(base_url: &str, html: &str) -> Vec<(String, String)> {
+    let base = Url::parse(base_url).ok();
+    let mut anchors: Vec<(String, String)> = Vec::new();
+
+    for capture in ANCHOR.captures_iter(html) {
+        if capture[1].trim().starts_with('#') {
+            continue;
+        }
+        let Some(url) = resolve(base.as_ref(), &capture[1]).map(String::from) else {
+            continue;
+        };
+        if anchors.iter().any(|(_, known)| *known == url) {
+            continue;
+        }
+        let text = TAG.replace_all(&capture[2], " ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let text = text
+            .replace("&nbsp;", " ")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&");
+        let text = if text.is_empty() { url.clone() } else { text };
+        anchors.push((text, url));
+    }
+    anchors
+}
+
+/// `[title](url)` for pasting into chat and notes; brackets in the title are
+/// escaped, and parentheses and spaces in the URL encoded
+pub fn markdown_link(title: &str, url: &str) -> String {
+    let title = title.split_whitespace().collect::<Vec<_>>().join(" ");
+    let title = title.replace('[', "\\[").replace(']', "\\]");
+    let url = url.trim().replace('(', "%28").replace(')', "%29").replace(' ', "%20");
+    format!("[{}]({})", title, url)
+}
+
+/// Absolute web URL of a link, relative ones resolved against `base`
+fn resolve(base: Option<&Url>, href: &str) -> Option<Url> {
+    let href = href.trim().replace("&amp;", "&");
+    let url = match base {
+        Some(base) => base.join(&href),
+        None => Url::parse(&href),
+    };
+    url.ok().filter(|url| matches!(url.scheme(), "http" | "https"))
+}
@@ -1,7 +1,40 @@
 mod feed;
 mod article;
 mod summary;
+mod sort;
+mod catchup;
+mod deadline;
+mod dedup;
+mod filter;
+mod unread;
+mod annotation;
+mod tag;
+mod sync;
+mod stats;
+mod links;
+mod backup;
+mod chat;
+mod discussion;
 
-pub use feed::{Feed, NewFeed};
-pub use article::{Article, NewArticle};
-pub use summary::{Summary, SummaryStatus};
+pub use feed::{
+    DirectoryFeed, Feed, FeedAuth, FeedAuthKind, FeedCredentials, FeedRule, FeedRules,
+    FeedSuggestion, FetchBackoff, NewFeed, OpmlImport, RefreshReport,
+};
+pub use article::{AgeGroup, Article, NewArticle};
+pub use summary::{
+    KeyFacts, Summary, SummarySource, SummaryStatus, SummaryVariant, TokenUsage,
+};
+pub use sort::SortMode;
+pub use catchup::{count_words, estimate_reading_minutes, CatchUpPlan};
+pub use deadline::extract_deadline;
+pub use dedup::{content_hash, is_similar_title, normalize_url, synthetic_guid, title_prefix};
+pub use links::{extract_anchors, extract_links, markdown_link};
+pub use filter::ArticleFilter;
+pub use unread::UnreadCounts;
+pub use annotation::Annotation;
+pub use tag::parse_tags;
+pub use sync::SyncItem;
+pub use stats::{AiSpend, FeedUsage, FetchUsage, RefreshUsage};
+pub use chat::{ChatMessage, ChatRole};
+pub use discussion::{origin_discussion, Discussion, DiscussionSite};
+pub use backup::{Backup, BackupArticle, BackupFeed, BackupImport, BackupSummary, BACKUP_VERSION};
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+/// Ordering applied to the article list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortMode {
+    #[default]
+    NewestFirst,
+    OldestFirst,
+    ByFeed,
+    ByTitle,
+    UnreadFirst,
+    /// Upcoming deadlines/event dates first, soonest first
+    Deadline,
+}
+
+impl SortMode {
+    /// Next mode in the `S` key cycle
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::NewestFirst => SortMode::OldestFirst,
+            SortMode::OldestFirst => SortMode::ByFeed,
+            SortMode::ByFeed => SortMode::ByTitle,
+            SortMode::ByTitle => SortMode::UnreadFirst,
+            SortMode::UnreadFirst => SortMode::Deadline,
+            SortMode::Deadline => SortMode::NewestFirst,
+        }
+    }
+
+    /// Short label shown in the header
+    pub fn label(self) -> &'static str {
+        match self {
+            SortMode::NewestFirst => "Newest",
+            SortMode::OldestFirst => "Oldest",
+            SortMode::ByFeed => "Feed",
+            SortMode::ByTitle => "Title",
+            SortMode::UnreadFirst => "Unread",
+            SortMode::Deadline => "Deadline",
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+
+/// Network use of refreshing one feed: HTTP requests made and response
+/// bytes downloaded, archive pages included
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FetchUsage {
+    pub requests: u32,
+    pub bytes: u64,
+}
+
+/// Network use of one refresh, over all feeds
+#[derive(Debug, Clone)]
+pub struct RefreshUsage {
+    pub refreshed_at: DateTime<Utc>,
+    pub feeds: u32,
+    pub requests: u64,
+    pub bytes: u64,
+}
+
+/// A feed's network use over the stored refresh history
+#[derive(Debug, Clone)]
+pub struct FeedUsage {
+    pub title: String,
+    pub refreshes: u32,
+    pub requests: u64,
+    pub bytes: u64,
+    /// Downloaded by the most recent refresh
+    pub last_bytes: u64,
+}
+
+impl FeedUsage {
+    pub fn bytes_per_refresh(&self) -> u64 {
+        self.bytes / self.refreshes.max(1) as u64
+    }
+}
+
+/// Claude usage and estimated cost of generating summaries
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AiSpend {
+    pub summaries: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
@@ -1,3 +1,5 @@
+use std::time::Instant;
+
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +10,128 @@ pub struct Summary {
     pub content: String,
     pub model_version: String,
     pub generated_at: DateTime<Utc>,
+    pub source: Option<SummarySource>,
+    /// Set for a summary regenerated in another style, kept beside the regular one
+    #[serde(default)]
+    pub variant: Option<SummaryVariant>,
+}
+
+/// Style picked when regenerating a summary (`g`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SummaryVariant {
+    Shorter,
+    Longer,
+    Bullets,
+    Eli5,
+    Skeptical,
+}
+
+impl SummaryVariant {
+    pub const ALL: [SummaryVariant; 5] = [
+        SummaryVariant::Shorter,
+        SummaryVariant::Longer,
+        SummaryVariant::Bullets,
+        SummaryVariant::Eli5,
+        SummaryVariant::Skeptical,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SummaryVariant::Shorter => "shorter",
+            SummaryVariant::Longer => "longer",
+            SummaryVariant::Bullets => "bullets",
+            SummaryVariant::Eli5 => "eli5",
+            SummaryVariant::Skeptical => "skeptical",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|variant| variant.as_str() == s)
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SummaryVariant::Shorter => "Shorter",
+            SummaryVariant::Longer => "Longer",
+            SummaryVariant::Bullets => "Bullet points",
+            SummaryVariant::Eli5 => "Explain like I'm five",
+            SummaryVariant::Skeptical => "Skeptical analysis",
+        }
+    }
+}
+
+/// Structured notes on an article (`key_facts` in `[summary]`), shown
+/// above its summary
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KeyFacts {
+    #[serde(default)]
+    pub tldr: String,
+    #[serde(default)]
+    pub key_facts: Vec<String>,
+    #[serde(default)]
+    pub people: Vec<String>,
+    #[serde(default)]
+    pub organizations: Vec<String>,
+    #[serde(default)]
+    pub places: Vec<String>,
+}
+
+/// Which text the summary was generated from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SummarySource {
+    /// Full article fetched from the web page
+    FullArticle,
+    /// Content included in the feed entry
+    FeedContent,
+    /// Feed entry only had a short excerpt
+    FeedExcerpt,
+    /// Input exceeded the summarizer limit and was cut off
+    Truncated,
+}
+
+impl SummarySource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SummarySource::FullArticle => "full_article",
+            SummarySource::FeedContent => "feed_content",
+            SummarySource::FeedExcerpt => "feed_excerpt",
+            SummarySource::Truncated => "truncated",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "full_article" => Some(SummarySource::FullArticle),
+            "feed_content" => Some(SummarySource::FeedContent),
+            "feed_excerpt" => Some(SummarySource::FeedExcerpt),
+            "truncated" => Some(SummarySource::Truncated),
+            _ => None,
+        }
+    }
+
+    /// Human-readable provenance for the status bar
+    pub fn description(self) -> &'static str {
+        match self {
+            SummarySource::FullArticle => "summary from full article",
+            SummarySource::FeedContent => "summary from RSS content",
+            SummarySource::FeedExcerpt => "summary from RSS excerpt only",
+            SummarySource::Truncated => "summary from truncated article",
+        }
+    }
+}
+
+/// Tokens used by Claude requests, as reported by the API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl TokenUsage {
+    pub fn add(&mut self, other: TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -15,6 +139,8 @@ pub enum SummaryStatus {
     #[default]
     NotGenerated,
     Generating,
+    /// Rate limited or overloaded; the request is retried at this time
+    Retrying(Instant),
     Generated,
     Failed,
     NoApiKey,
@@ -0,0 +1,11 @@
+/// A local article linked to an item on the sync server
+#[derive(Debug, Clone)]
+pub struct SyncItem {
+    pub article_id: i64,
+    pub item_id: String,
+    pub is_read: bool,
+    pub is_starred: bool,
+    /// State both sides agreed on at the last sync
+    pub synced_read: bool,
+    pub synced_starred: bool,
+}
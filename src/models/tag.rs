@@ -0,0 +1,12 @@
+/// Parse comma-separated tag input: trimmed, lowercased, without a leading
+/// `#`, de-duplicated and sorted
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = input
+        .split(',')
+        .map(|tag| tag.trim().trim_start_matches('#').trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
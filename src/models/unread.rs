@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use super::Feed;
+
+/// Unread (non-trashed) article counts per feed, loaded with one query and
+/// then adjusted as articles are read, trashed or restored
+#[derive(Debug, Clone, Default)]
+pub struct UnreadCounts {
+    by_feed: HashMap<i64, usize>,
+}
+
+impl UnreadCounts {
+    pub fn new(by_feed: HashMap<i64, usize>) -> Self {
+        Self { by_feed }
+    }
+
+    pub fn total(&self) -> usize {
+        self.by_feed.values().sum()
+    }
+
+    pub fn for_feed(&self, feed_id: i64) -> usize {
+        self.by_feed.get(&feed_id).copied().unwrap_or(0)
+    }
+
+    /// Sum over the feeds in a folder (`None` for feeds outside any folder)
+    pub fn for_category(&self, feeds: &[Feed], category: Option<&str>) -> usize {
+        feeds
+            .iter()
+            .filter(|f| f.category.as_deref() == category)
+            .map(|f| self.for_feed(f.id))
+            .sum()
+    }
+
+    pub fn increment(&mut self, feed_id: i64) {
+        *self.by_feed.entry(feed_id).or_default() += 1;
+    }
+
+    pub fn decrement(&mut self, feed_id: i64) {
+        if let Some(count) = self.by_feed.get_mut(&feed_id) {
+            *count = count.saturating_sub(1);
+        }
+    }
+
+    pub fn remove_feed(&mut self, feed_id: i64) {
+        self.by_feed.remove(&feed_id);
+    }
+}
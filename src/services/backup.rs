@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::db::Repository;
+use crate::error::{AppError, Result};
+use crate::models::{Backup, BackupArticle, BackupFeed, BackupImport, BackupSummary, BACKUP_VERSION};
+
+/// Write feeds, articles (trash included), read/star state, tags and
+/// summaries to a JSON file. Returns the number of feeds and articles written.
+pub async fn export_json(repository: &Repository, path: &Path) -> Result<(usize, usize)> {
+    let feeds = repository.get_all_feeds().await?;
+    let articles = repository.get_all_articles_sorted().await?;
+    let mut summaries: HashMap<i64, BackupSummary> = repository
+        .get_all_summaries()
+        .await?
+        .into_iter()
+        .map(|s| {
+            let summary = BackupSummary {
+                content: s.content,
+                model_version: s.model_version,
+                generated_at: s.generated_at,
+                source: s.source,
+            };
+            (s.article_id, summary)
+        })
+        .collect();
+
+    let mut by_feed: HashMap<i64, Vec<BackupArticle>> = HashMap::new();
+    let article_count = articles.len();
+    for article in articles {
+        by_feed.entry(article.feed_id).or_default().push(BackupArticle {
+            summary: summaries.remove(&article.id),
+            guid: article.guid,
+            title: article.title,
+            url: article.url,
+            author: article.author,
+            content: article.content,
+            content_text: article.content_text,
+            published_at: article.published_at,
+            fetched_at: article.fetched_at,
+            image_url: article.image_url,
+            is_read: article.is_read,
+            is_starred: article.is_starred,
+            deleted_at: article.deleted_at,
+            queued_at: article.queued_at,
+            tags: article.tags,
+        });
+    }
+
+    let backup = Backup {
+        version: BACKUP_VERSION,
+        exported_at: chrono::Utc::now(),
+        feeds: feeds
+            .into_iter()
+            .map(|feed| BackupFeed {
+                articles: by_feed.remove(&feed.id).unwrap_or_default(),
+                url: feed.url,
+                title: feed.title,
+                site_url: feed.site_url,
+                description: feed.description,
+                category: feed.category,
+                rules: feed.rules,
+            })
+            .collect(),
+    };
+    let feed_count = backup.feeds.len();
+
+    let file = std::io::BufWriter::new(std::fs::File::create(path)?);
+    serde_json::to_writer_pretty(file, &backup)?;
+    Ok((feed_count, article_count))
+}
+
+/// Merge a JSON file written by `export_json` into the database
+pub async fn import_json(repository: &Repository, path: &Path) -> Result<BackupImport> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    let backup: Backup = serde_json::from_reader(file)?;
+    if backup.version > BACKUP_VERSION {
+        return Err(AppError::Backup(format!(
+            "{} is format version {}; this version of speedy-reader reads up to {}",
+            path.display(),
+            backup.version,
+            BACKUP_VERSION
+        )));
+    }
+    repository.import_backup(backup).await
+}
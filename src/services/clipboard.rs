@@ -0,0 +1,56 @@
+use std::io::Write;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+
+use crate::error::{AppError, Result};
+
+/// Copies text to the system clipboard, or through the terminal (OSC 52)
+/// over SSH and where there is no clipboard to reach
+pub struct Clipboard {
+    // Kept open: on X11 the copied text lives only as long as its owner
+    native: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    pub fn new() -> Self {
+        let native = if is_remote() {
+            None
+        } else {
+            arboard::Clipboard::new()
+                .map_err(|e| tracing::debug!("No system clipboard, using OSC 52: {}", e))
+                .ok()
+        };
+        Self { native }
+    }
+
+    /// Replace the clipboard contents with `text`
+    pub fn copy(&mut self, text: &str) -> Result<()> {
+        if let Some(native) = self.native.as_mut() {
+            match native.set_text(text) {
+                Ok(()) => return Ok(()),
+                Err(e) => tracing::warn!("System clipboard failed, using OSC 52: {}", e),
+            }
+        }
+        copy_osc52(text)
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Ask the terminal to set its clipboard; works through SSH and, with
+/// `set-clipboard on`, tmux
+fn copy_osc52(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))
+        .and_then(|()| stdout.flush())
+        .map_err(|e| AppError::Clipboard(e.to_string()))
+}
+
+fn is_remote() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
@@ -6,24 +6,48 @@ use reqwest::Client;
 use rusqlite::params;
 use url::Url;
 
+use crate::config::ProxyRoute;
 use crate::error::Result;
 
 const USER_AGENT_STRING: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:128.0) Gecko/20100101 Firefox/128.0";
 
+#[derive(Clone)]
 pub struct ContentFetcher {
     client: Client,
+    // Domains whose cookies may be used, lowercase without a leading dot
+    cookie_domains: Vec<String>,
 }
 
 impl ContentFetcher {
-    pub fn new() -> Self {
-        let client = Client::builder()
+    pub fn new(cookie_domains: &[String], proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client");
-        Self { client }
+        Self {
+            client,
+            cookie_domains: cookie_domains
+                .iter()
+                .map(|d| d.trim().trim_start_matches('.').to_lowercase())
+                .filter(|d| !d.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Whether cookies may be sent to `host`: it is an allowlisted domain or
+    /// one of its subdomains
+    fn allows_cookies(&self, host: &str) -> bool {
+        let host = host.to_lowercase();
+        self.cookie_domains.iter().any(|domain| {
+            host == *domain
+                || host
+                    .strip_suffix(domain.as_str())
+                    .is_some_and(|sub| sub.ends_with('.'))
+        })
     }
 
-    /// Fetch full article content using browser cookies
+    /// Fetch full article content, with browser cookies for allowlisted domains
     pub async fn fetch_full_content(&self, article_url: &str) -> Result<Option<String>> {
         let url = match Url::parse(article_url) {
             Ok(u) => u,
@@ -35,8 +59,12 @@ impl ContentFetcher {
             None => return Ok(None),
         };
 
-        // Get cookies for this domain from Firefox
-        let cookies = self.get_firefox_cookies(domain)?;
+        // Get cookies for this domain from Firefox, if it is allowlisted
+        let cookies = if self.allows_cookies(domain) {
+            self.get_firefox_cookies(domain)?
+        } else {
+            String::new()
+        };
 
         // Build request with cookies
         let mut headers = HeaderMap::new();
@@ -123,6 +151,31 @@ impl ContentFetcher {
         Ok(cookies.join("; "))
     }
 
+    /// Check whether the Firefox cookie store can be read, returning the cookie count
+    pub fn check_cookie_access(&self) -> std::result::Result<usize, String> {
+        let firefox_dir = Self::find_firefox_profile().ok_or("no Firefox profile found")?;
+        let cookies_db = firefox_dir.join("cookies.sqlite");
+        if !cookies_db.exists() {
+            return Err(format!("{} not found", cookies_db.display()));
+        }
+
+        // Same copy-then-open approach as get_firefox_cookies (Firefox locks the file)
+        let temp_db = std::env::temp_dir().join("speedy-reader-cookies-check.sqlite");
+        std::fs::copy(&cookies_db, &temp_db).map_err(|e| format!("cannot copy: {}", e))?;
+
+        let count = rusqlite::Connection::open(&temp_db)
+            .and_then(|conn| {
+                conn.query_row("SELECT COUNT(*) FROM moz_cookies", [], |row| {
+                    row.get::<_, i64>(0)
+                })
+            })
+            .map_err(|e| format!("cannot read: {}", e));
+
+        let _ = std::fs::remove_file(&temp_db);
+
+        count.map(|c| c as usize)
+    }
+
     /// Find the default Firefox profile directory
     fn find_firefox_profile() -> Option<PathBuf> {
         let home = dirs::home_dir()?;
@@ -215,9 +268,3 @@ impl ContentFetcher {
         }
     }
 }
-
-impl Default for ContentFetcher {
-    fn default() -> Self {
-        Self::new()
-    }
-}
@@ -0,0 +1,94 @@
+use keyring::Entry;
+
+use crate::error::{AppError, Result};
+use crate::models::{Feed, FeedCredentials};
+
+const KEYRING_SERVICE: &str = "speedy-reader";
+
+/// Keyring entries are keyed by feed URL so they survive re-adding a feed
+fn entry(feed_url: &str) -> Result<Entry> {
+    Entry::new(KEYRING_SERVICE, &format!("feed:{}", feed_url))
+        .map_err(|e| AppError::Credentials(e.to_string()))
+}
+
+/// Store a feed secret in the OS keyring
+pub async fn store_feed_secret(feed_url: &str, secret: &str) -> Result<()> {
+    let feed_url = feed_url.to_string();
+    let secret = secret.to_string();
+    tokio::task::spawn_blocking(move || {
+        entry(&feed_url)?
+            .set_password(&secret)
+            .map_err(|e| AppError::Credentials(e.to_string()))
+    })
+    .await
+    .map_err(|e| AppError::Credentials(e.to_string()))?
+}
+
+/// Remove a feed secret from the OS keyring (missing entries are fine)
+pub async fn delete_feed_secret(feed_url: &str) -> Result<()> {
+    let feed_url = feed_url.to_string();
+    tokio::task::spawn_blocking(move || match entry(&feed_url)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(AppError::Credentials(e.to_string())),
+    })
+    .await
+    .map_err(|e| AppError::Credentials(e.to_string()))?
+}
+
+/// Look up the full credentials for a feed, if it has any configured
+pub async fn load_feed_credentials(feed: &Feed) -> Option<FeedCredentials> {
+    let auth = feed.auth.clone()?;
+    let feed_url = feed.url.clone();
+    let secret = tokio::task::spawn_blocking(move || {
+        entry(&feed_url)?
+            .get_password()
+            .map_err(|e| AppError::Credentials(e.to_string()))
+    })
+    .await
+    .ok()?;
+
+    match secret {
+        Ok(secret) => Some(FeedCredentials { auth, secret }),
+        Err(e) => {
+            tracing::warn!("No credentials for {} in keyring: {}", feed.url, e);
+            None
+        }
+    }
+}
+
+/// Keyring entries for the API keys and tokens normally set in config.toml
+fn config_entry(key: &str) -> Result<Entry> {
+    Entry::new(KEYRING_SERVICE, &format!("config:{}", key))
+        .map_err(|e| AppError::Credentials(e.to_string()))
+}
+
+/// Look up a config secret in the OS keyring. Runs on its own thread: the
+/// keyring backend drives its own runtime, which can't nest in ours.
+pub fn load_config_secret(key: &str) -> Option<String> {
+    let key = key.to_string();
+    let secret = std::thread::spawn(move || match config_entry(&key)?.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(AppError::Credentials(format!("{}: {}", key, e))),
+    })
+    .join()
+    .ok()?;
+
+    secret.unwrap_or_else(|e| {
+        tracing::warn!("Keyring lookup failed for {}", e);
+        None
+    })
+}
+
+/// Store a config secret in the OS keyring
+pub fn store_config_secret(key: &str, secret: &str) -> Result<()> {
+    let key = key.to_string();
+    let secret = secret.to_string();
+    std::thread::spawn(move || {
+        config_entry(&key)?
+            .set_password(&secret)
+            .map_err(|e| AppError::Credentials(e.to_string()))
+    })
+    .join()
+    .map_err(|_| AppError::Credentials("keyring thread panicked".to_string()))?
+}
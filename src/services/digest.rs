@@ -0,0 +1,249 @@
+use chrono::{Datelike, Duration, Local, Utc};
+use lettre::message::header::ContentType;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::config::EmailConfig;
+use crate::db::Repository;
+use crate::error::{AppError, Result};
+use crate::models::{estimate_reading_minutes, Article, Summary};
+
+/// Which articles a digest rounds up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestSelection {
+    Starred,
+    Unread,
+    /// The local reading queue
+    Queue,
+}
+
+impl DigestSelection {
+    /// The command-line flag choosing this selection
+    pub fn from_flag(flag: &str) -> Option<Self> {
+        match flag {
+            "--starred" => Some(DigestSelection::Starred),
+            "--unread" => Some(DigestSelection::Unread),
+            "--queue" => Some(DigestSelection::Queue),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DigestSelection::Starred => "starred",
+            DigestSelection::Unread => "unread",
+            DigestSelection::Queue => "queued",
+        }
+    }
+
+    fn includes(self, article: &Article) -> bool {
+        !article.is_trashed()
+            && match self {
+                DigestSelection::Starred => article.is_starred,
+                DigestSelection::Unread => !article.is_read,
+                DigestSelection::Queue => article.queued_at.is_some(),
+            }
+    }
+}
+
+/// A rendered roundup of articles with their AI summaries
+pub struct Digest {
+    pub subject: String,
+    pub html: String,
+    pub articles: usize,
+}
+
+/// Round up the articles picked by `selection` from the last `days` days,
+/// newest first, as an HTML page that reads well in mail clients; None when
+/// there are none
+pub async fn render_digest(
+    repository: &Repository,
+    selection: DigestSelection,
+    days: i64,
+    title: &str,
+) -> Result<Option<Digest>> {
+    let since = Utc::now() - Duration::days(days);
+    let mut entries = Vec::new();
+    for article in repository.get_all_articles_sorted().await? {
+        if !selection.includes(&article) || article.published_at.unwrap_or(article.fetched_at) < since
+        {
+            continue;
+        }
+        let summary = repository.get_summary(article.id).await?;
+        entries.push((article, summary));
+    }
+    if entries.is_empty() {
+        return Ok(None);
+    }
+
+    let today = Local::now().date_naive();
+    let start = today - Duration::days(days);
+    let start_format = if start.year() == today.year() { "%b %-d" } else { "%b %-d, %Y" };
+    let period = format!("{} – {}", start.format(start_format), today.format("%b %-d"));
+    let count = format!(
+        "{} {} article{}",
+        entries.len(),
+        selection.label(),
+        if entries.len() == 1 { "" } else { "s" }
+    );
+
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>{}</title>\n</head>\n", escape(title)));
+    html.push_str(
+        "<body style=\"margin:0;padding:24px;background:#f4f4f5;\
+         font-family:-apple-system,'Segoe UI',Helvetica,Arial,sans-serif;color:#18181b\">\n\
+         <div style=\"max-width:640px;margin:0 auto;background:#ffffff;border-radius:8px;padding:24px\">\n",
+    );
+    html.push_str(&format!(
+        "<h1 style=\"font-size:22px;margin:0 0 4px\">{}</h1>\n",
+        escape(title)
+    ));
+    html.push_str(&format!(
+        "<p style=\"color:#71717a;margin:0 0 24px\">{}, {}</p>\n",
+        count, period
+    ));
+    for (article, summary) in &entries {
+        html.push_str(&render_entry(article, summary.as_ref()));
+    }
+    html.push_str(
+        "<p style=\"color:#a1a1aa;font-size:12px;margin:24px 0 0\">Sent from SpeedyReader</p>\n\
+         </div>\n</body>\n</html>\n",
+    );
+
+    Ok(Some(Digest {
+        subject: format!("{}: {}, {}", title, count, period),
+        html,
+        articles: entries.len(),
+    }))
+}
+
+fn render_entry(article: &Article, summary: Option<&Summary>) -> String {
+    let mut entry = String::from("<div style=\"margin:0 0 24px\">\n");
+    let title = escape(&article.title);
+    if article.url.is_empty() {
+        entry.push_str(&format!("<div style=\"font-size:17px;font-weight:600\">{}</div>\n", title));
+    } else {
+        entry.push_str(&format!(
+            "<a href=\"{}\" style=\"font-size:17px;font-weight:600;color:#1d4ed8;\
+             text-decoration:none\">{}</a>\n",
+            escape(&article.url),
+            title
+        ));
+    }
+
+    let date = article
+        .published_at
+        .map(|dt| dt.with_timezone(&Local).format("%b %-d").to_string());
+    let minutes = match estimate_reading_minutes(article) {
+        0 => None,
+        minutes => Some(format!("{} min read", minutes)),
+    };
+    let byline: Vec<String> = [article.feed_title.clone(), article.author.clone(), date, minutes]
+        .into_iter()
+        .flatten()
+        .map(|part| escape(&part))
+        .collect();
+    if !byline.is_empty() {
+        entry.push_str(&format!(
+            "<div style=\"color:#71717a;font-size:13px;margin:4px 0 8px\">{}</div>\n",
+            byline.join(" · ")
+        ));
+    }
+
+    if let Some(summary) = summary {
+        entry.push_str(&render_summary(&summary.content));
+    }
+    entry.push_str("</div>\n");
+    entry
+}
+
+/// Summary text as HTML: bullet lines become a list, other lines paragraphs
+fn render_summary(content: &str) -> String {
+    let mut html = String::new();
+    let mut in_list = false;
+    for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let bullet = ["- ", "* ", "• "].iter().find_map(|b| line.strip_prefix(b));
+        match bullet {
+            Some(item) => {
+                if !in_list {
+                    html.push_str("<ul style=\"margin:0;padding-left:20px;line-height:1.5\">\n");
+                    in_list = true;
+                }
+                html.push_str(&format!("<li>{}</li>\n", escape(item)));
+            }
+            None => {
+                if in_list {
+                    html.push_str("</ul>\n");
+                    in_list = false;
+                }
+                html.push_str(&format!(
+                    "<p style=\"margin:0 0 8px;line-height:1.5\">{}</p>\n",
+                    escape(line)
+                ));
+            }
+        }
+    }
+    if in_list {
+        html.push_str("</ul>\n");
+    }
+    html
+}
+
+/// Email a digest through the SMTP server in `[email]`, logging in with
+/// `password` when a username is set
+pub async fn send_digest(
+    config: &EmailConfig,
+    password: Option<&str>,
+    digest: &Digest,
+) -> Result<()> {
+    let Some(host) = &config.host else {
+        return Err(AppError::Email("no SMTP host set in [email]".to_string()));
+    };
+    let Some(from) = config.from.as_ref().or(config.username.as_ref()) else {
+        return Err(AppError::Email("no from address set in [email]".to_string()));
+    };
+
+    let mailbox = |address: &str| {
+        address
+            .parse::<Mailbox>()
+            .map_err(|e| AppError::Email(format!("{}: {}", address, e)))
+    };
+    let mut message = Message::builder()
+        .from(mailbox(from)?)
+        .subject(&digest.subject)
+        .header(ContentType::TEXT_HTML);
+    for to in &config.to {
+        message = message.to(mailbox(to)?);
+    }
+    let message = message
+        .body(digest.html.clone())
+        .map_err(|e| AppError::Email(e.to_string()))?;
+
+    let relay = if config.starttls {
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+    } else {
+        AsyncSmtpTransport::<Tokio1Executor>::relay(host)
+    };
+    let mut transport = relay.map_err(|e| AppError::Email(e.to_string()))?;
+    if let Some(port) = config.port {
+        transport = transport.port(port);
+    }
+    if let Some(username) = &config.username {
+        let password = password.unwrap_or_default().to_string();
+        transport = transport.credentials(Credentials::new(username.clone(), password));
+    }
+    transport
+        .build()
+        .send(message)
+        .await
+        .map_err(|e| AppError::Email(e.to_string()))?;
+    Ok(())
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
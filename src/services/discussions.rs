@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::config::ProxyRoute;
+use crate::error::{AppError, Result};
+use crate::models::{normalize_url, Discussion, DiscussionSite};
+
+// Hacker News search, by Algolia; no account or API key needed
+const HN_SEARCH_URL: &str = "https://hn.algolia.com/api/v1/search";
+
+#[derive(Debug, Deserialize)]
+struct HnSearchResponse {
+    #[serde(default)]
+    hits: Vec<HnHit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HnHit {
+    #[serde(rename = "objectID")]
+    object_id: String,
+    title: Option<String>,
+    url: Option<String>,
+    num_comments: Option<u32>,
+    points: Option<i64>,
+}
+
+impl HnHit {
+    fn into_discussion(self) -> Discussion {
+        Discussion {
+            site: DiscussionSite::HackerNews,
+            url: format!("https://news.ycombinator.com/item?id={}", self.object_id),
+            title: self.title.unwrap_or_default(),
+            comments: Some(self.num_comments.unwrap_or(0)),
+            points: self.points,
+        }
+    }
+}
+
+// A Reddit post's JSON: the post listing, then the comments listing
+#[derive(Debug, Deserialize)]
+struct RedditListing {
+    data: RedditListingData,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditListingData {
+    children: Vec<RedditChild>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditChild {
+    data: RedditPost,
+}
+
+#[derive(Debug, Deserialize)]
+struct RedditPost {
+    title: Option<String>,
+    num_comments: Option<u32>,
+    score: Option<i64>,
+}
+
+/// Looks up comment threads about articles on Hacker News and Reddit
+#[derive(Clone)]
+pub struct DiscussionFinder {
+    client: Client,
+}
+
+impl DiscussionFinder {
+    pub fn new(proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
+            .timeout(Duration::from_secs(20))
+            .user_agent("speedy-reader/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Discussions of an article, most comments first: the one it comes
+    /// from (with current counts) and HN stories submitting its URL
+    pub async fn find(
+        &self,
+        article_url: &str,
+        origin: Option<Discussion>,
+    ) -> Result<Vec<Discussion>> {
+        let mut discussions = Vec::new();
+        if let Some(origin) = origin {
+            discussions.push(match self.refresh_counts(&origin).await {
+                Ok(discussion) => discussion,
+                Err(e) => {
+                    tracing::debug!("Failed to look up {}: {}", origin.url, e);
+                    origin
+                }
+            });
+        }
+
+        // Links to a discussion itself aren't submitted again
+        if !article_url.contains("news.ycombinator.com") && !article_url.contains("reddit.com") {
+            match self.search_hn(article_url).await {
+                Ok(found) => {
+                    for found in found {
+                        if !discussions.iter().any(|d| d.url == found.url) {
+                            discussions.push(found);
+                        }
+                    }
+                }
+                // The article's own thread is still worth listing
+                Err(e) if !discussions.is_empty() => {
+                    tracing::debug!("HN search for {} failed: {}", article_url, e)
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        discussions.sort_by_key(|d| std::cmp::Reverse(d.comments));
+        Ok(discussions)
+    }
+
+    /// The discussion with its current comment count and points
+    pub async fn refresh_counts(&self, discussion: &Discussion) -> Result<Discussion> {
+        match discussion.site {
+            DiscussionSite::HackerNews => {
+                let Some(id) = discussion.hn_id() else {
+                    return Ok(discussion.clone());
+                };
+                let tags = format!("story_{}", id);
+                let search: HnSearchResponse = self
+                    .get_json(HN_SEARCH_URL, &[("tags", tags.as_str())])
+                    .await?;
+                let Some(hit) = search.hits.into_iter().next() else {
+                    return Ok(discussion.clone());
+                };
+                let mut found = hit.into_discussion();
+                if found.title.is_empty() {
+                    found.title = discussion.title.clone();
+                }
+                Ok(found)
+            }
+            DiscussionSite::Reddit => {
+                let url = format!("{}.json", discussion.url.trim_end_matches('/'));
+                let listings: Vec<RedditListing> = self.get_json(&url, &[("limit", "1")]).await?;
+                let post = listings
+                    .into_iter()
+                    .next()
+                    .and_then(|listing| listing.data.children.into_iter().next())
+                    .map(|child| child.data)
+                    .ok_or_else(|| AppError::Discussion("Reddit post not found".to_string()))?;
+                Ok(Discussion {
+                    site: DiscussionSite::Reddit,
+                    url: discussion.url.clone(),
+                    title: post.title.unwrap_or_else(|| discussion.title.clone()),
+                    comments: Some(post.num_comments.unwrap_or(0)),
+                    points: post.score,
+                })
+            }
+        }
+    }
+
+    /// HN stories whose link is the article
+    async fn search_hn(&self, article_url: &str) -> Result<Vec<Discussion>> {
+        let search: HnSearchResponse = self
+            .get_json(
+                HN_SEARCH_URL,
+                &[
+                    ("query", article_url),
+                    ("restrictSearchableAttributes", "url"),
+                    ("tags", "story"),
+                ],
+            )
+            .await?;
+        // The search is fuzzy; keep exact matches only
+        let wanted = normalize_url(article_url);
+        Ok(search
+            .hits
+            .into_iter()
+            .filter(|hit| hit.url.as_deref().is_some_and(|url| normalize_url(url) == wanted))
+            .map(HnHit::into_discussion)
+            .collect())
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        query: &[(&str, &str)],
+    ) -> Result<T> {
+        let response = self.client.get(url).query(query).send().await?;
+        if !response.status().is_success() {
+            return Err(AppError::Discussion(format!("HTTP {}", response.status())));
+        }
+        Ok(response.json().await?)
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::config::ProxyRoute;
+use crate::error::{AppError, Result};
+use crate::models::DirectoryFeed;
+
+// Feedly's public feed search; no account or API key needed
+const FEEDLY_SEARCH_URL: &str = "https://cloud.feedly.com/v3/search/feeds";
+
+// Results listed per search
+const RESULT_LIMIT: usize = 20;
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    results: Vec<FeedlyFeed>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeedlyFeed {
+    /// "feed/" followed by the feed URL
+    feed_id: String,
+    title: Option<String>,
+    website: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    subscribers: u64,
+}
+
+impl FeedlyFeed {
+    fn into_directory_feed(self) -> Option<DirectoryFeed> {
+        let url = self.feed_id.strip_prefix("feed/")?.to_string();
+        Some(DirectoryFeed {
+            title: self.title.unwrap_or_else(|| url.clone()),
+            url,
+            site_url: self.website,
+            description: self.description,
+            subscribers: self.subscribers,
+        })
+    }
+}
+
+/// Keyword search over a public directory of feeds
+#[derive(Clone)]
+pub struct FeedDirectory {
+    client: Client,
+}
+
+impl FeedDirectory {
+    pub fn new(proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
+            .timeout(Duration::from_secs(20))
+            .user_agent("speedy-reader/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Feeds matching a keyword or topic, most followed first
+    pub async fn search(&self, query: &str) -> Result<Vec<DirectoryFeed>> {
+        let count = RESULT_LIMIT.to_string();
+        let response = self
+            .client
+            .get(FEEDLY_SEARCH_URL)
+            .query(&[("query", query), ("count", count.as_str())])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(AppError::FeedDirectory(format!(
+                "HTTP {}",
+                response.status()
+            )));
+        }
+
+        let search: SearchResponse = response.json().await?;
+        let mut feeds: Vec<DirectoryFeed> = search
+            .results
+            .into_iter()
+            .filter_map(FeedlyFeed::into_directory_feed)
+            // Only http(s) feeds can be subscribed to
+            .filter(|f| f.url.starts_with("http://") || f.url.starts_with("https://"))
+            .collect();
+        feeds.sort_by_key(|f| std::cmp::Reverse(f.subscribers));
+        Ok(feeds)
+    }
+}
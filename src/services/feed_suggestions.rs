@@ -0,0 +1,197 @@
+use std::collections::{HashMap, HashSet};
+
+use futures::stream::{self, StreamExt};
+use url::Url;
+
+use crate::db::Repository;
+use crate::error::Result;
+use crate::feed::FeedFetcher;
+use crate::models::{extract_links, normalize_url, FeedSuggestion, NewFeed};
+
+// Recent articles, read or starred, that suggestions are drawn from
+const HISTORY_ARTICLES: usize = 1000;
+
+// A star says more about taste than a read
+const STAR_WEIGHT: u32 = 3;
+const READ_WEIGHT: u32 = 1;
+
+// Sites linked from fewer articles than this are left out as one-offs
+const MIN_LINKING_ARTICLES: u32 = 2;
+
+// Best linked sites checked for a feed
+const MAX_LINKED_SITES: usize = 15;
+
+// Most-read feeds whose blogrolls are looked up, and what a listing is worth
+const BLOGROLL_FEEDS: usize = 8;
+const BLOGROLL_WEIGHT: u32 = 5;
+
+const MAX_SUGGESTIONS: usize = 20;
+
+// Sites linked from everywhere, which say nothing about what the reader likes
+const COMMON_HOSTS: &[&str] = &[
+    "amazon.com",
+    "apple.com",
+    "archive.org",
+    "bit.ly",
+    "bsky.app",
+    "doi.org",
+    "facebook.com",
+    "github.com",
+    "google.com",
+    "instagram.com",
+    "linkedin.com",
+    "mastodon.social",
+    "medium.com",
+    "news.ycombinator.com",
+    "reddit.com",
+    "t.co",
+    "twitter.com",
+    "wikipedia.org",
+    "x.com",
+    "youtu.be",
+    "youtube.com",
+];
+
+/// Feeds the reader may like, best first: sites that the articles they read
+/// and starred link to most, and feeds in the blogrolls of the feeds they
+/// read most. Feeds already subscribed to are left out.
+pub async fn suggest_feeds(
+    repository: &Repository,
+    fetcher: &FeedFetcher,
+) -> Result<Vec<FeedSuggestion>> {
+    let feeds = repository.get_all_feeds().await?;
+    let subscribed_urls: HashSet<String> = feeds.iter().map(|f| normalize_url(&f.url)).collect();
+    let subscribed_hosts: HashSet<String> = feeds
+        .iter()
+        .flat_map(|f| [Some(f.url.as_str()), f.site_url.as_deref()])
+        .flatten()
+        .filter_map(host)
+        .collect();
+
+    let history = repository
+        .get_all_articles_sorted()
+        .await?
+        .into_iter()
+        .filter(|a| (a.is_read || a.is_starred) && !a.is_trashed())
+        .take(HISTORY_ARTICLES);
+
+    // Weight of each linked site and of each feed, and how many articles link
+    // to each site
+    let mut sites: HashMap<String, (u32, u32)> = HashMap::new();
+    let mut feed_weights: HashMap<i64, u32> = HashMap::new();
+    for article in history {
+        let weight = if article.is_starred { STAR_WEIGHT } else { READ_WEIGHT };
+        *feed_weights.entry(article.feed_id).or_default() += weight;
+
+        let own_host = host(&article.url);
+        let content = article.content.as_deref().or(article.content_text.as_deref());
+        let linked: HashSet<String> = extract_links(&article.url, content.unwrap_or_default())
+            .iter()
+            .filter_map(|link| host(link))
+            .filter(|h| Some(h) != own_host.as_ref())
+            .collect();
+        for site in linked {
+            let (site_weight, articles) = sites.entry(site).or_default();
+            *site_weight += weight;
+            *articles += 1;
+        }
+    }
+
+    let mut linked_sites: Vec<(String, u32, u32)> = sites
+        .into_iter()
+        .filter(|(site, (_, articles))| {
+            *articles >= MIN_LINKING_ARTICLES
+                && !subscribed_hosts.contains(site)
+                && !is_common(site)
+        })
+        .map(|(site, (weight, articles))| (site, weight, articles))
+        .collect();
+    linked_sites.sort_by_key(|(_, weight, _)| std::cmp::Reverse(*weight));
+    linked_sites.truncate(MAX_LINKED_SITES);
+
+    let mut suggestions: Vec<FeedSuggestion> = stream::iter(linked_sites)
+        .map(|(site, weight, articles)| async move {
+            let home = format!("https://{}/", site);
+            let feed = match fetcher.discover_feed(&home, None).await {
+                Ok(mut found) if !found.is_empty() => found.swap_remove(0),
+                Ok(_) => return None,
+                Err(e) => {
+                    tracing::debug!("No feed suggested for {}: {}", site, e);
+                    return None;
+                }
+            };
+            Some(FeedSuggestion {
+                title: feed.title,
+                url: feed.url,
+                site_url: feed.site_url.or(Some(home)),
+                reason: format!("Linked from {} articles you read", articles),
+                score: weight,
+            })
+        })
+        .buffer_unordered(5)
+        .filter_map(|suggestion| async { suggestion })
+        .collect()
+        .await;
+
+    let mut read_most: Vec<(i64, u32)> = feed_weights.into_iter().collect();
+    read_most.sort_by_key(|(_, weight)| std::cmp::Reverse(*weight));
+    let blogroll_sources: Vec<(String, String)> = read_most
+        .iter()
+        .filter_map(|(id, _)| feeds.iter().find(|f| f.id == *id))
+        .filter_map(|f| Some((f.title.clone(), f.site_url.clone()?)))
+        .take(BLOGROLL_FEEDS)
+        .collect();
+    let blogrolls: Vec<(String, Vec<NewFeed>)> = stream::iter(blogroll_sources)
+        .map(|(title, site_url)| async move {
+            match fetcher.find_blogroll(&site_url).await {
+                Ok(listed) => Some((title, listed)),
+                Err(e) => {
+                    tracing::debug!("No blogroll read from {}: {}", site_url, e);
+                    None
+                }
+            }
+        })
+        .buffer_unordered(5)
+        .filter_map(|blogroll| async { blogroll })
+        .collect()
+        .await;
+    for (source, listed) in blogrolls {
+        for feed in listed {
+            let reason = format!("In the blogroll of {}", source);
+            let url = normalize_url(&feed.url);
+            match suggestions.iter_mut().find(|s| normalize_url(&s.url) == url) {
+                Some(known) => {
+                    known.score += BLOGROLL_WEIGHT;
+                    known.reason = format!("{}; {}", known.reason, reason);
+                }
+                None => suggestions.push(FeedSuggestion {
+                    title: feed.title,
+                    url: feed.url,
+                    site_url: feed.site_url,
+                    reason,
+                    score: BLOGROLL_WEIGHT,
+                }),
+            }
+        }
+    }
+
+    suggestions.retain(|s| {
+        !subscribed_urls.contains(&normalize_url(&s.url))
+            && !s.site_url.as_deref().and_then(host).is_some_and(|h| subscribed_hosts.contains(&h))
+    });
+    suggestions.sort_by_key(|s| std::cmp::Reverse(s.score));
+    suggestions.truncate(MAX_SUGGESTIONS);
+    Ok(suggestions)
+}
+
+/// A URL's host without `www.`
+fn host(url: &str) -> Option<String> {
+    let host = Url::parse(url).ok()?.host_str()?.to_lowercase();
+    Some(host.strip_prefix("www.").map(str::to_string).unwrap_or(host))
+}
+
+fn is_common(host: &str) -> bool {
+    COMMON_HOSTS
+        .iter()
+        .any(|common| host == *common || host.ends_with(&format!(".{}", common)))
+}
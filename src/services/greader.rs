@@ -0,0 +1,380 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder, Response};
+use serde::Deserialize;
+use tokio::sync::Mutex;
+
+use crate::config::{ProxyRoute, SyncConfig};
+use crate::error::{AppError, Result};
+
+pub const READING_LIST: &str = "user/-/state/com.google/reading-list";
+pub const READ_TAG: &str = "user/-/state/com.google/read";
+pub const STARRED_TAG: &str = "user/-/state/com.google/starred";
+
+const ITEM_ID_PREFIX: &str = "tag:google.com,2005:reader/item/";
+
+// Items requested per page, and pages followed per stream
+const PAGE_SIZE: usize = 1000;
+const MAX_PAGES: usize = 10;
+
+// Items changed per edit-tag request
+const EDIT_BATCH_SIZE: usize = 100;
+
+/// A feed subscribed on the sync server
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    pub url: String,
+    pub title: String,
+    pub site_url: Option<String>,
+    pub category: Option<String>,
+}
+
+/// An entry as stored on the sync server
+#[derive(Debug, Clone)]
+pub struct RemoteItem {
+    /// Long form item id (tag:google.com,2005:reader/item/...)
+    pub id: String,
+    pub feed_url: String,
+    pub title: String,
+    pub url: String,
+    pub author: Option<String>,
+    pub content: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub is_read: bool,
+    pub is_starred: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SubscriptionList {
+    subscriptions: Vec<SubscriptionRow>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubscriptionRow {
+    id: String,
+    title: String,
+    url: Option<String>,
+    html_url: Option<String>,
+    #[serde(default)]
+    categories: Vec<Category>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Category {
+    label: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemIds {
+    #[serde(default, rename = "itemRefs")]
+    item_refs: Vec<ItemRef>,
+    continuation: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemRef {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamContents {
+    #[serde(default)]
+    items: Vec<ItemRow>,
+    continuation: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ItemRow {
+    id: String,
+    #[serde(default)]
+    title: String,
+    published: Option<i64>,
+    author: Option<String>,
+    #[serde(default)]
+    canonical: Vec<Link>,
+    #[serde(default)]
+    alternate: Vec<Link>,
+    summary: Option<Body>,
+    content: Option<Body>,
+    origin: Origin,
+    #[serde(default)]
+    categories: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Link {
+    href: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Body {
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Origin {
+    stream_id: String,
+}
+
+impl From<ItemRow> for RemoteItem {
+    fn from(row: ItemRow) -> Self {
+        // Tags come back as user/<id>/state/..., not always as user/-/state/...
+        let has_state = |state: &str| row.categories.iter().any(|c| c.ends_with(state));
+        let is_read = has_state("/state/com.google/read");
+        let is_starred = has_state("/state/com.google/starred");
+        RemoteItem {
+            id: long_item_id(&row.id),
+            feed_url: row
+                .origin
+                .stream_id
+                .strip_prefix("feed/")
+                .unwrap_or(&row.origin.stream_id)
+                .to_string(),
+            url: row
+                .canonical
+                .into_iter()
+                .chain(row.alternate)
+                .map(|l| l.href)
+                .next()
+                .unwrap_or_default(),
+            title: row.title,
+            author: row.author.filter(|a| !a.is_empty()),
+            content: row.content.or(row.summary).map(|b| b.content),
+            published_at: row.published.and_then(|s| DateTime::from_timestamp(s, 0)),
+            is_read,
+            is_starred,
+        }
+    }
+}
+
+/// Item ids come as decimal numbers from some calls and as tag URIs from
+/// others; always use the tag URI
+fn long_item_id(id: &str) -> String {
+    match id.parse::<i64>() {
+        Ok(n) => format!("{}{:016x}", ITEM_ID_PREFIX, n as u64),
+        Err(_) => id.to_string(),
+    }
+}
+
+/// Client for the Google Reader compatible API of FreshRSS, The Old Reader
+/// and similar services
+pub struct GReaderClient {
+    client: Client,
+    base_url: String,
+    username: String,
+    password: String,
+    // ClientLogin auth token, fetched on first use
+    auth: Mutex<Option<String>>,
+}
+
+impl GReaderClient {
+    pub fn new(config: &SyncConfig, proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
+            .timeout(Duration::from_secs(60))
+            .user_agent("speedy-reader/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self {
+            client,
+            base_url: config.url.trim_end_matches('/').to_string(),
+            username: config.username.clone(),
+            password: config.password.clone(),
+            auth: Mutex::new(None),
+        }
+    }
+
+    /// Log in once and cache the auth token
+    async fn auth_token(&self) -> Result<String> {
+        let mut cached = self.auth.lock().await;
+        if let Some(auth) = cached.as_ref() {
+            return Ok(auth.clone());
+        }
+
+        let response = self
+            .client
+            .post(format!("{}/accounts/ClientLogin", self.base_url))
+            .form(&[("Email", &self.username), ("Passwd", &self.password)])
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(AppError::Sync(format!(
+                "login failed: HTTP {}",
+                response.status()
+            )));
+        }
+
+        // Plain text response: SID=...\nLSID=...\nAuth=...
+        let body = response.text().await?;
+        let auth = body
+            .lines()
+            .find_map(|line| line.strip_prefix("Auth="))
+            .map(|auth| auth.trim().to_string())
+            .ok_or_else(|| AppError::Sync("login response had no Auth token".to_string()))?;
+        *cached = Some(auth.clone());
+        Ok(auth)
+    }
+
+    async fn request(&self, builder: RequestBuilder) -> Result<Response> {
+        let auth = self.auth_token().await?;
+        let response = builder
+            .header(
+                reqwest::header::AUTHORIZATION,
+                format!("GoogleLogin auth={}", auth),
+            )
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::Sync(format!(
+                "HTTP {}: {}",
+                status,
+                error_text.trim()
+            )));
+        }
+        Ok(response)
+    }
+
+    fn api_url(&self, path: &str) -> String {
+        format!("{}/reader/api/0/{}", self.base_url, path)
+    }
+
+    /// Short-lived token required by every write call
+    async fn write_token(&self) -> Result<String> {
+        let response = self.request(self.client.get(self.api_url("token"))).await?;
+        Ok(response.text().await?.trim().to_string())
+    }
+
+    async fn post(&self, path: &str, mut form: Vec<(&str, String)>) -> Result<()> {
+        form.push(("T", self.write_token().await?));
+        self.request(self.client.post(self.api_url(path)).form(&form))
+            .await?;
+        Ok(())
+    }
+
+    /// Check that the server accepts the username and password
+    pub async fn verify_credentials(&self) -> Result<()> {
+        self.write_token().await.map(|_| ())
+    }
+
+    pub async fn subscriptions(&self) -> Result<Vec<Subscription>> {
+        let response = self
+            .request(
+                self.client
+                    .get(self.api_url("subscription/list"))
+                    .query(&[("output", "json")]),
+            )
+            .await?;
+        let list: SubscriptionList = response.json().await?;
+        Ok(list
+            .subscriptions
+            .into_iter()
+            .map(|row| Subscription {
+                url: row
+                    .url
+                    .unwrap_or_else(|| row.id.strip_prefix("feed/").unwrap_or(&row.id).to_string()),
+                title: row.title,
+                site_url: row.html_url,
+                category: row.categories.into_iter().find_map(|c| c.label),
+            })
+            .collect())
+    }
+
+    pub async fn subscribe(&self, url: &str, title: &str) -> Result<()> {
+        self.post(
+            "subscription/edit",
+            vec![
+                ("ac", "subscribe".to_string()),
+                ("s", format!("feed/{}", url)),
+                ("t", title.to_string()),
+            ],
+        )
+        .await
+    }
+
+    pub async fn unsubscribe(&self, url: &str) -> Result<()> {
+        self.post(
+            "subscription/edit",
+            vec![
+                ("ac", "unsubscribe".to_string()),
+                ("s", format!("feed/{}", url)),
+            ],
+        )
+        .await
+    }
+
+    /// Ids of the items in a stream, optionally leaving out those with a tag
+    pub async fn item_ids(&self, stream: &str, exclude: Option<&str>) -> Result<HashSet<String>> {
+        let mut ids = HashSet::new();
+        let mut continuation: Option<String> = None;
+        for _ in 0..MAX_PAGES {
+            let mut query = vec![
+                ("s", stream.to_string()),
+                ("n", PAGE_SIZE.to_string()),
+                ("output", "json".to_string()),
+            ];
+            if let Some(exclude) = exclude {
+                query.push(("xt", exclude.to_string()));
+            }
+            if let Some(c) = continuation.take() {
+                query.push(("c", c));
+            }
+
+            let response = self
+                .request(
+                    self.client
+                        .get(self.api_url("stream/items/ids"))
+                        .query(&query),
+                )
+                .await?;
+            let page: ItemIds = response.json().await?;
+            ids.extend(page.item_refs.iter().map(|r| long_item_id(&r.id)));
+            match page.continuation {
+                Some(c) if !c.is_empty() => continuation = Some(c),
+                _ => break,
+            }
+        }
+        Ok(ids)
+    }
+
+    /// Items from every subscription published since `since`, newest first
+    pub async fn items_since(&self, since: DateTime<Utc>) -> Result<Vec<RemoteItem>> {
+        let mut items = Vec::new();
+        let mut continuation: Option<String> = None;
+        for _ in 0..MAX_PAGES {
+            let mut query = vec![
+                ("n", PAGE_SIZE.to_string()),
+                ("ot", since.timestamp().to_string()),
+                ("output", "json".to_string()),
+            ];
+            if let Some(c) = continuation.take() {
+                query.push(("c", c));
+            }
+
+            let url = self.api_url(&format!("stream/contents/{}", READING_LIST));
+            let response = self.request(self.client.get(url).query(&query)).await?;
+            let page: StreamContents = response.json().await?;
+            items.extend(page.items.into_iter().map(RemoteItem::from));
+            match page.continuation {
+                Some(c) if !c.is_empty() => continuation = Some(c),
+                _ => break,
+            }
+        }
+        Ok(items)
+    }
+
+    /// Add or remove a state tag (read, starred) on items
+    pub async fn edit_tag(&self, item_ids: &[String], tag: &str, add: bool) -> Result<()> {
+        for batch in item_ids.chunks(EDIT_BATCH_SIZE) {
+            let mut form: Vec<(&str, String)> = batch.iter().map(|id| ("i", id.clone())).collect();
+            form.push((if add { "a" } else { "r" }, tag.to_string()));
+            self.post("edit-tag", form).await?;
+        }
+        Ok(())
+    }
+}
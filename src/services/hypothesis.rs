@@ -0,0 +1,233 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::config::ProxyRoute;
+use crate::error::{AppError, Result};
+use crate::models::Annotation;
+
+const HYPOTHESIS_API_URL: &str = "https://api.hypothes.is/api";
+const HYPOTHESIS_MEDIA_TYPE: &str = "application/vnd.hypothesis.v1+json";
+const PUBLIC_GROUP: &str = "__world__";
+
+// Annotations fetched per article (the API maximum)
+const SEARCH_LIMIT: usize = 200;
+
+#[derive(Debug, Deserialize)]
+struct ProfileResponse {
+    userid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    rows: Vec<AnnotationRow>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnnotationRow {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    updated: DateTime<Utc>,
+    permissions: Permissions,
+    #[serde(default)]
+    target: Vec<Target>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Permissions {
+    read: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Target {
+    source: String,
+    #[serde(default)]
+    selector: Vec<Selector>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Selector {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    exact: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateAnnotationRequest {
+    uri: String,
+    document: Document,
+    text: String,
+    tags: Vec<String>,
+    group: String,
+    permissions: Permissions,
+    target: Vec<Target>,
+}
+
+#[derive(Debug, Serialize)]
+struct Document {
+    title: Vec<String>,
+}
+
+impl From<AnnotationRow> for Annotation {
+    fn from(row: AnnotationRow) -> Self {
+        let quote = row
+            .target
+            .iter()
+            .flat_map(|t| &t.selector)
+            .find(|s| s.kind == "TextQuoteSelector")
+            .and_then(|s| s.exact.clone());
+        // Anything not readable by a group is "Only me"
+        let is_private = !row.permissions.read.iter().any(|r| r.starts_with("group:"));
+        Annotation {
+            quote,
+            text: row.text,
+            tags: row.tags,
+            is_private,
+            updated_at: row.updated,
+        }
+    }
+}
+
+pub struct HypothesisClient {
+    client: Client,
+    api_token: String,
+    // Cached account id (acct:name@hypothes.is) of the token's owner
+    userid: Mutex<Option<String>>,
+}
+
+impl HypothesisClient {
+    pub fn new(api_token: String, proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
+        Self {
+            client,
+            api_token,
+            userid: Mutex::new(None),
+        }
+    }
+
+    fn request(&self, builder: RequestBuilder) -> RequestBuilder {
+        builder
+            .bearer_auth(&self.api_token)
+            .header(reqwest::header::ACCEPT, HYPOTHESIS_MEDIA_TYPE)
+    }
+
+    /// Account id of the token's owner, fetched once and cached
+    async fn userid(&self) -> Result<String> {
+        let mut cached = self.userid.lock().await;
+        if let Some(userid) = cached.as_ref() {
+            return Ok(userid.clone());
+        }
+
+        let response = self
+            .request(self.client.get(format!("{}/profile", HYPOTHESIS_API_URL)))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(AppError::HypothesisApi(format!(
+                "HTTP {}",
+                response.status()
+            )));
+        }
+
+        // An invalid token still gets a profile, just an anonymous one
+        let profile: ProfileResponse = response.json().await?;
+        let userid = profile
+            .userid
+            .ok_or_else(|| AppError::HypothesisApi("API token not accepted".to_string()))?;
+        *cached = Some(userid.clone());
+        Ok(userid)
+    }
+
+    /// Check that the API token belongs to an account
+    pub async fn verify_credentials(&self) -> Result<()> {
+        self.userid().await.map(|_| ())
+    }
+
+    /// My public and private annotations on a page, oldest first
+    pub async fn annotations_for(&self, url: &str) -> Result<Vec<Annotation>> {
+        let userid = self.userid().await?;
+        let limit = SEARCH_LIMIT.to_string();
+
+        let response = self
+            .request(self.client.get(format!("{}/search", HYPOTHESIS_API_URL)))
+            .query(&[
+                ("uri", url),
+                ("user", userid.as_str()),
+                ("limit", limit.as_str()),
+                ("sort", "created"),
+                ("order", "asc"),
+            ])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(AppError::HypothesisApi(format!(
+                "API error: {}",
+                error_text
+            )));
+        }
+
+        let search: SearchResponse = response.json().await?;
+        Ok(search.rows.into_iter().map(Annotation::from).collect())
+    }
+
+    /// Create a private highlight of `quote` on a page
+    pub async fn create_highlight(
+        &self,
+        url: &str,
+        title: &str,
+        quote: &str,
+        tags: Vec<String>,
+    ) -> Result<Annotation> {
+        let userid = self.userid().await?;
+
+        let request = CreateAnnotationRequest {
+            uri: url.to_string(),
+            document: Document {
+                title: vec![title.to_string()],
+            },
+            text: String::new(),
+            tags,
+            group: PUBLIC_GROUP.to_string(),
+            permissions: Permissions { read: vec![userid] },
+            target: vec![Target {
+                source: url.to_string(),
+                selector: vec![Selector {
+                    kind: "TextQuoteSelector".to_string(),
+                    exact: Some(quote.to_string()),
+                }],
+            }],
+        };
+
+        let response = self
+            .request(
+                self.client
+                    .post(format!("{}/annotations", HYPOTHESIS_API_URL)),
+            )
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(AppError::HypothesisApi(format!(
+                "API error: {}",
+                error_text
+            )));
+        }
+
+        let row: AnnotationRow = response.json().await?;
+        Ok(row.into())
+    }
+}
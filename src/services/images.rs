@@ -0,0 +1,182 @@
+use std::io::Cursor;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use image::{DynamicImage, ImageFormat};
+use regex::Regex;
+use reqwest::Client;
+use url::Url;
+
+use crate::config::ProxyRoute;
+use crate::error::{AppError, Result};
+
+// Lead images are rarely more than a few hundred KB; skip anything huge
+const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+// Decoded images are scaled down to fit this (pixels) before they are cached
+const MAX_IMAGE_DIMENSION: u32 = 1024;
+// Favicons are stored scaled down to fit this (pixels)
+const FAVICON_SIZE: u32 = 32;
+
+static IMG_SRC: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)<img\b[^>]*?\bsrc\s*=\s*["']([^"']+)["']"#).unwrap());
+
+// <meta property="og:image" content="..."> with the attributes in either order
+static OG_IMAGE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<meta[^>]*(?:property|name)\s*=\s*["'](?:og:image|twitter:image)(?::src)?["'][^>]*content\s*=\s*["']([^"']+)["']"#).unwrap()
+});
+static OG_IMAGE_REVERSED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<meta[^>]*content\s*=\s*["']([^"']+)["'][^>]*(?:property|name)\s*=\s*["'](?:og:image|twitter:image)(?::src)?["']"#).unwrap()
+});
+
+// <link rel="icon">, "shortcut icon", "apple-touch-icon" and the like
+static ICON_LINK: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)<link\b[^>]*\brel\s*=\s*["'][^"']*\bicon\b[^"']*["'][^>]*>"#).unwrap()
+});
+static HREF: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)\bhref\s*=\s*["']([^"']+)["']"#).unwrap());
+
+/// First `<img>` in an article's HTML, resolved against the article URL
+pub fn first_content_image(html: &str, base_url: &str) -> Option<String> {
+    IMG_SRC
+        .captures_iter(html)
+        .filter_map(|cap| resolve_image_url(&cap[1], base_url))
+        .next()
+}
+
+/// The page's `og:image` (or `twitter:image`), resolved against its URL
+pub fn og_image(html: &str, base_url: &str) -> Option<String> {
+    OG_IMAGE
+        .captures(html)
+        .or_else(|| OG_IMAGE_REVERSED.captures(html))
+        .and_then(|cap| resolve_image_url(&cap[1], base_url))
+}
+
+/// Icons a page declares in its `<head>`, resolved against its URL
+pub fn icon_links(html: &str, base_url: &str) -> Vec<String> {
+    ICON_LINK
+        .find_iter(html)
+        .filter_map(|link| HREF.captures(link.as_str()))
+        .filter_map(|cap| resolve_image_url(&cap[1], base_url))
+        .collect()
+}
+
+/// Average color of an icon's opaque pixels, leaving out near-white and
+/// near-black ones (backgrounds and outlines); None for monochrome icons
+pub fn icon_color(icon: &DynamicImage) -> Option<(u8, u8, u8)> {
+    let (mut sum, mut count) = ([0u64; 3], 0u64);
+    for pixel in icon.to_rgba8().pixels() {
+        let [r, g, b, a] = pixel.0;
+        let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+        if a < 128 || min > 230 || max < 25 {
+            continue;
+        }
+        for (total, channel) in sum.iter_mut().zip([r, g, b]) {
+            *total += channel as u64;
+        }
+        count += 1;
+    }
+    (count > 0).then(|| {
+        let [r, g, b] = sum.map(|total| (total / count) as u8);
+        (r, g, b)
+    })
+}
+
+/// Absolute http(s) URL for an image reference; inline `data:` images and
+/// other schemes are skipped
+fn resolve_image_url(href: &str, base_url: &str) -> Option<String> {
+    let href = href.trim().replace("&amp;", "&");
+    let url = match Url::parse(&href) {
+        Ok(url) => url,
+        Err(_) => Url::parse(base_url).ok()?.join(&href).ok()?,
+    };
+    matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+}
+
+#[derive(Clone)]
+pub struct ImageFetcher {
+    client: Client,
+}
+
+impl ImageFetcher {
+    pub fn new(proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
+            .timeout(Duration::from_secs(20))
+            .connect_timeout(Duration::from_secs(10))
+            .user_agent("speedy-reader/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// Look up the lead image of an article page (og:image, else the first image)
+    pub async fn find_lead_image(&self, article_url: &str) -> Result<Option<String>> {
+        let response = self.client.get(article_url).send().await?;
+        if !response.status().is_success() {
+            return Ok(None);
+        }
+        let html = response.text().await?;
+        Ok(og_image(&html, article_url).or_else(|| first_content_image(&html, article_url)))
+    }
+
+    /// Look up a site's favicon, declared on its home page or else at
+    /// /favicon.ico, as a small PNG. None when the site has no usable icon.
+    pub async fn fetch_favicon(&self, site_url: &str) -> Result<Option<Vec<u8>>> {
+        let response = self.client.get(site_url).send().await?;
+        let page_url = response.url().to_string();
+        let mut candidates = if response.status().is_success() {
+            icon_links(&response.text().await?, &page_url)
+        } else {
+            Vec::new()
+        };
+        candidates.extend(resolve_image_url("/favicon.ico", &page_url));
+
+        for url in candidates {
+            match self.fetch_image(&url).await {
+                Ok(icon) => {
+                    let icon = icon.thumbnail(FAVICON_SIZE, FAVICON_SIZE);
+                    let mut png = Vec::new();
+                    icon.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+                        .map_err(|e| AppError::Image(e.to_string()))?;
+                    return Ok(Some(png));
+                }
+                Err(e) => tracing::debug!("No favicon at {}: {}", url, e),
+            }
+        }
+        Ok(None)
+    }
+
+    /// Download and decode an image, scaled down for display
+    pub async fn fetch_image(&self, url: &str) -> Result<DynamicImage> {
+        let response = self.client.get(url).send().await?;
+        if !response.status().is_success() {
+            return Err(AppError::Image(format!("HTTP {}", response.status())));
+        }
+        if response
+            .content_length()
+            .is_some_and(|len| len as usize > MAX_IMAGE_BYTES)
+        {
+            return Err(AppError::Image("image too large".to_string()));
+        }
+
+        let bytes = response.bytes().await?;
+        if bytes.len() > MAX_IMAGE_BYTES {
+            return Err(AppError::Image("image too large".to_string()));
+        }
+
+        // Decoding and scaling are CPU bound; keep them off the async workers
+        tokio::task::spawn_blocking(move || {
+            let image =
+                image::load_from_memory(&bytes).map_err(|e| AppError::Image(e.to_string()))?;
+            Ok(
+                if image.width() > MAX_IMAGE_DIMENSION || image.height() > MAX_IMAGE_DIMENSION {
+                    image.thumbnail(MAX_IMAGE_DIMENSION, MAX_IMAGE_DIMENSION)
+                } else {
+                    image
+                },
+            )
+        })
+        .await
+        .map_err(|e| AppError::Image(e.to_string()))?
+    }
+}
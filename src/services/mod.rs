@@ -1,5 +1,44 @@
+mod backup;
+mod clipboard;
 mod content_fetcher;
+mod credentials;
+mod digest;
+mod discussions;
+mod feed_directory;
+mod feed_suggestions;
+mod greader;
+mod hypothesis;
+mod images;
+mod notes;
+mod notifier;
+mod print;
 mod raindrop;
+mod social;
+mod speech;
+mod starred_feed;
+mod sync;
+mod webhook;
 
+pub use backup::{export_json, import_json};
+pub use clipboard::Clipboard;
 pub use content_fetcher::ContentFetcher;
-pub use raindrop::RaindropClient;
+pub use credentials::{
+    delete_feed_secret, load_config_secret, load_feed_credentials, store_config_secret,
+    store_feed_secret,
+};
+pub use digest::{render_digest, send_digest, DigestSelection};
+pub use discussions::DiscussionFinder;
+pub use feed_directory::FeedDirectory;
+pub use feed_suggestions::suggest_feeds;
+pub use greader::GReaderClient;
+pub use hypothesis::HypothesisClient;
+pub use images::{first_content_image, icon_color, ImageFetcher};
+pub use notes::export_starred_notes;
+pub use notifier::notify_desktop;
+pub use print::{print_text, render_print_text};
+pub use raindrop::{RaindropClient, RaindropCollection};
+pub use social::{ShareTarget, SocialPoster};
+pub use speech::Speaker;
+pub use starred_feed::{export_starred_feed, serve_starred_feed};
+pub use sync::{sync_greader, sync_raindrop, RaindropSyncReport, SyncReport};
+pub use webhook::{webhook_host, WebhookArticle, WebhookSender};
@@ -0,0 +1,127 @@
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::db::Repository;
+use crate::error::Result;
+use crate::models::{Article, Summary};
+
+// Keep generated file names a reasonable length
+const MAX_SLUG_CHARS: usize = 60;
+
+/// Outcome of exporting starred articles as Markdown notes
+#[derive(Debug, Default)]
+pub struct NotesExport {
+    pub written: usize,
+    pub skipped: usize,
+}
+
+/// Write every starred article to `dir` as a Markdown note with front-matter.
+/// Notes that already exist are left alone, so re-exporting never clobbers edits.
+pub async fn export_starred_notes(repository: &Repository, dir: &Path) -> Result<NotesExport> {
+    std::fs::create_dir_all(dir)?;
+
+    let mut export = NotesExport::default();
+    let articles = repository.get_all_articles_sorted().await?;
+    for article in articles.iter().filter(|a| a.is_starred && !a.is_trashed()) {
+        let path = note_path(dir, article);
+        if path.exists() {
+            export.skipped += 1;
+            continue;
+        }
+
+        // Created only when missing, so an existing note is never replaced,
+        // even one written since the check above
+        let summary = repository.get_summary(article.id).await?;
+        let file = OpenOptions::new().write(true).create_new(true).open(&path);
+        match file {
+            Ok(mut file) => {
+                file.write_all(render_note(article, summary.as_ref()).as_bytes())?;
+                export.written += 1;
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => export.skipped += 1,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(export)
+}
+
+/// Stable file name: publication date plus title slug, with the article id
+/// keeping notes of identically titled articles apart
+fn note_path(dir: &Path, article: &Article) -> PathBuf {
+    let date = article
+        .published_at
+        .unwrap_or(article.fetched_at)
+        .format("%Y-%m-%d");
+    dir.join(format!(
+        "{} {} ({}).md",
+        date,
+        slugify(&article.title),
+        article.id
+    ))
+}
+
+/// Strip characters that are invalid in file names or Obsidian links
+fn slugify(title: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .take(MAX_SLUG_CHARS)
+        .collect();
+    let slug = cleaned.split_whitespace().collect::<Vec<_>>().join(" ");
+    if slug.is_empty() {
+        "Untitled".to_string()
+    } else {
+        slug
+    }
+}
+
+fn render_note(article: &Article, summary: Option<&Summary>) -> String {
+    let mut note = String::from("---\n");
+    note.push_str(&format!("title: {}\n", yaml_string(&article.title)));
+    note.push_str(&format!("url: {}\n", yaml_string(&article.url)));
+    if let Some(feed) = &article.feed_title {
+        note.push_str(&format!("feed: {}\n", yaml_string(feed)));
+    }
+    if let Some(author) = &article.author {
+        note.push_str(&format!("author: {}\n", yaml_string(author)));
+    }
+    if let Some(published) = article.published_at {
+        note.push_str(&format!("date: {}\n", published.format("%Y-%m-%d")));
+    }
+    let tags: Vec<String> = std::iter::once("rss".to_string())
+        .chain(article.tags.iter().filter(|tag| *tag != "rss").map(|tag| yaml_string(tag)))
+        .collect();
+    note.push_str(&format!("tags: [{}]\n---\n\n", tags.join(", ")));
+
+    note.push_str(&format!("# {}\n\n", article.title));
+    note.push_str(&format!("[Original article]({})\n\n", article.url));
+
+    if let Some(summary) = summary {
+        note.push_str("## Summary\n\n");
+        note.push_str(summary.content.trim());
+        note.push_str("\n\n");
+    }
+
+    if let Some(content) = article
+        .content_text
+        .as_deref()
+        .or(article.content.as_deref())
+    {
+        note.push_str("## Content\n\n");
+        note.push_str(content.trim());
+        note.push('\n');
+    }
+
+    note
+}
+
+/// Quote a value for YAML front-matter
+fn yaml_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
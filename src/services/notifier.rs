@@ -0,0 +1,11 @@
+/// Show a desktop notification (blocking; call from a blocking task)
+pub fn notify_desktop(summary: &str, body: &str) {
+    if let Err(e) = notify_rust::Notification::new()
+        .appname("SpeedyReader")
+        .summary(summary)
+        .body(body)
+        .show()
+    {
+        tracing::debug!("Failed to show desktop notification: {}", e);
+    }
+}
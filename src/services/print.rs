@@ -0,0 +1,119 @@
+use std::process::Stdio;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::config::PrintConfig;
+use crate::error::{AppError, Result};
+use crate::models::{Article, Summary};
+
+// Characters per line of the printed text
+const PAGE_WIDTH: usize = 72;
+
+/// Lay out an article and its summary as plain text for printing
+pub fn render_print_text(article: &Article, summary: Option<&Summary>) -> String {
+    let mut text = String::new();
+
+    for line in textwrap::wrap(&article.title, PAGE_WIDTH) {
+        text.push_str(&line);
+        text.push('\n');
+    }
+    text.push_str(&"=".repeat(article.title.chars().count().min(PAGE_WIDTH)));
+    text.push('\n');
+
+    let date = article
+        .published_at
+        .map(|dt| dt.format("%B %-d, %Y").to_string());
+    let byline: Vec<&str> = [
+        article.feed_title.as_deref(),
+        article.author.as_deref(),
+        date.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    if !byline.is_empty() {
+        text.push_str(&byline.join(" | "));
+        text.push('\n');
+    }
+    text.push_str(&article.url);
+    text.push_str("\n\n");
+
+    if let Some(summary) = summary {
+        text.push_str("Summary\n-------\n");
+        push_wrapped(&mut text, &summary.content);
+        text.push('\n');
+    }
+
+    let body = match (&article.content_text, &article.content) {
+        (Some(plain), _) => Some(plain.clone()),
+        (None, Some(html)) => html2text::from_read(html.as_bytes(), PAGE_WIDTH).ok(),
+        (None, None) => None,
+    };
+    if let Some(body) = body {
+        push_wrapped(&mut text, &body);
+    }
+    text
+}
+
+/// Wrap each line, indenting the continuation of list items
+fn push_wrapped(text: &mut String, content: &str) {
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let indent = if trimmed.starts_with("- ") || trimmed.starts_with("* ") {
+            " ".repeat(line.len() - trimmed.len() + 2)
+        } else {
+            String::new()
+        };
+        let options = textwrap::Options::new(PAGE_WIDTH).subsequent_indent(&indent);
+        for wrapped in textwrap::wrap(line, options) {
+            text.push_str(&wrapped);
+            text.push('\n');
+        }
+    }
+}
+
+/// Send rendered text to the printer: through the formatter, if one is
+/// configured, and then the print command
+pub async fn print_text(config: &PrintConfig, text: String) -> Result<()> {
+    let document = match &config.formatter {
+        Some(formatter) => run(formatter, text.into_bytes()).await?,
+        None => text.into_bytes(),
+    };
+    run(&config.command, document).await?;
+    Ok(())
+}
+
+/// Run a command line with `input` on stdin, returning its output
+async fn run(command_line: &str, input: Vec<u8>) -> Result<Vec<u8>> {
+    let args: Vec<&str> = command_line.split_whitespace().collect();
+    let Some((program, args)) = args.split_first() else {
+        return Err(AppError::Print("empty command".to_string()));
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Print(format!("{}: {}", program, e)))?;
+
+    // Written from a task so a command that answers while reading can't block us
+    if let Some(mut stdin) = child.stdin.take() {
+        tokio::spawn(async move {
+            let _ = stdin.write_all(&input).await;
+        });
+    }
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = match stderr.trim() {
+            "" => output.status.to_string(),
+            stderr => stderr.to_string(),
+        };
+        return Err(AppError::Print(format!("{} failed: {}", program, reason)));
+    }
+    Ok(output.stdout)
+}
@@ -1,17 +1,21 @@
-use std::sync::OnceLock;
 use std::time::Duration;
 
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use tokio::sync::Mutex;
 
+use crate::config::ProxyRoute;
 use crate::error::{AppError, Result};
 
 const RAINDROP_API_URL: &str = "https://api.raindrop.io/rest/v1";
-const NEWS_COLLECTION_NAME: &str = "News Links";
 
-// Cache for collection ID
-static NEWS_COLLECTION_ID: OnceLock<Mutex<Option<i64>>> = OnceLock::new();
+/// Raindrop's built-in collection for bookmarks saved without one
+const UNSORTED_COLLECTION_ID: i64 = -1;
+
+/// Pseudo-collection with every bookmark except those in Trash
+const ALL_COLLECTION_ID: i64 = 0;
+
+// Most bookmarks the API returns per page
+const BOOKMARKS_PER_PAGE: usize = 50;
 
 #[derive(Debug, Serialize)]
 struct CreateRaindropRequest {
@@ -49,6 +53,21 @@ struct RaindropItem {
     id: i64,
 }
 
+#[derive(Debug, Deserialize)]
+struct RaindropsResponse {
+    items: Vec<Bookmark>,
+    count: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct Bookmark {
+    #[serde(rename = "_id")]
+    id: i64,
+    link: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct CollectionsResponse {
     items: Vec<Collection>,
@@ -59,16 +78,41 @@ struct Collection {
     #[serde(rename = "_id")]
     id: i64,
     title: String,
+    parent: Option<CollectionParent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CollectionParent {
+    #[serde(rename = "$id")]
+    id: i64,
+}
+
+/// A Raindrop collection bookmarks can be saved to
+#[derive(Debug, Clone)]
+pub struct RaindropCollection {
+    pub id: i64,
+    /// Title, prefixed with its parents' for nested collections ("Tech / Rust")
+    pub title: String,
+}
+
+/// A bookmark on Raindrop.io
+#[derive(Debug, Clone)]
+pub struct RaindropBookmark {
+    pub id: i64,
+    pub link: String,
+    pub tags: Vec<String>,
 }
 
+#[derive(Clone)]
 pub struct RaindropClient {
     client: Client,
     access_token: String,
 }
 
 impl RaindropClient {
-    pub fn new(access_token: String) -> Self {
-        let client = Client::builder()
+    pub fn new(access_token: String, proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
             .timeout(Duration::from_secs(30))
             .build()
             .expect("Failed to create HTTP client");
@@ -78,46 +122,113 @@ impl RaindropClient {
         }
     }
 
-    /// Get the News collection ID, fetching and caching it if needed
-    async fn get_news_collection_id(&self) -> Result<Option<i64>> {
-        let cache = NEWS_COLLECTION_ID.get_or_init(|| Mutex::new(None));
-        let mut cached = cache.lock().await;
+    /// Every collection, Unsorted first and the rest by title
+    pub async fn list_collections(&self) -> Result<Vec<RaindropCollection>> {
+        // Top-level collections and nested ones come from separate endpoints
+        let mut all = self.fetch_collections("collections").await?;
+        all.extend(self.fetch_collections("collections/childrens").await?);
+
+        let path = |collection: &Collection| {
+            let mut titles = vec![collection.title.clone()];
+            let mut parent = collection.parent.as_ref().map(|p| p.id);
+            // Bounded in case of a cycle in the returned data
+            while let Some(id) = parent.filter(|_| titles.len() < 10) {
+                let Some(found) = all.iter().find(|c| c.id == id) else {
+                    break;
+                };
+                titles.push(found.title.clone());
+                parent = found.parent.as_ref().map(|p| p.id);
+            }
+            titles.reverse();
+            titles.join(" / ")
+        };
+        let mut collections: Vec<RaindropCollection> = all
+            .iter()
+            .map(|c| RaindropCollection {
+                id: c.id,
+                title: path(c),
+            })
+            .collect();
+        collections.sort_by_key(|c| c.title.to_lowercase());
+        collections.insert(
+            0,
+            RaindropCollection {
+                id: UNSORTED_COLLECTION_ID,
+                title: "Unsorted".to_string(),
+            },
+        );
+        Ok(collections)
+    }
+
+    /// Every bookmark in every collection, leaving out Trash
+    pub async fn list_bookmarks(&self) -> Result<Vec<RaindropBookmark>> {
+        let mut bookmarks = Vec::new();
+        for page in 0.. {
+            let response = self
+                .client
+                .get(format!("{}/raindrops/{}", RAINDROP_API_URL, ALL_COLLECTION_ID))
+                .query(&[("page", page), ("perpage", BOOKMARKS_PER_PAGE)])
+                .bearer_auth(&self.access_token)
+                .send()
+                .await?;
+
+            if !response.status().is_success() {
+                return Err(AppError::RaindropApi(format!(
+                    "Failed to fetch bookmarks: HTTP {}",
+                    response.status()
+                )));
+            }
 
-        if cached.is_some() {
-            return Ok(*cached);
+            let found: RaindropsResponse = response.json().await?;
+            let last_page = found.items.len() < BOOKMARKS_PER_PAGE;
+            bookmarks.extend(found.items.into_iter().map(|b| RaindropBookmark {
+                id: b.id,
+                link: b.link,
+                tags: b.tags,
+            }));
+            if last_page || bookmarks.len() >= found.count {
+                break;
+            }
         }
+        Ok(bookmarks)
+    }
 
-        // Fetch collections from API
+    async fn fetch_collections(&self, endpoint: &str) -> Result<Vec<Collection>> {
         let response = self
             .client
-            .get(format!("{}/collections", RAINDROP_API_URL))
+            .get(format!("{}/{}", RAINDROP_API_URL, endpoint))
             .bearer_auth(&self.access_token)
             .send()
             .await?;
 
         if !response.status().is_success() {
-            tracing::warn!("Failed to fetch collections, will save to unsorted");
-            return Ok(None);
+            return Err(AppError::RaindropApi(format!(
+                "Failed to fetch collections: HTTP {}",
+                response.status()
+            )));
         }
 
         let collections: CollectionsResponse = response.json().await?;
+        Ok(collections.items)
+    }
 
-        // Find the News collection
-        let news_id = collections
-            .items
-            .iter()
-            .find(|c| c.title == NEWS_COLLECTION_NAME)
-            .map(|c| c.id);
+    /// Check that the access token is accepted
+    pub async fn verify_credentials(&self) -> Result<()> {
+        let response = self
+            .client
+            .get(format!("{}/user", RAINDROP_API_URL))
+            .bearer_auth(&self.access_token)
+            .send()
+            .await?;
 
-        if news_id.is_none() {
-            tracing::warn!("News collection not found, will save to unsorted");
+        if !response.status().is_success() {
+            return Err(AppError::RaindropApi(format!("HTTP {}", response.status())));
         }
 
-        *cached = news_id;
-        Ok(news_id)
+        Ok(())
     }
 
-    /// Save a bookmark to Raindrop.io (in the News collection)
+    /// Save a bookmark to Raindrop.io, in `collection` (None: Unsorted)
     pub async fn save_bookmark(
         &self,
         url: &str,
@@ -125,12 +236,9 @@ impl RaindropClient {
         excerpt: Option<&str>,
         note: Option<&str>,
         tags: Vec<String>,
+        collection: Option<i64>,
     ) -> Result<i64> {
-        // Get the News collection ID
-        let collection = self
-            .get_news_collection_id()
-            .await?
-            .map(|id| CollectionRef { id });
+        let collection = collection.map(|id| CollectionRef { id });
 
         let request = CreateRaindropRequest {
             link: url.to_string(),
@@ -0,0 +1,209 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use regex::Regex;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::config::ProxyRoute;
+use crate::error::{AppError, Result};
+
+const BLUESKY_API_URL: &str = "https://bsky.social/xrpc";
+
+// Mastodon counts every link as this many characters, however long it is
+const MASTODON_URL_CHARS: usize = 23;
+
+static URL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"https?://\S+").unwrap());
+
+/// Where an article can be shared
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareTarget {
+    Mastodon,
+    Bluesky,
+}
+
+impl ShareTarget {
+    pub fn label(self) -> &'static str {
+        match self {
+            ShareTarget::Mastodon => "Mastodon",
+            ShareTarget::Bluesky => "Bluesky",
+        }
+    }
+
+    /// Longest post the service takes
+    pub fn max_chars(self) -> usize {
+        match self {
+            ShareTarget::Mastodon => 500,
+            ShareTarget::Bluesky => 300,
+        }
+    }
+
+    /// Length of `text` as the service counts it
+    pub fn post_length(self, text: &str) -> usize {
+        let chars = text.chars().count();
+        match self {
+            ShareTarget::Mastodon => URL.find_iter(text).fold(chars, |chars, url| {
+                chars - url.as_str().chars().count() + MASTODON_URL_CHARS
+            }),
+            ShareTarget::Bluesky => chars,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct MastodonStatus {
+    url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlueskySession {
+    access_jwt: String,
+    did: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlueskyRecord {
+    uri: String,
+}
+
+/// Posts to the Mastodon and Bluesky accounts set in the config
+pub struct SocialPoster {
+    client: Client,
+    // Instance URL and access token
+    mastodon: Option<(String, String)>,
+    // Handle and app password
+    bluesky: Option<(String, String)>,
+}
+
+impl SocialPoster {
+    /// None when no account is set up
+    pub fn new(
+        mastodon: Option<(&str, &str)>,
+        bluesky: Option<(&str, &str)>,
+        proxy: ProxyRoute,
+    ) -> Option<Self> {
+        if mastodon.is_none() && bluesky.is_none() {
+            return None;
+        }
+        let client = proxy
+            .apply(Client::builder())
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
+        let mastodon = mastodon.map(|(instance, token)| {
+            let instance = instance.trim_end_matches('/');
+            let instance = if instance.contains("://") {
+                instance.to_string()
+            } else {
+                format!("https://{}", instance)
+            };
+            (instance, token.to_string())
+        });
+        let bluesky = bluesky.map(|(handle, password)| {
+            let handle = handle.trim_start_matches('@').to_string();
+            (handle, password.to_string())
+        });
+        Some(Self {
+            client,
+            mastodon,
+            bluesky,
+        })
+    }
+
+    /// Services with an account, Mastodon first
+    pub fn targets(&self) -> Vec<ShareTarget> {
+        let mut targets = Vec::new();
+        if self.mastodon.is_some() {
+            targets.push(ShareTarget::Mastodon);
+        }
+        if self.bluesky.is_some() {
+            targets.push(ShareTarget::Bluesky);
+        }
+        targets
+    }
+
+    /// Publish `text` as a public post, returning the post's web address
+    pub async fn post(&self, target: ShareTarget, text: &str) -> Result<String> {
+        match target {
+            ShareTarget::Mastodon => self.post_to_mastodon(text).await,
+            ShareTarget::Bluesky => self.post_to_bluesky(text).await,
+        }
+    }
+
+    async fn post_to_mastodon(&self, text: &str) -> Result<String> {
+        let Some((instance, token)) = &self.mastodon else {
+            return Err(AppError::Social("no Mastodon account set up".to_string()));
+        };
+        let response = self
+            .client
+            .post(format!("{}/api/v1/statuses", instance))
+            .bearer_auth(token)
+            .json(&json!({ "status": text }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::Social(format!("Mastodon: {} {}", status, body.trim())));
+        }
+        let posted: MastodonStatus = response.json().await?;
+        Ok(posted.url.unwrap_or_else(|| instance.clone()))
+    }
+
+    async fn post_to_bluesky(&self, text: &str) -> Result<String> {
+        let Some((handle, password)) = &self.bluesky else {
+            return Err(AppError::Social("no Bluesky account set up".to_string()));
+        };
+        let response = self
+            .client
+            .post(format!("{}/com.atproto.server.createSession", BLUESKY_API_URL))
+            .json(&json!({ "identifier": handle, "password": password }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            return Err(AppError::Social(format!("Bluesky sign-in failed: {}", status)));
+        }
+        let session: BlueskySession = response.json().await?;
+
+        // Links are only clickable when marked up as facets, by byte offset
+        let facets: Vec<_> = URL
+            .find_iter(text)
+            .map(|url| {
+                json!({
+                    "index": { "byteStart": url.start(), "byteEnd": url.end() },
+                    "features": [{ "$type": "app.bsky.richtext.facet#link", "uri": url.as_str() }]
+                })
+            })
+            .collect();
+        let record = json!({
+            "$type": "app.bsky.feed.post",
+            "text": text,
+            "facets": facets,
+            "createdAt": chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+        });
+        let response = self
+            .client
+            .post(format!("{}/com.atproto.repo.createRecord", BLUESKY_API_URL))
+            .bearer_auth(&session.access_jwt)
+            .json(&json!({
+                "repo": session.did,
+                "collection": "app.bsky.feed.post",
+                "record": record,
+            }))
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::Social(format!("Bluesky: {} {}", status, body.trim())));
+        }
+
+        // at://did/app.bsky.feed.post/<key> is shown at bsky.app/profile/<did>/post/<key>
+        let created: BlueskyRecord = response.json().await?;
+        let key = created.uri.rsplit('/').next().unwrap_or_default();
+        Ok(format!("https://bsky.app/profile/{}/post/{}", session.did, key))
+    }
+}
@@ -0,0 +1,130 @@
+use std::process::Stdio;
+
+use tokio::io::AsyncWriteExt;
+use tokio::process::{Child, Command};
+
+use crate::error::{AppError, Result};
+
+// Tried in order when no command is configured; each reads text from stdin
+const DEFAULT_COMMANDS: [&[&str]; 3] = [
+    &["say"],
+    &["espeak-ng", "--stdin"],
+    &["espeak", "--stdin"],
+];
+
+/// Reads text aloud through a text-to-speech command, one text at a time
+pub struct Speaker {
+    command: Vec<String>,
+    child: Option<Child>,
+    paused: bool,
+}
+
+impl Speaker {
+    /// Speaker for the configured command, or the first installed default.
+    /// None when there is no TTS command to use.
+    pub fn new(command: Option<&str>) -> Option<Self> {
+        let command: Vec<String> = match command {
+            Some(command) => command.split_whitespace().map(str::to_string).collect(),
+            None => DEFAULT_COMMANDS
+                .iter()
+                .find(|args| is_installed(args[0]))?
+                .iter()
+                .map(|arg| arg.to_string())
+                .collect(),
+        };
+        if command.is_empty() {
+            return None;
+        }
+        Some(Self {
+            command,
+            child: None,
+            paused: false,
+        })
+    }
+
+    /// Start reading `text`, stopping whatever was being read
+    pub fn speak(&mut self, text: String) -> Result<()> {
+        self.stop();
+
+        let mut child = Command::new(&self.command[0])
+            .args(&self.command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| AppError::Speech(format!("{}: {}", self.command[0], e)))?;
+
+        // Long articles don't fit in the pipe buffer; feed them in the background
+        if let Some(mut stdin) = child.stdin.take() {
+            tokio::spawn(async move {
+                let _ = stdin.write_all(text.as_bytes()).await;
+            });
+        }
+        self.child = Some(child);
+        Ok(())
+    }
+
+    /// Pause or resume reading. Returns whether reading is now paused.
+    pub fn toggle_pause(&mut self) -> Result<bool> {
+        let Some(pid) = self.child.as_ref().and_then(|child| child.id()) else {
+            return Ok(false);
+        };
+        signal(pid, !self.paused)?;
+        self.paused = !self.paused;
+        Ok(self.paused)
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            // A stopped process must be resumed to act on the kill
+            if self.paused {
+                if let Some(pid) = child.id() {
+                    let _ = signal(pid, false);
+                }
+            }
+            let _ = child.start_kill();
+        }
+        self.paused = false;
+    }
+
+    /// Whether a text is being read (or paused); notices when reading ends
+    pub fn is_speaking(&mut self) -> bool {
+        let finished = match self.child.as_mut() {
+            Some(child) => !matches!(child.try_wait(), Ok(None)),
+            None => return false,
+        };
+        if finished {
+            self.child = None;
+            self.paused = false;
+        }
+        !finished
+    }
+
+    /// The TTS program in use
+    pub fn program(&self) -> &str {
+        &self.command[0]
+    }
+}
+
+fn is_installed(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+/// Suspend (`stop`) or resume the speech process
+#[cfg(unix)]
+fn signal(pid: u32, stop: bool) -> Result<()> {
+    let signal = if stop { libc::SIGSTOP } else { libc::SIGCONT };
+    // SAFETY: kill() only sends a signal; the pid belongs to our child process
+    if unsafe { libc::kill(pid as libc::pid_t, signal) } == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error().into())
+    }
+}
+
+#[cfg(not(unix))]
+fn signal(_pid: u32, _stop: bool) -> Result<()> {
+    Err(AppError::Speech("pausing is not supported on this platform".to_string()))
+}
@@ -0,0 +1,174 @@
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::db::Repository;
+use crate::error::Result;
+use crate::models::{Article, Summary};
+
+// Requests are a single GET; anything longer is cut off
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+// Clients that haven't finished sending their request by then are dropped,
+// so idle connections don't pile up
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Atom feed of every starred article, with its AI summary when there is one,
+/// newest first
+pub async fn render_starred_feed(repository: &Repository) -> Result<(String, usize)> {
+    let articles = repository.get_all_articles_sorted().await?;
+    let mut entries = Vec::new();
+    for article in articles.into_iter().filter(|a| a.is_starred && !a.is_trashed()) {
+        let summary = repository.get_summary(article.id).await?;
+        entries.push((article, summary));
+    }
+
+    let updated = entries
+        .iter()
+        .map(|(article, summary)| entry_updated(article, summary.as_ref()))
+        .max()
+        .unwrap_or_else(Utc::now);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str("  <title>Starred articles</title>\n");
+    xml.push_str("  <id>urn:speedy-reader:starred</id>\n");
+    xml.push_str(&format!("  <updated>{}</updated>\n", timestamp(updated)));
+    xml.push_str("  <generator>SpeedyReader</generator>\n");
+    for (article, summary) in &entries {
+        xml.push_str(&render_entry(article, summary.as_ref()));
+    }
+    xml.push_str("</feed>\n");
+    Ok((xml, entries.len()))
+}
+
+/// Write the starred-articles feed to a file, returning how many entries it has
+pub async fn export_starred_feed(repository: &Repository, path: &Path) -> Result<usize> {
+    let (xml, count) = render_starred_feed(repository).await?;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, xml)?;
+    Ok(count)
+}
+
+/// Serve the starred-articles feed over HTTP at `addr` until the process is
+/// stopped; every request gets the current stars
+pub async fn serve_starred_feed(repository: Repository, addr: &str) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    loop {
+        let (stream, peer) = listener.accept().await?;
+        let repository = repository.clone();
+        tokio::spawn(async move {
+            if let Err(e) = answer(stream, &repository).await {
+                tracing::warn!("Starred feed request from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
+
+async fn answer(mut stream: TcpStream, repository: &Repository) -> Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    let read = async {
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_BYTES
+        {
+            let n = stream.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf[..n]);
+        }
+        Ok::<_, io::Error>(())
+    };
+    tokio::time::timeout(REQUEST_TIMEOUT, read)
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "request not received in time"))??;
+    let method = request.split(|&b| b == b' ').next().unwrap_or_default();
+
+    let response = match method {
+        b"GET" | b"HEAD" => {
+            let (xml, _) = render_starred_feed(repository).await?;
+            let mut response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/atom+xml; charset=utf-8\r\n\
+                 Content-Length: {}\r\nConnection: close\r\n\r\n",
+                xml.len()
+            );
+            if method == b"GET" {
+                response.push_str(&xml);
+            }
+            response
+        }
+        _ => "HTTP/1.1 405 Method Not Allowed\r\nAllow: GET, HEAD\r\n\
+              Content-Length: 0\r\nConnection: close\r\n\r\n"
+            .to_string(),
+    };
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+fn render_entry(article: &Article, summary: Option<&Summary>) -> String {
+    let id = if article.url.is_empty() {
+        format!("urn:speedy-reader:article:{}", article.id)
+    } else {
+        article.url.clone()
+    };
+
+    let mut entry = String::from("  <entry>\n");
+    entry.push_str(&format!("    <title>{}</title>\n", escape(&article.title)));
+    entry.push_str(&format!("    <id>{}</id>\n", escape(&id)));
+    if !article.url.is_empty() {
+        entry.push_str(&format!("    <link href=\"{}\"/>\n", escape(&article.url)));
+    }
+    entry.push_str(&format!(
+        "    <updated>{}</updated>\n",
+        timestamp(entry_updated(article, summary))
+    ));
+    if let Some(published) = article.published_at {
+        entry.push_str(&format!("    <published>{}</published>\n", timestamp(published)));
+    }
+    if let Some(author) = &article.author {
+        entry.push_str(&format!("    <author><name>{}</name></author>\n", escape(author)));
+    }
+    for tag in &article.tags {
+        entry.push_str(&format!("    <category term=\"{}\"/>\n", escape(tag)));
+    }
+    if let Some(summary) = summary {
+        entry.push_str(&format!(
+            "    <summary type=\"text\">{}</summary>\n",
+            escape(summary.content.trim())
+        ));
+    }
+    if let Some(feed) = &article.feed_title {
+        entry.push_str(&format!("    <source><title>{}</title></source>\n", escape(feed)));
+    }
+    entry.push_str("  </entry>\n");
+    entry
+}
+
+/// An entry changes when its summary is (re)generated
+fn entry_updated(article: &Article, summary: Option<&Summary>) -> DateTime<Utc> {
+    let posted = article.published_at.unwrap_or(article.fetched_at);
+    summary.map_or(posted, |s| s.generated_at.max(posted))
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Text for XML content and attributes, without the control characters XML
+/// doesn't allow (one would make the whole feed unreadable)
+fn escape(text: &str) -> String {
+    text.chars()
+        .filter(|&c| c >= ' ' || matches!(c, '\t' | '\n' | '\r'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
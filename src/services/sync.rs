@@ -0,0 +1,333 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+
+use crate::db::Repository;
+use crate::error::Result;
+use crate::models::{normalize_url, NewArticle, NewFeed};
+
+use super::greader::{GReaderClient, READING_LIST, READ_TAG, STARRED_TAG};
+use super::raindrop::RaindropClient;
+
+/// What a sync with the server changed
+#[derive(Debug, Default)]
+pub struct SyncReport {
+    pub feeds_added: usize,
+    pub feeds_removed: usize,
+    /// Feeds unsubscribed on the server but kept here for their starred or
+    /// saved articles
+    pub feeds_kept: usize,
+    pub articles_added: usize,
+    /// Read/starred changes sent to the server
+    pub pushed: usize,
+    /// Read/starred changes taken from the server
+    pub pulled: usize,
+}
+
+impl SyncReport {
+    /// Whether anything changed locally (the article list needs reloading)
+    pub fn changed_locally(&self) -> bool {
+        self.feeds_added + self.feeds_removed + self.articles_added + self.pulled > 0
+    }
+}
+
+/// Two-way sync with a Google Reader API server: subscriptions, items
+/// published since `since`, and read/starred state.
+///
+/// Both sides are compared with the state recorded at the last sync, so a
+/// change on one side wins over an unchanged other side; when both changed,
+/// the local change wins.
+pub async fn sync_greader(
+    client: &GReaderClient,
+    repository: &Repository,
+    since: DateTime<Utc>,
+) -> Result<SyncReport> {
+    let mut report = SyncReport::default();
+    sync_subscriptions(client, repository, &mut report).await?;
+    pull_items(client, repository, since, &mut report).await?;
+    sync_states(client, repository, &mut report).await?;
+    Ok(report)
+}
+
+async fn sync_subscriptions(
+    client: &GReaderClient,
+    repository: &Repository,
+    report: &mut SyncReport,
+) -> Result<()> {
+    let remote = client.subscriptions().await?;
+    let local = repository.get_all_feeds().await?;
+    let synced = repository.get_synced_feed_urls().await?;
+
+    // Matched on normalized links, so a server spelling a URL differently
+    // (scheme, trailing slash, tracking parameters) isn't a different feed
+    let synced: HashSet<String> = synced.iter().map(|url| normalize_url(url)).collect();
+    let remote_urls: HashSet<String> = remote.iter().map(|s| normalize_url(&s.url)).collect();
+    let local_urls: HashSet<String> = local.iter().map(|f| normalize_url(&f.url)).collect();
+    let mut subscribed: Vec<String> = Vec::new();
+
+    for subscription in &remote {
+        let url = normalize_url(&subscription.url);
+        if local_urls.contains(&url) {
+            subscribed.push(subscription.url.clone());
+        } else if synced.contains(&url) {
+            // Deleted here since the last sync
+            client.unsubscribe(&subscription.url).await?;
+        } else {
+            repository
+                .insert_feed(NewFeed {
+                    title: subscription.title.clone(),
+                    url: subscription.url.clone(),
+                    site_url: subscription.site_url.clone(),
+                    description: None,
+                    category: subscription.category.clone(),
+                })
+                .await?;
+            subscribed.push(subscription.url.clone());
+            report.feeds_added += 1;
+        }
+    }
+
+    for feed in local
+        .iter()
+        .filter(|f| !remote_urls.contains(&normalize_url(&f.url)))
+    {
+        if synced.contains(&normalize_url(&feed.url)) {
+            // Unsubscribed on the server (e.g. from another reader); starred
+            // and saved articles aren't thrown away without asking, so such a
+            // feed stays, recorded as synced so it isn't subscribed again
+            if repository.feed_has_kept_articles(feed.id).await? {
+                tracing::warn!(
+                    "{} was unsubscribed on the sync server; kept for its starred or saved articles",
+                    feed.url
+                );
+                subscribed.push(feed.url.clone());
+                report.feeds_kept += 1;
+                continue;
+            }
+            repository.delete_feed(feed.id).await?;
+            report.feeds_removed += 1;
+        } else if feed.auth.is_none() {
+            // Private feeds stay local: the server has no credentials for them
+            client.subscribe(&feed.url, &feed.title).await?;
+            subscribed.push(feed.url.clone());
+        }
+    }
+
+    repository.set_synced_feed_urls(subscribed).await
+}
+
+/// Add server items that aren't here yet, and link those that are (same
+/// feed and URL) to their server item
+async fn pull_items(
+    client: &GReaderClient,
+    repository: &Repository,
+    since: DateTime<Utc>,
+    report: &mut SyncReport,
+) -> Result<()> {
+    let feeds: HashMap<String, i64> = repository
+        .get_all_feeds()
+        .await?
+        .into_iter()
+        .map(|f| (normalize_url(&f.url), f.id))
+        .collect();
+    let linked: HashSet<String> = repository
+        .get_sync_items()
+        .await?
+        .into_iter()
+        .map(|item| item.item_id)
+        .collect();
+
+    for item in client.items_since(since).await? {
+        let Some(&feed_id) = feeds.get(&normalize_url(&item.feed_url)) else {
+            continue;
+        };
+        if linked.contains(&item.id) {
+            continue;
+        }
+
+        match repository.find_article_by_url(feed_id, &item.url).await? {
+            // Agree on the server's state; a local difference is pushed below
+            Some((article_id, is_read, is_starred)) => {
+                repository
+                    .link_sync_item(article_id, item.id.clone(), item.is_read, item.is_starred)
+                    .await?;
+                if item.is_read && !is_read {
+                    repository.set_article_read(article_id, true).await?;
+                }
+                if item.is_starred && !is_starred {
+                    repository.set_article_starred(article_id, true).await?;
+                }
+            }
+            None => {
+                let content_text = item
+                    .content
+                    .as_ref()
+                    .and_then(|html| html2text::from_read(html.as_bytes(), 80).ok());
+                let article = NewArticle {
+                    feed_id,
+                    guid: item.id.clone(),
+                    title: item.title.clone(),
+                    url: item.url.clone(),
+                    author: item.author.clone(),
+                    content: item.content.clone(),
+                    content_text,
+                    published_at: item.published_at,
+                    image_url: None,
+                };
+                if let Some(article_id) = repository.upsert_article(article).await? {
+                    repository
+                        .set_article_read(article_id, item.is_read)
+                        .await?;
+                    repository
+                        .set_article_starred(article_id, item.is_starred)
+                        .await?;
+                    repository
+                        .link_sync_item(article_id, item.id, item.is_read, item.is_starred)
+                        .await?;
+                    report.articles_added += 1;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reconcile read/starred state of linked articles
+async fn sync_states(
+    client: &GReaderClient,
+    repository: &Repository,
+    report: &mut SyncReport,
+) -> Result<()> {
+    let items = repository.get_sync_items().await?;
+    if items.is_empty() {
+        return Ok(());
+    }
+    let remote_unread = client.item_ids(READING_LIST, Some(READ_TAG)).await?;
+    let remote_starred = client.item_ids(STARRED_TAG, None).await?;
+
+    let mut mark_read = Vec::new();
+    let mut mark_unread = Vec::new();
+    let mut star = Vec::new();
+    let mut unstar = Vec::new();
+    let mut agreed = Vec::new();
+
+    for item in items {
+        // Items past the server's unread window count as read
+        let remote_read = !remote_unread.contains(&item.item_id);
+        let remote_star = remote_starred.contains(&item.item_id);
+
+        let is_read = if item.is_read != item.synced_read {
+            if remote_read != item.is_read {
+                let push = if item.is_read {
+                    &mut mark_read
+                } else {
+                    &mut mark_unread
+                };
+                push.push(item.item_id.clone());
+            }
+            item.is_read
+        } else if remote_read != item.synced_read {
+            repository
+                .set_article_read(item.article_id, remote_read)
+                .await?;
+            report.pulled += 1;
+            remote_read
+        } else {
+            item.is_read
+        };
+
+        let is_starred = if item.is_starred != item.synced_starred {
+            if remote_star != item.is_starred {
+                let push = if item.is_starred {
+                    &mut star
+                } else {
+                    &mut unstar
+                };
+                push.push(item.item_id.clone());
+            }
+            item.is_starred
+        } else if remote_star != item.synced_starred {
+            repository
+                .set_article_starred(item.article_id, remote_star)
+                .await?;
+            report.pulled += 1;
+            remote_star
+        } else {
+            item.is_starred
+        };
+
+        if (is_read, is_starred) != (item.synced_read, item.synced_starred) {
+            agreed.push((item.article_id, item.item_id, is_read, is_starred));
+        }
+    }
+
+    client.edit_tag(&mark_read, READ_TAG, true).await?;
+    client.edit_tag(&mark_unread, READ_TAG, false).await?;
+    client.edit_tag(&star, STARRED_TAG, true).await?;
+    client.edit_tag(&unstar, STARRED_TAG, false).await?;
+    report.pushed += mark_read.len() + mark_unread.len() + star.len() + unstar.len();
+
+    // Only recorded once the server has taken the changes
+    for (article_id, item_id, is_read, is_starred) in agreed {
+        repository
+            .link_sync_item(article_id, item_id, is_read, is_starred)
+            .await?;
+    }
+    Ok(())
+}
+
+/// What reconciling with Raindrop.io changed
+#[derive(Debug, Default)]
+pub struct RaindropSyncReport {
+    /// Articles bookmarked from another device, now marked saved
+    pub marked: usize,
+    /// Articles whose bookmark was deleted (or trashed) on Raindrop
+    pub unmarked: usize,
+}
+
+impl RaindropSyncReport {
+    pub fn changed(&self) -> bool {
+        self.marked + self.unmarked > 0
+    }
+}
+
+/// Reconcile the saved-to-Raindrop flags with the bookmarks on Raindrop.io:
+/// articles whose URL was bookmarked elsewhere are marked saved, and those
+/// whose bookmark is gone are unmarked
+pub async fn sync_raindrop(
+    client: &RaindropClient,
+    repository: &Repository,
+) -> Result<RaindropSyncReport> {
+    let mut report = RaindropSyncReport::default();
+    let started = Utc::now();
+    let bookmarks = client.list_bookmarks().await?;
+    let saves = repository.get_raindrop_saves().await?;
+
+    // Saves made while the bookmarks were being listed may not be in them yet
+    let remote_ids: HashSet<i64> = bookmarks.iter().map(|b| b.id).collect();
+    let gone: Vec<i64> = saves
+        .iter()
+        .filter(|(_, raindrop_id, saved_at)| {
+            *saved_at < started && !remote_ids.contains(raindrop_id)
+        })
+        .map(|(article_id, _, _)| *article_id)
+        .collect();
+    report.unmarked = gone.len();
+    repository.unmark_saved_to_raindrop(gone).await?;
+
+    let saved: HashSet<i64> = saves.iter().map(|(article_id, _, _)| *article_id).collect();
+    let urls = bookmarks.iter().map(|b| b.link.clone()).collect();
+    let articles = repository.find_articles_by_urls(urls).await?;
+    for bookmark in bookmarks {
+        let Some(ids) = articles.get(&normalize_url(&bookmark.link)) else {
+            continue;
+        };
+        for &article_id in ids.iter().filter(|id| !saved.contains(id)) {
+            repository
+                .mark_saved_to_raindrop(article_id, bookmark.id, bookmark.tags.clone())
+                .await?;
+            report.marked += 1;
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,101 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::config::{ProxyRoute, WebhookConfig, WebhookFormat};
+use crate::error::{AppError, Result};
+
+// Discord rejects messages longer than this
+const DISCORD_MAX_CHARS: usize = 2000;
+
+/// A new article as sent to webhooks
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookArticle {
+    pub id: i64,
+    pub feed_title: String,
+    pub feed_url: String,
+    pub title: String,
+    pub url: String,
+    pub author: Option<String>,
+    pub published_at: Option<DateTime<Utc>>,
+    pub summary: Option<String>,
+}
+
+/// Posts new articles to user-defined webhook URLs
+#[derive(Clone)]
+pub struct WebhookSender {
+    client: Client,
+}
+
+impl WebhookSender {
+    pub fn new(proxy: ProxyRoute) -> Self {
+        let client = proxy
+            .apply(Client::builder())
+            .timeout(Duration::from_secs(30))
+            .build()
+            .expect("Failed to create HTTP client");
+        Self { client }
+    }
+
+    /// POST one article to a webhook in the webhook's format
+    pub async fn send(&self, webhook: &WebhookConfig, article: &WebhookArticle) -> Result<()> {
+        let payload = match webhook.format {
+            WebhookFormat::Json => json!({ "event": "new_article", "article": article }),
+            WebhookFormat::Slack => json!({ "text": slack_text(article) }),
+            WebhookFormat::Discord => json!({ "content": discord_text(article) }),
+        };
+
+        let response = self.client.post(&webhook.url).json(&payload).send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(AppError::Webhook(format!(
+                "{}: {} {}",
+                webhook_host(&webhook.url),
+                status,
+                body.trim()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A webhook's host, for messages: Slack and Discord URLs hold a secret token
+pub fn webhook_host(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "webhook".to_string())
+}
+
+fn slack_text(article: &WebhookArticle) -> String {
+    // Slack's mrkdwn reserves these three characters
+    let escape = |s: &str| s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+    let mut text = format!(
+        "*<{}|{}>*\n{}",
+        article.url,
+        escape(&article.title),
+        escape(&article.feed_title)
+    );
+    if let Some(summary) = &article.summary {
+        text.push_str("\n\n");
+        text.push_str(&escape(summary));
+    }
+    text
+}
+
+fn discord_text(article: &WebhookArticle) -> String {
+    let mut text = format!("**{}**\n{}\n<{}>", article.title, article.feed_title, article.url);
+    if let Some(summary) = &article.summary {
+        text.push_str("\n\n");
+        text.push_str(summary);
+    }
+    if text.chars().count() > DISCORD_MAX_CHARS {
+        text = text.chars().take(DISCORD_MAX_CHARS - 1).collect();
+        text.push('…');
+    }
+    text
+}
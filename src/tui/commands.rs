@@ -0,0 +1,142 @@
+use crate::models::{ArticleFilter, SortMode};
+
+/// A command that can be run from the `:` command palette
+pub struct Command {
+    pub name: &'static str,
+    /// Usage hint for the argument, empty if there is none
+    pub args: &'static str,
+    pub description: &'static str,
+}
+
+const fn command(name: &'static str, args: &'static str, description: &'static str) -> Command {
+    Command {
+        name,
+        args,
+        description,
+    }
+}
+
+pub const COMMANDS: &[Command] = &[
+    command("addfeed", "[url]", "Add a feed by URL or website address"),
+    command("refresh", "", "Refresh all feeds"),
+    command("refresh-feed", "", "Refresh only the listed or selected article's feed"),
+    command("filter", "<all|unread|starred|queue|trash>", "Show only some articles"),
+    command("goto", "[feed]", "Show one feed's articles (no feed: all feeds)"),
+    command("category", "[folder]", "Show one folder's feeds (no folder: all feeds)"),
+    command("tag", "[tag]", "Show articles with a tag (no tag: all articles)"),
+    command("sort", "<newest|oldest|feed|title|unread|deadline>", "Change the sort order"),
+    command("hide-read", "", "Switch when read articles leave the Unread view"),
+    command("search", "<query>", "Search articles"),
+    command("workspace", "<name>", "Switch to a saved workspace"),
+    command("save-workspace", "<name>", "Save filter, sort, feed/folder, tag and layout"),
+    command("delete-workspace", "<name>", "Delete a saved workspace"),
+    command("list-width", "<percent>", "Width of the article list"),
+    command("mark-all-read", "", "Mark every listed article read"),
+    command("delete-feed", "", "Delete the selected article's feed"),
+    command("feed-settings", "", "Auto-star, auto-archive and unread filter for the feed"),
+    command("find-feeds", "", "Search a public feed directory"),
+    command("discover", "", "Feeds suggested from what you read and star"),
+    command("subscribe-site", "", "Subscribe to the selected article's website"),
+    command("import-opml", "", "Import feeds from an OPML file or URL"),
+    command("export-opml", "", "Export feeds to an OPML file"),
+    command("export-notes", "", "Export starred articles to Markdown notes"),
+    command("stats", "", "Bandwidth per refresh and per feed, and AI spend"),
+    command("logs", "", "Latest lines of the log file"),
+    command("catchup", "", "Plan a timed catch-up session"),
+    command("summarize", "", "Pick a style to regenerate the summary in"),
+    command("reader", "", "Open the full-screen reader"),
+    command("open", "", "Open the article in the browser"),
+    command("links", "", "Open one of the article's links"),
+    command("email", "", "Email the article"),
+    command("print", "", "Print the article and its summary"),
+    command("copy-url", "", "Copy the article's URL"),
+    command("copy-link", "", "Copy the title and URL as a Markdown link"),
+    command("copy-summary", "", "Copy the AI summary"),
+    command("bookmark", "", "Save the article to Raindrop.io"),
+    command("raindrop-sync", "", "Pick up bookmarks saved or deleted on Raindrop.io"),
+    command("star", "", "Star or unstar the article"),
+    command("queue", "", "Add the article to the reading queue, or take it out"),
+    command("finish", "", "Mark the queued article read and take it out of the queue"),
+    command("tags", "", "Edit the article's tags"),
+    command("highlight", "", "Highlight a summary line on Hypothes.is"),
+    command("related", "", "Jump to a related article"),
+    command("info", "", "Article details, and articles it links with"),
+    command("translate", "", "Translate the article or show the original"),
+    command("chat", "", "Ask Claude questions about the article"),
+    command("share", "", "Post the article to Mastodon or Bluesky"),
+    command(
+        "simplify",
+        "[elementary|general|learner]",
+        "Rewrite the article in plain language or show the original",
+    ),
+    command("speak", "", "Read the article aloud, or pause"),
+    command("stop", "", "Stop reading aloud"),
+    command("delete", "", "Move the article to the trash"),
+    command("undo", "", "Undo delete / restore from trash"),
+    command("settings", "", "View and edit config values"),
+    command("help", "", "Show key bindings"),
+    command("quit", "", "Quit"),
+];
+
+/// Values accepted by `:filter`
+pub const FILTERS: &[(&str, ArticleFilter)] = &[
+    ("all", ArticleFilter::All),
+    ("unread", ArticleFilter::Unread),
+    ("starred", ArticleFilter::Starred),
+    ("queue", ArticleFilter::Queue),
+    ("trash", ArticleFilter::Trash),
+];
+
+/// Values accepted by `:sort`
+pub const SORTS: &[(&str, SortMode)] = &[
+    ("newest", SortMode::NewestFirst),
+    ("oldest", SortMode::OldestFirst),
+    ("feed", SortMode::ByFeed),
+    ("title", SortMode::ByTitle),
+    ("unread", SortMode::UnreadFirst),
+    ("deadline", SortMode::Deadline),
+];
+
+pub fn find_command(name: &str) -> Option<&'static Command> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+/// Fuzzy match: every character of `query` appears in `candidate` in order
+/// (ignoring case). Higher scores for consecutive and word-start matches.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut pos = 0;
+    let mut previous: Option<usize> = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = pos + candidate[pos..].iter().position(|&c| c == q)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !candidate[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        pos = found + 1;
+    }
+    if score == 0 {
+        return Some(0); // Empty query: keep the candidates' order
+    }
+    // Prefer shorter candidates among equal matches
+    Some(score * 100 - candidate.len() as i32)
+}
+
+/// `candidates` matching `query`, best first
+pub fn fuzzy_filter<'a, T>(
+    query: &str,
+    candidates: impl IntoIterator<Item = (&'a str, T)>,
+) -> Vec<(&'a str, T)> {
+    let mut matches: Vec<(i32, (&str, T))> = candidates
+        .into_iter()
+        .filter_map(|(text, item)| fuzzy_score(query, text).map(|score| (score, (text, item))))
+        .collect();
+    matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    matches.into_iter().map(|(_, m)| m).collect()
+}
@@ -1,4 +1,9 @@
-use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+
+use crate::models::FeedRule;
+
+// Percentage points a pane grows or shrinks by with each Ctrl+arrow
+const RESIZE_STEP: i16 = 5;
 
 #[derive(Debug, Clone)]
 pub enum AppAction {
@@ -7,26 +12,97 @@ pub enum AppAction {
     MoveDown,
     MoveToTop,
     MoveToBottom,
+    HalfPageDown,
+    HalfPageUp,
+    NextGroup,
+    PrevGroup,
+    CountDigit(usize), // digit of a count typed before a motion, as in Vim
+    GoPrefix,          // first `g` of `gg`
+    CycleSortMode,
+    CycleHideRead,
+    CycleListDensity,
+    CycleFilter,
+    // Mouse: a left click at a screen cell, and wheel steps
+    Click { column: u16, row: u16 },
+    WheelUp,
+    WheelDown,
     SelectArticle,
     RefreshFeeds,
+    RefreshFeed,
     OpenInBrowser,
     EmailArticle,
+    PrintArticle,
+    CopyUrl,
+    CopyMarkdownLink,
+    CopySummary,
+    Speak,
+    StopSpeaking,
+    ToggleTranslation,
+    ToggleSimplified,
     SaveToRaindrop,
-    RegenerateSummary,
+    RaindropSync,
+    // Styles to (re)generate the summary in
+    SummaryStylesOpen,
+    SummaryStylesUp,
+    SummaryStylesDown,
+    SummaryStylesConfirm,
+    SummaryStylesRegenerate,
+    SummaryStylesClose,
     DeleteArticle,
     DeleteFeed,
     UndeleteArticle,
+    ToggleStar,
+    QueueToggle,
+    QueueFinish,
+    ExportNotes,
     AddFeed,
+    AddFeedFromArticle,
+    // Feed directory search actions
+    FeedPickerUp,
+    FeedPickerDown,
+    FeedPickerConfirm,
+    FeedPickerCancel,
+    DirectoryStart,
+    DirectoryChar(char),
+    DirectoryBackspace,
+    DirectoryUp,
+    DirectoryDown,
+    DirectoryConfirm,
+    DirectoryCancel,
+    // Discover screen: feeds suggested from reading history
+    DiscoverOpen,
+    DiscoverUp,
+    DiscoverDown,
+    DiscoverSubscribe,
+    DiscoverOpenSite,
+    DiscoverClose,
     ShowHelp,
     HideHelp,
     // Tag input actions
     TagInputChar(char),
     TagInputBackspace,
+    TagInputNext,
+    TagInputPrev,
+    TagInputAccept,
     TagInputConfirm,
     TagInputCancel,
+    // Raindrop collection picker
+    RaindropUp,
+    RaindropDown,
+    RaindropConfirm,
+    RaindropCancel,
+    // Local article tag actions
+    EditTags,
+    ArticleTagsChar(char),
+    ArticleTagsBackspace,
+    ArticleTagsComplete,
+    ArticleTagsConfirm,
+    ArticleTagsCancel,
+    CycleTagFilter,
     // Feed input actions
     FeedInputChar(char),
     FeedInputBackspace,
+    FeedInputCycleImport,
     FeedInputConfirm,
     FeedInputCancel,
     // OPML input actions
@@ -37,49 +113,263 @@ pub enum AppAction {
     OpmlInputCancel,
     // OPML export actions
     ExportOpmlStart,
+    OpmlPickerUp,
+    OpmlPickerDown,
+    OpmlPickerToggle,
+    OpmlPickerToggleAll,
+    OpmlPickerToggleCategory,
+    OpmlPickerConfirm,
+    OpmlPickerCancel,
     OpmlExportChar(char),
     OpmlExportBackspace,
     OpmlExportConfirm,
     OpmlExportCancel,
+    // Catch-up mode actions
+    CatchUpStart,
+    CatchUpInputChar(char),
+    CatchUpInputBackspace,
+    CatchUpInputConfirm,
+    CatchUpInputCancel,
+    CatchUpNext,
+    // Semantic search actions
+    SearchStart,
+    SearchInputChar(char),
+    SearchInputBackspace,
+    SearchInputConfirm,
+    SearchInputCancel,
+    // Leave catch-up or search results
+    ExitMode,
+    // Feed settings actions
+    FeedSettingsOpen,
+    FeedSettingsToggle(FeedRule),
+    ToggleSimilarGroup,
+    FeedSettingsClose,
+    // Hypothes.is highlight actions
+    HighlightStart,
+    HighlightUp,
+    HighlightDown,
+    HighlightConfirm,
+    HighlightCancel,
+    RelatedStart,
+    RelatedUp,
+    RelatedDown,
+    RelatedConfirm,
+    RelatedCancel,
+    StatsOpen,
+    StatsScrollUp,
+    StatsScrollDown,
+    StatsClose,
+    LogsOpen,
+    LogsScrollUp,
+    LogsScrollDown,
+    LogsTop,
+    LogsBottom,
+    LogsClose,
+    InfoOpen,
+    InfoUp,
+    InfoDown,
+    InfoConfirm,
+    InfoClose,
+    LinksOpen,
+    LinksUp,
+    LinksDown,
+    LinksConfirm,
+    LinksNumber(usize), // 1-based position in the menu
+    LinksClose,
+    // Hacker News and Reddit discussions of the selected article
+    DiscussionsOpen,
+    DiscussionsUp,
+    DiscussionsDown,
+    DiscussionsConfirm,
+    DiscussionsClose,
+    // Chat about the selected article
+    ShareOpen,
+    ShareChar(char),
+    ShareBackspace,
+    ShareNextTarget,
+    ShareSend,
+    ShareClose,
+    ChatOpen,
+    ChatChar(char),
+    ChatBackspace,
+    ChatSend,
+    ChatScrollUp,
+    ChatScrollDown,
+    ChatClose,
+    // Settings screen
+    SettingsOpen,
+    SettingsUp,
+    SettingsDown,
+    SettingsEdit,
+    SettingsChar(char),
+    SettingsBackspace,
+    SettingsConfirm,
+    SettingsCancel,
+    SettingsClose,
+    ResizeList(i16),        // percentage points added to the list width
+    ResizeContent(i16),     // percentage points added to the feed content height
+    SwitchWorkspace(usize), // index into the configured workspaces
+    FilterFeed(Option<usize>), // index into the feeds, or None for all of them
+    // Command palette actions
+    CommandStart,
+    CommandChar(char),
+    CommandBackspace,
+    CommandUp,
+    CommandDown,
+    CommandComplete,
+    CommandConfirm,
+    CommandCancel,
+    // Reader view actions
+    OpenReader,
+    CloseReader,
+    ReaderScrollDown,
+    ReaderScrollUp,
+    ReaderPageDown,
+    ReaderPageUp,
+    ReaderNextUnread,
+    ReaderPrevUnread,
+}
+
+/// Which popup (if any) currently receives key input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    Normal,
+    Help,
+    CommandPalette,
+    Settings,
+    SettingsEdit,
+    TagInput,
+    RaindropPicker,
+    ArticleTags,
+    FeedInput,
+    FeedPicker,
+    FeedDirectory,
+    Discover,
+    OpmlInput,
+    OpmlExport,
+    OpmlExportPicker,
+    CatchUpInput,
+    SearchInput,
+    FeedSettings,
+    HighlightPicker,
+    RelatedPicker,
+    RefreshStats,
+    Logs,
+    ArticleInfo,
+    LinkMenu,
+    DiscussionMenu,
+    SummaryStyles,
+    Chat,
+    Share,
+    Reader,
 }
 
-pub fn handle_key_event(
-    key: KeyEvent,
-    tag_input_active: bool,
-    feed_input_active: bool,
-    opml_input_active: bool,
-    opml_export_active: bool,
-    show_help: bool,
-) -> Option<AppAction> {
+pub fn handle_key_event(key: KeyEvent, mode: InputMode) -> Option<AppAction> {
     // If help is showing, any key closes it
-    if show_help {
+    if mode == InputMode::Help {
         return Some(AppAction::HideHelp);
     }
 
+    // `:` command palette
+    if mode == InputMode::CommandPalette {
+        return match key.code {
+            KeyCode::Enter => Some(AppAction::CommandConfirm),
+            KeyCode::Esc => Some(AppAction::CommandCancel),
+            KeyCode::Backspace => Some(AppAction::CommandBackspace),
+            KeyCode::Tab => Some(AppAction::CommandComplete),
+            KeyCode::Up => Some(AppAction::CommandUp),
+            KeyCode::Down => Some(AppAction::CommandDown),
+            KeyCode::Char(c) => Some(AppAction::CommandChar(c)),
+            _ => None,
+        };
+    }
+
     // Tag input mode
-    if tag_input_active {
+    if mode == InputMode::TagInput {
         return match key.code {
             KeyCode::Enter => Some(AppAction::TagInputConfirm),
             KeyCode::Esc => Some(AppAction::TagInputCancel),
+            KeyCode::Tab => Some(AppAction::TagInputAccept),
+            KeyCode::Right | KeyCode::Down => Some(AppAction::TagInputNext),
+            KeyCode::Left | KeyCode::Up => Some(AppAction::TagInputPrev),
             KeyCode::Backspace => Some(AppAction::TagInputBackspace),
             KeyCode::Char(c) => Some(AppAction::TagInputChar(c)),
             _ => None,
         };
     }
 
+    // Raindrop collection picker
+    if mode == InputMode::RaindropPicker {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::RaindropDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::RaindropUp),
+            KeyCode::Enter => Some(AppAction::RaindropConfirm),
+            KeyCode::Esc => Some(AppAction::RaindropCancel),
+            _ => None,
+        };
+    }
+
+    // Local tag editor
+    if mode == InputMode::ArticleTags {
+        return match key.code {
+            KeyCode::Enter => Some(AppAction::ArticleTagsConfirm),
+            KeyCode::Esc => Some(AppAction::ArticleTagsCancel),
+            KeyCode::Tab => Some(AppAction::ArticleTagsComplete),
+            KeyCode::Backspace => Some(AppAction::ArticleTagsBackspace),
+            KeyCode::Char(c) => Some(AppAction::ArticleTagsChar(c)),
+            _ => None,
+        };
+    }
+
     // Feed input mode
-    if feed_input_active {
+    if mode == InputMode::FeedInput {
         return match key.code {
             KeyCode::Enter => Some(AppAction::FeedInputConfirm),
             KeyCode::Esc => Some(AppAction::FeedInputCancel),
             KeyCode::Backspace => Some(AppAction::FeedInputBackspace),
+            KeyCode::Tab => Some(AppAction::FeedInputCycleImport),
             KeyCode::Char(c) => Some(AppAction::FeedInputChar(c)),
             _ => None,
         };
     }
 
+    // Choice between the feeds found on a site
+    if mode == InputMode::FeedPicker {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::FeedPickerDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::FeedPickerUp),
+            KeyCode::Enter => Some(AppAction::FeedPickerConfirm),
+            KeyCode::Esc => Some(AppAction::FeedPickerCancel),
+            _ => None,
+        };
+    }
+
+    // Feed directory search: type a query, Up/Down pick a result
+    if mode == InputMode::Discover {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::DiscoverDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::DiscoverUp),
+            KeyCode::Enter | KeyCode::Char('s') => Some(AppAction::DiscoverSubscribe),
+            KeyCode::Char('o') => Some(AppAction::DiscoverOpenSite),
+            KeyCode::Esc | KeyCode::Char('q') => Some(AppAction::DiscoverClose),
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::FeedDirectory {
+        return match key.code {
+            KeyCode::Enter => Some(AppAction::DirectoryConfirm),
+            KeyCode::Esc => Some(AppAction::DirectoryCancel),
+            KeyCode::Up => Some(AppAction::DirectoryUp),
+            KeyCode::Down | KeyCode::Tab => Some(AppAction::DirectoryDown),
+            KeyCode::Backspace => Some(AppAction::DirectoryBackspace),
+            KeyCode::Char(c) => Some(AppAction::DirectoryChar(c)),
+            _ => None,
+        };
+    }
+
     // OPML import input mode
-    if opml_input_active {
+    if mode == InputMode::OpmlInput {
         return match key.code {
             KeyCode::Enter => Some(AppAction::OpmlInputConfirm),
             KeyCode::Esc => Some(AppAction::OpmlInputCancel),
@@ -90,7 +380,7 @@ pub fn handle_key_event(
     }
 
     // OPML export input mode
-    if opml_export_active {
+    if mode == InputMode::OpmlExport {
         return match key.code {
             KeyCode::Enter => Some(AppAction::OpmlExportConfirm),
             KeyCode::Esc => Some(AppAction::OpmlExportCancel),
@@ -100,32 +390,312 @@ pub fn handle_key_event(
         };
     }
 
+    // OPML export feed checklist
+    if mode == InputMode::OpmlExportPicker {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::OpmlPickerDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::OpmlPickerUp),
+            KeyCode::Char(' ') => Some(AppAction::OpmlPickerToggle),
+            KeyCode::Char('a') => Some(AppAction::OpmlPickerToggleAll),
+            KeyCode::Char('c') => Some(AppAction::OpmlPickerToggleCategory),
+            KeyCode::Enter => Some(AppAction::OpmlPickerConfirm),
+            KeyCode::Esc => Some(AppAction::OpmlPickerCancel),
+            _ => None,
+        };
+    }
+
+    // Catch-up time budget input mode
+    if mode == InputMode::CatchUpInput {
+        return match key.code {
+            KeyCode::Enter => Some(AppAction::CatchUpInputConfirm),
+            KeyCode::Esc => Some(AppAction::CatchUpInputCancel),
+            KeyCode::Backspace => Some(AppAction::CatchUpInputBackspace),
+            KeyCode::Char(c) => Some(AppAction::CatchUpInputChar(c)),
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::SettingsEdit {
+        return match key.code {
+            KeyCode::Enter => Some(AppAction::SettingsConfirm),
+            KeyCode::Esc => Some(AppAction::SettingsCancel),
+            KeyCode::Backspace => Some(AppAction::SettingsBackspace),
+            KeyCode::Char(c) => Some(AppAction::SettingsChar(c)),
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::Settings {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::SettingsDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::SettingsUp),
+            KeyCode::Enter => Some(AppAction::SettingsEdit),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char(',') => {
+                Some(AppAction::SettingsClose)
+            }
+            _ => None,
+        };
+    }
+
+    // Chat about the selected article
+    if mode == InputMode::Chat {
+        return match key.code {
+            KeyCode::Enter => Some(AppAction::ChatSend),
+            KeyCode::Esc => Some(AppAction::ChatClose),
+            KeyCode::Backspace => Some(AppAction::ChatBackspace),
+            KeyCode::Up | KeyCode::PageUp => Some(AppAction::ChatScrollUp),
+            KeyCode::Down | KeyCode::PageDown => Some(AppAction::ChatScrollDown),
+            KeyCode::Char(c) => Some(AppAction::ChatChar(c)),
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::Share {
+        return match key.code {
+            KeyCode::Enter => Some(AppAction::ShareSend),
+            KeyCode::Esc => Some(AppAction::ShareClose),
+            KeyCode::Tab => Some(AppAction::ShareNextTarget),
+            KeyCode::Backspace => Some(AppAction::ShareBackspace),
+            KeyCode::Char(c) => Some(AppAction::ShareChar(c)),
+            _ => None,
+        };
+    }
+
+    // Search query input mode
+    if mode == InputMode::SearchInput {
+        return match key.code {
+            KeyCode::Enter => Some(AppAction::SearchInputConfirm),
+            KeyCode::Esc => Some(AppAction::SearchInputCancel),
+            KeyCode::Backspace => Some(AppAction::SearchInputBackspace),
+            KeyCode::Char(c) => Some(AppAction::SearchInputChar(c)),
+            _ => None,
+        };
+    }
+
+    // Feed settings popup
+    if mode == InputMode::FeedSettings {
+        return match key.code {
+            KeyCode::Char('1') => Some(AppAction::FeedSettingsToggle(FeedRule::AutoStar)),
+            KeyCode::Char('2') => Some(AppAction::FeedSettingsToggle(FeedRule::AutoArchive)),
+            KeyCode::Char('3') => Some(AppAction::FeedSettingsToggle(FeedRule::SkipUnreadFilter)),
+            KeyCode::Char('4') => Some(AppAction::FeedSettingsToggle(FeedRule::AutoSummarize)),
+            KeyCode::Char('5') => Some(AppAction::FeedSettingsToggle(FeedRule::CollapseSimilar)),
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('F') => {
+                Some(AppAction::FeedSettingsClose)
+            }
+            _ => None,
+        };
+    }
+
+    // Summary line picker for Hypothes.is highlights
+    if mode == InputMode::HighlightPicker {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::HighlightDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::HighlightUp),
+            KeyCode::Enter => Some(AppAction::HighlightConfirm),
+            KeyCode::Esc => Some(AppAction::HighlightCancel),
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::RelatedPicker {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::RelatedDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::RelatedUp),
+            KeyCode::Enter => Some(AppAction::RelatedConfirm),
+            KeyCode::Esc => Some(AppAction::RelatedCancel),
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::RefreshStats {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::StatsScrollDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::StatsScrollUp),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('I') => Some(AppAction::StatsClose),
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::Logs {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::LogsScrollDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::LogsScrollUp),
+            KeyCode::Char('g') | KeyCode::Home => Some(AppAction::LogsTop),
+            KeyCode::Char('G') | KeyCode::End => Some(AppAction::LogsBottom),
+            KeyCode::Char('r') => Some(AppAction::LogsOpen),
+            KeyCode::Esc | KeyCode::Char('q') => Some(AppAction::LogsClose),
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::ArticleInfo {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::InfoDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::InfoUp),
+            KeyCode::Enter => Some(AppAction::InfoConfirm),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('m') => Some(AppAction::InfoClose),
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::LinkMenu {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::LinksDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::LinksUp),
+            KeyCode::Enter => Some(AppAction::LinksConfirm),
+            KeyCode::Char(c @ '1'..='9') => Some(AppAction::LinksNumber(c as usize - '0' as usize)),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('L') => Some(AppAction::LinksClose),
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::DiscussionMenu {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::DiscussionsDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::DiscussionsUp),
+            KeyCode::Enter => Some(AppAction::DiscussionsConfirm),
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('c') => {
+                Some(AppAction::DiscussionsClose)
+            }
+            _ => None,
+        };
+    }
+
+    if mode == InputMode::SummaryStyles {
+        return match key.code {
+            KeyCode::Char('j') | KeyCode::Down => Some(AppAction::SummaryStylesDown),
+            KeyCode::Char('k') | KeyCode::Up => Some(AppAction::SummaryStylesUp),
+            KeyCode::Enter => Some(AppAction::SummaryStylesConfirm),
+            KeyCode::Char('g') => Some(AppAction::SummaryStylesRegenerate),
+            KeyCode::Esc | KeyCode::Char('q') => Some(AppAction::SummaryStylesClose),
+            _ => None,
+        };
+    }
+
+    // Full-screen reader view
+    if mode == InputMode::Reader {
+        return match (key.code, key.modifiers) {
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) => Some(AppAction::Quit),
+            (KeyCode::Esc, _) | (KeyCode::Char('q'), _) | (KeyCode::Char('v'), _) => {
+                Some(AppAction::CloseReader)
+            }
+            (KeyCode::Char('j'), _) | (KeyCode::Down, _) => Some(AppAction::ReaderScrollDown),
+            (KeyCode::Char('k'), _) | (KeyCode::Up, _) => Some(AppAction::ReaderScrollUp),
+            (KeyCode::Char(' '), _) | (KeyCode::PageDown, _) => Some(AppAction::ReaderPageDown),
+            (KeyCode::PageUp, _) => Some(AppAction::ReaderPageUp),
+            (KeyCode::Char('n'), _) => Some(AppAction::ReaderNextUnread),
+            (KeyCode::Char('p'), _) => Some(AppAction::ReaderPrevUnread),
+            (KeyCode::Enter, _) => Some(AppAction::SelectArticle),
+            (KeyCode::Char('g'), _) => Some(AppAction::SummaryStylesOpen),
+            (KeyCode::Char('o'), _) => Some(AppAction::OpenInBrowser),
+            (KeyCode::Char('L'), _) => Some(AppAction::LinksOpen),
+            (KeyCode::Char('P'), _) => Some(AppAction::PrintArticle),
+            (KeyCode::Char('y'), KeyModifiers::CONTROL) => Some(AppAction::CopySummary),
+            (KeyCode::Char('y'), _) => Some(AppAction::CopyUrl),
+            (KeyCode::Char('Y'), _) => Some(AppAction::CopyMarkdownLink),
+            (KeyCode::Char('N'), _) => Some(AppAction::Speak),
+            (KeyCode::Char('M'), _) => Some(AppAction::StopSpeaking),
+            (KeyCode::Char('Z'), _) => Some(AppAction::ToggleTranslation),
+            (KeyCode::Char('z'), _) => Some(AppAction::ToggleSimplified),
+            (KeyCode::Char('c'), _) => Some(AppAction::DiscussionsOpen),
+            (KeyCode::Char('Q'), _) => Some(AppAction::ChatOpen),
+            (KeyCode::Char('l'), _) => Some(AppAction::QueueToggle),
+            (KeyCode::Char('x'), _) => Some(AppAction::QueueFinish),
+            (KeyCode::Char('?'), _) => Some(AppAction::ShowHelp),
+            _ => None,
+        };
+    }
+
     // Normal mode
     match (key.code, key.modifiers) {
         (KeyCode::Char('q'), _) => Some(AppAction::Quit),
         (KeyCode::Char('c'), KeyModifiers::CONTROL) => Some(AppAction::Quit),
 
+        (KeyCode::Left, KeyModifiers::CONTROL) => Some(AppAction::ResizeList(-RESIZE_STEP)),
+        (KeyCode::Right, KeyModifiers::CONTROL) => Some(AppAction::ResizeList(RESIZE_STEP)),
+        (KeyCode::Up, KeyModifiers::CONTROL) => Some(AppAction::ResizeContent(-RESIZE_STEP)),
+        (KeyCode::Down, KeyModifiers::CONTROL) => Some(AppAction::ResizeContent(RESIZE_STEP)),
+
         (KeyCode::Char('j'), _) | (KeyCode::Down, _) => Some(AppAction::MoveDown),
         (KeyCode::Char('k'), _) | (KeyCode::Up, _) => Some(AppAction::MoveUp),
         (KeyCode::Char('<'), _) => Some(AppAction::MoveToTop),
-        (KeyCode::Char('>'), _) => Some(AppAction::MoveToBottom),
+        (KeyCode::Char('>'), _) | (KeyCode::Char('G'), _) => Some(AppAction::MoveToBottom),
+        (KeyCode::Char('d'), KeyModifiers::CONTROL) => Some(AppAction::HalfPageDown),
+        (KeyCode::Char('u'), KeyModifiers::CONTROL) => Some(AppAction::HalfPageUp),
+        (KeyCode::Char('}'), _) => Some(AppAction::NextGroup),
+        (KeyCode::Char('{'), _) => Some(AppAction::PrevGroup),
 
         (KeyCode::Enter, _) => Some(AppAction::SelectArticle),
+        (KeyCode::Char('v'), _) => Some(AppAction::OpenReader),
 
+        (KeyCode::Char('r'), KeyModifiers::CONTROL) => Some(AppAction::RefreshFeed),
         (KeyCode::Char('r'), _) => Some(AppAction::RefreshFeeds),
         (KeyCode::Char('o'), _) => Some(AppAction::OpenInBrowser),
+        (KeyCode::Char('L'), _) => Some(AppAction::LinksOpen),
         (KeyCode::Char('e'), _) => Some(AppAction::EmailArticle),
+        (KeyCode::Char('P'), _) => Some(AppAction::PrintArticle),
+        (KeyCode::Char('y'), KeyModifiers::CONTROL) => Some(AppAction::CopySummary),
+        (KeyCode::Char('y'), _) => Some(AppAction::CopyUrl),
+        (KeyCode::Char('Y'), _) => Some(AppAction::CopyMarkdownLink),
+        (KeyCode::Char('N'), _) => Some(AppAction::Speak),
+        (KeyCode::Char('M'), _) => Some(AppAction::StopSpeaking),
+        (KeyCode::Char('Z'), _) => Some(AppAction::ToggleTranslation),
+        (KeyCode::Char('z'), _) => Some(AppAction::ToggleSimplified),
+        (KeyCode::Char('c'), _) => Some(AppAction::DiscussionsOpen),
+        (KeyCode::Char('Q'), _) => Some(AppAction::ChatOpen),
         (KeyCode::Char('b'), _) => Some(AppAction::SaveToRaindrop),
-        (KeyCode::Char('g'), _) => Some(AppAction::RegenerateSummary),
+        (KeyCode::Char('p'), _) => Some(AppAction::ShareOpen),
+        (KeyCode::Char('g'), _) => Some(AppAction::GoPrefix),
         (KeyCode::Char('d'), KeyModifiers::NONE) => Some(AppAction::DeleteArticle),
         (KeyCode::Char('D'), KeyModifiers::SHIFT) => Some(AppAction::DeleteFeed),
         (KeyCode::Char('u'), _) => Some(AppAction::UndeleteArticle),
+        (KeyCode::Char('s'), _) => Some(AppAction::ToggleStar),
+        (KeyCode::Char('l'), _) => Some(AppAction::QueueToggle),
+        (KeyCode::Char('x'), _) => Some(AppAction::QueueFinish),
+        (KeyCode::Char('W'), _) => Some(AppAction::HighlightStart),
+        (KeyCode::Char('R'), _) => Some(AppAction::RelatedStart),
+        (KeyCode::Char('I'), _) => Some(AppAction::StatsOpen),
+        (KeyCode::Char('m'), _) => Some(AppAction::InfoOpen),
+        (KeyCode::Char('X'), _) => Some(AppAction::ExportNotes),
         (KeyCode::Char('a'), _) => Some(AppAction::AddFeed),
+        (KeyCode::Char('A'), _) => Some(AppAction::AddFeedFromArticle),
+        (KeyCode::Char('E'), _) => Some(AppAction::DirectoryStart),
+        (KeyCode::Char('F'), _) => Some(AppAction::FeedSettingsOpen),
         (KeyCode::Char('i'), _) => Some(AppAction::ImportOpmlStart),
         (KeyCode::Char('w'), _) => Some(AppAction::ExportOpmlStart),
+        (KeyCode::Char('S'), _) => Some(AppAction::CycleSortMode),
+        (KeyCode::Char('h'), _) => Some(AppAction::CycleHideRead),
+        (KeyCode::Char('V'), _) => Some(AppAction::CycleListDensity),
+        (KeyCode::Char('f'), _) => Some(AppAction::CycleFilter),
+        (KeyCode::Char('t'), _) => Some(AppAction::EditTags),
+        (KeyCode::Char('T'), _) => Some(AppAction::CycleTagFilter),
+        (KeyCode::Char('C'), _) => Some(AppAction::CatchUpStart),
+        (KeyCode::Char('n'), _) => Some(AppAction::CatchUpNext),
+        (KeyCode::Char('/'), _) => Some(AppAction::SearchStart),
+        (KeyCode::Char(':'), _) => Some(AppAction::CommandStart),
+        (KeyCode::Char(','), _) => Some(AppAction::SettingsOpen),
+        (KeyCode::Char(' '), _) => Some(AppAction::ToggleSimilarGroup),
+        (KeyCode::Char(c @ '0'..='9'), _) => Some(AppAction::CountDigit(c as usize - '0' as usize)),
+        (KeyCode::Esc, _) => Some(AppAction::ExitMode),
 
         (KeyCode::Char('?'), _) => Some(AppAction::ShowHelp),
 
         _ => None,
     }
 }
+
+/// Mouse input on the main screen and in the reader; popups ignore the mouse
+pub fn handle_mouse_event(mouse: MouseEvent, mode: InputMode) -> Option<AppAction> {
+    match (mode, mouse.kind) {
+        (InputMode::Normal, MouseEventKind::Down(MouseButton::Left)) => Some(AppAction::Click {
+            column: mouse.column,
+            row: mouse.row,
+        }),
+        (InputMode::Normal, MouseEventKind::ScrollDown) => Some(AppAction::WheelDown),
+        (InputMode::Normal, MouseEventKind::ScrollUp) => Some(AppAction::WheelUp),
+        (InputMode::Reader, MouseEventKind::ScrollDown) => Some(AppAction::ReaderScrollDown),
+        (InputMode::Reader, MouseEventKind::ScrollUp) => Some(AppAction::ReaderScrollUp),
+        _ => None,
+    }
+}
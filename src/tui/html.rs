@@ -0,0 +1,121 @@
+use html2text::render::RichAnnotation;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+
+// Background of code blocks, a shade off the usual dark terminal background
+const CODE_BACKGROUND: Color = Color::Indexed(236);
+
+/// Feed HTML as styled lines wrapped to `width`: bold and italic text,
+/// headings, bullet lists, block quotes and code blocks. `None` when the HTML
+/// can't be laid out at that width.
+pub fn html_lines(html: &str, width: usize) -> Option<Vec<Line<'static>>> {
+    let rendered = html2text::from_read_rich(html.as_bytes(), width.max(1)).ok()?;
+    let mut lines: Vec<Line<'static>> = rendered
+        .iter()
+        .map(|line| {
+            let segments = line
+                .tagged_strings()
+                .map(|ts| (ts.s.clone(), ts.tag.clone()))
+                .collect();
+            styled_line(segments, width)
+        })
+        .collect();
+
+    // Collapse runs of blank lines and drop them at either end
+    lines.dedup_by(|a, b| a.width() == 0 && b.width() == 0);
+    while lines.last().is_some_and(|l| l.width() == 0) {
+        lines.pop();
+    }
+    if lines.first().is_some_and(|l| l.width() == 0) {
+        lines.remove(0);
+    }
+    Some(lines)
+}
+
+fn styled_line(mut segments: Vec<(String, Vec<RichAnnotation>)>, width: usize) -> Line<'static> {
+    let mut spans = Vec::new();
+
+    // Block quotes come as "> " before every line, once per level
+    let mut quotes = 0;
+    while take_prefix(&mut segments, "> ") {
+        quotes += 1;
+    }
+    if quotes > 0 {
+        spans.push(Span::styled("│ ".repeat(quotes), Style::default().fg(Color::DarkGray)));
+    }
+
+    // Headings as "## " before every line
+    let text: String = segments.iter().map(|(s, _)| s.as_str()).collect();
+    let level = text.chars().take_while(|c| *c == '#').count();
+    let heading = level > 0 && text[level..].starts_with(' ');
+    if heading {
+        take_prefix(&mut segments, &format!("{} ", "#".repeat(level)));
+    }
+
+    // List bullets, after any indentation for nesting
+    let indent = text.len() - text.trim_start_matches(' ').len();
+    if !heading && text[indent..].starts_with("* ") {
+        let (first, _) = &mut segments[0];
+        if first.len() >= indent + 2 {
+            first.replace_range(indent..indent + 2, "• ");
+        }
+    }
+
+    let base = if heading {
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)
+    } else if quotes > 0 {
+        Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC)
+    } else {
+        Style::default()
+    };
+    let is_code = segments
+        .iter()
+        .any(|(_, tags)| tags.iter().any(|t| matches!(t, RichAnnotation::Preformat(_))));
+    for (text, tags) in segments {
+        let style = tags.iter().fold(base, annotate);
+        spans.push(Span::styled(text, style));
+    }
+
+    // Code blocks are shaded across the whole width
+    let line = Line::from(spans);
+    if is_code {
+        let padding = width.saturating_sub(line.width());
+        let mut spans = line.spans;
+        spans.push(Span::styled(" ".repeat(padding), Style::default().bg(CODE_BACKGROUND)));
+        return Line::from(spans);
+    }
+    line
+}
+
+fn annotate(style: Style, tag: &RichAnnotation) -> Style {
+    match tag {
+        RichAnnotation::Strong => style.add_modifier(Modifier::BOLD),
+        RichAnnotation::Emphasis => style.add_modifier(Modifier::ITALIC),
+        RichAnnotation::Strikeout => style.add_modifier(Modifier::CROSSED_OUT),
+        RichAnnotation::Code => style.fg(Color::Yellow),
+        RichAnnotation::Preformat(_) => style.bg(CODE_BACKGROUND),
+        RichAnnotation::Link(_) => style.fg(Color::Blue).add_modifier(Modifier::UNDERLINED),
+        RichAnnotation::Image(_) => style.fg(Color::DarkGray),
+        _ => style,
+    }
+}
+
+/// Remove `prefix` from the start of the line's text if it is there, even
+/// when it spans several segments
+fn take_prefix(segments: &mut Vec<(String, Vec<RichAnnotation>)>, prefix: &str) -> bool {
+    let text: String = segments.iter().map(|(s, _)| s.as_str()).collect();
+    if !text.starts_with(prefix) {
+        return false;
+    }
+    let mut remaining = prefix.len();
+    for (s, _) in segments.iter_mut() {
+        let cut = remaining.min(s.len());
+        s.drain(..cut);
+        remaining -= cut;
+        if remaining == 0 {
+            break;
+        }
+    }
+    segments.retain(|(s, _)| !s.is_empty());
+    true
+}
@@ -0,0 +1,185 @@
+use std::io::{self, Cursor, Write};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use crossterm::{cursor::MoveTo, queue, terminal::window_size};
+use icy_sixel::{DiffusionMethod, MethodForLargest, MethodForRep, PixelFormat, Quality};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageFormat};
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+
+use crate::config::GraphicsProtocol;
+
+// Kitty takes base64 payloads in chunks of at most 4096 bytes
+const KITTY_CHUNK_SIZE: usize = 4096;
+// Kitty image id of the lead image; feed icons are numbered after it
+const LEAD_IMAGE_ID: u32 = 1;
+// Cells a feed icon takes in the article list
+pub const ICON_COLUMNS: usize = 2;
+// Kitty draws a placed image wherever this character appears, followed by
+// diacritics numbering the row and column of the image shown in that cell
+const PLACEHOLDER: char = '\u{10EEEE}';
+const DIACRITICS: [char; ICON_COLUMNS] = ['\u{0305}', '\u{030D}'];
+// Cell size (pixels) to assume when the terminal doesn't report its size in pixels
+const FALLBACK_CELL_SIZE: (u32, u32) = (8, 16);
+
+/// Guess the graphics protocol from the terminal's environment. Sixel
+/// support can't be detected reliably, so only terminals known to have it
+/// are picked; others can set `protocol` in `[images]`.
+pub fn detect_graphics_protocol() -> Option<GraphicsProtocol> {
+    let var = |name: &str| std::env::var(name).unwrap_or_default();
+    let term = var("TERM");
+    let term_program = var("TERM_PROGRAM");
+
+    // tmux and screen swallow graphics escape sequences
+    if std::env::var_os("TMUX").is_some() || term.starts_with("screen") {
+        return None;
+    }
+
+    if std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || term == "xterm-kitty"
+        || term == "xterm-ghostty"
+        || term_program == "ghostty"
+    {
+        Some(GraphicsProtocol::Kitty)
+    } else if term_program == "iTerm.app" || term_program == "WezTerm" {
+        Some(GraphicsProtocol::ITerm2)
+    } else if term.starts_with("foot") || term.starts_with("mlterm") || term.contains("sixel") {
+        Some(GraphicsProtocol::Sixel)
+    } else {
+        None
+    }
+}
+
+/// Size of a terminal cell in pixels
+fn cell_size() -> (u32, u32) {
+    match window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => (
+            (size.width / size.columns) as u32,
+            (size.height / size.rows) as u32,
+        ),
+        _ => FALLBACK_CELL_SIZE,
+    }
+}
+
+/// Draw an image in the top-left of `area`, scaled to fit while keeping its
+/// aspect ratio
+pub fn draw_image<W: Write>(
+    out: &mut W,
+    protocol: GraphicsProtocol,
+    image: &DynamicImage,
+    area: Rect,
+) -> io::Result<()> {
+    let (cell_width, cell_height) = cell_size();
+    let image = image.resize(
+        area.width as u32 * cell_width,
+        area.height as u32 * cell_height,
+        FilterType::Triangle,
+    );
+    let columns = image.width().div_ceil(cell_width).min(area.width as u32);
+    let rows = image.height().div_ceil(cell_height).min(area.height as u32);
+
+    queue!(out, MoveTo(area.x, area.y))?;
+    match protocol {
+        GraphicsProtocol::Kitty => {
+            let control = format!(
+                "a=T,f=100,q=2,C=1,i={},c={},r={}",
+                LEAD_IMAGE_ID, columns, rows
+            );
+            write_kitty(out, &control, &encode_png(&image)?)?;
+        }
+        GraphicsProtocol::ITerm2 => {
+            let png = encode_png(&image)?;
+            write!(
+                out,
+                "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+                png.len(),
+                columns,
+                rows,
+                STANDARD.encode(&png)
+            )?;
+        }
+        GraphicsProtocol::Sixel => {
+            let rgb = image.to_rgb8();
+            let sixel = icy_sixel::sixel_string(
+                rgb.as_raw(),
+                rgb.width() as i32,
+                rgb.height() as i32,
+                PixelFormat::RGB888,
+                DiffusionMethod::Stucki,
+                MethodForLargest::Auto,
+                MethodForRep::Auto,
+                Quality::HIGH,
+            )
+            .map_err(|e| io::Error::other(e.to_string()))?;
+            out.write_all(sixel.as_bytes())?;
+        }
+    }
+    out.flush()
+}
+
+/// Remove images drawn with `draw_image`. Kitty keeps images on a separate
+/// layer; for the others the text redrawn over them is enough.
+pub fn clear_images<W: Write>(out: &mut W, protocol: GraphicsProtocol) -> io::Result<()> {
+    if protocol == GraphicsProtocol::Kitty {
+        // Only the lead image; feed icons stay in use
+        write!(out, "\x1b_Ga=d,d=I,i={},q=2\x1b\\", LEAD_IMAGE_ID)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// Kitty image id of a feed's icon
+pub fn icon_image_id(feed_id: i64) -> u32 {
+    LEAD_IMAGE_ID + feed_id as u32
+}
+
+/// Send a feed icon to Kitty once, to show wherever `icon_placeholder` text
+/// is drawn (Kitty's Unicode placeholders), so it scrolls with the list
+pub fn transmit_icon<W: Write>(out: &mut W, image_id: u32, icon: &DynamicImage) -> io::Result<()> {
+    let control = format!("a=T,U=1,f=100,q=2,i={},c={},r=1", image_id, ICON_COLUMNS);
+    write_kitty(out, &control, &encode_png(icon)?)?;
+    out.flush()
+}
+
+/// Text showing a transmitted icon; it must be drawn in the foreground
+/// color `icon_id_color(image_id)`
+pub fn icon_placeholder() -> String {
+    // One cell per column, all in the first row
+    DIACRITICS
+        .iter()
+        .flat_map(|column| [PLACEHOLDER, DIACRITICS[0], *column])
+        .collect()
+}
+
+/// Foreground color that tells Kitty which image a placeholder shows
+pub fn icon_id_color(image_id: u32) -> Color {
+    let [_, r, g, b] = image_id.to_be_bytes();
+    Color::Rgb(r, g, b)
+}
+
+/// Send a PNG with Kitty's graphics protocol, in chunks
+fn write_kitty<W: Write>(out: &mut W, control: &str, png: &[u8]) -> io::Result<()> {
+    let data = STANDARD.encode(png);
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK_SIZE).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            write!(out, "\x1b_G{},m={};", control, more)?;
+        } else {
+            write!(out, "\x1b_Gm={};", more)?;
+        }
+        out.write_all(chunk)?;
+        write!(out, "\x1b\\")?;
+    }
+    Ok(())
+}
+
+fn encode_png(image: &DynamicImage) -> io::Result<Vec<u8>> {
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .map_err(io::Error::other)?;
+    Ok(png)
+}
@@ -1,6 +1,21 @@
 mod ui;
+mod commands;
 mod handler;
+mod html;
+mod image;
+mod scheduler;
+mod terminal;
+mod title;
+mod tty;
 pub mod widgets;
 
 pub use ui::draw;
-pub use handler::{handle_key_event, AppAction};
+pub use commands::{find_command, fuzzy_filter, COMMANDS, FILTERS, SORTS};
+pub use handler::{handle_key_event, handle_mouse_event, AppAction, InputMode};
+pub use image::{
+    clear_images, detect_graphics_protocol, draw_image, icon_image_id, transmit_icon,
+};
+pub use scheduler::{Scheduler, Tick};
+pub use terminal::{install_panic_hook, TerminalGuard};
+pub use title::{set_progress, set_title};
+pub use tty::detect_tty_mode;
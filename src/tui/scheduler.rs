@@ -0,0 +1,116 @@
+use std::time::{Duration, Instant};
+
+use crate::config::Config;
+
+// How often the read timer checks the selected article
+const READ_TIMER_PERIOD: Duration = Duration::from_secs(1);
+
+// How often to check whether a new day's starred article is due
+const REVISIT_PERIOD: Duration = Duration::from_secs(10 * 60);
+
+/// Periodic work driven by the event loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tick {
+    /// Advance spinner animations
+    Animation,
+    /// Check whether the selected article has been on screen long enough to count as read
+    ReadTimer,
+    /// Refresh all feeds in the background
+    AutoRefresh,
+    /// Resurface a starred article once a day
+    Revisit,
+    /// Reconcile saved flags with Raindrop.io bookmarks
+    RaindropSync,
+}
+
+/// Fixed-rate timer that fires at most once per check, skipping missed periods
+struct Interval {
+    tick: Tick,
+    period: Duration,
+    next: Instant,
+}
+
+impl Interval {
+    fn new(tick: Tick, period: Duration) -> Self {
+        Self {
+            tick,
+            period,
+            next: Instant::now() + period,
+        }
+    }
+
+    fn fire(&mut self, now: Instant) -> bool {
+        if now < self.next {
+            return false;
+        }
+        // Catch up without bursting if the loop was blocked for a while
+        while self.next <= now {
+            self.next += self.period;
+        }
+        true
+    }
+}
+
+/// Drives animations, the read timer and auto-refresh at their own rates,
+/// independent of how often key events wake the event loop
+pub struct Scheduler {
+    intervals: Vec<Interval>,
+}
+
+impl Scheduler {
+    pub fn new(config: &Config) -> Self {
+        let mut intervals = vec![
+            Interval::new(
+                Tick::Animation,
+                Duration::from_millis(config.tick_rate_ms.max(10)),
+            ),
+            Interval::new(Tick::ReadTimer, READ_TIMER_PERIOD),
+        ];
+        if config.revisit.enabled {
+            intervals.push(Interval::new(Tick::Revisit, REVISIT_PERIOD));
+        }
+        if config.raindrop_token.is_some() && config.raindrop_sync_minutes > 0 {
+            intervals.push(Interval::new(
+                Tick::RaindropSync,
+                Duration::from_secs(u64::from(config.raindrop_sync_minutes) * 60),
+            ));
+        }
+        if config.refresh_interval_minutes > 0 {
+            intervals.push(Interval::new(
+                Tick::AutoRefresh,
+                Duration::from_secs(u64::from(config.refresh_interval_minutes) * 60),
+            ));
+        }
+        Self { intervals }
+    }
+
+    /// Restart auto-refresh at a new interval in minutes, 0 turning it off
+    pub fn set_refresh_interval(&mut self, minutes: u32) {
+        self.intervals.retain(|i| i.tick != Tick::AutoRefresh);
+        if minutes > 0 {
+            self.intervals.push(Interval::new(
+                Tick::AutoRefresh,
+                Duration::from_secs(u64::from(minutes) * 60),
+            ));
+        }
+    }
+
+    /// Time until the next tick is due (used as the event poll timeout)
+    pub fn until_next(&self) -> Duration {
+        let now = Instant::now();
+        self.intervals
+            .iter()
+            .map(|i| i.next.saturating_duration_since(now))
+            .min()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    /// Ticks that have come due since the last call
+    pub fn due(&mut self) -> Vec<Tick> {
+        let now = Instant::now();
+        self.intervals
+            .iter_mut()
+            .filter_map(|i| i.fire(now).then_some(i.tick))
+            .collect()
+    }
+}
@@ -0,0 +1,94 @@
+use std::backtrace::Backtrace;
+use std::io::{self, Write};
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use super::title::{pop_title, push_title, set_progress};
+
+// Set while the terminal is in raw mode on the alternate screen
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+// Set while our title is pushed on the terminal's title stack
+static TITLE_PUSHED: AtomicBool = AtomicBool::new(false);
+
+/// Raw mode, the alternate screen and mouse capture for the TUI, undone when
+/// dropped: on a normal exit, an error return or a panic unwinding past it
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn enter(terminal_title: bool) -> io::Result<Self> {
+        enable_raw_mode()?;
+        ACTIVE.store(true, Ordering::SeqCst);
+        // From here on a failure still leaves through Drop
+        let guard = Self;
+        let mut stdout = io::stdout();
+        execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        if terminal_title {
+            push_title(&mut stdout)?;
+            TITLE_PUSHED.store(true, Ordering::SeqCst);
+        }
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Put the terminal back the way it was found; safe to call more than once,
+/// and errors are ignored since there is nothing left to report them on
+pub fn restore_terminal() {
+    if !ACTIVE.swap(false, Ordering::SeqCst) {
+        return;
+    }
+    let mut stdout = io::stdout();
+    if TITLE_PUSHED.swap(false, Ordering::SeqCst) {
+        let _ = set_progress(&mut stdout, false);
+        let _ = pop_title(&mut stdout);
+    }
+    let _ = disable_raw_mode();
+    let _ = execute!(stdout, LeaveAlternateScreen, DisableMouseCapture, Show);
+}
+
+/// Write panics with a backtrace to `log_path`. A panic in the UI thread
+/// restores the terminal first so the report can be read; one in a background
+/// task only goes to the log, as the UI keeps running.
+pub fn install_panic_hook(log_path: PathBuf) {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let on_main = std::thread::current().name() == Some("main");
+        let logged = log_panic(&log_path, info).is_ok();
+        if on_main || !ACTIVE.load(Ordering::SeqCst) {
+            restore_terminal();
+            default_hook(info);
+            if logged {
+                eprintln!("Crash report written to {}", log_path.display());
+            }
+        }
+    }));
+}
+
+fn log_panic(path: &Path, info: &PanicHookInfo) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut log = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        log,
+        "[{}] speedy-reader {} panicked in thread '{}': {}\n{}\n",
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S"),
+        env!("CARGO_PKG_VERSION"),
+        std::thread::current().name().unwrap_or("unnamed"),
+        info,
+        Backtrace::force_capture()
+    )
+}
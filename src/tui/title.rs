@@ -0,0 +1,29 @@
+use std::io::{self, Write};
+
+/// Set the window (or tab) title; tmux takes it as the pane title
+pub fn set_title<W: Write>(out: &mut W, title: &str) -> io::Result<()> {
+    // Control characters would end the sequence early
+    let title: String = title.chars().filter(|c| !c.is_control()).collect();
+    write!(out, "\x1b]2;{}\x07", title)?;
+    out.flush()
+}
+
+/// Save the current title on the terminal's title stack (xterm and most
+/// others), so `pop_title` can put it back on exit
+pub fn push_title<W: Write>(out: &mut W) -> io::Result<()> {
+    write!(out, "\x1b[22;0t")?;
+    out.flush()
+}
+
+pub fn pop_title<W: Write>(out: &mut W) -> io::Result<()> {
+    write!(out, "\x1b[23;0t")?;
+    out.flush()
+}
+
+/// Show or clear a busy indicator in the tab bar (OSC 9;4 from ConEmu, also
+/// understood by Windows Terminal, Ghostty and others; ignored elsewhere)
+pub fn set_progress<W: Write>(out: &mut W, busy: bool) -> io::Result<()> {
+    let state = if busy { 3 } else { 0 };
+    write!(out, "\x1b]9;4;{};0\x07", state)?;
+    out.flush()
+}
@@ -0,0 +1,65 @@
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+
+// Terminals with 8 colors and no line-drawing or braille glyphs (besides vt*)
+const LIMITED_TERMS: [&str; 4] = ["dumb", "linux", "ansi", "cons25"];
+
+/// Whether the terminal looks like a raw virtual console or serial line
+pub fn detect_tty_mode() -> bool {
+    let term = std::env::var("TERM").unwrap_or_default();
+    LIMITED_TERMS.contains(&term.as_str()) || term.starts_with("vt")
+}
+
+/// Rewrite a drawn frame for tty mode: ASCII for box drawing and symbols,
+/// and only the 8 basic colors
+pub fn downgrade_buffer(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if let Some(symbol) = ascii_symbol(cell.symbol()) {
+            cell.set_symbol(symbol);
+        }
+        // Dark gray marks less important text; dim is the closest on a console
+        if cell.fg == Color::DarkGray {
+            cell.modifier.insert(Modifier::DIM);
+        }
+        cell.fg = basic_color(cell.fg, Color::Reset);
+        // A dark gray background marks the selection; keep it visible
+        cell.bg = basic_color(cell.bg, Color::Blue);
+    }
+}
+
+fn ascii_symbol(symbol: &str) -> Option<&'static str> {
+    Some(match symbol {
+        "─" | "━" | "═" => "-",
+        "│" | "┃" | "║" => "|",
+        "┌" | "┐" | "└" | "┘" | "┏" | "┓" | "┗" | "┛" | "╭" | "╮" | "╰" | "╯" | "├" | "┤" | "┬"
+        | "┴" | "┼" => "+",
+        "·" | "•" | "★" | "◆" => "*",
+        "↑" => "^",
+        "↓" | "▾" => "v",
+        "▸" => ">",
+        "✓" => "x",
+        "❝" | "❞" => "\"",
+        "⏸" => "=",
+        "⏰" => "!",
+        "↻" => "@",
+        "◷" => "~",
+        "🔊" => ">",
+        "💬" => "#",
+        _ => return None,
+    })
+}
+
+fn basic_color(color: Color, dark_gray: Color) -> Color {
+    match color {
+        Color::DarkGray => dark_gray,
+        Color::White => Color::Gray,
+        Color::LightRed => Color::Red,
+        Color::LightGreen => Color::Green,
+        Color::LightYellow => Color::Yellow,
+        Color::LightBlue => Color::Blue,
+        Color::LightMagenta => Color::Magenta,
+        Color::LightCyan => Color::Cyan,
+        Color::Rgb(..) | Color::Indexed(_) => Color::Reset,
+        color => color,
+    }
+}
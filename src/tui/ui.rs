@@ -1,16 +1,53 @@
-use chrono::Datelike;
+use std::cell::Cell;
+use std::time::Instant;
+
+use chrono::{Datelike, NaiveDate};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState, Paragraph, Wrap},
     Frame,
 };
 
-use crate::app::App;
-use crate::models::SummaryStatus;
+use super::html::html_lines;
+use super::image::{icon_id_color, icon_image_id, icon_placeholder, ICON_COLUMNS};
+use super::tty::downgrade_buffer;
+use super::InputMode;
+use crate::app::{App, ListRow, Pane, PaneAreas};
+use crate::config::{GraphicsProtocol, ListDensity, Setting};
+use crate::models::{
+    estimate_reading_minutes, AgeGroup, Article, ChatRole, FeedRule, SortMode, SummarySource,
+    SummaryStatus, SummaryVariant,
+};
+
+// The lead image takes at most this share of the feed content width
+const IMAGE_MAX_WIDTH_PERCENT: u16 = 40;
+
+// Feed badge colors for feeds without a (colorful) favicon
+const BADGE_COLORS: [Color; 8] = [
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::Yellow,
+    Color::Green,
+];
 
 pub fn draw(frame: &mut Frame, app: &App) {
+    draw_screen(frame, app);
+    if app.tty_mode {
+        downgrade_buffer(frame.buffer_mut());
+    }
+}
+
+fn draw_screen(frame: &mut Frame, app: &App) {
+    // Set again below if the lead image has a place this frame
+    app.image_area.set(None);
+    app.pane_areas.set(PaneAreas::default());
+
     // Main vertical split: content area + status bar
     let main_vertical = Layout::default()
         .direction(Direction::Vertical)
@@ -20,12 +57,40 @@ pub fn draw(frame: &mut Frame, app: &App) {
         ])
         .split(frame.area());
 
+    // Full-screen reader replaces the split layout
+    if app.reader_active {
+        render_reader(frame, app, main_vertical[0]);
+        render_status_bar(frame, app, main_vertical[1]);
+        if app.link_menu.is_some() {
+            render_link_menu(frame, app);
+        }
+        if app.discussion_menu.is_some() {
+            render_discussion_menu(frame, app);
+        }
+        if app.summary_styles.is_some() {
+            render_summary_styles(frame, app);
+        }
+        if app.chat.is_some() {
+            render_chat(frame, app);
+        }
+        if app.share.is_some() {
+            render_share(frame, app);
+        }
+        if app.settings.is_some() {
+            render_settings(frame, app);
+        }
+        if app.show_help {
+            render_help(frame);
+        }
+        return;
+    }
+
     // Content area: left pane + right pane
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
-            Constraint::Percentage(27), // Left pane: article list
-            Constraint::Percentage(73), // Right pane: summary
+            Constraint::Percentage(app.list_width),       // Left pane: article list
+            Constraint::Percentage(100 - app.list_width), // Right pane: summary
         ])
         .split(main_vertical[0]);
 
@@ -39,15 +104,22 @@ pub fn draw(frame: &mut Frame, app: &App) {
         .split(main_chunks[0]);
 
     // Right pane: title + feed content + AI summary
+    let content_height = app.content_height();
     let right_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3),   // Article title (single line)
-            Constraint::Percentage(30), // Feed content (30%)
-            Constraint::Percentage(70), // AI summary (70%)
+            Constraint::Length(3),                        // Article title (single line)
+            Constraint::Percentage(content_height),       // Feed content
+            Constraint::Percentage(100 - content_height), // AI summary
         ])
         .split(main_chunks[1]);
 
+    app.pane_areas.set(PaneAreas {
+        list: left_chunks[1],
+        content: right_chunks[1],
+        ..PaneAreas::default()
+    });
+
     // Render left pane
     render_header(frame, app, left_chunks[0]);
     render_article_list(frame, app, left_chunks[1]);
@@ -55,7 +127,30 @@ pub fn draw(frame: &mut Frame, app: &App) {
     // Render right pane
     render_article_title(frame, app, right_chunks[0]);
     render_feed_content(frame, app, right_chunks[1]);
-    render_summary(frame, app, right_chunks[2]);
+
+    // Related articles take a few lines at the bottom of the summary space
+    let related = app.related_articles();
+    let summary_area = if related.is_empty() {
+        right_chunks[2]
+    } else {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(related.len() as u16 + 2)])
+            .split(right_chunks[2]);
+        render_related(frame, app, &related, chunks[1]);
+        chunks[0]
+    };
+    if app.annotations.is_empty() && !app.is_loading_annotations {
+        render_summary(frame, app, summary_area);
+    } else {
+        // Share the summary space with Hypothes.is annotations
+        let summary_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(summary_area);
+        render_summary(frame, app, summary_chunks[0]);
+        render_annotations(frame, app, summary_chunks[1]);
+    }
 
     // Render unified status bar
     render_status_bar(frame, app, main_vertical[1]);
@@ -65,21 +160,121 @@ pub fn draw(frame: &mut Frame, app: &App) {
         render_tag_input(frame, app);
     }
 
+    // Render Raindrop collection picker if open
+    if app.raindrop_picker.is_some() {
+        render_raindrop_picker(frame, app);
+    }
+
+    // Render article tag editor if active
+    if app.article_tags_active {
+        render_article_tags(frame, app);
+    }
+
+    // Render suggested feeds if open, under the add-feed popup that subscribes
+    if app.feed_suggestions.is_some() {
+        render_feed_suggestions(frame, app);
+    }
+
     // Render feed input popup if active
     if app.feed_input_active {
         render_feed_input(frame, app);
     }
 
+    // Render the choice of feeds found on a site, over the add-feed popup
+    if app.feed_picker.is_some() {
+        render_feed_picker(frame, app);
+    }
+
+    // Render feed directory search if active (add-feed popup goes on top)
+    if app.directory_search.is_some() && !app.feed_input_active {
+        render_feed_directory(frame, app);
+    }
+
     // Render OPML input popup if active
     if app.opml_input_active {
         render_opml_input(frame, app);
     }
 
+    // Render OPML export feed checklist if active (path popup goes on top)
+    if app.opml_export_picker.is_some() {
+        render_opml_export_picker(frame, app);
+    }
+
     // Render OPML export popup if active
     if app.opml_export_active {
         render_opml_export(frame, app);
     }
 
+    // Render catch-up time budget popup if active
+    if app.catchup_input_active {
+        render_catchup_input(frame, app);
+    }
+
+    // Render search popup if active
+    if app.search_input_active {
+        render_search_input(frame, app);
+    }
+
+    // Render feed settings popup if active
+    if app.feed_settings.is_some() {
+        render_feed_settings(frame, app);
+    }
+
+    // Render highlight picker if active
+    if app.highlight_picker.is_some() {
+        render_highlight_picker(frame, app);
+    }
+
+    // Render refresh statistics if open
+    if app.refresh_stats.is_some() {
+        render_refresh_stats(frame, app);
+    }
+
+    // Render the log tail if open
+    if app.log_viewer.is_some() {
+        render_log_viewer(frame, app);
+    }
+
+    // Render article info if open
+    if app.article_info.is_some() {
+        render_article_info(frame, app);
+    }
+
+    // Render link menu if open
+    if app.link_menu.is_some() {
+        render_link_menu(frame, app);
+    }
+
+    // Render discussions if open
+    if app.discussion_menu.is_some() {
+        render_discussion_menu(frame, app);
+    }
+
+    // Render summary styles if open
+    if app.summary_styles.is_some() {
+        render_summary_styles(frame, app);
+    }
+
+    // Render article chat if open
+    if app.chat.is_some() {
+        render_chat(frame, app);
+    }
+
+    // Render share compose popup if open
+    if app.share.is_some() {
+        render_share(frame, app);
+    }
+
+    // Render settings if open
+    if app.settings.is_some() {
+        render_settings(frame, app);
+    }
+
+    // Render command palette if active
+    if app.command_palette.is_some() {
+        render_command_palette(frame, app);
+    }
+
     // Render help popup if active
     if app.show_help {
         render_help(frame);
@@ -87,16 +282,67 @@ pub fn draw(frame: &mut Frame, app: &App) {
 }
 
 fn render_header(frame: &mut Frame, app: &App, area: Rect) {
-    let total_articles = app.articles.len();
-    let left_text = format!(" {} Articles", total_articles);
-    let right_text = format!("{} Saved ", app.saved_count);
-
+    let total_articles = app.filtered_len();
+    let inner = Block::default().borders(Borders::ALL).inner(area);
+    let left_text = match &app.catchup {
+        Some(plan) => format!(
+            " Catch-up {}/{} ({}/{} min)",
+            (plan.position + 1).min(plan.article_ids.len()),
+            plan.article_ids.len(),
+            plan.planned_minutes,
+            plan.budget_minutes
+        ),
+        None => match &app.search_results {
+            Some(search) => format!(" Search: \"{}\" ({})", search.query, search.article_ids.len()),
+            None => {
+                let mut scope = String::new();
+                if let Some(category) = &app.category_filter {
+                    scope.push_str(&format!(" in {}/", category));
+                }
+                if let Some(feed) =
+                    app.feed_filter.and_then(|id| app.feeds.iter().find(|f| f.id == id))
+                {
+                    scope.push_str(&format!(" in {}", feed.title));
+                }
+                if let Some(tag) = &app.tag_filter {
+                    scope.push_str(&format!(" #{}", tag));
+                }
+                // Clicking the filter name cycles filters
+                let label_x = format!(" {} ", total_articles).len() as u16;
+                let mut areas = app.pane_areas.get();
+                areas.filter_label = Rect {
+                    x: inner.x + label_x,
+                    y: inner.y,
+                    width: app.filter.label().chars().count() as u16,
+                    height: 1,
+                }
+                .intersection(inner);
+                app.pane_areas.set(areas);
+                format!(
+                    " {} {}{} [{}]",
+                    total_articles,
+                    app.filter.label(),
+                    scope,
+                    app.sort_mode.label()
+                )
+            }
+        },
+    };
+    let right_text = format!(
+        "{} Unread  {} Saved ",
+        app.unread_counts.total(),
+        app.saved_count
+    );
+
+    let title = match &app.workspace {
+        Some(name) => format!(" SpeedyReader - {} ", name),
+        None => " SpeedyReader ".to_string(),
+    };
     let block = Block::default()
-        .title(" SpeedyReader ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan));
 
-    let inner = block.inner(area);
     frame.render_widget(block, area);
 
     // Calculate padding to right-justify the saved count
@@ -116,43 +362,47 @@ fn render_header(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_article_list(frame: &mut Frame, app: &App, area: Rect) {
-    let articles = app.filtered_articles();
+    let today = chrono::Local::now().date_naive();
+
+    // Only the rows that fit are built; scroll just far enough to keep the
+    // selection, and the date header right above it, on screen. Rows are
+    // two lines high in the detailed density.
+    let rows = app.list_rows();
+    let len = rows.len();
+    let height = area.height.saturating_sub(2) as usize;
+    let lines = |rows: &[ListRow]| rows.iter().map(|row| app.row_height(*row)).sum::<usize>();
+    let selected = app.selected_row();
+    let top = match selected.checked_sub(1).map(|i| rows[i]) {
+        Some(ListRow::Header(_)) => selected - 1,
+        _ => selected,
+    };
+    let mut offset = app.list_offset.get();
+    if top < offset {
+        offset = top;
+    }
+    while offset < selected && selected < len && lines(&rows[offset..=selected]) > height {
+        offset += 1;
+    }
+    // No blank space below the last row
+    let mut last_page = len;
+    while last_page > 0 && lines(&rows[last_page - 1..]) <= height {
+        last_page -= 1;
+    }
+    offset = offset.min(last_page);
+    app.list_offset.set(offset);
 
-    let items: Vec<ListItem> = articles
+    let items: Vec<ListItem> = rows[offset..len.min(offset + height)]
         .iter()
-        .map(|article| {
-            let (day, date) = article
-                .published_at
-                .map(|dt| {
-                    let day = match dt.weekday() {
-                        chrono::Weekday::Mon => "M ",
-                        chrono::Weekday::Tue => "T ",
-                        chrono::Weekday::Wed => "W ",
-                        chrono::Weekday::Thu => "Th",
-                        chrono::Weekday::Fri => "F ",
-                        chrono::Weekday::Sat => "Sa",
-                        chrono::Weekday::Sun => "Su",
-                    };
-                    let date = format!("{:02}-{:02}", dt.month(), dt.day());
-                    (day, date)
-                })
-                .unwrap_or(("? ", "??-??".to_string()));
-            let feed = article.feed_title.as_deref().unwrap_or("Unknown");
-
-            let line = Line::from(vec![
-                Span::styled(day, Style::default().fg(Color::DarkGray)),
-                Span::styled(" ", Style::default()),
-                Span::styled(date, Style::default().fg(Color::DarkGray)),
-                Span::styled(" ", Style::default()),
-                Span::styled(feed.to_string(), Style::default().fg(Color::White)),
-            ]);
-
-            ListItem::new(line)
+        .filter_map(|row| match *row {
+            ListRow::Header(age) => Some(date_header(age)),
+            ListRow::Article(index) => {
+                app.filtered_article(index).map(|article| article_row(app, article, today))
+            }
         })
         .collect();
 
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL))
+        .block(pane_block(app, Pane::List))
         .highlight_style(
             Style::default()
                 .bg(Color::DarkGray)
@@ -160,34 +410,240 @@ fn render_article_list(frame: &mut Frame, app: &App, area: Rect) {
         );
 
     let mut state = ListState::default();
-    state.select(Some(app.selected_index));
+    state.select(selected.checked_sub(offset));
 
     frame.render_stateful_widget(list, area, &mut state);
 }
 
+/// "── Today" above the articles of one age
+fn date_header(age: AgeGroup) -> ListItem<'static> {
+    let style = Style::default().fg(age_color(age)).add_modifier(Modifier::BOLD);
+    ListItem::new(Line::styled(format!("── {} ", age.label()), style))
+}
+
+fn article_row(app: &App, article: &Article, today: NaiveDate) -> ListItem<'static> {
+    let age = age_color(article.age_group(today));
+    let (day, date) = article
+        .published_at
+        .map(|dt| {
+            let day = match dt.weekday() {
+                chrono::Weekday::Mon => "M ",
+                chrono::Weekday::Tue => "T ",
+                chrono::Weekday::Wed => "W ",
+                chrono::Weekday::Thu => "Th",
+                chrono::Weekday::Fri => "F ",
+                chrono::Weekday::Sat => "Sa",
+                chrono::Weekday::Sun => "Su",
+            };
+            let date = format!("{:02}-{:02}", dt.month(), dt.day());
+            (day, date)
+        })
+        .unwrap_or(("? ", "??-??".to_string()));
+    let feed = article.feed_title.as_deref().unwrap_or("Unknown");
+    let density = app.list_density();
+
+    let mut spans = vec![
+        Span::styled(day, Style::default().fg(age)),
+        Span::styled(" ", Style::default()),
+        Span::styled(date, Style::default().fg(age)),
+        Span::styled(" ", Style::default()),
+    ];
+    if app.feed_badges && density != ListDensity::Compact {
+        spans.push(feed_badge(app, article));
+        spans.push(Span::raw(" "));
+    }
+    spans.push(Span::styled(feed.to_string(), Style::default().fg(Color::White)));
+    if article.is_starred {
+        spans.push(Span::styled(" ★", Style::default().fg(Color::Yellow)));
+    }
+    if article.queued_at.is_some() {
+        spans.push(Span::styled(" ◷", Style::default().fg(Color::Green)));
+    }
+    if app.is_summary_pending(article.id) {
+        spans.push(Span::styled(" ⏳", Style::default().fg(Color::Cyan)));
+    }
+    if app.list_reading_time() && article.word_count > 0 {
+        spans.push(Span::styled(
+            format!(" ~{} min", estimate_reading_minutes(article)),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if app.revisit_id == Some(article.id) {
+        spans.push(Span::styled(" ↻ revisit", Style::default().fg(Color::Magenta)));
+    }
+    if let Some(comments) = app.comment_counts.get(&article.id) {
+        spans.push(Span::styled(
+            format!(" 💬{}", comments),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if article.also_in_feeds > 0 {
+        spans.push(Span::styled(
+            format!(" +{}", article.also_in_feeds),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    if let Some((more, expanded)) = app.similar_titles(article.id) {
+        let marker = if expanded { "▾" } else { "▸" };
+        spans.push(Span::styled(
+            format!(" {} ({} more)", marker, more),
+            Style::default().fg(Color::Blue),
+        ));
+    }
+    if app.sort_mode == SortMode::Deadline {
+        if let Some(deadline) = article.deadline {
+            let color = if deadline >= today { Color::Magenta } else { Color::DarkGray };
+            spans.push(Span::styled(
+                format!(" ⏰ {}", deadline.format("%b %d")),
+                Style::default().fg(color),
+            ));
+        }
+    }
+    if density == ListDensity::Detailed {
+        return ListItem::new(vec![Line::from(spans), article_details(article)]);
+    }
+    ListItem::new(Line::from(spans))
+}
+
+/// "   Oct 16 14:05 · Author · 1200 words · Start of the summary…" below a row
+/// in the detailed density; the list cuts it at its width
+fn article_details(article: &Article) -> Line<'static> {
+    let mut parts = Vec::new();
+    if let Some(published) = article.published_at {
+        let published = published.with_timezone(&chrono::Local);
+        parts.push(published.format("%b %-d %H:%M").to_string());
+    }
+    if let Some(author) = article.author.as_deref().filter(|a| !a.trim().is_empty()) {
+        parts.push(author.trim().to_string());
+    }
+    if article.word_count > 0 {
+        parts.push(format!("{} words", article.word_count));
+    }
+    if let Some(text) = article.content_text.as_deref() {
+        let snippet: String = text.split_whitespace().take(40).collect::<Vec<_>>().join(" ");
+        if !snippet.is_empty() {
+            parts.push(snippet);
+        }
+    }
+    Line::styled(format!("   {}", parts.join(" · ")), Style::default().fg(Color::DarkGray))
+}
+
+/// Fresher articles stand out; older ones fade
+fn age_color(age: AgeGroup) -> Color {
+    match age {
+        AgeGroup::Today => Color::Green,
+        AgeGroup::Yesterday => Color::Cyan,
+        AgeGroup::ThisWeek => Color::Gray,
+        AgeGroup::Older => Color::DarkGray,
+    }
+}
+
+/// "Refreshing 12/48 feeds: Name, Name" for the status bar
+fn refresh_status(app: &App) -> String {
+    let progress = &app.refresh_progress;
+    let mut status = format!("Refreshing {}/{} feeds", progress.done, progress.total);
+    let names: Vec<&str> = progress
+        .fetching
+        .iter()
+        .filter_map(|id| app.feeds.iter().find(|feed| feed.id == *id))
+        .map(|feed| feed.title.as_str())
+        .collect();
+    if !names.is_empty() {
+        status.push_str(": ");
+        status.push_str(&names.join(", "));
+    }
+    status
+}
+
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
-    let status = if app.is_refreshing {
-        format!("{} Refreshing...", app.spinner_char())
+    let (status, status_color) = if let Some(keys) = app.key_prefix_label() {
+        (keys, Color::Yellow)
+    } else if app.is_refreshing {
+        (format!("{} {}", app.spinner_char(), refresh_status(app)), Color::DarkGray)
+    } else if app.translating.is_some() {
+        (format!("{} Translating...", app.spinner_char()), Color::DarkGray)
+    } else if app.simplifying.is_some() {
+        (format!("{} Simplifying...", app.spinner_char()), Color::DarkGray)
+    } else if app.is_syncing {
+        (format!("{} Syncing...", app.spinner_char()), Color::DarkGray)
     } else if matches!(app.summary_status, SummaryStatus::Generating) {
-        format!("{} Summarizing...", app.spinner_char())
+        (format!("{} Summarizing...", app.spinner_char()), Color::DarkGray)
+    } else if let SummaryStatus::Retrying(at) = app.summary_status {
+        (
+            format!("{} Claude busy, retrying in {}s...", app.spinner_char(), seconds_until(at)),
+            Color::Yellow,
+        )
+    } else if let Some(toast) = app.active_toast() {
+        (toast.to_string(), Color::Cyan)
+    } else if app.speech_paused {
+        ("⏸ Paused  N:resume  M:stop".to_string(), Color::Yellow)
+    } else if app.is_speaking {
+        ("🔊 Reading aloud  N:pause  M:stop".to_string(), Color::Green)
     } else {
-        "j/k:move  Enter:summarize  o:open  d:delete  a:add  ?:help  q:quit".to_string()
+        (
+            format!(
+                "j/k:move  Enter:{}  o:open  d:delete  a:add  ?:help  q:quit",
+                app.enter_action().label()
+            ),
+            Color::DarkGray,
+        )
+    };
+
+    // Right-justified provenance of the current summary
+    let (provenance, provenance_color) = match app.current_summary.as_ref().and_then(|s| s.source) {
+        Some(source) if matches!(app.summary_status, SummaryStatus::Generated) => {
+            let color = match source {
+                SummarySource::FullArticle => Color::DarkGray,
+                _ => Color::Yellow,
+            };
+            (format!("{} ", source.description()), color)
+        }
+        _ => (String::new(), Color::DarkGray),
     };
 
-    let paragraph = Paragraph::new(status).style(Style::default().fg(Color::DarkGray));
+    let padding = (area.width as usize).saturating_sub(status.chars().count() + provenance.chars().count());
+
+    let line = Line::from(vec![
+        Span::styled(status, Style::default().fg(status_color)),
+        Span::raw(" ".repeat(padding)),
+        Span::styled(provenance, Style::default().fg(provenance_color)),
+    ]);
+
+    let paragraph = Paragraph::new(line);
     frame.render_widget(paragraph, area);
 }
 
 fn render_article_title(frame: &mut Frame, app: &App, area: Rect) {
-    let title = app
-        .selected_article()
+    let article = app.selected_article();
+    let title = article
         .map(|a| a.title.as_str())
         .unwrap_or("No article selected");
 
-    let block = Block::default()
-        .title(" Article ")
+    let block_title = match article.map(|a| a.also_in_feeds) {
+        Some(count) if count > 0 => format!(" Article (also in {}) ", feeds_label(count)),
+        _ => " Article ".to_string(),
+    };
+    let mut block = Block::default()
+        .title(block_title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Green));
+    if let Some(article) = article.filter(|a| !a.tags.is_empty()) {
+        let tags = format!(" {} ", tags_label(&article.tags));
+        block = block.title(Line::styled(tags, Style::default().fg(Color::Cyan)).right_aligned());
+    }
+    if article.is_some() && app.is_saved_to_raindrop {
+        let saved = Line::styled(" In Raindrop ", Style::default().fg(Color::Magenta));
+        block = block.title(saved.right_aligned());
+    }
+    if let Some(article) = article.filter(|a| a.word_count > 0) {
+        let length = format!(
+            " ~{} min · {} words ",
+            estimate_reading_minutes(article),
+            article.word_count
+        );
+        let length = Line::styled(length, Style::default().fg(Color::DarkGray));
+        block = block.title(length.right_aligned());
+    }
 
     let paragraph = Paragraph::new(title)
         .block(block)
@@ -197,75 +653,451 @@ fn render_article_title(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_feed_content(frame: &mut Frame, app: &App, area: Rect) {
-    let content = app
+    let title = match app.article_text_label() {
+        Some(label) => format!(" Feed Content ({}) ", label),
+        None => " Feed Content ".to_string(),
+    };
+    let block = pane_block(app, Pane::Content)
+        .title(title)
+        .border_style(Style::default().fg(Color::Blue));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    // Popups would be hidden under the image, so it only shows without one
+    let image = app
+        .preview_image()
+        .filter(|_| app.input_mode() == InputMode::Normal);
+    let Some(image) = image.filter(|_| inner.width > 0 && inner.height > 0) else {
+        render_content_lines(frame, app, inner);
+        return;
+    };
+
+    // Terminal cells are about twice as tall as they are wide. Worked out in
+    // u32, as a percentage of a wide pane's width doesn't fit in u16.
+    let aspect = image.width() as f64 / image.height().max(1) as f64;
+    let max_width = (u32::from(inner.width) * u32::from(IMAGE_MAX_WIDTH_PERCENT) / 100).max(1);
+    let height_in_columns = u32::from(inner.height) * 2;
+    let width = ((f64::from(height_in_columns) * aspect).round() as u32).clamp(1, max_width);
+    let width = u16::try_from(width).unwrap_or(u16::MAX);
+    let image_area = Rect { width, ..inner };
+    let text_area = Rect {
+        x: inner.x + width + 1,
+        width: inner.width.saturating_sub(width + 1),
+        ..inner
+    };
+    render_content_lines(frame, app, text_area);
+
+    // The image itself is written after drawing; keep ratatui off those cells
+    let buffer = frame.buffer_mut();
+    for y in image_area.top()..image_area.bottom() {
+        for x in image_area.left()..image_area.right() {
+            buffer[(x, y)].set_skip(true);
+        }
+    }
+    app.image_area.set(Some(image_area));
+}
+
+/// The selected article's text, scrolled within `area`
+fn render_content_lines(frame: &mut Frame, app: &App, area: Rect) {
+    let lines = app
         .selected_article()
-        .and_then(|a| a.content_text.as_ref().or(a.content.as_ref()))
-        .map(|c| c.split_whitespace().collect::<Vec<_>>().join(" "))
-        .unwrap_or_else(|| "No content available".to_string());
+        .map(|article| article_lines(app, article, area.width as usize))
+        .unwrap_or_else(|| vec![Line::raw("No content available")]);
+    let max = u16::try_from(lines.len().saturating_sub(area.height as usize)).unwrap_or(u16::MAX);
+    app.content_max_scroll.set(max);
+    let scroll = app.content_scroll.min(max);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), area);
+}
 
-    let block = Block::default()
-        .title(" Feed Content ")
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Blue));
+/// An article's text wrapped to `width`: the feed's HTML with its styling,
+/// or plain text for translations, rewrites and feeds without markup
+fn article_lines(app: &App, article: &Article, width: usize) -> Vec<Line<'static>> {
+    let html = article.content.as_deref().filter(|_| app.article_text_label().is_none());
+    if let Some(lines) = html.and_then(|html| html_lines(html, width)) {
+        return lines;
+    }
+    let content = app.article_text(article).unwrap_or("No content available");
+    let mut lines = Vec::new();
+    for paragraph in content.lines() {
+        if paragraph.trim().is_empty() {
+            lines.push(Line::raw(""));
+            continue;
+        }
+        for line in textwrap::wrap(paragraph.trim_end(), width.max(1)) {
+            lines.push(Line::raw(line.into_owned()));
+        }
+    }
+    lines
+}
+
+fn render_summary(frame: &mut Frame, app: &App, area: Rect) {
+    let area = render_key_facts(frame, app, area);
+    let content = summary_text(app);
+    let mut areas = app.pane_areas.get();
+    areas.summary = area;
+    app.pane_areas.set(areas);
+
+    let title = match app.current_summary.as_ref().and_then(|s| s.variant) {
+        Some(variant) if app.summary_status == SummaryStatus::Generated => {
+            format!(" AI Summary - {} ", variant.label())
+        }
+        _ => " AI Summary ".to_string(),
+    };
+    let block = pane_block(app, Pane::Summary)
+        .title(title)
+        .border_style(Style::default().fg(Color::Magenta));
+    let scroll =
+        clamp_scroll(&content, block.inner(area), app.summary_scroll, &app.summary_max_scroll);
 
     let paragraph = Paragraph::new(content)
         .block(block)
-        .wrap(Wrap { trim: true });
+        .wrap(Wrap { trim: true })
+        .scroll((scroll, 0));
 
     frame.render_widget(paragraph, area);
 }
 
-fn render_summary(frame: &mut Frame, app: &App, area: Rect) {
-    let content = match app.summary_status {
+/// Compact box with the article's TL;DR, key facts and named entities at
+/// the top of `area`, returning the space left for the summary
+fn render_key_facts(frame: &mut Frame, app: &App, area: Rect) -> Rect {
+    let selected = app.selected_article().map(|a| a.id);
+    let mut lines = Vec::new();
+    if let Some(facts) = &app.key_facts {
+        let label = Style::default().fg(Color::Yellow);
+        if !facts.tldr.is_empty() {
+            lines.push(Line::styled(
+                facts.tldr.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+        }
+        for fact in &facts.key_facts {
+            lines.push(Line::raw(format!("• {}", fact)));
+        }
+        for (name, entities) in [
+            ("People", &facts.people),
+            ("Organizations", &facts.organizations),
+            ("Places", &facts.places),
+        ] {
+            if !entities.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{}: ", name), label),
+                    Span::raw(entities.join(", ")),
+                ]));
+            }
+        }
+    } else if selected.is_some() && app.extracting_facts == selected {
+        lines.push(Line::styled(
+            format!("{} Extracting key facts...", app.spinner_char()),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if lines.is_empty() {
+        return area;
+    }
+
+    // As tall as the wrapped lines, leaving the summary at least half
+    let width = (area.width.saturating_sub(2) as usize).max(1);
+    let height: usize = lines
+        .iter()
+        .map(|line| textwrap::wrap(&line.to_string(), width).len().max(1))
+        .sum();
+    let height = (height as u16 + 2).min(area.height / 2);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(height), Constraint::Min(0)])
+        .split(area);
+
+    let block = Block::default()
+        .title(" Key facts ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+    frame.render_widget(
+        Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+        chunks[0],
+    );
+    chunks[1]
+}
+
+/// Bordered block for one of the main panes; the focused one, which the
+/// mouse wheel scrolls, gets thick borders
+fn pane_block(app: &App, pane: Pane) -> Block<'static> {
+    let block = Block::default().borders(Borders::ALL);
+    if app.focused_pane == pane {
+        block.border_type(BorderType::Thick)
+    } else {
+        block
+    }
+}
+
+/// Scroll offset for wrapped text in `area`, recording the largest useful
+/// one so the app can clamp wheel scrolling
+fn clamp_scroll(text: &str, area: Rect, scroll: u16, max_scroll: &Cell<u16>) -> u16 {
+    let width = (area.width as usize).max(1);
+    let lines: usize = text.lines().map(|line| textwrap::wrap(line, width).len().max(1)).sum();
+    let max = u16::try_from(lines.saturating_sub(area.height as usize)).unwrap_or(u16::MAX);
+    max_scroll.set(max);
+    scroll.min(max)
+}
+
+fn summary_text(app: &App) -> String {
+    match app.summary_status {
         SummaryStatus::NotGenerated => "Press Enter to generate summary...".to_string(),
         SummaryStatus::Generating => format!("{} Generating summary...", app.spinner_char()),
+        SummaryStatus::Retrying(at) => format!(
+            "{} Claude API is rate limited or overloaded, retrying in {}s...",
+            app.spinner_char(),
+            seconds_until(at)
+        ),
         SummaryStatus::Failed => "Failed to generate summary. Press 'g' to retry.".to_string(),
-        SummaryStatus::NoApiKey => "Claude API key not configured.\n\nPlease add your API key to:\n~/.config/speedy-reader/config.toml\n\nExample:\nclaude_api_key = \"sk-ant-...\"".to_string(),
+        SummaryStatus::NoApiKey => "Claude API key not configured.\n\nPlease add your API key to:\n~/.config/speedy-reader/config.toml\n\nExample:\nclaude_api_key = \"sk-ant-...\"\n\nor set SPEEDY_CLAUDE_API_KEY, or run\nspeedy-reader set-secret claude_api_key".to_string(),
         SummaryStatus::Generated => app
             .current_summary
             .as_ref()
             .map(|s| s.content.clone())
             .unwrap_or_else(|| "No summary available".to_string()),
-    };
+    }
+}
 
+fn render_annotations(frame: &mut Frame, app: &App, area: Rect) {
+    let title = if app.is_loading_annotations {
+        format!(" Hypothes.is {} ", app.spinner_char())
+    } else {
+        format!(" Hypothes.is ({}) ", app.annotations.len())
+    };
     let block = Block::default()
-        .title(" AI Summary ")
+        .title(title)
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Magenta));
+        .border_style(Style::default().fg(Color::Yellow));
 
-    let paragraph = Paragraph::new(content)
+    let paragraph = Paragraph::new(annotation_lines(app))
         .block(block)
         .wrap(Wrap { trim: true });
 
     frame.render_widget(paragraph, area);
 }
 
-fn render_tag_input(frame: &mut Frame, app: &App) {
-    let area = centered_rect(60, 20, frame.area());
+/// Other articles about the same story; a cursor while picking one with `R`
+fn render_related(frame: &mut Frame, app: &App, related: &[&Article], area: Rect) {
+    let title = if app.related_picker.is_some() {
+        " Related (Enter:open  Esc:cancel) "
+    } else {
+        " Related (R to open) "
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+
+    let items: Vec<ListItem> = related
+        .iter()
+        .map(|article| {
+            let mut spans = Vec::new();
+            if let Some(feed) = &article.feed_title {
+                spans.push(Span::styled(
+                    format!("{} · ", feed),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            spans.push(Span::raw(article.title.clone()));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select(app.related_picker);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// My annotations on the selected article: quote, note, tags and visibility
+fn annotation_lines(app: &App) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for annotation in &app.annotations {
+        if !lines.is_empty() {
+            lines.push(Line::raw(""));
+        }
+        let visibility = if annotation.is_private { "Only me" } else { "Public" };
+        let mut meta = format!(
+            "{} · {}",
+            annotation.updated_at.format("%Y-%m-%d"),
+            visibility
+        );
+        if !annotation.tags.is_empty() {
+            meta.push_str(&format!(" · {}", annotation.tags.join(", ")));
+        }
+        lines.push(Line::styled(meta, Style::default().fg(Color::DarkGray)));
+        if let Some(quote) = &annotation.quote {
+            lines.push(Line::styled(
+                format!("❝ {} ❞", quote.trim()),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        for text_line in annotation.text.lines() {
+            lines.push(Line::raw(text_line.to_string()));
+        }
+    }
+    lines
+}
+
+/// Badge before the feed name: the favicon in Kitty, otherwise the feed's
+/// initials in a color taken from its favicon, or picked by feed
+fn feed_badge(app: &App, article: &Article) -> Span<'static> {
+    let icon = app.feed_icons.get(&article.feed_id);
+    if icon.is_some() && app.image_protocol == Some(GraphicsProtocol::Kitty) {
+        let color = icon_id_color(icon_image_id(article.feed_id));
+        return Span::styled(icon_placeholder(), Style::default().fg(color));
+    }
+
+    // Dark icon colors would be hard to read on the terminal background
+    let color = match icon.and_then(|icon| icon.color) {
+        Some((r, g, b)) if !app.tty_mode && (r as u32 * 3 + g as u32 * 6 + b as u32) / 10 > 80 => {
+            Color::Rgb(r, g, b)
+        }
+        _ => BADGE_COLORS[article.feed_id.rem_euclid(BADGE_COLORS.len() as i64) as usize],
+    };
+    let title = article.feed_title.as_deref().unwrap_or_default();
+    Span::styled(
+        feed_initials(title),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Two-letter badge text: the first letters of the first two words, or the
+/// first two of a single word ("Hacker News" → "HN", "Wired" → "Wi"); a
+/// glyph for titles without Latin letters or digits
+fn feed_initials(title: &str) -> String {
+    let words: Vec<&str> = title
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let mut initials: String = match words.as_slice() {
+        [] => return "◆ ".to_string(),
+        [word] => word.chars().take(ICON_COLUMNS).collect(),
+        [first, second, ..] => [first, second].iter().filter_map(|w| w.chars().next()).collect(),
+    };
+    initials[..1].make_ascii_uppercase();
+    format!("{:<width$}", initials, width = ICON_COLUMNS)
+}
+
+/// Tags as "#a #b"
+fn tags_label(tags: &[String]) -> String {
+    tags.iter().map(|tag| format!("#{}", tag)).collect::<Vec<_>>().join(" ")
+}
+
+/// "1 feed" / "N feeds"
+fn feeds_label(count: usize) -> String {
+    if count == 1 {
+        "1 feed".to_string()
+    } else {
+        format!("{} feeds", count)
+    }
+}
+
+/// Whole seconds left until `at`, rounded up
+fn seconds_until(at: Instant) -> u64 {
+    at.saturating_duration_since(Instant::now()).as_secs_f64().ceil() as u64
+}
 
+fn render_reader(frame: &mut Frame, app: &App, area: Rect) {
     let block = Block::default()
-        .title(" Save to Raindrop.io - Enter tags (comma separated) ")
+        .title(" Reader (j/k:scroll  n/p:next/prev unread  Esc:close) ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(Color::Green));
 
     let inner = block.inner(area);
-
-    // Clear the area first
-    frame.render_widget(ratatui::widgets::Clear, area);
     frame.render_widget(block, area);
 
-    let input_text = format!("> {}_", app.tag_input);
-    let paragraph = Paragraph::new(input_text).style(Style::default().fg(Color::White));
-    frame.render_widget(paragraph, inner);
+    let Some(article) = app.selected_article() else {
+        frame.render_widget(Paragraph::new("No article selected"), inner);
+        return;
+    };
+
+    // Leave a margin on both sides for readability
+    let width = (inner.width as usize).saturating_sub(4).max(20);
+    let mut lines: Vec<Line> = Vec::new();
+
+    for line in textwrap::wrap(&article.title, width) {
+        lines.push(Line::styled(
+            line.into_owned(),
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    let mut meta = vec![article.feed_title.clone().unwrap_or_else(|| "Unknown".to_string())];
+    if let Some(author) = &article.author {
+        meta.push(author.clone());
+    }
+    if let Some(published) = article.published_at {
+        meta.push(published.format("%a %Y-%m-%d %H:%M").to_string());
+    }
+    meta.push(format!("~{} min read", estimate_reading_minutes(article)));
+    if article.also_in_feeds > 0 {
+        meta.push(format!("also in {}", feeds_label(article.also_in_feeds)));
+    }
+    if !article.tags.is_empty() {
+        meta.push(tags_label(&article.tags));
+    }
+    lines.push(Line::styled(meta.join(" · "), Style::default().fg(Color::DarkGray)));
+    lines.push(Line::raw(""));
+
+    lines.push(Line::styled("── AI Summary ──", Style::default().fg(Color::Magenta)));
+    for paragraph in summary_text(app).lines() {
+        for line in textwrap::wrap(paragraph, width) {
+            lines.push(Line::raw(line.into_owned()));
+        }
+    }
+    lines.push(Line::raw(""));
+
+    if !app.annotations.is_empty() {
+        lines.push(Line::styled(
+            "── Hypothes.is ──",
+            Style::default().fg(Color::Yellow),
+        ));
+        for annotation_line in annotation_lines(app) {
+            // Re-wrap each line, keeping its style
+            let style = annotation_line.style;
+            let text = annotation_line.to_string();
+            for line in textwrap::wrap(&text, width) {
+                lines.push(Line::styled(line.into_owned(), style));
+            }
+        }
+        lines.push(Line::raw(""));
+    }
+
+    let heading = match app.article_text_label() {
+        Some(label) => format!("── Article ({}) ──", label),
+        None => "── Article ──".to_string(),
+    };
+    lines.push(Line::styled(heading, Style::default().fg(Color::Blue)));
+    lines.extend(article_lines(app, article, width));
+
+    // Let the app clamp scrolling to the content we actually rendered; a
+    // very long article scrolls as far as a u16 offset reaches
+    let max_scroll =
+        u16::try_from(lines.len().saturating_sub(inner.height as usize)).unwrap_or(u16::MAX);
+    app.reader_max_scroll.set(max_scroll);
+    app.reader_page_height.set(inner.height);
+
+    let text_area = Rect {
+        x: inner.x + 2,
+        width: inner.width.saturating_sub(4),
+        ..inner
+    };
+    let paragraph = Paragraph::new(lines).scroll((app.reader_scroll.min(max_scroll), 0));
+    frame.render_widget(paragraph, text_area);
 }
 
-fn render_feed_input(frame: &mut Frame, app: &App) {
-    let area = centered_rect(70, 25, frame.area());
+fn render_tag_input(frame: &mut Frame, app: &App) {
+    let area = centered_rect(60, 20, frame.area());
 
     let block = Block::default()
-        .title(" Add Feed - Enter URL or website address ")
+        .title(" Save to Raindrop.io - tags, comma separated (Tab:add suggestion  ←→:pick) ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Cyan));
+        .border_style(Style::default().fg(Color::Yellow));
 
     let inner = block.inner(area);
 
@@ -273,41 +1105,50 @@ fn render_feed_input(frame: &mut Frame, app: &App) {
     frame.render_widget(ratatui::widgets::Clear, area);
     frame.render_widget(block, area);
 
-    // Split inner area for input and status
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(0)])
         .split(inner);
 
-    let input_text = format!("> {}_", app.feed_input);
+    let input_text = format!("> {}_", app.tag_input);
     let paragraph = Paragraph::new(input_text).style(Style::default().fg(Color::White));
     frame.render_widget(paragraph, chunks[0]);
 
-    // Show status message if any
-    if let Some(status) = &app.feed_input_status {
-        let (display_status, color) = if status.starts_with("Added:") {
-            (status.clone(), Color::Green)
-        } else if status.starts_with("Error:") || status.starts_with("Not found:") {
-            (status.clone(), Color::Red)
-        } else if status.starts_with("Feed already") {
-            (status.clone(), Color::Yellow)
-        } else if status.starts_with("Discovering") {
-            (format!("{} {}", app.spinner_char(), status), Color::Cyan)
-        } else {
-            (status.clone(), Color::DarkGray)
-        };
-        let status_paragraph = Paragraph::new(display_status).style(Style::default().fg(color));
-        frame.render_widget(status_paragraph, chunks[1]);
+    let Some(suggestions) = &app.raindrop_tags else {
+        return;
+    };
+    let choices = app.raindrop_tag_choices();
+    let cursor = suggestions.cursor.min(choices.len().saturating_sub(1));
+    let mut spans: Vec<Span> = choices
+        .iter()
+        .enumerate()
+        .map(|(i, tag)| {
+            // Tab adds the highlighted suggestion
+            let style = if i == cursor {
+                Style::default().fg(Color::Black).bg(Color::Cyan)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            Span::styled(format!("#{}", tag), style)
+        })
+        .flat_map(|tag| [tag, Span::raw("  ")])
+        .collect();
+    if suggestions.loading {
+        spans.push(Span::styled(
+            format!("{} suggesting tags...", app.spinner_char()),
+            Style::default().fg(Color::Yellow),
+        ));
     }
+    frame.render_widget(Paragraph::new(Line::from(spans)).wrap(Wrap { trim: true }), chunks[2]);
 }
 
-fn render_opml_input(frame: &mut Frame, app: &App) {
-    let area = centered_rect(70, 25, frame.area());
+fn render_article_tags(frame: &mut Frame, app: &App) {
+    let area = centered_rect(60, 20, frame.area());
 
     let block = Block::default()
-        .title(" Import OPML - Enter file path ")
+        .title(" Tags - comma separated (Tab:complete  Enter:save  Esc:cancel) ")
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::Yellow));
+        .border_style(Style::default().fg(Color::Cyan));
 
     let inner = block.inner(area);
 
@@ -315,24 +1156,947 @@ fn render_opml_input(frame: &mut Frame, app: &App) {
     frame.render_widget(ratatui::widgets::Clear, area);
     frame.render_widget(block, area);
 
-    // Split inner area for input and status
+    // Split inner area for input and suggestions
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(0)])
         .split(inner);
 
-    let input_text = format!("> {}_", app.opml_input);
+    let input_text = format!("> {}_", app.article_tags_input);
     let paragraph = Paragraph::new(input_text).style(Style::default().fg(Color::White));
     frame.render_widget(paragraph, chunks[0]);
 
-    // Show status message if any
+    let suggestions = app.tag_suggestions();
+    if !suggestions.is_empty() {
+        let spans: Vec<Span> = suggestions
+            .iter()
+            .enumerate()
+            .map(|(i, tag)| {
+                // Tab completes the first suggestion
+                let color = if i == 0 { Color::Cyan } else { Color::DarkGray };
+                Span::styled(format!("#{}  ", tag), Style::default().fg(color))
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(Line::from(spans)), chunks[1]);
+    }
+}
+
+fn render_feed_input(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 25, frame.area());
+
+    let title = if app.feed_auth_url.is_some() {
+        " Add Feed - Enter user:password or Header-Name: value "
+    } else if app.feed_offer.is_some() {
+        " Add Feed - Subscribe to this site's feed? "
+    } else {
+        " Add Feed - Enter URL or website address "
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+
+    // Clear the area first
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    // Split inner area for input and status
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+        ])
+        .split(inner);
+
+    // Mask everything after the first ':' while entering credentials
+    let input_text = match (&app.feed_auth_url, app.feed_input.split_once(':')) {
+        (Some(_), Some((name, secret))) => {
+            format!("> {}:{}_", name, "*".repeat(secret.chars().count()))
+        }
+        _ => format!("> {}_", app.feed_input),
+    };
+    let paragraph = Paragraph::new(input_text).style(Style::default().fg(Color::White));
+    frame.render_widget(paragraph, chunks[0]);
+
+    // Show status message if any
+    if let Some(status) = &app.feed_input_status {
+        let (display_status, color) = if status.starts_with("Added:") || status.starts_with("Found")
+        {
+            (status.clone(), Color::Green)
+        } else if status.starts_with("Error:") || status.starts_with("Not found:") {
+            (status.clone(), Color::Red)
+        } else if status.starts_with("Feed already")
+            || status.starts_with("Already")
+            || status.starts_with("Authentication")
+        {
+            (status.clone(), Color::Yellow)
+        } else if status.starts_with("Discovering") {
+            (format!("{} {}", app.spinner_char(), status), Color::Cyan)
+        } else {
+            (status.clone(), Color::DarkGray)
+        };
+        let status_paragraph = Paragraph::new(display_status).style(Style::default().fg(color));
+        frame.render_widget(status_paragraph, chunks[1]);
+    }
+
+    let import = Paragraph::new(format!("Import: {}  (Tab to change)", app.feed_import.label()))
+        .style(Style::default().fg(Color::DarkGray));
+    frame.render_widget(import, chunks[2]);
+}
+
+fn render_feed_picker(frame: &mut Frame, app: &App) {
+    let Some(picker) = &app.feed_picker else {
+        return;
+    };
+
+    let area = centered_rect(70, 50, frame.area());
+
+    let block = Block::default()
+        .title(" Subscribe - pick a feed (Enter:subscribe  Esc:back) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let items: Vec<ListItem> = picker
+        .feeds
+        .iter()
+        .map(|feed| {
+            let subscribed = app.feeds.iter().any(|f| f.url == feed.url);
+            let mut title = vec![Span::styled(
+                format!(" {}", feed.title),
+                Style::default().fg(Color::White),
+            )];
+            if subscribed {
+                title.push(Span::styled(
+                    " (subscribed)",
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            ListItem::new(vec![
+                Line::from(title),
+                Line::styled(format!("   {}", feed.url), Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select(Some(picker.cursor));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_feed_directory(frame: &mut Frame, app: &App) {
+    let Some(search) = &app.directory_search else {
+        return;
+    };
+
+    let area = centered_rect(70, 70, frame.area());
+
+    let block = Block::default()
+        .title(" Find Feeds - Search a public directory by keyword or topic ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+
+    let inner = block.inner(area);
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    // The text cursor only shows while the query (not a result) has focus
+    let cursor = if search.cursor.is_none() { "_" } else { "" };
+    let input_text = format!("> {}{}", search.query, cursor);
+    frame.render_widget(
+        Paragraph::new(input_text).style(Style::default().fg(Color::White)),
+        chunks[0],
+    );
+
+    if let Some(status) = &search.status {
+        let (display_status, color) = if search.is_searching {
+            (format!("{} {}", app.spinner_char(), status), Color::Cyan)
+        } else if status.starts_with("Error:") {
+            (status.clone(), Color::Red)
+        } else {
+            (status.clone(), Color::Yellow)
+        };
+        frame.render_widget(
+            Paragraph::new(display_status).style(Style::default().fg(color)),
+            chunks[1],
+        );
+    }
+
+    let width = chunks[2].width.saturating_sub(4) as usize;
+    let items: Vec<ListItem> = search
+        .results
+        .iter()
+        .map(|feed| {
+            let mut title = vec![
+                Span::styled(feed.title.clone(), Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("  {} followers", format_count(feed.subscribers)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+            if app.feeds.iter().any(|f| f.url == feed.url) {
+                title.push(Span::styled("  ✓ subscribed", Style::default().fg(Color::Green)));
+            }
+            let detail = feed
+                .description
+                .as_deref()
+                .or(feed.site_url.as_deref())
+                .unwrap_or(&feed.url)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            let detail: String = detail.chars().take(width).collect();
+            ListItem::new(vec![
+                Line::from(title),
+                Line::from(Span::styled(
+                    format!("  {}", detail),
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select(search.cursor);
+    frame.render_stateful_widget(list, chunks[2], &mut state);
+}
+
+/// Feeds suggested from reading history, each with why it was picked
+fn render_feed_suggestions(frame: &mut Frame, app: &App) {
+    let Some(discover) = &app.feed_suggestions else {
+        return;
+    };
+
+    let area = centered_rect(70, 70, frame.area());
+    let block = Block::default()
+        .title(" Discover - Feeds like yours (Enter:subscribe  o:open site  Esc:close) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+    let inner = block.inner(area);
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    if let Some(status) = &discover.status {
+        let (display_status, color) = if discover.is_loading {
+            (format!("{} {}", app.spinner_char(), status), Color::Cyan)
+        } else if status.starts_with("Error:") {
+            (status.clone(), Color::Red)
+        } else {
+            (status.clone(), Color::Yellow)
+        };
+        frame.render_widget(
+            Paragraph::new(display_status).style(Style::default().fg(color)),
+            chunks[0],
+        );
+    }
+
+    let width = chunks[1].width.saturating_sub(4) as usize;
+    let items: Vec<ListItem> = discover
+        .suggestions
+        .iter()
+        .map(|feed| {
+            let mut title = vec![Span::styled(
+                feed.title.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )];
+            if app.feeds.iter().any(|f| f.url == feed.url) {
+                title.push(Span::styled("  ✓ subscribed", Style::default().fg(Color::Green)));
+            }
+            let site = feed.site_url.as_deref().unwrap_or(&feed.url);
+            let detail = format!("{} - {}", feed.reason, site);
+            let detail: String = detail.chars().take(width).collect();
+            ListItem::new(vec![
+                Line::from(title),
+                Line::from(Span::styled(
+                    format!("  {}", detail),
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ])
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select((!discover.suggestions.is_empty()).then_some(discover.cursor));
+    frame.render_stateful_widget(list, chunks[1], &mut state);
+}
+
+fn render_command_palette(frame: &mut Frame, app: &App) {
+    let Some(palette) = &app.command_palette else {
+        return;
+    };
+
+    let area = centered_rect(60, 50, frame.area());
+
+    let block = Block::default()
+        .title(" Command - Tab completes, Enter runs ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+
+    frame.render_widget(
+        Paragraph::new(format!(":{}_", palette.input)).style(Style::default().fg(Color::White)),
+        chunks[0],
+    );
+
+    let items: Vec<ListItem> = app
+        .command_suggestions()
+        .into_iter()
+        .map(|(line, hint)| {
+            ListItem::new(Line::from(vec![
+                Span::raw(line),
+                Span::styled(format!("  {}", hint), Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select(Some(palette.cursor));
+    frame.render_stateful_widget(list, chunks[2], &mut state);
+}
+
+/// Bandwidth and requests of recent refreshes, overall and per feed
+fn render_refresh_stats(frame: &mut Frame, app: &App) {
+    let Some(stats) = &app.refresh_stats else {
+        return;
+    };
+
+    let area = centered_rect(80, 70, frame.area());
+    let block = Block::default()
+        .title(" Refresh statistics (j/k:scroll  Esc:close) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4), // Totals
+            Constraint::Length(1), // Column headings
+            Constraint::Min(0),    // Feeds
+        ])
+        .split(inner);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut totals = match stats.history.first() {
+        None => vec![Line::styled(" No refreshes recorded yet", dim)],
+        Some(last) => {
+            let refreshes = stats.history.len() as u64;
+            let bytes: u64 = stats.history.iter().map(|r| r.bytes).sum();
+            let requests: u64 = stats.history.iter().map(|r| r.requests).sum();
+            vec![
+                Line::raw(format!(
+                    " Last refresh ({}): {} feeds, {} requests, {}",
+                    last.refreshed_at.with_timezone(&chrono::Local).format("%a %H:%M"),
+                    last.feeds,
+                    last.requests,
+                    format_bytes(last.bytes)
+                )),
+                Line::raw(format!(
+                    " Last 30 days: {} refreshes, {} requests, {} ({} per refresh)",
+                    refreshes,
+                    requests,
+                    format_bytes(bytes),
+                    format_bytes(bytes / refreshes)
+                )),
+            ]
+        }
+    };
+    let month = &stats.month_spend;
+    let budget = match stats.monthly_budget {
+        Some(budget) => format!(" of ${:.2} budget", budget),
+        None => String::new(),
+    };
+    totals.push(Line::raw(format!(
+        " Claude: ${:.2}{} this month ({} summaries plus chat, {} in / {} out tokens), \
+         ${:.2} all time",
+        month.cost_usd,
+        budget,
+        month.summaries,
+        format_count(month.input_tokens),
+        format_count(month.output_tokens),
+        stats.total_spend.cost_usd
+    )));
+    frame.render_widget(Paragraph::new(totals), chunks[0]);
+
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            format!(" {:>11} {:>10} {:>9}  Feed", "Per refresh", "Last", "Requests"),
+            dim.add_modifier(Modifier::BOLD),
+        )),
+        chunks[1],
+    );
+
+    let lines: Vec<Line> = stats
+        .feeds
+        .iter()
+        .skip(stats.scroll)
+        .map(|feed| {
+            Line::raw(format!(
+                " {:>11} {:>10} {:>9}  {}",
+                format_bytes(feed.bytes_per_refresh()),
+                format_bytes(feed.last_bytes),
+                feed.requests,
+                feed.title
+            ))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), chunks[2]);
+}
+
+/// Latest log lines, newest at the bottom, with warnings and errors colored
+fn render_log_viewer(frame: &mut Frame, app: &App) {
+    let Some(logs) = &app.log_viewer else {
+        return;
+    };
+
+    let area = centered_rect(90, 80, frame.area());
+    let path = crate::logging::log_path().map_or_else(String::new, |p| p.display().to_string());
+    let block = Block::default()
+        .title(format!(" Log {} (j/k:scroll  g/G:top/end  r:reload  Esc:close) ", path))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    if logs.lines.is_empty() {
+        let empty = Line::styled(" Nothing logged yet", Style::default().fg(Color::DarkGray));
+        frame.render_widget(Paragraph::new(empty), inner);
+        return;
+    }
+
+    let end = logs.lines.len() - logs.back;
+    let start = end.saturating_sub(inner.height as usize);
+    let lines: Vec<Line> = logs.lines[start..end]
+        .iter()
+        .map(|line| {
+            let color = if line.contains(" ERROR ") {
+                Color::Red
+            } else if line.contains(" WARN ") {
+                Color::Yellow
+            } else if line.contains(" DEBUG ") || line.contains(" TRACE ") {
+                Color::DarkGray
+            } else {
+                Color::Reset
+            };
+            Line::styled(line.as_str(), Style::default().fg(color))
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn render_article_info(frame: &mut Frame, app: &App) {
+    let Some(info) = &app.article_info else {
+        return;
+    };
+
+    let area = centered_rect(80, 70, frame.area());
+    let block = Block::default()
+        .title(" Article info (j/k:move  Enter:go to  Esc:close) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let heading = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let article = &info.article;
+    let mut lines = vec![Line::styled(
+        format!(" {}", article.title),
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    let date = article
+        .published_at
+        .map(|dt| dt.with_timezone(&chrono::Local).format("%a %Y-%m-%d %H:%M").to_string());
+    let byline: Vec<&str> = [
+        article.feed_title.as_deref(),
+        article.author.as_deref(),
+        date.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    lines.push(Line::raw(format!(" {}", byline.join(" · "))));
+    lines.push(Line::styled(format!(" {}", article.url), dim));
+    if !article.tags.is_empty() {
+        lines.push(Line::raw(format!(" {}", tags_label(&article.tags))));
+    }
+
+    let mut cursor_line = 0;
+    let mut index = 0;
+    for (title, articles) in [
+        ("References", &info.references),
+        ("Referenced by", &info.referenced_by),
+    ] {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(format!(" {} ({})", title, articles.len()), heading));
+        if articles.is_empty() {
+            lines.push(Line::styled("   none stored", dim));
+        }
+        for linked in articles {
+            let mut spans = vec![Span::raw("   ")];
+            if let Some(feed) = &linked.feed_title {
+                spans.push(Span::styled(format!("{} · ", feed), dim));
+            }
+            spans.push(Span::raw(linked.title.clone()));
+            let mut line = Line::from(spans);
+            if index == info.cursor {
+                let highlight = Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD);
+                line = line.style(highlight);
+                cursor_line = lines.len();
+            }
+            lines.push(line);
+            index += 1;
+        }
+    }
+
+    // Keep the highlighted article on screen
+    let scroll = cursor_line.saturating_sub(inner.height.saturating_sub(1) as usize);
+    let scroll = u16::try_from(scroll).unwrap_or(u16::MAX);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner);
+}
+
+fn render_chat(frame: &mut Frame, app: &App) {
+    let Some(chat) = &app.chat else {
+        return;
+    };
+
+    let area = centered_rect(80, 80, frame.area());
+    let block = Block::default()
+        .title(" Chat (Enter:send  ↑/↓:scroll  Esc:close) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    // Wrapped here rather than by the Paragraph, to know how far to scroll
+    let width = (chunks[0].width as usize).saturating_sub(1).max(1);
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = vec![
+        Line::styled(format!(" {}", chat.title), Style::default().add_modifier(Modifier::BOLD)),
+        Line::raw(""),
+    ];
+    if chat.messages.is_empty() {
+        lines.push(Line::styled(" Ask anything about the article", dim));
+    }
+    for message in &chat.messages {
+        let (name, color) = match message.role {
+            ChatRole::User => ("You", Color::Cyan),
+            ChatRole::Assistant => ("Claude", Color::Green),
+        };
+        lines.push(Line::styled(
+            format!(" {}", name),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ));
+        for line in message.content.lines() {
+            for wrapped in textwrap::wrap(line, width) {
+                lines.push(Line::raw(format!(" {}", wrapped)));
+            }
+        }
+        lines.push(Line::raw(""));
+    }
+    if chat.waiting {
+        lines.push(Line::styled(format!(" {} Thinking...", app.spinner_char()), dim));
+    }
+
+    // Scrolled up from the bottom, so the latest message shows
+    let max_scroll =
+        u16::try_from(lines.len().saturating_sub(chunks[0].height as usize)).unwrap_or(u16::MAX);
+    app.chat_max_scroll.set(max_scroll);
+    let scroll = max_scroll - chat.scroll.min(max_scroll);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll, 0)), chunks[0]);
+
+    let input = format!("> {}_", chat.input);
+    frame.render_widget(Paragraph::new(input).style(Style::default().fg(Color::White)), chunks[1]);
+}
+
+fn render_share(frame: &mut Frame, app: &App) {
+    let Some(share) = &app.share else {
+        return;
+    };
+    let target = share.target();
+
+    let area = centered_rect(70, 40, frame.area());
+    let title = if share.targets.len() > 1 {
+        format!(" Share to {} (Tab:switch  Enter:post  Esc:cancel) ", target.label())
+    } else {
+        format!(" Share to {} (Enter:post  Esc:cancel) ", target.label())
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    let width = (chunks[0].width as usize).saturating_sub(1).max(1);
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+    let cursor = if share.drafting || share.posting { "" } else { "_" };
+    let text = format!("{}{}", share.text, cursor);
+    for line in text.split('\n') {
+        if line.is_empty() {
+            lines.push(Line::raw(""));
+        }
+        for wrapped in textwrap::wrap(line, width) {
+            lines.push(Line::raw(format!(" {}", wrapped)));
+        }
+    }
+    if share.drafting {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!(" {} Writing an opening line...", app.spinner_char()),
+            dim,
+        ));
+    }
+    frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+    let length = target.post_length(&share.text);
+    let count_color = if length > target.max_chars() {
+        Color::Red
+    } else {
+        Color::DarkGray
+    };
+    let mut footer = vec![Span::styled(
+        format!(" {}/{}", length, target.max_chars()),
+        Style::default().fg(count_color),
+    )];
+    if share.posting {
+        footer.push(Span::styled(format!("  {} Posting...", app.spinner_char()), dim));
+    } else if let Some(status) = &share.status {
+        let color = if status.starts_with("Error:") {
+            Color::Red
+        } else {
+            Color::DarkGray
+        };
+        footer.push(Span::styled(format!("  {}", status), Style::default().fg(color)));
+    }
+    frame.render_widget(Paragraph::new(Line::from(footer)), chunks[1]);
+}
+
+fn render_link_menu(frame: &mut Frame, app: &App) {
+    let Some(menu) = &app.link_menu else {
+        return;
+    };
+
+    let area = centered_rect(70, 60, frame.area());
+    let block = Block::default()
+        .title(" Links (1-9/Enter:open in browser  Esc:close) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let items: Vec<ListItem> = menu
+        .links
+        .iter()
+        .enumerate()
+        .map(|(i, (text, url))| {
+            let number = if i < 9 { format!("{:>2}. ", i + 1) } else { "    ".to_string() };
+            let host = url::Url::parse(url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_string))
+                .unwrap_or_default();
+            ListItem::new(Line::from(vec![
+                Span::styled(number, dim),
+                Span::raw(text.clone()),
+                Span::styled(format!("  {}", host), dim),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select(Some(menu.cursor));
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_discussion_menu(frame: &mut Frame, app: &App) {
+    let Some(menu) = &app.discussion_menu else {
+        return;
+    };
+
+    let area = centered_rect(70, 50, frame.area());
+    let block = Block::default()
+        .title(" Discussions (Enter:open in browser  Esc:close) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut items: Vec<ListItem> = menu
+        .discussions
+        .iter()
+        .map(|discussion| {
+            let mut counts = Vec::new();
+            if let Some(points) = discussion.points {
+                counts.push(format!("{} points", points));
+            }
+            if let Some(comments) = discussion.comments {
+                counts.push(format!("{} comments", comments));
+            }
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {:<7}", discussion.site.label()),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(discussion.title.clone()),
+                Span::styled(format!("  {}", counts.join(" · ")), dim),
+            ]))
+        })
+        .collect();
+    if menu.loading {
+        items.push(ListItem::new(Line::styled(
+            format!(" {} Looking on Hacker News and Reddit...", app.spinner_char()),
+            dim,
+        )));
+    } else if menu.discussions.is_empty() {
+        items.push(ListItem::new(Line::styled(" No discussions found", dim)));
+    }
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select((!menu.discussions.is_empty()).then_some(menu.cursor));
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_summary_styles(frame: &mut Frame, app: &App) {
+    let Some(picker) = &app.summary_styles else {
+        return;
+    };
+
+    let area = centered_rect(50, 30, frame.area());
+    let block = Block::default()
+        .title(" Summary style (Enter:show  g:regenerate  Esc:close) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let items: Vec<ListItem> = std::iter::once((None, "Default"))
+        .chain(SummaryVariant::ALL.into_iter().map(|v| (Some(v), v.label())))
+        .map(|(variant, label)| {
+            let saved = variant.is_some_and(|v| picker.saved.contains(&v));
+            ListItem::new(Line::from(vec![
+                Span::raw(format!(" {}", label)),
+                Span::styled(if saved { "  (saved)" } else { "" }, dim),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select(Some(picker.cursor));
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_settings(frame: &mut Frame, app: &App) {
+    let Some(settings) = &app.settings else {
+        return;
+    };
+
+    let area = centered_rect(80, 80, frame.area());
+    let title = if settings.editing.is_some() {
+        " Settings (Enter:save  Esc:cancel) "
+    } else {
+        " Settings (j/k:move  Enter:edit  Esc:close) "
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(inner);
+
+    let key_width = Setting::ALL.iter().map(|s| s.key().len()).max().unwrap_or(0);
+    let items: Vec<ListItem> = Setting::ALL
+        .iter()
+        .map(|&setting| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" {:<width$}  ", setting.key(), width = key_width),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::raw(app.setting_value(setting)),
+            ]))
+        })
+        .collect();
+    let list = List::new(items)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+    let mut state = ListState::default();
+    state.select(Some(settings.cursor));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    let setting = Setting::ALL[settings.cursor];
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut footer = vec![Line::styled(format!(" {}", setting.description()), dim)];
+    match &settings.editing {
+        Some(value) => {
+            let shown = if setting.is_secret() {
+                "•".repeat(value.chars().count())
+            } else {
+                value.clone()
+            };
+            footer.push(Line::from(vec![
+                Span::styled(" > ", Style::default().fg(Color::Cyan)),
+                Span::raw(format!("{}_", shown)),
+            ]));
+        }
+        None => footer.push(Line::styled(" Changes are saved to config.toml", dim)),
+    }
+    if let Some(error) = &settings.error {
+        footer.push(Line::styled(format!(" {}", error), Style::default().fg(Color::Red)));
+    }
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
+
+fn render_raindrop_picker(frame: &mut Frame, app: &App) {
+    let Some(picker) = &app.raindrop_picker else {
+        return;
+    };
+
+    let area = centered_rect(50, 60, frame.area());
+    let block = Block::default()
+        .title(" Save to collection (j/k:move  Enter:save  Esc:cancel) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let items: Vec<ListItem> = picker
+        .collections
+        .iter()
+        .map(|collection| ListItem::new(collection.title.clone()))
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select(Some(picker.cursor));
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Human-readable byte count ("512 B", "12.3 KB", "1.2 MB")
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=999 => format!("{} B", bytes),
+        1_000..=999_999 => format!("{:.1} KB", bytes as f64 / 1_000.0),
+        1_000_000..=999_999_999 => format!("{:.1} MB", bytes as f64 / 1_000_000.0),
+        _ => format!("{:.1} GB", bytes as f64 / 1_000_000_000.0),
+    }
+}
+
+/// Compact follower count ("950", "12k", "1.3M")
+fn format_count(count: u64) -> String {
+    match count {
+        0..=999 => count.to_string(),
+        1_000..=999_999 => format!("{}k", count / 1_000),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
+
+fn render_opml_input(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 25, frame.area());
+
+    let block = Block::default()
+        .title(" Import OPML - Enter file path or URL ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    let inner = block.inner(area);
+
+    // Clear the area first
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    // Split inner area for input and status
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+
+    let input_text = format!("> {}_", app.opml_input);
+    let paragraph = Paragraph::new(input_text).style(Style::default().fg(Color::White));
+    frame.render_widget(paragraph, chunks[0]);
+
+    // Show status message if any
     if let Some(status) = &app.opml_input_status {
         let (display_status, color) = if status.starts_with("Imported") {
             (status.clone(), Color::Green)
         } else if status.starts_with("Error:") || status.starts_with("Not found:") {
             (status.clone(), Color::Red)
-        } else if status == "Importing..." {
-            (format!("{} Importing...", app.spinner_char()), Color::Yellow)
+        } else if status == "Importing..." || status == "Downloading..." {
+            (format!("{} {}", app.spinner_char(), status), Color::Yellow)
         } else {
             (status.clone(), Color::DarkGray)
         };
@@ -381,32 +2145,326 @@ fn render_opml_export(frame: &mut Frame, app: &App) {
     }
 }
 
+fn render_opml_export_picker(frame: &mut Frame, app: &App) {
+    let Some(picker) = &app.opml_export_picker else {
+        return;
+    };
+
+    let area = centered_rect(70, 70, frame.area());
+    let selected = picker.entries.iter().filter(|(_, included)| *included).count();
+
+    let block = Block::default()
+        .title(format!(
+            " Export OPML - {} of {} feeds (Space:toggle  c:category  a:all  Enter:next) ",
+            selected,
+            picker.entries.len()
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Green));
+
+    let items: Vec<ListItem> = picker
+        .entries
+        .iter()
+        .filter_map(|(id, included)| {
+            let feed = app.feeds.iter().find(|f| f.id == *id)?;
+            let mark = if *included { "[x]" } else { "[ ]" };
+            let mut spans = vec![Span::styled(
+                format!("{} ", mark),
+                Style::default().fg(if *included { Color::Green } else { Color::DarkGray }),
+            )];
+            if let Some(category) = &feed.category {
+                spans.push(Span::styled(
+                    format!("{} / ", category),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            spans.push(Span::raw(feed.title.clone()));
+            let unread = app.unread_counts.for_feed(feed.id);
+            if unread > 0 {
+                spans.push(Span::styled(
+                    format!(" ({})", unread),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            Some(ListItem::new(Line::from(spans)))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select(Some(picker.cursor));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_catchup_input(frame: &mut Frame, app: &App) {
+    let area = centered_rect(60, 20, frame.area());
+
+    let block = Block::default()
+        .title(" Catch-up - How many minutes do you have? ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Magenta));
+
+    let inner = block.inner(area);
+
+    // Clear the area first
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    // Split inner area for input and status
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+
+    let input_text = format!("> {}_ minutes", app.catchup_input);
+    let paragraph = Paragraph::new(input_text).style(Style::default().fg(Color::White));
+    frame.render_widget(paragraph, chunks[0]);
+
+    if let Some(status) = &app.catchup_input_status {
+        let status_paragraph = Paragraph::new(status.as_str()).style(Style::default().fg(Color::Red));
+        frame.render_widget(status_paragraph, chunks[1]);
+    }
+}
+
+fn render_feed_settings(frame: &mut Frame, app: &App) {
+    let Some(feed) = app
+        .feed_settings
+        .and_then(|id| app.feeds.iter().find(|f| f.id == id))
+    else {
+        return;
+    };
+
+    let area = centered_rect(60, 35, frame.area());
+
+    let unread = app.unread_counts.for_feed(feed.id);
+    let title = match &feed.category {
+        Some(category) => format!(
+            " Feed Settings - {} ({} unread; {}: {} unread) ",
+            feed.title,
+            unread,
+            category,
+            app.unread_counts.for_category(&app.feeds, Some(category))
+        ),
+        None => format!(" Feed Settings - {} ({} unread) ", feed.title, unread),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+
+    // Clear the area first
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    let mut lines: Vec<Line> = FeedRule::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, rule)| {
+            let (mark, color) = if feed.rules.get(*rule) {
+                ("[x]", Color::Green)
+            } else {
+                ("[ ]", Color::DarkGray)
+            };
+            Line::from(vec![
+                Span::styled(format!(" {} ", mark), Style::default().fg(color)),
+                Span::styled(format!("{}  ", i + 1), Style::default().fg(Color::Yellow)),
+                Span::raw(rule.label()),
+            ])
+        })
+        .collect();
+    let backoff = &feed.backoff;
+    if backoff.failures > 0 {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(
+            format!(
+                " Failed {} time{} in a row: {}",
+                backoff.failures,
+                if backoff.failures == 1 { "" } else { "s" },
+                backoff.error.as_deref().unwrap_or("unknown error")
+            ),
+            Style::default().fg(Color::Red),
+        ));
+        let retry = match backoff.retry_at {
+            Some(retry_at) if backoff.is_waiting(chrono::Utc::now()) => format!(
+                " Skipped by refreshes until {} (Ctrl+r in the feed tries it now)",
+                retry_at.with_timezone(&chrono::Local).format("%a %H:%M")
+            ),
+            _ => " Tried again at the next refresh".to_string(),
+        };
+        lines.push(Line::styled(retry, Style::default().fg(Color::DarkGray)));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(
+        " 1-5: toggle  Esc: close",
+        Style::default().fg(Color::DarkGray),
+    ));
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+fn render_highlight_picker(frame: &mut Frame, app: &App) {
+    let Some(cursor) = app.highlight_picker else {
+        return;
+    };
+
+    let area = centered_rect(70, 50, frame.area());
+
+    let block = Block::default()
+        .title(" Highlight on Hypothes.is - pick a summary line (Enter:save  Esc:cancel) ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow));
+
+    // Wrap long lines so the whole quote is visible
+    let width = (block.inner(area).width as usize).saturating_sub(2).max(20);
+    let items: Vec<ListItem> = app
+        .highlight_candidates()
+        .iter()
+        .map(|line| {
+            let wrapped: Vec<Line> = textwrap::wrap(line, width)
+                .into_iter()
+                .map(|l| Line::raw(format!(" {}", l)))
+                .collect();
+            ListItem::new(wrapped)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray).add_modifier(Modifier::BOLD));
+
+    let mut state = ListState::default();
+    state.select(Some(cursor));
+
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn render_search_input(frame: &mut Frame, app: &App) {
+    let area = centered_rect(70, 25, frame.area());
+
+    let title = if app.has_semantic_search() {
+        " Search - Describe what you're looking for (\"quotes\" for exact phrase) "
+    } else {
+        " Search - Keywords or \"exact phrase\" "
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+
+    let inner = block.inner(area);
+
+    // Clear the area first
+    frame.render_widget(ratatui::widgets::Clear, area);
+    frame.render_widget(block, area);
+
+    // Split inner area for input and status
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Min(0)])
+        .split(inner);
+
+    let input_text = format!("> {}_", app.search_input);
+    let paragraph = Paragraph::new(input_text).style(Style::default().fg(Color::White));
+    frame.render_widget(paragraph, chunks[0]);
+
+    // Show status message if any
+    if let Some(status) = &app.search_input_status {
+        let (display_status, color) = if app.is_searching {
+            (format!("{} {}", app.spinner_char(), status), Color::Cyan)
+        } else if status.starts_with("Error:") {
+            (status.clone(), Color::Red)
+        } else {
+            (status.clone(), Color::Yellow)
+        };
+        let status_paragraph = Paragraph::new(display_status).style(Style::default().fg(color));
+        frame.render_widget(status_paragraph, chunks[1]);
+    }
+}
+
 fn render_help(frame: &mut Frame) {
     let area = centered_rect(50, 80, frame.area());
 
     let help_text = vec![
         "",
         " Navigation:",
-        "   j / ↓    Move down",
-        "   k / ↑    Move up",
-        "   <        Go to top",
-        "   >        Go to bottom",
-        "   Enter    Select / Generate summary",
+        "   j / ↓    Move down (5j: five articles)",
+        "   k / ↑    Move up (5k: five articles)",
+        "   gg / <   Go to top (5gg: fifth article)",
+        "   G / >    Go to bottom (5G: fifth article)",
+        "   Ctrl+d/u Half a page down / up",
+        "   } / {    Next / previous date group (or feed)",
+        "   Ctrl+←/→ Narrow / widen the article list",
+        "   Ctrl+↑/↓ Shrink / grow the feed content above the summary",
+        "   S        Cycle sort order",
+        "   h        Hide read articles: immediately / on navigation / next refresh",
+        "   V        List density: compact / comfortable / detailed",
+        "   Enter    Summarize (see enter_action in config)",
+        "   v        Open full-screen reader",
+        "",
+        " Mouse:",
+        "   Click    Select article / focus pane (thick border)",
+        "   Wheel    Scroll the focused pane",
+        "   Click the filter name in the header to cycle filters",
         "",
         " Actions:",
         "   r        Refresh all feeds",
+        "   Ctrl+r   Refresh only this feed (the listed one or the article's)",
         "   a        Add new feed",
-        "   i        Import OPML file",
+        "   A        Subscribe to the article's website",
+        "   E        Find feeds in a public directory",
+        "   i        Import OPML file or URL",
         "   w        Export OPML file",
         "   o        Open in browser",
+        "   L        Open one of the article's links",
         "   e        Email article",
-        "   b        Save to Raindrop.io",
-        "   g        Regenerate summary",
-        "   d        Delete article",
+        "   P        Print article and summary",
+        "   y / Y    Copy URL / Markdown link (Ctrl+y: summary)",
+        "   N        Read aloud / pause (M: stop)",
+        "   Z        Translate / show original",
+        "   z        Plain-language rewrite / show original",
+        "   c        Hacker News / Reddit discussions",
+        "   Q        Ask Claude questions about the article (chat)",
+        "   p        Share the article to Mastodon or Bluesky",
+        "   b        Save to Raindrop.io (tags, Tab adds a suggested one; then collection)",
+        "   g        Summary styles, unless a second g follows (shorter, ELI5, ...)",
+        "   d        Move to trash (delete forever in trash)",
         "   D        Delete feed",
-        "   u        Undelete last",
+        "   u        Undo delete / restore from trash",
+        "   s        Star / unstar article",
+        "   l        Add to / take out of the reading queue",
+        "   x        Finish queued article (mark read, dequeue)",
+        "   W        Highlight summary line on Hypothes.is",
+        "   R        Jump to a related article",
+        "   m        Article info: articles it links to and from",
+        "   X        Export starred to Markdown notes",
+        "   I        Refresh statistics (bandwidth per feed, AI spend)",
+        "   F        Feed settings (auto-star, auto-archive, auto-summarize, collapse)",
+        "   Space    Expand or collapse a run of similar titles",
+        "   f        Cycle filter (All/Unread/Starred/Queued/Trash)",
+        "   t        Edit article tags",
+        "   T        Cycle tag filter",
+        "",
+        " Catch-up:",
+        "   C        Plan a timed catch-up session",
+        "   n        Mark read, go to next",
+        "   Esc      Leave catch-up / search / feed view",
+        "",
+        " Search:",
+        "   /        Search (\"quotes\" for exact phrase)",
         "",
         " General:",
+        "   :        Command palette (every action, e.g. :goto, :filter)",
+        "   ,        Settings (view and edit config values)",
+        "   1-9      List only that feed, or switch workspace (number_keys), when no",
+        "            motion follows; 0 lists all feeds",
         "   ?        Toggle this help",
         "   q        Quit",
         "",